@@ -1132,8 +1132,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
                     if url.is_empty() {
                         //Blank URL on movie loads = unload!
-                        if let Some(mut mc) = level.as_movie_clip() {
-                            mc.replace_with_movie(&mut self.context, None, None)
+                        if let Some(mc) = level.as_movie_clip() {
+                            crate::loader::unload_avm1_clip(&mut self.context, mc);
                         }
                     } else {
                         let future = self.context.load_manager.load_movie_into_clip(
@@ -1143,6 +1143,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                             None,
                             None,
                             None,
+                            Default::default(),
                         );
                         self.context.navigator.spawn_future(future);
                     }
@@ -1249,8 +1250,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             if let Some(clip_target) = clip_target {
                 if url.is_empty() {
                     // Blank URL on movie loads = unload!
-                    if let Some(mut mc) = clip_target.as_movie_clip() {
-                        mc.replace_with_movie(&mut self.context, None, None)
+                    if let Some(mc) = clip_target.as_movie_clip() {
+                        crate::loader::unload_avm1_clip(&mut self.context, mc);
                     }
                 } else {
                     let request = self.locals_into_request(
@@ -1264,6 +1265,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                         None,
                         None,
                         None,
+                        Default::default(),
                     );
                     self.context.navigator.spawn_future(future);
                 }
@@ -1274,8 +1276,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             if let Some(clip_target) = clip_target {
                 if url.is_empty() {
                     // Blank URL on movie loads = unload!
-                    if let Some(mut mc) = clip_target.as_movie_clip() {
-                        mc.replace_with_movie(&mut self.context, None, None)
+                    if let Some(mc) = clip_target.as_movie_clip() {
+                        crate::loader::unload_avm1_clip(&mut self.context, mc);
                     }
                 } else {
                     let future = self.context.load_manager.load_movie_into_clip(
@@ -1285,6 +1287,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                         None,
                         None,
                         None,
+                        Default::default(),
                     );
                     self.context.navigator.spawn_future(future);
                 }
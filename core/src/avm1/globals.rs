@@ -30,6 +30,8 @@ pub mod displacement_map_filter;
 pub mod drop_shadow_filter;
 pub(crate) mod error;
 mod external_interface;
+pub(crate) mod file_reference;
+pub(crate) mod file_reference_list;
 mod function;
 mod glow_filter;
 pub mod gradient_bevel_filter;
@@ -51,12 +53,14 @@ pub(crate) mod shared_object;
 pub(crate) mod sound;
 mod stage;
 pub(crate) mod string;
+pub(crate) mod style_sheet;
 pub(crate) mod system;
 pub(crate) mod system_capabilities;
 pub(crate) mod system_ime;
 pub(crate) mod system_security;
 pub(crate) mod text_field;
 mod text_format;
+pub(crate) mod text_snapshot;
 mod transform;
 mod video;
 mod xml;
@@ -473,6 +477,7 @@ pub struct SystemPrototypes<'gc> {
     pub sound: Object<'gc>,
     pub text_field: Object<'gc>,
     pub text_format: Object<'gc>,
+    pub text_snapshot: Object<'gc>,
     pub array: Object<'gc>,
     pub array_constructor: Object<'gc>,
     pub xml_node: Object<'gc>,
@@ -517,6 +522,8 @@ pub struct SystemPrototypes<'gc> {
     pub bitmap_data_constructor: Object<'gc>,
     pub video: Object<'gc>,
     pub video_constructor: Object<'gc>,
+    pub file_reference: Object<'gc>,
+    pub file_reference_constructor: Object<'gc>,
 }
 
 /// Initialize default global scope and builtins for an AVM1 instance.
@@ -539,7 +546,9 @@ pub fn create_globals<'gc>(
     let sound_proto = sound::create_proto(gc_context, object_proto, function_proto);
 
     let text_field_proto = text_field::create_proto(gc_context, object_proto, function_proto);
+    let style_sheet_proto = style_sheet::create_proto(gc_context, object_proto, function_proto);
     let text_format_proto = text_format::create_proto(gc_context, object_proto, function_proto);
+    let text_snapshot_proto = text_snapshot::create_proto(gc_context, object_proto, function_proto);
 
     let array_proto = array::create_proto(gc_context, object_proto, function_proto);
 
@@ -658,6 +667,19 @@ pub fn create_globals<'gc>(
         function_proto,
         text_field_proto,
     );
+    let style_sheet = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(style_sheet::constructor),
+        constructor_to_fn!(style_sheet::constructor),
+        function_proto,
+        style_sheet_proto,
+    );
+    text_field.define_value(
+        gc_context,
+        "StyleSheet",
+        style_sheet.into(),
+        Attribute::DONT_ENUM,
+    );
     let text_format = FunctionObject::constructor(
         gc_context,
         Executable::Native(text_format::constructor),
@@ -665,6 +687,13 @@ pub fn create_globals<'gc>(
         function_proto,
         text_format_proto,
     );
+    let text_snapshot = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(text_snapshot::constructor),
+        constructor_to_fn!(text_snapshot::constructor),
+        function_proto,
+        text_snapshot_proto,
+    );
     let array = array::create_array_object(gc_context, array_proto, function_proto);
     let xmlnode = FunctionObject::constructor(
         gc_context,
@@ -913,6 +942,50 @@ pub fn create_globals<'gc>(
         Attribute::empty(),
     );
 
+    let net = ScriptObject::new(gc_context, Some(object_proto));
+    let file_reference_proto = file_reference::create_proto(
+        gc_context,
+        object_proto,
+        function_proto,
+        array_proto,
+        broadcaster_functions,
+    );
+    let file_reference = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(file_reference::constructor),
+        constructor_to_fn!(file_reference::constructor),
+        function_proto,
+        file_reference_proto,
+    );
+    let file_reference_list_proto = file_reference_list::create_proto(
+        gc_context,
+        object_proto,
+        function_proto,
+        array_proto,
+        broadcaster_functions,
+    );
+    let file_reference_list = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(file_reference_list::constructor),
+        constructor_to_fn!(file_reference_list::constructor),
+        function_proto,
+        file_reference_list_proto,
+    );
+
+    flash.define_value(gc_context, "net", net.into(), Attribute::empty());
+    net.define_value(
+        gc_context,
+        "FileReference",
+        file_reference.into(),
+        Attribute::empty(),
+    );
+    net.define_value(
+        gc_context,
+        "FileReferenceList",
+        file_reference_list.into(),
+        Attribute::empty(),
+    );
+
     let globals = ScriptObject::new(gc_context, None);
     globals.define_value(
         gc_context,
@@ -969,6 +1042,12 @@ pub fn create_globals<'gc>(
         text_format.into(),
         Attribute::DONT_ENUM,
     );
+    globals.define_value(
+        gc_context,
+        "TextSnapshot",
+        text_snapshot.into(),
+        Attribute::DONT_ENUM,
+    );
     globals.define_value(gc_context, "XMLNode", xmlnode.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "XML", xml.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "String", string.into(), Attribute::DONT_ENUM);
@@ -1114,6 +1193,7 @@ pub fn create_globals<'gc>(
             sound: sound_proto,
             text_field: text_field_proto,
             text_format: text_format_proto,
+            text_snapshot: text_snapshot_proto,
             array: array_proto,
             array_constructor: array,
             xml_node: xmlnode_proto,
@@ -1158,6 +1238,8 @@ pub fn create_globals<'gc>(
             bitmap_data_constructor: bitmap_data,
             video: video_proto,
             video_constructor: video,
+            file_reference: file_reference_proto,
+            file_reference_constructor: file_reference,
         },
         globals.into(),
         broadcaster_functions,
@@ -8,7 +8,7 @@ use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, Object, TObject, Value};
 use crate::avm_error;
 use crate::bitmap::bitmap_data::IBitmapDrawable;
-use crate::bitmap::bitmap_data::{BitmapData, ChannelOptions, Color};
+use crate::bitmap::bitmap_data::{BitmapData, ChannelOptions, Color, ThresholdOperation};
 use crate::bitmap::is_size_valid;
 use crate::character::Character;
 use crate::display_object::TDisplayObject;
@@ -740,13 +740,97 @@ pub fn perlin_noise<'gc>(
 }
 
 pub fn hit_test<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bitmap_data) = this.as_bitmap_data_object() {
         if !bitmap_data.disposed() {
-            log::warn!("BitmapData.hitTest - not yet implemented");
+            let first_point = args
+                .get(0)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+            let top_left = (
+                first_point
+                    .get("x", activation)?
+                    .coerce_to_f64(activation)? as i32,
+                first_point
+                    .get("y", activation)?
+                    .coerce_to_f64(activation)? as i32,
+            );
+            let source_threshold = args
+                .get(1)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_u32(activation)?;
+            let compare_object = args
+                .get(2)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let point_class = activation.context.avm1.prototypes().point_constructor;
+            let point_proto = activation.context.avm1.prototypes().point;
+            let rectangle_class = activation.context.avm1.prototypes().rectangle_constructor;
+            let rectangle_proto = activation.context.avm1.prototypes().rectangle;
+
+            if let Some(other_bitmap) = compare_object.as_bitmap_data_object() {
+                let second_point = args
+                    .get(3)
+                    .unwrap_or(&Value::Undefined)
+                    .coerce_to_object(activation);
+                let second_point = (
+                    second_point
+                        .get("x", activation)?
+                        .coerce_to_f64(activation)? as i32,
+                    second_point
+                        .get("y", activation)?
+                        .coerce_to_f64(activation)? as i32,
+                );
+                let second_threshold =
+                    args.get(4).unwrap_or(&1.into()).coerce_to_u32(activation)?;
+
+                // A disposed bitmap has no pixels, so it never hits anything.
+                let result = bitmap_data.bitmap_data().read().hit_test_bitmapdata(
+                    top_left,
+                    source_threshold,
+                    &other_bitmap.bitmap_data().read(),
+                    second_point,
+                    second_threshold,
+                );
+                return Ok(result.into());
+            } else if compare_object.is_instance_of(activation, point_class, point_proto)? {
+                let test_x = compare_object
+                    .get("x", activation)?
+                    .coerce_to_f64(activation)? as i32;
+                let test_y = compare_object
+                    .get("y", activation)?
+                    .coerce_to_f64(activation)? as i32;
+                let result = bitmap_data
+                    .bitmap_data()
+                    .read()
+                    .hit_test_point(source_threshold, (test_x - top_left.0, test_y - top_left.1));
+                return Ok(result.into());
+            } else if compare_object.is_instance_of(activation, rectangle_class, rectangle_proto)? {
+                let test_x = compare_object
+                    .get("x", activation)?
+                    .coerce_to_f64(activation)? as i32;
+                let test_y = compare_object
+                    .get("y", activation)?
+                    .coerce_to_f64(activation)? as i32;
+                let width = compare_object
+                    .get("width", activation)?
+                    .coerce_to_f64(activation)? as i32;
+                let height = compare_object
+                    .get("height", activation)?
+                    .coerce_to_f64(activation)? as i32;
+                let result = bitmap_data.bitmap_data().read().hit_test_rectangle(
+                    source_threshold,
+                    (test_x - top_left.0, test_y - top_left.1),
+                    (width, height),
+                );
+                return Ok(result.into());
+            }
+
+            // Anything else isn't a valid target.
             return Ok(Value::Undefined);
         }
     }
@@ -1073,13 +1157,81 @@ pub fn palette_map<'gc>(
 }
 
 pub fn pixel_dissolve<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bitmap_data) = this.as_bitmap_data_object() {
         if !bitmap_data.disposed() {
-            log::warn!("BitmapData.pixelDissolve - not yet implemented");
+            let source_bitmap = args
+                .get(0)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let source_rect = args
+                .get(1)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let src_min_x = source_rect
+                .get("x", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let src_min_y = source_rect
+                .get("y", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let src_width = source_rect
+                .get("width", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let src_height = source_rect
+                .get("height", activation)?
+                .coerce_to_f64(activation)? as i32;
+
+            let dest_point = args
+                .get(2)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let dest_x = dest_point.get("x", activation)?.coerce_to_f64(activation)? as i32;
+            let dest_y = dest_point.get("y", activation)?.coerce_to_f64(activation)? as i32;
+
+            let random_seed = args.get(3).unwrap_or(&0.into()).coerce_to_i32(activation)?;
+
+            // By default, a thirtieth of the pixels are dissolved.
+            let num_pixels = match args.get(4) {
+                Some(num_pixels) => num_pixels.coerce_to_i32(activation)?,
+                None => src_width.max(0).saturating_mul(src_height.max(0)) / 30,
+            };
+
+            let fill_color = args.get(5).unwrap_or(&0.into()).coerce_to_u32(activation)?;
+
+            if let Some(src_bitmap) = source_bitmap.as_bitmap_data_object() {
+                if !src_bitmap.disposed() {
+                    // dealing with object aliasing...
+                    let src_bitmap_data_cell = src_bitmap.bitmap_data();
+                    let read;
+                    let source: Option<&BitmapData> =
+                        if GcCell::ptr_eq(src_bitmap_data_cell, bitmap_data.bitmap_data()) {
+                            None
+                        } else {
+                            read = src_bitmap_data_cell.read();
+                            Some(&read)
+                        };
+
+                    let next_seed = bitmap_data
+                        .bitmap_data()
+                        .write(activation.context.gc_context)
+                        .pixel_dissolve(
+                            source,
+                            (src_min_x, src_min_y, src_width, src_height),
+                            (dest_x, dest_y),
+                            random_seed,
+                            num_pixels,
+                            fill_color,
+                        );
+                    return Ok(next_seed.into());
+                }
+            }
+
             return Ok(Value::Undefined);
         }
     }
@@ -1116,14 +1268,105 @@ pub fn scroll<'gc>(
 }
 
 pub fn threshold<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bitmap_data) = this.as_bitmap_data_object() {
         if !bitmap_data.disposed() {
-            log::warn!("BitmapData.threshold - not yet implemented");
-            return Ok(Value::Undefined);
+            let source_bitmap = args
+                .get(0)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let source_rect = args
+                .get(1)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let src_min_x = source_rect
+                .get("x", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let src_min_y = source_rect
+                .get("y", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let src_width = source_rect
+                .get("width", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let src_height = source_rect
+                .get("height", activation)?
+                .coerce_to_f64(activation)? as i32;
+
+            let dest_point = args
+                .get(2)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let dest_x = dest_point.get("x", activation)?.coerce_to_f64(activation)? as i32;
+            let dest_y = dest_point.get("y", activation)?.coerce_to_f64(activation)? as i32;
+
+            let operation = match ThresholdOperation::from_str(
+                &args
+                    .get(3)
+                    .unwrap_or(&Value::Undefined)
+                    .coerce_to_string(activation)?
+                    .to_string(),
+            ) {
+                Ok(operation) => operation,
+                // Invalid operations silently do nothing.
+                Err(()) => return Ok(0.into()),
+            };
+
+            let threshold = args
+                .get(4)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_u32(activation)?;
+
+            let color = args.get(5).unwrap_or(&0.into()).coerce_to_u32(activation)?;
+
+            let mask = args
+                .get(6)
+                .unwrap_or(&0xFFFFFFFFu32.into())
+                .coerce_to_u32(activation)?;
+
+            let copy_source = args
+                .get(7)
+                .unwrap_or(&false.into())
+                .as_bool(activation.swf_version());
+
+            if let Some(src_bitmap) = source_bitmap.as_bitmap_data_object() {
+                if !src_bitmap.disposed() {
+                    // dealing with object aliasing...
+                    let src_bitmap_clone: BitmapData; // only initialized if source is the same object as self
+                    let src_bitmap_data_cell = src_bitmap.bitmap_data();
+                    let src_bitmap_gc_ref; // only initialized if source is a different object than self
+                    let source_bitmap_ref = // holds the reference to either of the ones above
+                        if GcCell::ptr_eq(src_bitmap.bitmap_data(), bitmap_data.bitmap_data()) {
+                            src_bitmap_clone = src_bitmap_data_cell.read().clone();
+                            &src_bitmap_clone
+                        } else {
+                            src_bitmap_gc_ref = src_bitmap_data_cell.read();
+                            &src_bitmap_gc_ref
+                        };
+
+                    let count = bitmap_data
+                        .bitmap_data()
+                        .write(activation.context.gc_context)
+                        .threshold(
+                            source_bitmap_ref,
+                            (src_min_x, src_min_y, src_width, src_height),
+                            (dest_x, dest_y),
+                            operation,
+                            threshold,
+                            color,
+                            mask,
+                            copy_source,
+                        );
+                    return Ok(count.into());
+                }
+            }
+
+            return Ok(0.into());
         }
     }
 
@@ -0,0 +1,321 @@
+//! flash.net.FileReference object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
+use crate::avm1::object::NativeObject;
+use crate::avm1::property::Attribute;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{ArrayObject, Object, ScriptObject, TObject, Value};
+use crate::backend::navigator::Request;
+use crate::backend::ui::{DialogFile, FileFilter};
+use crate::loader::{multipart_upload_body, FileDialogPurpose, FileReferenceTarget, FileTransfer};
+use crate::string::AvmString;
+use chrono::{DateTime, Utc};
+use gc_arena::{Collect, GcCell, MutationContext};
+use url::form_urlencoded;
+
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(require_static)]
+pub struct FileReferenceObject {
+    /// The file picked in the last successful dialog.
+    file: Option<DialogFile>,
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "browse" => method(browse; DONT_ENUM | DONT_DELETE);
+    "cancel" => method(cancel; DONT_ENUM | DONT_DELETE);
+    "download" => method(download; DONT_ENUM | DONT_DELETE);
+    "upload" => method(upload; DONT_ENUM | DONT_DELETE);
+    "creationDate" => property(creation_date; DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "creator" => property(creator; DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "modificationDate" => property(modification_date; DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "name" => property(name; DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "size" => property(size; DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "type" => property(file_type; DONT_ENUM | DONT_DELETE | READ_ONLY);
+};
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    this.set_native(
+        activation.context.gc_context,
+        NativeObject::FileReference(GcCell::allocate(
+            activation.context.gc_context,
+            FileReferenceObject::default(),
+        )),
+    );
+
+    let listeners = ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        [this.into()],
+    );
+    this.define_value(
+        activation.context.gc_context,
+        "_listeners",
+        Value::Object(listeners.into()),
+        Attribute::DONT_ENUM,
+    );
+    Ok(this.into())
+}
+
+/// Stores the details of a file picked in a dialog on a `FileReference`.
+pub fn set_selected_file<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    object: Object<'gc>,
+    file: DialogFile,
+) {
+    if let NativeObject::FileReference(file_reference) = object.native() {
+        file_reference.write(gc_context).file = Some(file);
+    }
+}
+
+fn selected_file(this: Object<'_>) -> Option<DialogFile> {
+    match this.native() {
+        NativeObject::FileReference(file_reference) => file_reference.read().file.clone(),
+        _ => None,
+    }
+}
+
+/// Reads the `FileFilter`s from a `typelist` argument.
+pub fn file_filters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    type_list: Option<&Value<'gc>>,
+) -> Result<Vec<FileFilter>, Error<'gc>> {
+    let type_list = match type_list {
+        Some(Value::Object(type_list)) => *type_list,
+        _ => return Ok(Vec::new()),
+    };
+
+    let length = type_list.length(activation)?;
+    let mut filters = Vec::new();
+    for i in 0..length {
+        if let Value::Object(item) = type_list.get_element(activation, i) {
+            let description = item
+                .get("description", activation)?
+                .coerce_to_string(activation)?;
+            let extension = item
+                .get("extension", activation)?
+                .coerce_to_string(activation)?;
+            filters.push(FileFilter::new(
+                description.to_string(),
+                &extension.to_string(),
+            ));
+        }
+    }
+    Ok(filters)
+}
+
+fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Only one dialog may be open at a time.
+    if activation.context.load_manager.is_file_dialog_open() {
+        return Ok(false.into());
+    }
+
+    let filters = file_filters(activation, args.get(0))?;
+    let dialog = activation
+        .context
+        .ui
+        .display_file_open_dialog(filters, false);
+    let future = activation.context.load_manager.open_file_dialog(
+        activation.context.player.clone(),
+        FileReferenceTarget::Avm1(this),
+        dialog,
+        FileDialogPurpose::Browse,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
+
+fn cancel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation
+        .context
+        .load_manager
+        .cancel_file_reference_transfers(FileReferenceTarget::Avm1(this));
+    Ok(Value::Undefined)
+}
+
+fn download<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = match args.get(0) {
+        Some(Value::String(url)) if !url.is_empty() => url.to_utf8_lossy().into_owned(),
+        _ => return Ok(false.into()),
+    };
+    if activation.context.load_manager.is_file_dialog_open() {
+        return Ok(false.into());
+    }
+
+    let default_name = match args.get(1) {
+        None | Some(Value::Undefined) | Some(Value::Null) => {
+            // Default to the last path segment of the URL.
+            let path = url.split(['?', '#']).next().unwrap_or_default();
+            path.rsplit('/').next().unwrap_or_default().to_string()
+        }
+        Some(name) => name.coerce_to_string(activation)?.to_string(),
+    };
+
+    let dialog = activation.context.ui.display_file_save_dialog(default_name);
+    let future = activation.context.load_manager.open_file_dialog(
+        activation.context.player.clone(),
+        FileReferenceTarget::Avm1(this),
+        Box::pin(async move { dialog.await.map(|file| vec![file]) }),
+        FileDialogPurpose::Download(Request::get(url)),
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
+
+fn upload<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let file = match selected_file(this) {
+        Some(file) => file,
+        None => return Ok(false.into()),
+    };
+    let url = match args.get(0) {
+        Some(Value::String(url)) if !url.is_empty() => url.to_utf8_lossy().into_owned(),
+        _ => return Ok(false.into()),
+    };
+    let field_name = match args.get(1) {
+        None | Some(Value::Undefined) | Some(Value::Null) => "Filedata".to_string(),
+        Some(name) => name.coerce_to_string(activation)?.to_string(),
+    };
+
+    // `postData` holds URL-encoded variables that are sent as form fields.
+    let variables: Vec<(String, String)> = match this.get("postData", activation)? {
+        Value::Undefined | Value::Null => Vec::new(),
+        post_data => {
+            let post_data = post_data.coerce_to_string(activation)?.to_string();
+            form_urlencoded::parse(post_data.as_bytes())
+                .into_owned()
+                .collect()
+        }
+    };
+
+    let contents = match activation.context.ui.read_file(&file.path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            log::warn!("FileReference.upload: unable to read file: {}", e);
+            return Ok(false.into());
+        }
+    };
+    let file_size = contents.len();
+    let body = multipart_upload_body(&file.name, &field_name, &contents, &variables);
+
+    let future = activation.context.load_manager.transfer_file_reference(
+        activation.context.player.clone(),
+        FileReferenceTarget::Avm1(this),
+        Request::post(url, Some(body)),
+        FileTransfer::Upload { file_size },
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
+
+fn date<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    date_time: Option<DateTime<Utc>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    match date_time {
+        Some(date_time) => {
+            let date_constructor = activation.context.avm1.prototypes().date_constructor;
+            let millis = date_time.timestamp_millis() as f64;
+            date_constructor.construct(activation, &[millis.into()])
+        }
+        None => Ok(Value::Undefined),
+    }
+}
+
+fn creation_date<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let creation_date = selected_file(this).and_then(|file| file.creation_date);
+    date(activation, creation_date)
+}
+
+/// Only used by Mac OS 9 and earlier.
+fn creator<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Null)
+}
+
+fn modification_date<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let modification_date = selected_file(this).and_then(|file| file.modification_date);
+    date(activation, modification_date)
+}
+
+fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match selected_file(this) {
+        Some(file) => AvmString::new_utf8(activation.context.gc_context, file.name).into(),
+        None => Value::Undefined,
+    })
+}
+
+fn size<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match selected_file(this) {
+        Some(file) => (file.size as f64).into(),
+        None => Value::Undefined,
+    })
+}
+
+fn file_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(
+        match selected_file(this).and_then(|file| file.file_type()) {
+            Some(file_type) => AvmString::new_utf8(activation.context.gc_context, file_type).into(),
+            None => Value::Undefined,
+        },
+    )
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+    array_proto: Object<'gc>,
+    broadcaster_functions: BroadcasterFunctions<'gc>,
+) -> Object<'gc> {
+    let file_reference_proto = ScriptObject::new(gc_context, Some(proto));
+    broadcaster_functions.initialize(gc_context, file_reference_proto.into(), array_proto);
+    define_properties_on(PROTO_DECLS, gc_context, file_reference_proto, fn_proto);
+    file_reference_proto.into()
+}
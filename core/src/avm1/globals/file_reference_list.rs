@@ -0,0 +1,103 @@
+//! flash.net.FileReferenceList object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
+use crate::avm1::globals::file_reference::{file_filters, set_selected_file};
+use crate::avm1::property::Attribute;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{ArrayObject, Object, ScriptObject, TObject, Value};
+use crate::backend::ui::DialogFile;
+use crate::loader::{FileDialogPurpose, FileReferenceTarget};
+use gc_arena::MutationContext;
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "browse" => method(browse; DONT_ENUM | DONT_DELETE);
+};
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let listeners = ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        [this.into()],
+    );
+    this.define_value(
+        activation.context.gc_context,
+        "_listeners",
+        Value::Object(listeners.into()),
+        Attribute::DONT_ENUM,
+    );
+    Ok(this.into())
+}
+
+fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Only one dialog may be open at a time.
+    if activation.context.load_manager.is_file_dialog_open() {
+        return Ok(false.into());
+    }
+
+    let filters = file_filters(activation, args.get(0))?;
+    let dialog = activation
+        .context
+        .ui
+        .display_file_open_dialog(filters, true);
+    let future = activation.context.load_manager.open_file_dialog(
+        activation.context.player.clone(),
+        FileReferenceTarget::Avm1(this),
+        dialog,
+        FileDialogPurpose::BrowseMultiple,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
+
+/// Fills in the `fileList` of a `FileReferenceList` with the files picked in
+/// a dialog.
+pub fn set_file_list<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    files: Vec<DialogFile>,
+) -> Result<(), Error<'gc>> {
+    let file_reference_constructor = activation
+        .context
+        .avm1
+        .prototypes()
+        .file_reference_constructor;
+    let mut file_list = Vec::with_capacity(files.len());
+    for file in files {
+        let file_reference = file_reference_constructor.construct(activation, &[])?;
+        if let Value::Object(object) = file_reference {
+            set_selected_file(activation.context.gc_context, object, file);
+        }
+        file_list.push(file_reference);
+    }
+
+    let file_list = ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        file_list,
+    );
+    this.set("fileList", file_list.into(), activation)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+    array_proto: Object<'gc>,
+    broadcaster_functions: BroadcasterFunctions<'gc>,
+) -> Object<'gc> {
+    let file_reference_list_proto = ScriptObject::new(gc_context, Some(proto));
+    broadcaster_functions.initialize(gc_context, file_reference_list_proto.into(), array_proto);
+    define_properties_on(PROTO_DECLS, gc_context, file_reference_list_proto, fn_proto);
+    file_reference_list_proto.into()
+}
@@ -76,6 +76,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "getInstanceAtDepth" => method(mc_method!(get_instance_at_depth); DONT_ENUM | DONT_DELETE | VERSION_7);
     "getNextHighestDepth" => method(mc_method!(get_next_highest_depth); DONT_ENUM | DONT_DELETE | VERSION_7);
     "getRect" => method(mc_method!(get_rect); DONT_ENUM | DONT_DELETE | VERSION_8);
+    "getTextSnapshot" => method(mc_method!(get_text_snapshot); DONT_ENUM | DONT_DELETE | VERSION_6);
     "getURL" => method(mc_method!(get_url); DONT_ENUM | DONT_DELETE);
     "globalToLocal" => method(mc_method!(global_to_local); DONT_ENUM | DONT_DELETE);
     "gotoAndPlay" => method(mc_method!(goto_and_play); DONT_ENUM | DONT_DELETE);
@@ -110,6 +111,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "_lockroot" => property(mc_getter!(lock_root), mc_setter!(set_lock_root); DONT_DELETE | DONT_ENUM);
     "useHandCursor" => property(mc_getter!(use_hand_cursor), mc_setter!(set_use_hand_cursor); DONT_DELETE | DONT_ENUM);
     "blendMode" => property(mc_getter!(blend_mode), mc_setter!(set_blend_mode); DONT_DELETE | DONT_ENUM);
+    "cacheAsBitmap" => property(mc_getter!(cache_as_bitmap), mc_setter!(set_cache_as_bitmap); DONT_DELETE | DONT_ENUM | VERSION_8);
     "scrollRect" => property(mc_getter!(scroll_rect), mc_setter!(set_scroll_rect); DONT_DELETE | DONT_ENUM | VERSION_8);
 };
 
@@ -845,8 +847,7 @@ pub fn duplicate_movie_clip_with_bias<'gc>(
     let clip_actions = movie_clip.clip_actions().to_vec();
     new_clip.set_clip_event_handlers(activation.context.gc_context, clip_actions);
 
-    *new_clip.drawing(activation.context.gc_context) =
-        movie_clip.drawing(activation.context.gc_context).clone();
+    *new_clip.drawing(activation.context.gc_context) = movie_clip.drawing_ref().clone();
     // TODO: Any other properties we should copy...?
     // Definitely not ScriptObject properties.
 
@@ -877,6 +878,14 @@ fn get_bytes_total<'gc>(
     Ok(movie_clip.total_bytes().into())
 }
 
+fn get_text_snapshot<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(globals::text_snapshot::create_text_snapshot(activation, movie_clip).into())
+}
+
 fn get_instance_at_depth<'gc>(
     movie_clip: MovieClip<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
@@ -1323,6 +1332,7 @@ fn load_movie<'gc>(
         None,
         None,
         None,
+        Default::default(),
     );
     activation.context.navigator.spawn_future(future);
 
@@ -1351,12 +1361,11 @@ fn load_variables<'gc>(
 }
 
 fn unload_movie<'gc>(
-    mut target: MovieClip<'gc>,
+    target: MovieClip<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    target.unload(&mut activation.context);
-    target.replace_with_movie(&mut activation.context, None, None);
+    crate::loader::unload_avm1_clip(&mut activation.context, target);
 
     Ok(Value::Undefined)
 }
@@ -1485,3 +1494,20 @@ fn set_blend_mode<'gc>(
     }
     Ok(())
 }
+
+fn cache_as_bitmap<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.is_bitmap_cached().into())
+}
+
+fn set_cache_as_bitmap<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let value = value.as_bool(activation.swf_version());
+    this.set_is_bitmap_cached(activation.context.gc_context, value);
+    Ok(())
+}
@@ -66,6 +66,7 @@ fn load_clip<'gc>(
                     None,
                     Some(MovieLoaderEventHandler::Avm1Broadcast(this)),
                     None,
+                    Default::default(),
                 );
                 activation.context.navigator.spawn_future(future);
 
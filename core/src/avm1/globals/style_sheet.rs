@@ -0,0 +1,238 @@
+//! AVM1 `TextField.StyleSheet` object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::globals::text_field::new_text_format;
+use crate::avm1::property::Attribute;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{ArrayObject, Object, ScriptObject, TObject, Value};
+use crate::html::{CssStyle, StyleSheet};
+use crate::string::AvmString;
+use gc_arena::MutationContext;
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "clear" => method(clear; DONT_ENUM | DONT_DELETE);
+    "getStyle" => method(get_style; DONT_ENUM | DONT_DELETE);
+    "getStyleNames" => method(get_style_names; DONT_ENUM | DONT_DELETE);
+    "parseCSS" => method(parse_css; DONT_ENUM | DONT_DELETE);
+    "setStyle" => method(set_style; DONT_ENUM | DONT_DELETE);
+    "transform" => method(transform; DONT_ENUM | DONT_DELETE);
+};
+
+/// Implements `TextField.StyleSheet`
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // The styles are kept on a hidden object, mapping lowercased style names
+    // to objects holding their properties.
+    let styles = new_object(activation);
+    this.define_value(
+        activation.context.gc_context,
+        "_styles",
+        styles.into(),
+        Attribute::DONT_ENUM | Attribute::DONT_DELETE,
+    );
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, gc_context, object, fn_proto);
+    object.into()
+}
+
+fn new_object<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+    ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    )
+    .into()
+}
+
+fn styles<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<Option<Object<'gc>>, Error<'gc>> {
+    match this.get("_styles", activation)? {
+        Value::Object(styles) => Ok(Some(styles)),
+        _ => Ok(None),
+    }
+}
+
+fn copy_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    style: Object<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let copy = new_object(activation);
+    for key in style.get_keys(activation) {
+        let value = style.get(key, activation)?;
+        copy.set(key, value, activation)?;
+    }
+    Ok(copy)
+}
+
+/// Reads the properties of a style object as CSS declarations.
+fn style_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    style: Object<'gc>,
+) -> Result<CssStyle, Error<'gc>> {
+    let mut declarations = CssStyle::new();
+    for key in style.get_keys(activation) {
+        let value = style.get(key, activation)?.coerce_to_string(activation)?;
+        declarations.push((
+            key.to_utf8_lossy().into_owned(),
+            value.to_utf8_lossy().into_owned(),
+        ));
+    }
+    Ok(declarations)
+}
+
+/// Builds a snapshot of the styles held by a `TextField.StyleSheet`, for use
+/// by text fields.
+pub fn style_sheet_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<StyleSheet, Error<'gc>> {
+    let mut style_sheet = StyleSheet::new();
+    if let Some(styles) = styles(activation, this)? {
+        for name in styles.get_keys(activation) {
+            if let Value::Object(style) = styles.get(name, activation)? {
+                let style = style_from_object(activation, style)?;
+                style_sheet.set_style(&name.to_utf8_lossy(), style);
+            }
+        }
+    }
+    Ok(style_sheet)
+}
+
+fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let styles = new_object(activation);
+    this.define_value(
+        activation.context.gc_context,
+        "_styles",
+        styles.into(),
+        Attribute::DONT_ENUM | Attribute::DONT_DELETE,
+    );
+    Ok(Value::Undefined)
+}
+
+fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let name = AvmString::new_utf8(
+        activation.context.gc_context,
+        name.to_utf8_lossy().to_ascii_lowercase(),
+    );
+
+    if let Some(styles) = styles(activation, this)? {
+        if let Value::Object(style) = styles.get(name, activation)? {
+            return Ok(copy_style(activation, style)?.into());
+        }
+    }
+    Ok(Value::Null)
+}
+
+fn get_style_names<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = match styles(activation, this)? {
+        Some(styles) => styles.get_keys(activation),
+        None => Vec::new(),
+    };
+    Ok(ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        names.into_iter().map(Value::from),
+    )
+    .into())
+}
+
+fn set_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let name = AvmString::new_utf8(
+        activation.context.gc_context,
+        name.to_utf8_lossy().to_ascii_lowercase(),
+    );
+
+    if let Some(styles) = styles(activation, this)? {
+        match args.get(1) {
+            Some(Value::Object(style)) => {
+                let style = copy_style(activation, *style)?;
+                styles.set(name, style.into(), activation)?;
+            }
+            _ => {
+                styles.delete(activation, name);
+            }
+        }
+    }
+    Ok(Value::Undefined)
+}
+
+fn parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let rules = match StyleSheet::parse_css(&css.to_utf8_lossy()) {
+        Some(rules) => rules,
+        None => return Ok(false.into()),
+    };
+
+    let styles = match styles(activation, this)? {
+        Some(styles) => styles,
+        None => return Ok(false.into()),
+    };
+    for (selector, declarations) in rules {
+        let selector = AvmString::new_utf8(activation.context.gc_context, selector);
+        let style = new_object(activation);
+        for (name, value) in declarations {
+            let name = AvmString::new_utf8(activation.context.gc_context, name);
+            let value = AvmString::new_utf8(activation.context.gc_context, value);
+            style.set(name, value.into(), activation)?;
+        }
+        styles.set(selector, style.into(), activation)?;
+    }
+    Ok(true.into())
+}
+
+fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let style = match args.get(0) {
+        Some(Value::Object(style)) => style_from_object(activation, *style)?,
+        _ => return Ok(Value::Null),
+    };
+    let text_format = StyleSheet::style_to_text_format(&style);
+    Ok(new_text_format(activation, text_format).into())
+}
@@ -1,5 +1,6 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
+use crate::avm1::globals::style_sheet::style_sheet_from_object;
 use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{globals, Object, ScriptObject, TObject, Value};
@@ -7,8 +8,9 @@ use crate::display_object::{AutoSizeMode, EditText, TDisplayObject, TextSelectio
 use crate::font::round_down_to_pixel;
 use crate::html::TextFormat;
 use crate::string::{AvmString, WStr};
+use crate::vminterface::AvmObject;
 use gc_arena::{GcCell, MutationContext};
-use swf::Color;
+use swf::{Color, Twips};
 
 macro_rules! tf_method {
     ($fn:expr) => {
@@ -58,24 +60,37 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "replaceSel" => method(tf_method!(replace_sel); DONT_ENUM | DONT_DELETE);
     "replaceText" => method(tf_method!(replace_text); DONT_ENUM | DONT_DELETE);
     "removeTextField" => method(tf_method!(remove_text_field); DONT_ENUM | DONT_DELETE);
+    "getCharBoundaries" => method(tf_method!(get_char_boundaries); DONT_ENUM | DONT_DELETE);
+    "getCharIndexAtPoint" => method(tf_method!(get_char_index_at_point); DONT_ENUM | DONT_DELETE);
+    "getFirstCharInParagraph" => method(tf_method!(get_first_char_in_paragraph); DONT_ENUM | DONT_DELETE);
+    "getLineIndexAtPoint" => method(tf_method!(get_line_index_at_point); DONT_ENUM | DONT_DELETE);
+    "getLineIndexOfChar" => method(tf_method!(get_line_index_of_char); DONT_ENUM | DONT_DELETE);
+    "getLineLength" => method(tf_method!(get_line_length); DONT_ENUM | DONT_DELETE);
+    "getLineOffset" => method(tf_method!(get_line_offset); DONT_ENUM | DONT_DELETE);
+    "getLineText" => method(tf_method!(get_line_text); DONT_ENUM | DONT_DELETE);
+    "getParagraphLength" => method(tf_method!(get_paragraph_length); DONT_ENUM | DONT_DELETE);
     "autoSize" => property(tf_getter!(auto_size), tf_setter!(set_auto_size));
     "background" => property(tf_getter!(background), tf_setter!(set_background));
     "backgroundColor" => property(tf_getter!(background_color), tf_setter!(set_background_color));
     "border" => property(tf_getter!(border), tf_setter!(set_border));
     "borderColor" => property(tf_getter!(border_color), tf_setter!(set_border_color));
     "bottomScroll" => property(tf_getter!(bottom_scroll));
+    "condenseWhite" => property(tf_getter!(condense_white), tf_setter!(set_condense_white));
     "embedFonts" => property(tf_getter!(embed_fonts), tf_setter!(set_embed_fonts));
     "getDepth" => method(globals::get_depth; DONT_ENUM | DONT_DELETE | READ_ONLY | VERSION_6);
     "hscroll" => property(tf_getter!(hscroll), tf_setter!(set_hscroll));
     "html" => property(tf_getter!(html), tf_setter!(set_html));
     "htmlText" => property(tf_getter!(html_text), tf_setter!(set_html_text));
     "length" => property(tf_getter!(length));
+    "maxChars" => property(tf_getter!(max_chars), tf_setter!(set_max_chars));
     "maxhscroll" => property(tf_getter!(maxhscroll));
     "maxscroll" => property(tf_getter!(maxscroll));
     "multiline" => property(tf_getter!(multiline), tf_setter!(set_multiline));
     "password" => property(tf_getter!(password), tf_setter!(set_password));
+    "restrict" => property(tf_getter!(restrict), tf_setter!(set_restrict));
     "scroll" => property(tf_getter!(scroll), tf_setter!(set_scroll));
     "selectable" => property(tf_getter!(selectable), tf_setter!(set_selectable));
+    "styleSheet" => property(tf_getter!(style_sheet), tf_setter!(set_style_sheet));
     "text" => property(tf_getter!(text), tf_setter!(set_text));
     "textColor" => property(tf_getter!(text_color), tf_setter!(set_text_color));
     "textHeight" => property(tf_getter!(text_height));
@@ -126,7 +141,7 @@ pub fn set_password<'gc>(
     Ok(())
 }
 
-fn new_text_format<'gc>(
+pub(crate) fn new_text_format<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     text_format: TextFormat,
 ) -> ScriptObject<'gc> {
@@ -153,6 +168,10 @@ fn set_new_text_format<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if text_field.style_sheet_object().is_some() {
+        return Ok(Value::Undefined);
+    }
+
     if let [Value::Object(text_format), ..] = args {
         if let NativeObject::TextFormat(text_format) = text_format.native() {
             text_field.set_new_text_format(text_format.read().clone(), &mut activation.context);
@@ -194,6 +213,10 @@ fn set_text_format<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if text_field.style_sheet_object().is_some() {
+        return Ok(Value::Undefined);
+    }
+
     let (begin_index, end_index, text_format) = match args {
         [begin_index, end_index, text_format, ..] => {
             let begin_index = begin_index.coerce_to_u32(activation)? as usize;
@@ -232,6 +255,10 @@ fn replace_sel<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if text_field.style_sheet_object().is_some() {
+        return Ok(Value::Undefined);
+    }
+
     let text = args
         .get(0)
         .unwrap_or(&Value::Undefined)
@@ -264,6 +291,10 @@ fn replace_text<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if text_field.style_sheet_object().is_some() {
+        return Ok(Value::Undefined);
+    }
+
     let from = args
         .get(0)
         .cloned()
@@ -285,6 +316,149 @@ fn replace_text<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Reads a character or line index argument, which is None when negative.
+fn index_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Option<usize>, Error<'gc>> {
+    let index = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    Ok(usize::try_from(index).ok())
+}
+
+/// Reads a point argument in pixels.
+fn point_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<(Twips, Twips), Error<'gc>> {
+    let x = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_f64(activation)?;
+    let y = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_f64(activation)?;
+    Ok((Twips::from_pixels(x), Twips::from_pixels(y)))
+}
+
+fn get_char_boundaries<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let bounds = index_arg(activation, args)?.and_then(|index| text_field.char_bounds(index));
+    if let Some(bounds) = bounds {
+        let proto = activation.context.avm1.prototypes().rectangle_constructor;
+        return proto.construct(
+            activation,
+            &[
+                bounds.offset_x().to_pixels().into(),
+                bounds.offset_y().to_pixels().into(),
+                bounds.width().to_pixels().into(),
+                bounds.height().to_pixels().into(),
+            ],
+        );
+    }
+
+    Ok(Value::Null)
+}
+
+fn get_char_index_at_point<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let position = point_args(activation, args)?;
+    Ok(text_field
+        .char_index_at_point(position)
+        .map_or(-1.0, |index| index as f64)
+        .into())
+}
+
+fn get_first_char_in_paragraph<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(index_arg(activation, args)?
+        .and_then(|index| text_field.first_char_in_paragraph(index))
+        .map_or(-1.0, |index| index as f64)
+        .into())
+}
+
+fn get_line_index_at_point<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let position = point_args(activation, args)?;
+    Ok(text_field
+        .line_index_at_point(position)
+        .map_or(-1.0, |line| line as f64)
+        .into())
+}
+
+fn get_line_index_of_char<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(index_arg(activation, args)?
+        .and_then(|index| text_field.line_index_of_char(index))
+        .map_or(-1.0, |line| line as f64)
+        .into())
+}
+
+fn get_line_length<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(index_arg(activation, args)?
+        .and_then(|line| text_field.line_length(line))
+        .map_or(-1.0, |length| length as f64)
+        .into())
+}
+
+fn get_line_offset<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(index_arg(activation, args)?
+        .and_then(|line| text_field.line_offset(line))
+        .map_or(-1.0, |offset| offset as f64)
+        .into())
+}
+
+fn get_line_text<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match index_arg(activation, args)?.and_then(|line| text_field.line_text(line)) {
+        Some(text) => Ok(AvmString::new(activation.context.gc_context, text).into()),
+        None => Ok(Value::Undefined),
+    }
+}
+
+fn get_paragraph_length<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(index_arg(activation, args)?
+        .and_then(|index| text_field.paragraph_length(index))
+        .map_or(-1.0, |length| length as f64)
+        .into())
+}
+
 pub fn remove_text_field<'gc>(
     text_field: EditText<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
@@ -380,6 +554,32 @@ pub fn set_html_text<'gc>(
     Ok(())
 }
 
+pub fn style_sheet<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    match this.style_sheet_object() {
+        Some(AvmObject::Avm1(object)) => Ok(object.into()),
+        _ => Ok(Value::Undefined),
+    }
+}
+
+pub fn set_style_sheet<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let style_sheet = match value {
+        Value::Object(object) => {
+            let style_sheet = style_sheet_from_object(activation, object)?;
+            Some((AvmObject::Avm1(object), style_sheet))
+        }
+        _ => None,
+    };
+    this.set_style_sheet(&mut activation.context, style_sheet);
+    Ok(())
+}
+
 pub fn background<'gc>(
     this: EditText<'gc>,
     _activation: &mut Activation<'_, 'gc, '_>,
@@ -507,6 +707,70 @@ pub fn set_multiline<'gc>(
     Ok(())
 }
 
+pub fn condense_white<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.condense_white().into())
+}
+
+pub fn set_condense_white<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let condense_white = value.as_bool(activation.swf_version());
+    this.set_condense_white(condense_white, activation.context.gc_context);
+    Ok(())
+}
+
+pub fn max_chars<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Unlimited `maxChars` is returned as null.
+    match this.max_chars() {
+        0 => Ok(Value::Null),
+        max_chars => Ok(max_chars.into()),
+    }
+}
+
+pub fn set_max_chars<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let max_chars = match value {
+        Value::Undefined | Value::Null => 0,
+        value => value.coerce_to_i32(activation)?,
+    };
+    this.set_max_chars(max_chars, activation.context.gc_context);
+    Ok(())
+}
+
+pub fn restrict<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    match this.restrict() {
+        Some(restrict) => Ok(AvmString::new(activation.context.gc_context, restrict).into()),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn set_restrict<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let restrict = match value {
+        Value::Undefined | Value::Null => None,
+        value => Some(value.coerce_to_string(activation)?),
+    };
+    this.set_restrict(restrict.as_deref(), activation.context.gc_context);
+    Ok(())
+}
+
 pub fn selectable<'gc>(
     this: EditText<'gc>,
     _activation: &mut Activation<'_, 'gc, '_>,
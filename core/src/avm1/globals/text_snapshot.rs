@@ -0,0 +1,435 @@
+//! TextSnapshot object
+
+use crate::avm1::object::NativeObject;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, ArrayObject, Error, Object, ScriptObject, TObject, Value};
+use crate::display_object::{
+    MovieClip, StaticGlyph, TDisplayObject, TDisplayObjectContainer, Text,
+};
+use crate::string::{utils as string_utils, AvmString, WString};
+use gc_arena::MutationContext;
+use ruffle_render::bounding_box::BoundingBox;
+use ruffle_render::matrix::Matrix;
+use swf::{Color, Twips};
+
+macro_rules! snapshot_method {
+    ( $fn:expr ) => {
+        |activation, this, args| {
+            if let NativeObject::TextSnapshot(movie_clip) = this.native() {
+                return $fn(movie_clip, activation, args);
+            }
+            Ok(Value::Undefined)
+        }
+    };
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "findText" => method(snapshot_method!(find_text); DONT_ENUM | DONT_DELETE);
+    "getCount" => method(snapshot_method!(get_count); DONT_ENUM | DONT_DELETE);
+    "getSelected" => method(snapshot_method!(get_selected); DONT_ENUM | DONT_DELETE);
+    "getSelectedText" => method(snapshot_method!(get_selected_text); DONT_ENUM | DONT_DELETE);
+    "getText" => method(snapshot_method!(get_text); DONT_ENUM | DONT_DELETE);
+    "getTextRunInfo" => method(snapshot_method!(get_text_run_info); DONT_ENUM | DONT_DELETE);
+    "hitTestTextNearPos" => method(snapshot_method!(hit_test_text_near_pos); DONT_ENUM | DONT_DELETE);
+    "setSelectColor" => method(snapshot_method!(set_select_color); DONT_ENUM | DONT_DELETE);
+    "setSelected" => method(snapshot_method!(set_selected); DONT_ENUM | DONT_DELETE);
+};
+
+/// A single character of the static text in a movie clip.
+struct SnapshotChar<'gc> {
+    /// The static text containing this character.
+    text: Text<'gc>,
+
+    /// The index of this character within `text`.
+    index: usize,
+
+    glyph: StaticGlyph<'gc>,
+
+    /// Whether this character starts a new line of text.
+    is_line_start: bool,
+}
+
+impl<'gc> SnapshotChar<'gc> {
+    /// The matrix from the text space of this character into the space of
+    /// the movie clip.
+    fn text_matrix(&self) -> Matrix {
+        *self.text.base().matrix() * self.text.text_transform()
+    }
+
+    /// The box covering this character, in the space of the movie clip.
+    fn bounds(&self) -> BoundingBox {
+        self.glyph.bounds().transform(&self.text_matrix())
+    }
+}
+
+/// Collect the characters of all static text directly inside `movie_clip`,
+/// in depth order.
+fn characters<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Vec<SnapshotChar<'gc>> {
+    let mut characters = Vec::new();
+    for child in movie_clip.iter_render_list() {
+        if let Some(text) = child.as_text() {
+            let mut line_y = None;
+            for (index, glyph) in text
+                .glyphs(activation.context.library)
+                .into_iter()
+                .enumerate()
+            {
+                let is_line_start = line_y != Some(glyph.matrix.ty);
+                line_y = Some(glyph.matrix.ty);
+                characters.push(SnapshotChar {
+                    text,
+                    index,
+                    glyph,
+                    is_line_start,
+                });
+            }
+        }
+    }
+    characters
+}
+
+/// Build the text of the given characters, optionally separating lines with
+/// newlines.
+fn characters_to_string<'a, 'gc: 'a>(
+    characters: impl Iterator<Item = &'a SnapshotChar<'gc>>,
+    include_line_endings: bool,
+) -> WString {
+    let mut text = WString::new();
+    for character in characters {
+        if include_line_endings && character.is_line_start && !text.is_empty() {
+            text.push_char('\n');
+        }
+        text.push(character.glyph.code);
+    }
+    text
+}
+
+/// Clamp a `start`/`end` argument pair to the characters of the snapshot.
+fn index_range<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    count: usize,
+) -> Result<std::ops::Range<usize>, Error<'gc>> {
+    let start = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+    let end = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+    let start = (start.max(0.0) as usize).min(count);
+    let end = (end.max(0.0) as usize).clamp(start, count);
+    Ok(start..end)
+}
+
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.into())
+}
+
+/// Create a `TextSnapshot` of the static text in a movie clip.
+pub fn create_text_snapshot<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    movie_clip: MovieClip<'gc>,
+) -> Object<'gc> {
+    let snapshot = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().text_snapshot),
+    );
+    snapshot.set_native(
+        activation.context.gc_context,
+        NativeObject::TextSnapshot(movie_clip),
+    );
+    snapshot.into()
+}
+
+fn find_text<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let start = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+    let needle = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let case_sensitive = args
+        .get(2)
+        .unwrap_or(&Value::Undefined)
+        .as_bool(activation.swf_version());
+
+    let characters = characters(movie_clip, activation);
+    if start < 0.0 || start as usize >= characters.len() || needle.is_empty() {
+        return Ok((-1).into());
+    }
+    let start = start as usize;
+
+    let mut haystack = characters_to_string(characters[start..].iter(), false);
+    let mut needle = WString::from(needle.as_wstr());
+    if !case_sensitive {
+        haystack = haystack
+            .iter()
+            .map(string_utils::swf_to_lowercase)
+            .collect();
+        needle = needle.iter().map(string_utils::swf_to_lowercase).collect();
+    }
+
+    Ok(match haystack.find(&needle[..]) {
+        Some(index) => ((start + index) as f64).into(),
+        None => (-1).into(),
+    })
+}
+
+fn get_count<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(characters(movie_clip, activation).len().into())
+}
+
+fn get_selected<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let characters = characters(movie_clip, activation);
+    let range = index_range(activation, args, characters.len())?;
+    Ok(characters[range]
+        .iter()
+        .any(|c| c.text.is_selected(c.index))
+        .into())
+}
+
+fn get_selected_text<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let include_line_endings = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .as_bool(activation.swf_version());
+
+    let characters = characters(movie_clip, activation);
+    let text = characters_to_string(
+        characters.iter().filter(|c| c.text.is_selected(c.index)),
+        include_line_endings,
+    );
+    Ok(AvmString::new(activation.context.gc_context, text).into())
+}
+
+fn get_text<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let include_line_endings = args
+        .get(2)
+        .unwrap_or(&Value::Undefined)
+        .as_bool(activation.swf_version());
+
+    let characters = characters(movie_clip, activation);
+    let range = index_range(activation, args, characters.len())?;
+    let text = characters_to_string(characters[range].iter(), include_line_endings);
+    Ok(AvmString::new(activation.context.gc_context, text).into())
+}
+
+fn get_text_run_info<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let characters = characters(movie_clip, activation);
+    let begin = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+    let end = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+
+    let mut runs = Vec::new();
+    if begin >= 0.0 && end >= begin {
+        // Unlike the other methods, the end index is inclusive.
+        let begin = begin as usize;
+        let end = (end as usize + 1).min(characters.len());
+        for character in characters.get(begin..end).unwrap_or_default() {
+            runs.push(text_run_info(activation, character)?);
+        }
+    }
+
+    Ok(ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        runs,
+    )
+    .into())
+}
+
+fn text_run_info<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    character: &SnapshotChar<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let info = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+
+    // The matrix places the character, without the scaling of the glyph.
+    let glyph_matrix = character.glyph.matrix;
+    let matrix = character.text_matrix()
+        * Matrix {
+            tx: glyph_matrix.tx,
+            ty: glyph_matrix.ty,
+            ..Default::default()
+        };
+    let bounds = character.glyph.bounds();
+    let text_matrix = character.text_matrix();
+    let corners = [
+        (bounds.x_min, bounds.y_max),
+        (bounds.x_max, bounds.y_max),
+        (bounds.x_max, bounds.y_min),
+        (bounds.x_min, bounds.y_min),
+    ];
+
+    let font_name = AvmString::new_utf8(
+        activation.context.gc_context,
+        character.glyph.font.descriptor().class(),
+    );
+    info.set("indexInRun", character.index.into(), activation)?;
+    info.set(
+        "selected",
+        character.text.is_selected(character.index).into(),
+        activation,
+    )?;
+    info.set("font", font_name.into(), activation)?;
+    info.set("color", character.glyph.color.to_rgb().into(), activation)?;
+    info.set(
+        "height",
+        character.glyph.height.to_pixels().into(),
+        activation,
+    )?;
+    info.set("matrix_a", matrix.a.into(), activation)?;
+    info.set("matrix_b", matrix.b.into(), activation)?;
+    info.set("matrix_c", matrix.c.into(), activation)?;
+    info.set("matrix_d", matrix.d.into(), activation)?;
+    info.set("matrix_tx", matrix.tx.to_pixels().into(), activation)?;
+    info.set("matrix_ty", matrix.ty.to_pixels().into(), activation)?;
+    for (i, corner) in corners.into_iter().enumerate() {
+        let (x, y) = text_matrix * corner;
+        info.set(
+            AvmString::new_utf8(activation.context.gc_context, format!("corner{}x", i)),
+            x.to_pixels().into(),
+            activation,
+        )?;
+        info.set(
+            AvmString::new_utf8(activation.context.gc_context, format!("corner{}y", i)),
+            y.to_pixels().into(),
+            activation,
+        )?;
+    }
+
+    Ok(info.into())
+}
+
+fn hit_test_text_near_pos<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let x = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+    let y = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+    let close_distance = args
+        .get(2)
+        .unwrap_or(&0.into())
+        .coerce_to_f64(activation)?
+        .max(0.0);
+    let (x, y) = (Twips::from_pixels(x), Twips::from_pixels(y));
+    let close_distance = Twips::from_pixels(close_distance);
+
+    // Find the closest character within the given distance.
+    let mut closest: Option<(usize, Twips)> = None;
+    for (i, character) in characters(movie_clip, activation).iter().enumerate() {
+        let bounds = character.bounds();
+        let dx = (bounds.x_min - x).max(x - bounds.x_max).max(Twips::ZERO);
+        let dy = (bounds.y_min - y).max(y - bounds.y_max).max(Twips::ZERO);
+        let distance = dx.max(dy);
+        if distance <= close_distance && closest.map_or(true, |(_, d)| distance < d) {
+            closest = Some((i, distance));
+        }
+    }
+
+    Ok(match closest {
+        Some((i, _)) => i.into(),
+        None => (-1).into(),
+    })
+}
+
+fn set_select_color<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let color = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_u32(activation)?;
+    for child in movie_clip.iter_render_list() {
+        if let Some(text) = child.as_text() {
+            text.set_select_color(activation.context.gc_context, Color::from_rgb(color, 0xFF));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn set_selected<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let selected = args
+        .get(2)
+        .unwrap_or(&true.into())
+        .as_bool(activation.swf_version());
+
+    let characters = characters(movie_clip, activation);
+    let range = index_range(activation, args, characters.len())?;
+    for character in &characters[range] {
+        character.text.set_selected(
+            activation.context.gc_context,
+            character.index..character.index + 1,
+            selected,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, gc_context, object, fn_proto);
+    object.into()
+}
@@ -205,11 +205,17 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
             }
         }
 
-        // 3) Child display objects with the given instance name
+        // 3) Child display objects with the given instance name, including
+        //    images embedded in a text field's HTML
         if let Some(child) = obj
             .display_object
             .as_container()
             .and_then(|o| o.child_by_name(&name, activation.is_case_sensitive()))
+            .or_else(|| {
+                obj.display_object
+                    .as_edit_text()
+                    .and_then(|o| o.image_by_id(&name, activation.is_case_sensitive()))
+            })
         {
             return Some(child.object());
         }
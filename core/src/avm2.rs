@@ -6,9 +6,12 @@ use crate::avm2::globals::SystemClasses;
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::script::{Script, TranslationUnit};
 use crate::context::UpdateContext;
+use crate::display_object::TDisplayObject;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use fnv::FnvHashMap;
 use gc_arena::{Collect, GcCell, MutationContext};
+use std::sync::Arc;
 use swf::avm2::read::Reader;
 use swf::{DoAbc, DoAbcFlag};
 
@@ -227,6 +230,50 @@ impl<'gc> Avm2<'gc> {
         bucket.push(object);
     }
 
+    /// Remove all display objects belonging to the given movie from every
+    /// broadcast list.
+    pub fn unregister_broadcast_listeners_for_movie(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        movie: &Arc<SwfMovie>,
+    ) {
+        for bucket in context.avm2.broadcast_list.values_mut() {
+            bucket.retain(|object| {
+                object
+                    .as_display_object()
+                    .map_or(true, |dobj| !Arc::ptr_eq(&dobj.movie(), movie))
+            });
+        }
+    }
+
+    /// Remove every event listener on `target` whose handler was defined by
+    /// the given movie.
+    pub fn remove_event_listeners_for_movie(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        movie: &Arc<SwfMovie>,
+    ) -> Result<(), Error<'gc>> {
+        use crate::avm2::events::NS_EVENT_DISPATCHER;
+        let mut activation = Activation::from_nothing(context.reborrow());
+        let dispatch_list = target.get_property(
+            &Multiname::new(Namespace::private(NS_EVENT_DISPATCHER), "dispatch_list"),
+            &mut activation,
+        )?;
+
+        if let Some(mut dispatch_list) = dispatch_list
+            .as_object()
+            .and_then(|o| o.as_dispatch_mut(activation.context.gc_context))
+        {
+            dispatch_list.retain_handlers(|handler| {
+                handler
+                    .as_executable()
+                    .and_then(|exec| exec.movie())
+                    .map_or(true, |owner| !Arc::ptr_eq(&owner, movie))
+            });
+        }
+
+        Ok(())
+    }
+
     /// Dispatch an event on all objects in the current execution list.
     ///
     /// `on_type` specifies a class or interface constructor whose instances,
@@ -296,6 +343,7 @@ impl<'gc> Avm2<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         do_abc: DoAbc,
         domain: Domain<'gc>,
+        movie: Arc<SwfMovie>,
     ) -> Result<(), Error<'gc>> {
         let mut reader = Reader::new(do_abc.data);
         let abc = match reader.read() {
@@ -311,7 +359,7 @@ impl<'gc> Avm2<'gc> {
         };
 
         let num_scripts = abc.scripts.len();
-        let tunit = TranslationUnit::from_abc(abc, domain, context.gc_context);
+        let tunit = TranslationUnit::from_abc(abc, domain, movie, context.gc_context);
         for i in (0..num_scripts).rev() {
             let mut script = tunit.load_script(i as u32, context)?;
 
@@ -19,10 +19,12 @@ use crate::avm2::{value, Avm2, Error};
 use crate::context::UpdateContext;
 use crate::string::{AvmString, WStr, WString};
 use crate::swf::extensions::ReadSwfExt;
+use crate::tag_utils::SwfMovie;
 use gc_arena::{Gc, GcCell};
 use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::cmp::{min, Ordering};
+use std::sync::Arc;
 use swf::avm2::read::Reader;
 use swf::avm2::types::{
     Class as AbcClass, Exception, Index, Method as AbcMethod, MethodFlags as AbcMethodFlags,
@@ -626,6 +628,18 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         self.caller_domain
     }
 
+    /// Returns the movie whose code called the currently executing builtin method.
+    ///
+    /// This falls back to the root movie when no bytecode is running.
+    pub fn caller_movie(&self) -> Arc<SwfMovie> {
+        self.context
+            .avm2
+            .call_stack()
+            .read()
+            .innermost_movie()
+            .unwrap_or_else(|| self.context.swf.clone())
+    }
+
     /// Returns the global scope of this activation.
     ///
     /// The global scope refers to scope at the bottom of the
@@ -1,6 +1,8 @@
 use crate::avm2::function::Executable;
 use crate::string::WString;
+use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
+use std::sync::Arc;
 
 #[derive(Collect, Debug, Clone)]
 #[collect(no_drop)]
@@ -32,6 +34,14 @@ impl<'gc> CallStack<'gc> {
         self.stack.pop()
     }
 
+    /// The movie that defined the innermost bytecode method on the stack.
+    pub fn innermost_movie(&self) -> Option<Arc<SwfMovie>> {
+        self.stack.iter().rev().find_map(|call| match call {
+            CallNode::GlobalInit => None,
+            CallNode::Method(exec) => exec.movie(),
+        })
+    }
+
     pub fn display(&self, output: &mut WString) {
         for call in self.stack.iter().rev() {
             output.push_utf8("\n\tat ");
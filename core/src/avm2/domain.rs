@@ -20,7 +20,7 @@ pub struct Domain<'gc>(GcCell<'gc, DomainData<'gc>>);
 #[collect(no_drop)]
 struct DomainData<'gc> {
     /// A list of all exported definitions and the script that exported them.
-    defs: PropertyMap<'gc, Script<'gc>>,
+    defs: PropertyMap<'gc, Definition<'gc>>,
 
     /// The parent domain.
     parent: Option<Domain<'gc>>,
@@ -34,7 +34,22 @@ struct DomainData<'gc> {
     pub domain_memory: Option<ByteArrayObject<'gc>>,
 }
 
+/// A definition exported into a domain.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+struct Definition<'gc> {
+    /// The script that exported the definition.
+    script: Script<'gc>,
+
+    /// Whether the definition was declared in a package, rather than in a
+    /// user-defined namespace such as `mx_internal`.
+    in_package: bool,
+}
+
 impl<'gc> Domain<'gc> {
+    /// The minimum length, in bytes, of a `ByteArray` used as domain memory.
+    pub const MIN_DOMAIN_MEMORY_LENGTH: usize = 1024;
+
     /// Create a new domain with no parent.
     ///
     /// This is intended exclusively for creating the player globals domain,
@@ -85,6 +100,11 @@ impl<'gc> Domain<'gc> {
         self.0.read().parent
     }
 
+    /// Check if two domains are the same domain.
+    pub fn ptr_eq(a: Domain<'gc>, b: Domain<'gc>) -> bool {
+        GcCell::ptr_eq(a.0, b.0)
+    }
+
     /// Determine if something has been defined within the current domain.
     pub fn has_definition(self, name: QName<'gc>) -> bool {
         let read = self.0.read();
@@ -100,8 +120,25 @@ impl<'gc> Domain<'gc> {
         false
     }
 
+    /// List the names of all definitions exported into this domain from a
+    /// package.
+    ///
+    /// Unlike lookups, this does not include definitions from parent domains.
+    pub fn package_definition_names(self) -> Vec<QName<'gc>> {
+        self.0
+            .read()
+            .defs
+            .iter()
+            .filter(|(_, _, def)| def.in_package)
+            .map(|(name, ns, _)| QName::new(ns, name))
+            .collect()
+    }
+
     /// Resolve a QName and return the script that provided it.
     ///
+    /// Only this domain and its parents are searched, so definitions from
+    /// sibling or child domains are never returned.
+    ///
     /// If a name does not exist or cannot be resolved, no script or name will
     /// be returned.
     pub fn get_defining_script(
@@ -111,9 +148,9 @@ impl<'gc> Domain<'gc> {
         let read = self.0.read();
 
         if let Some(name) = multiname.local_name() {
-            if let Some((ns, script)) = read.defs.get_with_ns_for_multiname(multiname) {
+            if let Some((ns, def)) = read.defs.get_with_ns_for_multiname(multiname) {
                 let qname = QName::new(ns, name);
-                return Ok(Some((qname, *script)));
+                return Ok(Some((qname, def.script)));
             }
         }
 
@@ -150,12 +187,16 @@ impl<'gc> Domain<'gc> {
 
     /// Export a definition from a script into the current application domain.
     ///
+    /// `in_package` records whether the definition was declared in a package,
+    /// which decides if `getQualifiedDefinitionNames` lists it.
+    ///
     /// This returns an error if the name is already defined in the current or
     /// any parent domains.
     pub fn export_definition(
         &mut self,
         name: QName<'gc>,
         script: Script<'gc>,
+        in_package: bool,
         mc: MutationContext<'gc, '_>,
     ) -> Result<(), Error<'gc>> {
         if self.has_definition(name) {
@@ -166,7 +207,10 @@ impl<'gc> Domain<'gc> {
             .into());
         }
 
-        self.0.write(mc).defs.insert(name, script);
+        self.0
+            .write(mc)
+            .defs
+            .insert(name, Definition { script, in_package });
 
         Ok(())
     }
@@ -202,7 +246,7 @@ impl<'gc> Domain<'gc> {
         domain_memory
             .as_bytearray_mut(activation.context.gc_context)
             .unwrap()
-            .set_length(1024);
+            .set_length(Self::MIN_DOMAIN_MEMORY_LENGTH);
 
         let mut write = self.0.write(activation.context.gc_context);
         write
@@ -31,6 +31,17 @@ static_assertions::assert_eq_size!(Result<Value<'_>, Error<'_>>, [u8; 24]);
 #[cfg(target_pointer_width = "64")]
 static_assertions::assert_eq_size!(Result<Value<'_>, Error<'_>>, [u8; 32]);
 
+#[inline(never)]
+#[cold]
+pub fn error<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    message: &str,
+    code: u32,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let class = activation.avm2().classes().error;
+    error_constructor(activation, class, message, code)
+}
+
 #[inline(never)]
 #[cold]
 pub fn range_error<'gc>(
@@ -108,6 +119,28 @@ pub fn eof_error<'gc>(
     error_constructor(activation, class, message, code)
 }
 
+#[inline(never)]
+#[cold]
+pub fn security_error<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    message: &str,
+    code: u32,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let class = activation.avm2().classes().securityerror;
+    error_constructor(activation, class, message, code)
+}
+
+#[inline(never)]
+#[cold]
+pub fn illegal_operation_error<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    message: &str,
+    code: u32,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let class = activation.avm2().classes().illegaloperationerror;
+    error_constructor(activation, class, message, code)
+}
+
 fn error_constructor<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     class: ClassObject<'gc>,
@@ -6,6 +6,7 @@ use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
+use crate::context::ActionType;
 use crate::display_object::TDisplayObject;
 use crate::string::AvmString;
 use fnv::FnvHashMap;
@@ -264,6 +265,17 @@ impl<'gc> DispatchList<'gc> {
         }
     }
 
+    /// Remove every event handler for which `predicate` returns `false`.
+    ///
+    /// This applies across all event types, priorities and phases.
+    pub fn retain_handlers(&mut self, mut predicate: impl FnMut(Object<'gc>) -> bool) {
+        for event_sheaf in self.0.values_mut() {
+            for set in event_sheaf.values_mut() {
+                set.retain(|h| predicate(h.handler));
+            }
+        }
+    }
+
     /// Determine if there are any event listeners in this dispatch list.
     pub fn has_event_listener(&self, event: impl Into<AvmString<'gc>>) -> bool {
         if let Some(event_sheaf) = self.get_event(event) {
@@ -431,6 +443,32 @@ pub fn dispatch_event_to_target<'gc>(
     Ok(())
 }
 
+/// Queue `event` to be dispatched on `target` once the current script has
+/// finished running.
+///
+/// Flash Player reports the results of asynchronous operations this way,
+/// even when they complete immediately.
+pub fn queue_dispatch_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let dispatch_event = target
+        .get_property(&Multiname::public("dispatchEvent"), activation)?
+        .coerce_to_object(activation)?;
+    let root = activation.context.stage.root_clip();
+    activation.context.action_queue.queue_action(
+        root,
+        ActionType::Callable2 {
+            callable: dispatch_event,
+            reciever: Some(target),
+            args: vec![event.into()],
+        },
+        false,
+    );
+    Ok(())
+}
+
 pub fn dispatch_event<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
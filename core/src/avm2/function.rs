@@ -8,8 +8,10 @@ use crate::avm2::traits::TraitKind;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::string::WString;
+use crate::tag_utils::SwfMovie;
 use gc_arena::{Collect, Gc};
 use std::fmt;
+use std::sync::Arc;
 
 /// Represents code written in AVM2 bytecode that can be executed by some
 /// means.
@@ -189,6 +191,18 @@ impl<'gc> Executable<'gc> {
         }
     }
 
+    /// The movie that defined this executable's code.
+    ///
+    /// Native executables are not owned by any movie and yield `None`.
+    pub fn movie(&self) -> Option<Arc<SwfMovie>> {
+        match self {
+            Executable::Native(_) => None,
+            Executable::Action(BytecodeExecutable { method, .. }) => {
+                Some(method.translation_unit().movie())
+            }
+        }
+    }
+
     pub fn write_full_name(&self, output: &mut WString) {
         let class_def = self.bound_superclass().map(|superclass| {
             let class_def = superclass.inner_class_definition();
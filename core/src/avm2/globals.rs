@@ -95,11 +95,15 @@ pub struct SystemClasses<'gc> {
     pub transform: ClassObject<'gc>,
     pub colortransform: ClassObject<'gc>,
     pub matrix: ClassObject<'gc>,
+    pub matrix3d: ClassObject<'gc>,
+    pub vector3d: ClassObject<'gc>,
+    pub perspectiveprojection: ClassObject<'gc>,
     pub illegaloperationerror: ClassObject<'gc>,
     pub eventdispatcher: ClassObject<'gc>,
     pub rectangle: ClassObject<'gc>,
     pub keyboardevent: ClassObject<'gc>,
     pub point: ClassObject<'gc>,
+    pub error: ClassObject<'gc>,
     pub rangeerror: ClassObject<'gc>,
     pub referenceerror: ClassObject<'gc>,
     pub argumenterror: ClassObject<'gc>,
@@ -107,9 +111,11 @@ pub struct SystemClasses<'gc> {
     pub verifyerror: ClassObject<'gc>,
     pub ioerror: ClassObject<'gc>,
     pub eoferror: ClassObject<'gc>,
+    pub securityerror: ClassObject<'gc>,
     pub uncaughterrorevents: ClassObject<'gc>,
     pub statictext: ClassObject<'gc>,
     pub textlinemetrics: ClassObject<'gc>,
+    pub textline: ClassObject<'gc>,
     pub stage3d: ClassObject<'gc>,
     pub context3d: ClassObject<'gc>,
     pub indexbuffer3d: ClassObject<'gc>,
@@ -180,11 +186,15 @@ impl<'gc> SystemClasses<'gc> {
             transform: object,
             colortransform: object,
             matrix: object,
+            matrix3d: object,
+            vector3d: object,
+            perspectiveprojection: object,
             illegaloperationerror: object,
             eventdispatcher: object,
             rectangle: object,
             keyboardevent: object,
             point: object,
+            error: object,
             rangeerror: object,
             referenceerror: object,
             argumenterror: object,
@@ -192,9 +202,11 @@ impl<'gc> SystemClasses<'gc> {
             verifyerror: object,
             ioerror: object,
             eoferror: object,
+            securityerror: object,
             uncaughterrorevents: object,
             statictext: object,
             textlinemetrics: object,
+            textline: object,
             stage3d: object,
             context3d: object,
             indexbuffer3d: object,
@@ -218,7 +230,7 @@ fn function<'gc>(
     let qname = QName::new(Namespace::package(package), name);
     let method = Method::from_builtin(nf, name, mc);
     let as3fn = FunctionObject::from_method(activation, method, scope, None, None).into();
-    domain.export_definition(qname, script, mc)?;
+    domain.export_definition(qname, script, true, mc)?;
     global.install_const_late(mc, qname, as3fn, activation.avm2().classes().function);
 
     Ok(())
@@ -240,7 +252,7 @@ fn dynamic_class<'gc>(
     let name = class.read().name();
 
     global.install_const_late(mc, name, class_object.into(), class_class);
-    domain.export_definition(name, script, mc)
+    domain.export_definition(name, script, true, mc)
 }
 
 /// Add a class builtin to the global scope.
@@ -290,7 +302,7 @@ fn class<'gc>(
         class_object.into(),
         activation.avm2().classes().class,
     );
-    domain.export_definition(class_name, script, activation.context.gc_context)?;
+    domain.export_definition(class_name, script, true, activation.context.gc_context)?;
 
     Ok(class_object)
 }
@@ -420,6 +432,13 @@ pub fn load_player_globals<'gc>(
         toplevel::log_warn,
         script,
     )?;
+    function(
+        activation,
+        "__ruffle__",
+        "stub_method",
+        toplevel::stub_method,
+        script,
+    )?;
     function(activation, "", "isFinite", toplevel::is_finite, script)?;
     function(activation, "", "isNaN", toplevel::is_nan, script)?;
     function(activation, "", "parseInt", toplevel::parse_int, script)?;
@@ -601,7 +620,8 @@ fn load_playerglobal<'gc>(
     let movie =
         SwfMovie::from_data(PLAYERGLOBAL, None, None).expect("playerglobal.swf should be valid");
 
-    let slice = SwfSlice::from(Arc::new(movie));
+    let movie = Arc::new(movie);
+    let slice = SwfSlice::from(movie.clone());
 
     let mut reader = slice.read_from(0);
 
@@ -610,7 +630,7 @@ fn load_playerglobal<'gc>(
             let do_abc = reader
                 .read_do_abc()
                 .expect("playerglobal.swf should be valid");
-            Avm2::do_abc(&mut activation.context, do_abc, domain)
+            Avm2::do_abc(&mut activation.context, do_abc, domain, movie.clone())
                 .expect("playerglobal.swf should be valid");
         } else if tag_code != TagCode::End {
             panic!("playerglobal should only contain `DoAbc` tag - found tag {tag_code:?}")
@@ -638,8 +658,10 @@ fn load_playerglobal<'gc>(
         script,
         [
             ("", "ArgumentError", argumenterror),
+            ("", "Error", error),
             ("", "RangeError", rangeerror),
             ("", "ReferenceError", referenceerror),
+            ("", "SecurityError", securityerror),
             ("", "TypeError", typeerror),
             ("", "VerifyError", verifyerror),
             ("", "XML", xml),
@@ -669,6 +691,9 @@ fn load_playerglobal<'gc>(
             ("flash.events", "FullScreenEvent", fullscreenevent),
             ("flash.events", "UncaughtErrorEvents", uncaughterrorevents),
             ("flash.geom", "Matrix", matrix),
+            ("flash.geom", "Matrix3D", matrix3d),
+            ("flash.geom", "PerspectiveProjection", perspectiveprojection),
+            ("flash.geom", "Vector3D", vector3d),
             ("flash.geom", "Point", point),
             ("flash.geom", "Rectangle", rectangle),
             ("flash.geom", "Transform", transform),
@@ -676,6 +701,7 @@ fn load_playerglobal<'gc>(
             ("flash.utils", "ByteArray", bytearray),
             ("flash.text", "StaticText", statictext),
             ("flash.text", "TextLineMetrics", textlinemetrics),
+            ("flash.text.engine", "TextLine", textline),
         ]
     );
 
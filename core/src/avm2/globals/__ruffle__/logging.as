@@ -1,3 +1,4 @@
 package __ruffle__ {
     public native function log_warn(...arguments);
+    public native function stub_method(className: String, methodName: String);
 }
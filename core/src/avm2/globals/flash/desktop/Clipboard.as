@@ -0,0 +1,87 @@
+package flash.desktop {
+    namespace ruffle = "__ruffle__";
+
+    public class Clipboard {
+        private static var _generalClipboard: Clipboard = null;
+
+        // Data in formats that aren't shared with the system clipboard,
+        // keyed by format.
+        ruffle var _data: Object = {};
+
+        public function Clipboard() {
+
+        }
+
+        public static function get generalClipboard(): Clipboard {
+            if (_generalClipboard == null) {
+                _generalClipboard = new Clipboard();
+            }
+            return _generalClipboard;
+        }
+
+        public function get formats(): Array {
+            var formats: Array = [];
+            if (this.usesSystemClipboard(ClipboardFormats.TEXT_FORMAT) && this.hasFormat(ClipboardFormats.TEXT_FORMAT)) {
+                formats.push(ClipboardFormats.TEXT_FORMAT);
+            }
+            for (var format: String in this.ruffle::_data) {
+                formats.push(format);
+            }
+            return formats;
+        }
+
+        public function clear(): void {
+            this.ruffle::_data = {};
+            if (this.usesSystemClipboard(ClipboardFormats.TEXT_FORMAT)) {
+                this.setSystemText("");
+            }
+        }
+
+        public function clearData(format: String): void {
+            if (this.usesSystemClipboard(format)) {
+                this.setSystemText("");
+            } else {
+                delete this.ruffle::_data[format];
+            }
+        }
+
+        public function getData(format: String, transferMode: String = "originalPreferred"): Object {
+            if (this.usesSystemClipboard(format)) {
+                var text: String = this.getSystemText();
+                return text == "" ? null : text;
+            }
+            if (format in this.ruffle::_data) {
+                return this.ruffle::_data[format];
+            }
+            return null;
+        }
+
+        public function hasFormat(format: String): Boolean {
+            return this.getData(format) != null;
+        }
+
+        public function setData(format: String, data: Object, serializable: Boolean = true): Boolean {
+            if (this.usesSystemClipboard(format)) {
+                this.setSystemText(String(data));
+            } else {
+                this.ruffle::_data[format] = data;
+            }
+            return true;
+        }
+
+        public function setDataHandler(format: String, handler: Function, serializable: Boolean = true): Boolean {
+            // The data is requested straight away, rather than when it's pasted.
+            return this.setData(format, handler(), serializable);
+        }
+
+        // Plain text on the general clipboard is exchanged with the system
+        // clipboard. Everything else, such as rich text, stays in the player.
+        private function usesSystemClipboard(format: String): Boolean {
+            return this === _generalClipboard && format == ClipboardFormats.TEXT_FORMAT;
+        }
+
+        private native function getSystemText(): String;
+
+        private native function setSystemText(text: String): void;
+    }
+}
@@ -0,0 +1,32 @@
+//! `flash.desktop.Clipboard` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::string::AvmString;
+
+/// Implements `Clipboard.getSystemText`
+pub fn get_system_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let content = activation.context.ui.clipboard_content();
+    Ok(AvmString::new_utf8(activation.context.gc_context, content).into())
+}
+
+/// Implements `Clipboard.setSystemText`
+pub fn set_system_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let content = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_string();
+    activation.context.ui.set_clipboard_content(content);
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,16 @@
+// This is a stub - the actual class is defined in `graphics.rs`
+// `flash.geom.Matrix` and typed vectors aren't available to the stubs, so those
+// parameters are untyped here.
+package flash.display {
+    public final class Graphics {
+        public native function beginBitmapFill(bitmap:BitmapData, matrix:* = null, repeat:Boolean = true, smooth:Boolean = false):void;
+        public native function beginFill(color:uint, alpha:Number = 1.0):void;
+        public native function beginGradientFill(type:String, colors:Array, alphas:Array, ratios:Array, matrix:* = null, spreadMethod:String = "pad", interpolationMethod:String = "rgb", focalPointRatio:Number = 0):void;
+        public native function drawPath(commands:*, data:*, winding:String = "evenOdd"):void;
+        public native function drawTriangles(vertices:*, indices:* = null, uvtData:* = null, culling:String = "none"):void;
+        public native function endFill():void;
+        public native function lineBitmapStyle(bitmap:BitmapData, matrix:* = null, repeat:Boolean = true, smooth:Boolean = false):void;
+        public native function lineGradientStyle(type:String, colors:Array, alphas:Array, ratios:Array, matrix:* = null, spreadMethod:String = "pad", interpolationMethod:String = "rgb", focalPointRatio:Number = 0):void;
+        public native function lineStyle(thickness:Number = NaN, color:uint = 0, alpha:Number = 1.0, pixelHinting:Boolean = false, scaleMode:String = "normal", caps:String = null, joints:String = null, miterLimit:Number = 3):void;
+    }
+}
@@ -0,0 +1,12 @@
+package flash.display {
+    namespace ruffle = "__ruffle__";
+
+    public final class GraphicsEndFill implements IGraphicsFill, IGraphicsData {
+        public function GraphicsEndFill() {
+        }
+
+        ruffle function draw(graphics: Graphics): void {
+            graphics.endFill();
+        }
+    }
+}
@@ -0,0 +1,69 @@
+package flash.display {
+    namespace ruffle = "__ruffle__";
+
+    public final class GraphicsPath implements IGraphicsPath, IGraphicsData {
+        public var commands: Vector.<int>;
+        public var data: Vector.<Number>;
+        private var _winding: String;
+
+        public function GraphicsPath(commands: Vector.<int> = null, data: Vector.<Number> = null, winding: String = "evenOdd") {
+            this.commands = commands;
+            this.data = data;
+            this.winding = winding;
+        }
+
+        public function get winding(): String {
+            return this._winding;
+        }
+
+        public function set winding(value: String): void {
+            if (value != GraphicsPathWinding.EVEN_ODD && value != GraphicsPathWinding.NON_ZERO) {
+                throw new ArgumentError("Error #2008: Parameter winding must be one of the accepted values.", 2008);
+            }
+            this._winding = value;
+        }
+
+        private function push(command: int, ...values): void {
+            if (this.commands == null) {
+                this.commands = new Vector.<int>();
+            }
+            if (this.data == null) {
+                this.data = new Vector.<Number>();
+            }
+            this.commands.push(command);
+            for each (var value: Number in values) {
+                this.data.push(value);
+            }
+        }
+
+        public function moveTo(x: Number, y: Number): void {
+            this.push(GraphicsPathCommand.MOVE_TO, x, y);
+        }
+
+        public function lineTo(x: Number, y: Number): void {
+            this.push(GraphicsPathCommand.LINE_TO, x, y);
+        }
+
+        public function curveTo(controlX: Number, controlY: Number, anchorX: Number, anchorY: Number): void {
+            this.push(GraphicsPathCommand.CURVE_TO, controlX, controlY, anchorX, anchorY);
+        }
+
+        public function cubicCurveTo(controlX1: Number, controlY1: Number, controlX2: Number, controlY2: Number, anchorX: Number, anchorY: Number): void {
+            this.push(GraphicsPathCommand.CUBIC_CURVE_TO, controlX1, controlY1, controlX2, controlY2, anchorX, anchorY);
+        }
+
+        public function wideLineTo(x: Number, y: Number): void {
+            this.push(GraphicsPathCommand.WIDE_LINE_TO, 0, 0, x, y);
+        }
+
+        public function wideMoveTo(x: Number, y: Number): void {
+            this.push(GraphicsPathCommand.WIDE_MOVE_TO, 0, 0, x, y);
+        }
+
+        ruffle function draw(graphics: Graphics): void {
+            if (this.commands != null && this.data != null) {
+                graphics.drawPath(this.commands, this.data, this.winding);
+            }
+        }
+    }
+}
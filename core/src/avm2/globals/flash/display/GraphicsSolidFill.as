@@ -0,0 +1,17 @@
+package flash.display {
+    namespace ruffle = "__ruffle__";
+
+    public final class GraphicsSolidFill implements IGraphicsFill, IGraphicsData {
+        public var color: uint;
+        public var alpha: Number;
+
+        public function GraphicsSolidFill(color: uint = 0, alpha: Number = 1.0) {
+            this.color = color;
+            this.alpha = alpha;
+        }
+
+        ruffle function draw(graphics: Graphics): void {
+            graphics.beginFill(this.color, this.alpha);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+package flash.display {
+    namespace ruffle = "__ruffle__";
+
+    public final class GraphicsStroke implements IGraphicsStroke, IGraphicsData {
+        public var thickness: Number;
+        public var pixelHinting: Boolean;
+        public var miterLimit: Number;
+        public var fill: IGraphicsFill;
+        private var _scaleMode: String;
+        private var _caps: String;
+        private var _joints: String;
+
+        public function GraphicsStroke(thickness: Number = NaN, pixelHinting: Boolean = false, scaleMode: String = "normal", caps: String = "none",
+                                       joints: String = "round", miterLimit: Number = 3.0, fill: IGraphicsFill = null) {
+            this.thickness = thickness;
+            this.pixelHinting = pixelHinting;
+            this.scaleMode = scaleMode;
+            this.caps = caps;
+            this.joints = joints;
+            this.miterLimit = miterLimit;
+            this.fill = fill;
+        }
+
+        public function get scaleMode(): String {
+            return this._scaleMode;
+        }
+
+        public function set scaleMode(value: String): void {
+            if (value != LineScaleMode.NORMAL && value != LineScaleMode.NONE && value != LineScaleMode.VERTICAL && value != LineScaleMode.HORIZONTAL) {
+                throw new ArgumentError("Error #2008: Parameter scaleMode must be one of the accepted values.", 2008);
+            }
+            this._scaleMode = value;
+        }
+
+        public function get caps(): String {
+            return this._caps;
+        }
+
+        public function set caps(value: String): void {
+            if (value != CapsStyle.NONE && value != CapsStyle.ROUND && value != CapsStyle.SQUARE) {
+                throw new ArgumentError("Error #2008: Parameter caps must be one of the accepted values.", 2008);
+            }
+            this._caps = value;
+        }
+
+        public function get joints(): String {
+            return this._joints;
+        }
+
+        public function set joints(value: String): void {
+            if (value != JointStyle.BEVEL && value != JointStyle.MITER && value != JointStyle.ROUND) {
+                throw new ArgumentError("Error #2008: Parameter joints must be one of the accepted values.", 2008);
+            }
+            this._joints = value;
+        }
+
+        ruffle function draw(graphics: Graphics): void {
+            var solidFill: GraphicsSolidFill = this.fill as GraphicsSolidFill;
+            var color: uint = solidFill != null ? solidFill.color : 0;
+            var alpha: Number = solidFill != null ? solidFill.alpha : 1.0;
+            graphics.lineStyle(this.thickness, color, alpha, this.pixelHinting, this.scaleMode, this.caps, this.joints, this.miterLimit);
+
+            if (this.fill is GraphicsGradientFill) {
+                var gradientFill: GraphicsGradientFill = this.fill as GraphicsGradientFill;
+                graphics.lineGradientStyle(gradientFill.type, gradientFill.colors, gradientFill.alphas, gradientFill.ratios, gradientFill.matrix,
+                                           gradientFill.spreadMethod, gradientFill.interpolationMethod, gradientFill.focalPointRatio);
+            } else if (this.fill is GraphicsBitmapFill) {
+                var bitmapFill: GraphicsBitmapFill = this.fill as GraphicsBitmapFill;
+                graphics.lineBitmapStyle(bitmapFill.bitmapData, bitmapFill.matrix, bitmapFill.repeat, bitmapFill.smooth);
+            }
+        }
+    }
+}
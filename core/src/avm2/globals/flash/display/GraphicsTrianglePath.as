@@ -0,0 +1,35 @@
+package flash.display {
+    namespace ruffle = "__ruffle__";
+
+    public final class GraphicsTrianglePath implements IGraphicsPath, IGraphicsData {
+        public var vertices: Vector.<Number>;
+        public var indices: Vector.<int>;
+        public var uvtData: Vector.<Number>;
+        private var _culling: String;
+
+        public function GraphicsTrianglePath(vertices: Vector.<Number> = null, indices: Vector.<int> = null, uvtData: Vector.<Number> = null,
+                                             culling: String = "none") {
+            this.vertices = vertices;
+            this.indices = indices;
+            this.uvtData = uvtData;
+            this.culling = culling;
+        }
+
+        public function get culling(): String {
+            return this._culling;
+        }
+
+        public function set culling(value: String): void {
+            if (value != TriangleCulling.NONE && value != TriangleCulling.POSITIVE && value != TriangleCulling.NEGATIVE) {
+                throw new ArgumentError("Error #2008: Parameter culling must be one of the accepted values.", 2008);
+            }
+            this._culling = value;
+        }
+
+        ruffle function draw(graphics: Graphics): void {
+            if (this.vertices != null) {
+                graphics.drawTriangles(this.vertices, this.indices, this.uvtData, this.culling);
+            }
+        }
+    }
+}
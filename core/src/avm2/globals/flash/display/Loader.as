@@ -24,16 +24,19 @@ package flash.display {
 			if (this.numChildren == 0) {
 				return null;
 			}
+			this.checkContentAccess();
 			return this.getChildAt(0)
 		}
 
+		private native function checkContentAccess():void;
+
 		public native function load(request: URLRequest, context: LoaderContext = null):void;
 
 		public native function loadBytes(data: ByteArray, context: LoaderContext = null):void;
 		
-		public function unload():void {
-			throw new Error("flash.display.Loader.unload - not yet implemented");
-		}
+		public native function unload():void;
+
+		public native function unloadAndStop(gc:Boolean = true):void;
 
 		override public function addChild(child:DisplayObject):DisplayObject {
 			throw new IllegalOperationError("Error #2069: The Loader class does not implement this method.", 2069);
@@ -1,19 +1,28 @@
 //! `flash.display.BitmapData` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::error::{
+    argument_error, illegal_operation_error, range_error, security_error, type_error,
+};
 use crate::avm2::method::{Method, NativeMethodImpl};
-use crate::avm2::object::{bitmapdata_allocator, BitmapDataObject, Object, TObject};
+use crate::avm2::object::{
+    bitmapdata_allocator, BitmapDataObject, ByteArrayObject, Object, TObject, VectorObject,
+};
 use crate::avm2::value::Value;
+use crate::avm2::vector::VectorStorage;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
 use crate::avm2::QName;
 use crate::bitmap::bitmap_data::IBitmapDrawable;
-use crate::bitmap::bitmap_data::{BitmapData, ChannelOptions, Color};
+use crate::bitmap::bitmap_data::{
+    BitmapData, BitmapEncoding, ChannelOptions, Color, ThresholdOperation,
+};
 use crate::bitmap::is_size_valid;
 use crate::character::Character;
-use crate::display_object::Bitmap;
+use crate::display_object::{Bitmap, StageQuality};
 use crate::swf::BlendMode;
 use gc_arena::{GcCell, MutationContext};
 use ruffle_render::transform::Transform;
@@ -350,6 +359,7 @@ pub fn get_pixel32<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
         let x = args
             .get(0)
             .unwrap_or(&Value::Undefined)
@@ -372,6 +382,7 @@ pub fn set_pixel<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
         let x = args
             .get(0)
             .unwrap_or(&Value::Undefined)
@@ -399,6 +410,7 @@ pub fn set_pixel32<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
         let x = args
             .get(0)
             .unwrap_or(&Value::Undefined)
@@ -435,6 +447,7 @@ pub fn set_pixels<'gc>(
         .unwrap_or(&Value::Undefined)
         .coerce_to_object(activation)?;
     if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
         let x = rectangle
             .get_property(&Multiname::public("x"), activation)?
             .coerce_to_u32(activation)?;
@@ -739,6 +752,26 @@ pub fn draw<'gc>(
             log::warn!("BitmapData.draw with clip rect - not implemented")
         }
 
+        if let Some(source_object) = args
+            .get(0)
+            .and_then(|v| v.as_object())
+            .and_then(|o| o.as_display_object())
+        {
+            let requester = activation.context.swf.url().map(|u| u.to_string());
+            if let Some(url) = activation
+                .context
+                .security_sandbox
+                .find_inaccessible_content(requester.as_deref(), source_object, true)
+            {
+                let message = format!(
+                    "Error #2122: Security sandbox violation: BitmapData.draw: {} cannot access {}. A policy file is required, but the checkPolicyFile flag was not set when this media was loaded.",
+                    requester.unwrap_or_default(),
+                    url
+                );
+                return Err(Error::AvmError(security_error(activation, &message, 2122)?));
+            }
+        }
+
         let mut bitmap_data = bitmap_data.write(activation.context.gc_context);
         // FIXME - handle other arguments
         let smoothing = args.get(5).unwrap_or(&false.into()).coerce_to_boolean();
@@ -784,6 +817,7 @@ pub fn fill_rect<'gc>(
         .coerce_to_u32(activation)? as i32;
 
     if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
         let x = rectangle
             .get_property(&Multiname::public("x"), activation)?
             .coerce_to_u32(activation)?;
@@ -962,6 +996,749 @@ pub fn perlin_noise<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implement `BitmapData.threshold`
+pub fn threshold<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let source_bitmap = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let source_rect = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let src_min_x = source_rect
+            .get_property(&Multiname::public("x"), activation)?
+            .coerce_to_i32(activation)?;
+        let src_min_y = source_rect
+            .get_property(&Multiname::public("y"), activation)?
+            .coerce_to_i32(activation)?;
+        let src_width = source_rect
+            .get_property(&Multiname::public("width"), activation)?
+            .coerce_to_i32(activation)?;
+        let src_height = source_rect
+            .get_property(&Multiname::public("height"), activation)?
+            .coerce_to_i32(activation)?;
+
+        let dest_point = args
+            .get(2)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let dest_x = dest_point
+            .get_property(&Multiname::public("x"), activation)?
+            .coerce_to_i32(activation)?;
+        let dest_y = dest_point
+            .get_property(&Multiname::public("y"), activation)?
+            .coerce_to_i32(activation)?;
+
+        let operation = match ThresholdOperation::from_str(
+            &args
+                .get(3)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_string(activation)?
+                .to_string(),
+        ) {
+            Ok(operation) => operation,
+            // Invalid operations silently do nothing.
+            Err(()) => return Ok(0.into()),
+        };
+
+        let threshold = args
+            .get(4)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        let color = args.get(5).unwrap_or(&0.into()).coerce_to_u32(activation)?;
+
+        let mask = args
+            .get(6)
+            .unwrap_or(&0xFFFFFFFFu32.into())
+            .coerce_to_u32(activation)?;
+
+        let copy_source = args.get(7).unwrap_or(&false.into()).coerce_to_boolean();
+
+        if let Some(src_bitmap) = source_bitmap.as_bitmap_data() {
+            src_bitmap.read().check_valid(activation)?;
+            // dealing with object aliasing...
+            let src_bitmap_clone: BitmapData; // only initialized if source is the same object as self
+            let src_bitmap_data_cell = src_bitmap;
+            let src_bitmap_gc_ref; // only initialized if source is a different object than self
+            let source_bitmap_ref = // holds the reference to either of the ones above
+                if GcCell::ptr_eq(src_bitmap, bitmap_data) {
+                    src_bitmap_clone = src_bitmap_data_cell.read().clone();
+                    &src_bitmap_clone
+                } else {
+                    src_bitmap_gc_ref = src_bitmap_data_cell.read();
+                    &src_bitmap_gc_ref
+                };
+
+            let count = bitmap_data.write(activation.context.gc_context).threshold(
+                source_bitmap_ref,
+                (src_min_x, src_min_y, src_width, src_height),
+                (dest_x, dest_y),
+                operation,
+                threshold,
+                color,
+                mask,
+                copy_source,
+            );
+            return Ok(count.into());
+        }
+    }
+
+    Ok(0.into())
+}
+
+/// Implement `BitmapData.hitTest`
+pub fn hit_test<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let first_point = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let top_left = (
+            first_point
+                .get_property(&Multiname::public("x"), activation)?
+                .coerce_to_i32(activation)?,
+            first_point
+                .get_property(&Multiname::public("y"), activation)?
+                .coerce_to_i32(activation)?,
+        );
+        let source_threshold = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_u32(activation)?;
+        let compare_object = args
+            .get(2)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let point_class = activation.avm2().classes().point;
+        let rectangle_class = activation.avm2().classes().rectangle;
+
+        // A `Bitmap` is tested through the `BitmapData` it displays.
+        let other_bitmap = compare_object.as_bitmap_data().or_else(|| {
+            compare_object
+                .as_display_object()
+                .and_then(|o| o.as_bitmap())
+                .map(|bitmap| bitmap.bitmap_data())
+        });
+
+        if let Some(other_bitmap) = other_bitmap {
+            other_bitmap.read().check_valid(activation)?;
+            let second_point = args
+                .get(3)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation)?;
+            let second_point = (
+                second_point
+                    .get_property(&Multiname::public("x"), activation)?
+                    .coerce_to_i32(activation)?,
+                second_point
+                    .get_property(&Multiname::public("y"), activation)?
+                    .coerce_to_i32(activation)?,
+            );
+            let second_threshold = args.get(4).unwrap_or(&1.into()).coerce_to_u32(activation)?;
+
+            let result = bitmap_data.read().hit_test_bitmapdata(
+                top_left,
+                source_threshold,
+                &other_bitmap.read(),
+                second_point,
+                second_threshold,
+            );
+            return Ok(result.into());
+        } else if compare_object.is_of_type(point_class, activation) {
+            let test_x = compare_object
+                .get_property(&Multiname::public("x"), activation)?
+                .coerce_to_i32(activation)?;
+            let test_y = compare_object
+                .get_property(&Multiname::public("y"), activation)?
+                .coerce_to_i32(activation)?;
+            let result = bitmap_data
+                .read()
+                .hit_test_point(source_threshold, (test_x - top_left.0, test_y - top_left.1));
+            return Ok(result.into());
+        } else if compare_object.is_of_type(rectangle_class, activation) {
+            let test_x = compare_object
+                .get_property(&Multiname::public("x"), activation)?
+                .coerce_to_i32(activation)?;
+            let test_y = compare_object
+                .get_property(&Multiname::public("y"), activation)?
+                .coerce_to_i32(activation)?;
+            let width = compare_object
+                .get_property(&Multiname::public("width"), activation)?
+                .coerce_to_i32(activation)?;
+            let height = compare_object
+                .get_property(&Multiname::public("height"), activation)?
+                .coerce_to_i32(activation)?;
+            let result = bitmap_data.read().hit_test_rectangle(
+                source_threshold,
+                (test_x - top_left.0, test_y - top_left.1),
+                (width, height),
+            );
+            return Ok(result.into());
+        }
+
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2005: Parameter 2 is of the incorrect type. Should be type BitmapData, Point, Rectangle or Bitmap.",
+            2005,
+        )?));
+    }
+
+    Ok(false.into())
+}
+
+/// Implement `BitmapData.pixelDissolve`
+pub fn pixel_dissolve<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let source_bitmap = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let source_rect = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let src_min_x = source_rect
+            .get_property(&Multiname::public("x"), activation)?
+            .coerce_to_i32(activation)?;
+        let src_min_y = source_rect
+            .get_property(&Multiname::public("y"), activation)?
+            .coerce_to_i32(activation)?;
+        let src_width = source_rect
+            .get_property(&Multiname::public("width"), activation)?
+            .coerce_to_i32(activation)?;
+        let src_height = source_rect
+            .get_property(&Multiname::public("height"), activation)?
+            .coerce_to_i32(activation)?;
+
+        let dest_point = args
+            .get(2)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let dest_x = dest_point
+            .get_property(&Multiname::public("x"), activation)?
+            .coerce_to_i32(activation)?;
+        let dest_y = dest_point
+            .get_property(&Multiname::public("y"), activation)?
+            .coerce_to_i32(activation)?;
+
+        let random_seed = args.get(3).unwrap_or(&0.into()).coerce_to_i32(activation)?;
+
+        let num_pixels = args.get(4).unwrap_or(&0.into()).coerce_to_i32(activation)?;
+
+        let fill_color = args.get(5).unwrap_or(&0.into()).coerce_to_u32(activation)?;
+
+        if let Some(src_bitmap) = source_bitmap.as_bitmap_data() {
+            src_bitmap.read().check_valid(activation)?;
+            // dealing with object aliasing...
+            let read;
+            let source: Option<&BitmapData> = if GcCell::ptr_eq(src_bitmap, bitmap_data) {
+                None
+            } else {
+                read = src_bitmap.read();
+                Some(&read)
+            };
+
+            let next_seed = bitmap_data
+                .write(activation.context.gc_context)
+                .pixel_dissolve(
+                    source,
+                    (src_min_x, src_min_y, src_width, src_height),
+                    (dest_x, dest_y),
+                    random_seed,
+                    num_pixels,
+                    fill_color,
+                );
+            return Ok(next_seed.into());
+        }
+    }
+
+    Ok(0.into())
+}
+
+/// Reads the position and size of a `Rectangle` argument, which must be non-null.
+fn rectangle_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    name: &str,
+) -> Result<(i32, i32, i32, i32), Error<'gc>> {
+    let rectangle = non_null_arg(activation, args, index, name)?;
+    let x = rectangle
+        .get_property(&Multiname::public("x"), activation)?
+        .coerce_to_i32(activation)?;
+    let y = rectangle
+        .get_property(&Multiname::public("y"), activation)?
+        .coerce_to_i32(activation)?;
+    let width = rectangle
+        .get_property(&Multiname::public("width"), activation)?
+        .coerce_to_i32(activation)?;
+    let height = rectangle
+        .get_property(&Multiname::public("height"), activation)?
+        .coerce_to_i32(activation)?;
+    Ok((x, y, width, height))
+}
+
+/// Reads the position of a `Point` argument, which must be non-null.
+fn point_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    name: &str,
+) -> Result<(i32, i32), Error<'gc>> {
+    let point = non_null_arg(activation, args, index, name)?;
+    let x = point
+        .get_property(&Multiname::public("x"), activation)?
+        .coerce_to_i32(activation)?;
+    let y = point
+        .get_property(&Multiname::public("y"), activation)?
+        .coerce_to_i32(activation)?;
+    Ok((x, y))
+}
+
+/// Reads an object argument, throwing a `TypeError` if it's null.
+fn non_null_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    name: &str,
+) -> Result<Object<'gc>, Error<'gc>> {
+    match args.get(index) {
+        Some(Value::Object(object)) => Ok(*object),
+        _ => Err(Error::AvmError(type_error(
+            activation,
+            &format!("Error #2007: Parameter {name} must be non-null."),
+            2007,
+        )?)),
+    }
+}
+
+/// Reads a `BitmapData` argument, which must be non-null and not disposed.
+fn bitmap_data_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    name: &str,
+) -> Result<GcCell<'gc, BitmapData<'gc>>, Error<'gc>> {
+    let bitmap_data = non_null_arg(activation, args, index, name)?
+        .as_bitmap_data()
+        .ok_or_else(|| {
+            format!("TypeError: Error #1034: Type Coercion failed: cannot convert {name} to flash.display.BitmapData.")
+        })?;
+    bitmap_data.read().check_valid(activation)?;
+    Ok(bitmap_data)
+}
+
+/// Implements `BitmapData.getPixels`
+pub fn get_pixels<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let (x, y, width, height) = rectangle_arg(activation, args, 0, "rect")?;
+
+        let mut storage = ByteArrayStorage::new();
+        for color in bitmap_data.read().get_pixels_rect(x, y, width, height) {
+            storage.write_unsigned_int(color)?;
+        }
+        return Ok(ByteArrayObject::from_storage(activation, storage)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.copyPixelsToByteArray`
+pub fn copy_pixels_to_byte_array<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let (x, y, width, height) = rectangle_arg(activation, args, 0, "rect")?;
+        let data = non_null_arg(activation, args, 1, "data")?;
+
+        let mut storage = data
+            .as_bytearray_mut(activation.context.gc_context)
+            .ok_or("TypeError: Error #1034: Type Coercion failed: cannot convert data to flash.utils.ByteArray.")?;
+        for color in bitmap_data.read().get_pixels_rect(x, y, width, height) {
+            storage.write_unsigned_int(color)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.getVector`
+pub fn get_vector<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let (x, y, width, height) = rectangle_arg(activation, args, 0, "rect")?;
+
+        let pixels = bitmap_data
+            .read()
+            .get_pixels_rect(x, y, width, height)
+            .into_iter()
+            .map(Value::from)
+            .collect();
+        let storage = VectorStorage::from_values(pixels, false, activation.avm2().classes().uint);
+        return Ok(VectorObject::from_vector(storage, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.setVector`
+pub fn set_vector<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let (x, y, width, height) = rectangle_arg(activation, args, 0, "rect")?;
+        let vector = non_null_arg(activation, args, 1, "inputVector")?;
+
+        let values: Vec<Value<'gc>> = vector
+            .as_vector_storage()
+            .ok_or("TypeError: Error #1034: Type Coercion failed: cannot convert inputVector to __AS3__.vec.Vector.<uint>.")?
+            .iter()
+            .collect();
+        let length = values.len();
+        let mut pixels = Vec::with_capacity(length);
+        for value in values {
+            pixels.push(value.coerce_to_u32(activation)?);
+        }
+
+        let is_complete = bitmap_data
+            .write(activation.context.gc_context)
+            .set_pixels_rect(x, y, width, height, pixels);
+        if !is_complete {
+            return Err(Error::AvmError(range_error(
+                activation,
+                &format!("Error #1125: The index {length} is out of range {length}."),
+                1125,
+            )?));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.histogram`
+pub fn histogram<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let (x, y, width, height) = match args.get(0) {
+            None | Some(Value::Undefined) | Some(Value::Null) => {
+                let bitmap_data = bitmap_data.read();
+                (
+                    0,
+                    0,
+                    bitmap_data.width() as i32,
+                    bitmap_data.height() as i32,
+                )
+            }
+            _ => rectangle_arg(activation, args, 0, "hRect")?,
+        };
+
+        let histogram = bitmap_data.read().histogram(x, y, width, height);
+
+        let number = activation.avm2().classes().number;
+        let mut channels = Vec::with_capacity(histogram.len());
+        for channel in histogram {
+            let counts = channel.iter().map(|count| (*count as f64).into()).collect();
+            let storage = VectorStorage::from_values(counts, false, number);
+            channels.push(VectorObject::from_vector(storage, activation)?.into());
+        }
+
+        let vector_of_number = activation
+            .avm2()
+            .classes()
+            .vector
+            .apply(activation, &[number.into()])?;
+        let storage = VectorStorage::from_values(channels, false, vector_of_number);
+        return Ok(VectorObject::from_vector(storage, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.merge`
+pub fn merge<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let src_bitmap = bitmap_data_arg(activation, args, 0, "sourceBitmapData")?;
+        let source_rect = rectangle_arg(activation, args, 1, "sourceRect")?;
+        let dest_point = point_arg(activation, args, 2, "destPoint")?;
+
+        let mut rgba_mult = [0; 4];
+        for (i, mult) in rgba_mult.iter_mut().enumerate() {
+            *mult = args
+                .get(3 + i)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_u32(activation)?
+                .min(256) as i32;
+        }
+        let [red_mult, green_mult, blue_mult, alpha_mult] = rgba_mult;
+
+        // dealing with object aliasing...
+        let src_bitmap_clone: BitmapData; // only initialized if source is the same object as self
+        let src_bitmap_gc_ref; // only initialized if source is a different object than self
+        let source_bitmap_ref = // holds the reference to either of the ones above
+            if GcCell::ptr_eq(src_bitmap, bitmap_data) {
+                src_bitmap_clone = src_bitmap.read().clone();
+                &src_bitmap_clone
+            } else {
+                src_bitmap_gc_ref = src_bitmap.read();
+                &src_bitmap_gc_ref
+            };
+
+        bitmap_data.write(activation.context.gc_context).merge(
+            source_bitmap_ref,
+            source_rect,
+            dest_point,
+            (red_mult, green_mult, blue_mult, alpha_mult),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.paletteMap`
+pub fn palette_map<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let src_bitmap = bitmap_data_arg(activation, args, 0, "sourceBitmapData")?;
+        let source_rect = rectangle_arg(activation, args, 1, "sourceRect")?;
+        let dest_point = point_arg(activation, args, 2, "destPoint")?;
+
+        let mut get_channel = |index: usize, shift: usize| -> Result<[u32; 256], Error<'gc>> {
+            let mut array = [0_u32; 256];
+            let values = match args.get(index) {
+                Some(Value::Object(arg)) => arg.as_array_storage().map(|storage| {
+                    (0..256)
+                        .map(|i| storage.get(i).unwrap_or(Value::Undefined))
+                        .collect::<Vec<_>>()
+                }),
+                _ => None,
+            };
+            for (i, item) in array.iter_mut().enumerate() {
+                *item = if let Some(values) = &values {
+                    values[i].coerce_to_u32(activation)?
+                } else {
+                    // This is an "identity mapping", fulfilling the part of the spec that
+                    // says that channels which have no array provided are simply copied.
+                    (i << shift) as u32
+                }
+            }
+            Ok(array)
+        };
+
+        let red_array = get_channel(3, 16)?;
+        let green_array = get_channel(4, 8)?;
+        let blue_array = get_channel(5, 0)?;
+        let alpha_array = get_channel(6, 24)?;
+
+        // dealing with object aliasing...
+        let read;
+        let source: Option<&BitmapData> = if GcCell::ptr_eq(src_bitmap, bitmap_data) {
+            None
+        } else {
+            read = src_bitmap.read();
+            Some(&read)
+        };
+
+        bitmap_data
+            .write(activation.context.gc_context)
+            .palette_map(
+                source,
+                source_rect,
+                dest_point,
+                (red_array, green_array, blue_array, alpha_array),
+            );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.compare`
+pub fn compare<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    const EQUIVALENT: i32 = 0;
+    const DIFFERENT_WIDTHS: i32 = -3;
+    const DIFFERENT_HEIGHTS: i32 = -4;
+
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let other_bitmap_data = bitmap_data_arg(activation, args, 0, "otherBitmapData")?;
+
+        let bitmap_data = bitmap_data.read();
+        let other_bitmap_data = other_bitmap_data.read();
+
+        if bitmap_data.width() != other_bitmap_data.width() {
+            return Ok(DIFFERENT_WIDTHS.into());
+        }
+
+        if bitmap_data.height() != other_bitmap_data.height() {
+            return Ok(DIFFERENT_HEIGHTS.into());
+        }
+
+        return match BitmapData::compare(&bitmap_data, &other_bitmap_data) {
+            Some(difference) => {
+                let difference = GcCell::allocate(activation.context.gc_context, difference);
+                let class = activation.avm2().classes().bitmapdata;
+                Ok(BitmapDataObject::from_bitmap_data(activation, difference, class)?.into())
+            }
+            None => Ok(EQUIVALENT.into()),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.drawWithQuality`
+pub fn draw_with_quality<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let quality = args.get(6).unwrap_or(&Value::Null);
+    if !matches!(quality, Value::Null)
+        && StageQuality::from_str(&quality.coerce_to_string(activation)?.to_string()).is_err()
+    {
+        return Err(
+            "ArgumentError: Error #2008: Parameter quality must be one of the accepted values."
+                .into(),
+        );
+    }
+
+    // Offscreen draws are always rendered at the stage quality, as the renderers can't
+    // change it for a single draw. Everything else is the same as `draw`.
+    draw(activation, this, args)
+}
+
+/// Implements `BitmapData.encode`
+///
+/// Only PNG and JPEG are supported. JPEG-XR throws `IllegalOperationError` #2014.
+pub fn encode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let (x, y, width, height) = rectangle_arg(activation, args, 0, "rect")?;
+        let compressor = non_null_arg(activation, args, 1, "compressor")?;
+
+        let mut encoder_class = |name: &'static str| {
+            activation.resolve_class(&Multiname::new(Namespace::package("flash.display"), name))
+        };
+        let png_class = encoder_class("PNGEncoderOptions")?;
+        let jpeg_class = encoder_class("JPEGEncoderOptions")?;
+        let jpeg_xr_class = encoder_class("JPEGXREncoderOptions")?;
+
+        let encoding = if compressor.is_of_type(png_class, activation) {
+            let fast_compression = compressor
+                .get_property(&Multiname::public("fastCompression"), activation)?
+                .coerce_to_boolean();
+            BitmapEncoding::Png { fast_compression }
+        } else if compressor.is_of_type(jpeg_class, activation) {
+            let quality = compressor
+                .get_property(&Multiname::public("quality"), activation)?
+                .coerce_to_u32(activation)?;
+            BitmapEncoding::Jpeg {
+                quality: quality.clamp(1, 100) as u8,
+            }
+        } else if compressor.is_of_type(jpeg_xr_class, activation) {
+            // There's no JPEG-XR encoder available to us, so fail with the error Flash
+            // uses for unavailable features, rather than handing back some other format.
+            log::warn!("BitmapData.encode: JPEG-XR encoding is not supported");
+            return Err(Error::AvmError(illegal_operation_error(
+                activation,
+                "Error #2014: Feature is not available at this time.",
+                2014,
+            )?));
+        } else {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2004: One of the parameters is invalid.",
+                2004,
+            )?));
+        };
+
+        let bytes = match bitmap_data.read().encode(x, y, width, height, encoding) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                log::warn!("BitmapData.encode: {}", e);
+                return Err(Error::AvmError(argument_error(
+                    activation,
+                    "Error #2004: One of the parameters is invalid.",
+                    2004,
+                )?));
+            }
+        };
+
+        // The image is written at the current position of the given `ByteArray`, if any.
+        let byte_array = match args.get(2) {
+            Some(Value::Object(byte_array)) if byte_array.as_bytearray().is_some() => *byte_array,
+            _ => ByteArrayObject::from_storage(activation, ByteArrayStorage::new())?,
+        };
+        byte_array
+            .as_bytearray_mut(activation.context.gc_context)
+            .unwrap()
+            .write_bytes(&bytes)?;
+        return Ok(byte_array.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `BitmapData`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -1015,6 +1792,19 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("applyFilter", apply_filter),
         ("clone", clone),
         ("perlinNoise", perlin_noise),
+        ("threshold", threshold),
+        ("hitTest", hit_test),
+        ("pixelDissolve", pixel_dissolve),
+        ("getPixels", get_pixels),
+        ("copyPixelsToByteArray", copy_pixels_to_byte_array),
+        ("getVector", get_vector),
+        ("setVector", set_vector),
+        ("histogram", histogram),
+        ("merge", merge),
+        ("paletteMap", palette_map),
+        ("compare", compare),
+        ("drawWithQuality", draw_with_quality),
+        ("encode", encode),
     ];
     write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
 
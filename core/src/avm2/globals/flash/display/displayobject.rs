@@ -375,93 +375,149 @@ pub fn set_y<'gc>(
     Ok(Value::Undefined)
 }
 
-/// Stubs `z`'s getter.
+/// Implements `z`'s getter.
 pub fn z<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(0.into())
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.z().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
-/// Stubs `z`'s setter.
+/// Implements `z`'s setter.
 pub fn set_z<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        dobj.set_z(activation.context.gc_context, new_value);
+    }
+
     Ok(Value::Undefined)
 }
 
-/// Stubs `rotationX`'s getter.
+/// Implements `rotationX`'s getter.
 pub fn rotation_x<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(0.into())
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(f64::from(dobj.rotation_x()).into());
+    }
+
+    Ok(Value::Undefined)
 }
 
-/// Stubs `rotationX`'s setter.
+/// Implements `rotationX`'s setter.
 pub fn set_rotation_x<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        dobj.set_rotation_x(activation.context.gc_context, Degrees::from(new_value));
+    }
+
     Ok(Value::Undefined)
 }
 
-/// Stubs `rotationY`'s getter.
+/// Implements `rotationY`'s getter.
 pub fn rotation_y<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(0.into())
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(f64::from(dobj.rotation_y()).into());
+    }
+
+    Ok(Value::Undefined)
 }
 
-/// Stubs `rotationY`'s setter.
+/// Implements `rotationY`'s setter.
 pub fn set_rotation_y<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        dobj.set_rotation_y(activation.context.gc_context, Degrees::from(new_value));
+    }
+
     Ok(Value::Undefined)
 }
 
-/// Stubs `rotationZ`'s getter.
+/// Implements `rotationZ`'s getter, which is the same as `rotation`.
 pub fn rotation_z<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(0.into())
+    rotation(activation, this, args)
 }
 
-/// Stubs `rotationZ`'s setter.
+/// Implements `rotationZ`'s setter, which is the same as `rotation`.
 pub fn set_rotation_z<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(Value::Undefined)
+    set_rotation(activation, this, args)
 }
 
-/// Stubs `scaleZ`'s getter.
+/// Implements `scaleZ`'s getter.
 pub fn scale_z<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(1.into())
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.scale_z().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
-/// Stubs `scaleZ`'s setter.
+/// Implements `scaleZ`'s setter.
 pub fn set_scale_z<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        dobj.set_scale_z(activation.context.gc_context, new_value);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -784,6 +840,8 @@ pub fn set_transform<'gc>(
         let mut write = dobj.base_mut(activation.context.gc_context);
         write.set_matrix(matrix);
         write.set_color_transform(color_transform);
+        drop(write);
+        dobj.invalidate_parent_cached_bitmap(activation.context.gc_context);
     }
     Ok(Value::Undefined)
 }
@@ -971,6 +1029,83 @@ fn global_to_local<'gc>(
     Ok(Value::Undefined)
 }
 
+fn local_3d_to_global<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let point = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let mut coords = [0.0; 3];
+        for (name, value) in ["x", "y", "z"].into_iter().zip(&mut coords) {
+            *value = point
+                .get_property(&Multiname::public(name), activation)?
+                .coerce_to_number(activation)?;
+        }
+
+        let matrix =
+            dobj.projection_matrix(activation.context.stage) * dobj.local_to_global_matrix_3d();
+        let [x, y, _, w] = matrix.transform_point(coords[0], coords[1], coords[2]);
+        return Ok(activation
+            .avm2()
+            .classes()
+            .point
+            .construct(activation, &[(x / w).into(), (y / w).into()])?
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn global_to_local_3d<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let point = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let x = point
+            .get_property(&Multiname::public("x"), activation)?
+            .coerce_to_number(activation)?;
+        let y = point
+            .get_property(&Multiname::public("y"), activation)?
+            .coerce_to_number(activation)?;
+
+        // Find the point on the object's local Z = 0 plane that projects onto the global
+        // point. Each global coordinate gives a linear equation in the local X and Y.
+        let m = dobj.projection_matrix(activation.context.stage) * dobj.local_to_global_matrix_3d();
+        let row = |r: usize, target: f64| {
+            [
+                m.get(r, 0) - target * m.get(3, 0),
+                m.get(r, 1) - target * m.get(3, 1),
+                m.get(r, 3) - target * m.get(3, 3),
+            ]
+        };
+        let [a, b, c] = row(0, x);
+        let [d, e, f] = row(1, y);
+        let det = a * e - b * d;
+        let (out_x, out_y) = if det == 0.0 {
+            (0.0, 0.0)
+        } else {
+            ((b * f - c * e) / det, (c * d - a * f) / det)
+        };
+        return Ok(activation
+            .avm2()
+            .classes()
+            .vector3d
+            .construct(activation, &[out_x.into(), out_y.into(), 0.into()])?
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 fn get_bounds<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -1074,6 +1209,44 @@ fn set_cache_as_bitmap<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `DisplayObject.cacheAsBitmapMatrix`'s getter.
+fn cache_as_bitmap_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|this| this.as_display_object()) {
+        return match this.cache_as_bitmap_matrix() {
+            Some(matrix) => {
+                crate::avm2::globals::flash::geom::transform::matrix_to_object(matrix, activation)
+            }
+            None => Ok(Value::Null),
+        };
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.cacheAsBitmapMatrix`'s setter.
+fn set_cache_as_bitmap_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|this| this.as_display_object()) {
+        let matrix = match args.get(0).unwrap_or(&Value::Null) {
+            Value::Null | Value::Undefined => None,
+            value => Some(
+                crate::avm2::globals::flash::geom::transform::object_to_matrix(
+                    value.coerce_to_object(activation)?,
+                    activation,
+                )?,
+            ),
+        };
+        this.set_cache_as_bitmap_matrix(activation.context.gc_context, matrix);
+    }
+    Ok(Value::Undefined)
+}
+
 /// Construct `DisplayObject`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -1137,6 +1310,11 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
             Some(cache_as_bitmap),
             Some(set_cache_as_bitmap),
         ),
+        (
+            "cacheAsBitmapMatrix",
+            Some(cache_as_bitmap_matrix),
+            Some(set_cache_as_bitmap_matrix),
+        ),
     ];
     write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
 
@@ -1145,6 +1323,8 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("hitTestObject", hit_test_object),
         ("localToGlobal", local_to_global),
         ("globalToLocal", global_to_local),
+        ("local3DToGlobal", local_3d_to_global),
+        ("globalToLocal3D", global_to_local_3d),
         ("getBounds", get_bounds),
         ("getRect", get_rect),
     ];
@@ -9,10 +9,14 @@ use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
 use crate::avm2::QName;
+use crate::avm2::{ArrayObject, ArrayStorage};
 use crate::context::UpdateContext;
-use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::display_object::{
+    DisplayObject, HitTestOptions, TDisplayObject, TDisplayObjectContainer,
+};
 use gc_arena::{GcCell, MutationContext};
 use std::cmp::min;
+use swf::Twips;
 
 /// Implements `flash.display.DisplayObjectContainer`'s instance constructor.
 pub fn instance_init<'gc>(
@@ -558,22 +562,139 @@ pub fn stop_all_movie_clips<'gc>(
     Ok(Value::Undefined)
 }
 
-/// Stubs `DisplayObjectContainer.getObjectsUnderPoint`
+/// Whether a stage point lies outside of the `scrollRect` of an object, hiding whatever
+/// the object draws there.
+fn is_scrolled_out(object: DisplayObject<'_>, point: (Twips, Twips)) -> bool {
+    object.scroll_rect().map_or(false, |rect| {
+        let (x, y) = object.global_to_local_matrix() * point;
+        x < rect.x_min || x >= rect.x_max || y < rect.y_min || y >= rect.y_max
+    })
+}
+
+/// Collects every object within `object` whose own content lies under a stage point, in
+/// the order they're drawn.
+///
+/// Like in Flash, this ignores masks as well as `mouseEnabled`, but leaves out the masks
+/// themselves. Invisible objects and anything outside of a `scrollRect` aren't shown, so
+/// they're left out too.
+fn objects_under_point<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    object: DisplayObject<'gc>,
+    point: (Twips, Twips),
+    objects: &mut Vec<DisplayObject<'gc>>,
+) {
+    if !object.visible() || is_scrolled_out(object, point) {
+        return;
+    }
+    if let Some(container) = object.as_container() {
+        // The graphics of a container are drawn underneath its children.
+        let local_matrix = object.global_to_local_matrix();
+        let hit = object.as_drawing_ref().map_or(false, |drawing| {
+            drawing.hit_test(local_matrix * point, &local_matrix)
+        });
+        if hit {
+            objects.push(object);
+        }
+        for child in container.iter_render_list() {
+            if child.maskee().is_none() && child.clip_depth() == 0 {
+                objects_under_point(context, child, point, objects);
+            }
+        }
+    } else if object.hit_test_shape(context, point, HitTestOptions::AVM_HIT_TEST) {
+        objects.push(object);
+    }
+}
+
+/// Reads the stage point passed to `getObjectsUnderPoint` and
+/// `areInaccessibleObjectsUnderPoint`, and finds the objects under it.
+fn objects_under_point_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: DisplayObject<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Vec<DisplayObject<'gc>>, Error<'gc>> {
+    let point = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_object(activation)?;
+    let x = point
+        .get_property(&Multiname::public("x"), activation)?
+        .coerce_to_number(activation)?;
+    let y = point
+        .get_property(&Multiname::public("y"), activation)?
+        .coerce_to_number(activation)?;
+
+    Ok(children_under_point(
+        &mut activation.context,
+        this,
+        (Twips::from_pixels(x), Twips::from_pixels(y)),
+    ))
+}
+
+/// Finds the objects within the children of `this` that lie under a stage point.
+fn children_under_point<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: DisplayObject<'gc>,
+    point: (Twips, Twips),
+) -> Vec<DisplayObject<'gc>> {
+    let mut objects = vec![];
+    if let Some(container) = this
+        .as_container()
+        .filter(|_| !is_scrolled_out(this, point))
+    {
+        for child in container.iter_render_list() {
+            if child.maskee().is_none() && child.clip_depth() == 0 {
+                objects_under_point(context, child, point, &mut objects);
+            }
+        }
+    }
+    objects
+}
+
+/// Whether the movie running ActionScript may access an object found under a point.
+fn is_accessible(activation: &Activation<'_, '_, '_>, object: DisplayObject<'_>) -> bool {
+    let requester = activation.caller_movie();
+    activation
+        .context
+        .security_sandbox
+        .can_access_content(requester.url(), object.movie().url())
+}
+
+/// Implements `DisplayObjectContainer.getObjectsUnderPoint`
 pub fn get_objects_under_point<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Err("DisplayObjectContainer.getObjectsUnderPoint not yet implemented".into())
+    if let Some(this) = this.and_then(|this| this.as_display_object()) {
+        // Objects from other security sandboxes are left out.
+        let objects: Vec<Option<Value<'gc>>> = objects_under_point_arg(activation, this, args)?
+            .into_iter()
+            .filter(|object| is_accessible(activation, *object))
+            .filter_map(|object| object.object2().as_object())
+            .map(|object| Some(object.into()))
+            .collect();
+        return Ok(
+            ArrayObject::from_storage(activation, ArrayStorage::from_storage(objects))?.into(),
+        );
+    }
+
+    Ok(Value::Undefined)
 }
 
-/// Stubs `DisplayObjectContainer.areInaccessibleObjectsUnderPoint`
+/// Implements `DisplayObjectContainer.areInaccessibleObjectsUnderPoint`
 pub fn are_inaccessible_objects_under_point<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Err("DisplayObjectContainer.areInaccessibleObjectsUnderPoint not yet implemented".into())
+    if let Some(this) = this.and_then(|this| this.as_display_object()) {
+        return Ok(objects_under_point_arg(activation, this, args)?
+            .into_iter()
+            .any(|object| !is_accessible(activation, object))
+            .into());
+    }
+
+    Ok(Value::Undefined)
 }
 
 pub fn mouse_children<'gc>(
@@ -672,3 +793,150 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::RenderContext;
+    use crate::display_object::MovieClip;
+    use crate::player::PlayerBuilder;
+    use crate::tag_utils::SwfMovie;
+    use ruffle_render::commands::CommandList;
+    use ruffle_render::shape_utils::DrawCommand;
+    use ruffle_render::transform::TransformStack;
+    use swf::{Color, FillStyle, Rectangle};
+
+    fn with_update_context<F>(test: F)
+    where
+        F: for<'a, 'gc> FnOnce(&mut UpdateContext<'a, 'gc, '_>),
+    {
+        let player = PlayerBuilder::new().with_movie(SwfMovie::empty(10)).build();
+        let mut player = player.lock().unwrap();
+        player.mutate_with_update_context(|context| test(context));
+    }
+
+    /// Create a clip with a filled 20x20 pixel square drawn in it.
+    fn square_clip<'gc>(context: &mut UpdateContext<'_, 'gc, '_>) -> DisplayObject<'gc> {
+        let clip = MovieClip::new(context.swf.clone(), context.gc_context);
+        let mut drawing = clip.drawing(context.gc_context);
+        drawing.set_fill_style(Some(FillStyle::Color(Color::from_rgb(0xFF0000, 255))));
+        for (x, y) in [(20.0, 0.0), (20.0, 20.0), (0.0, 20.0), (0.0, 0.0)] {
+            drawing.draw_command(DrawCommand::LineTo {
+                x: Twips::from_pixels(x),
+                y: Twips::from_pixels(y),
+            });
+        }
+        drop(drawing);
+        clip.into()
+    }
+
+    /// Create a clip holding two squares, the second one 10 pixels to the right.
+    fn two_squares<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> (DisplayObject<'gc>, DisplayObject<'gc>, DisplayObject<'gc>) {
+        let container = MovieClip::new(context.swf.clone(), context.gc_context);
+        let (a, b) = (square_clip(context), square_clip(context));
+        b.set_x(context.gc_context, 10.0);
+        container.replace_at_depth(context, a, 1);
+        container.replace_at_depth(context, b, 2);
+        (container.into(), a, b)
+    }
+
+    /// Give `object` a `scrollRect`, and apply it as rendering the object would.
+    fn set_scroll_rect<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: DisplayObject<'gc>,
+        rect: Rectangle<Twips>,
+    ) {
+        object.set_next_scroll_rect(context.gc_context, rect);
+        object.set_has_scroll_rect(context.gc_context, true);
+        let mut transform_stack = TransformStack::new();
+        let mut render_context = RenderContext {
+            renderer: &mut *context.renderer,
+            commands: CommandList::new(),
+            gc_context: context.gc_context,
+            ui: &mut *context.ui,
+            library: &*context.library,
+            transform_stack: &mut transform_stack,
+            is_offscreen: false,
+            cull_bounds: Default::default(),
+            dirty_region: None,
+            known_viewport_bounds: Default::default(),
+            stage: context.stage,
+            clip_depth_stack: vec![],
+            allow_mask: true,
+        };
+        object.pre_render(&mut render_context);
+    }
+
+    /// Whether the objects found under a point are exactly `expected`, in order.
+    fn found<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        container: DisplayObject<'gc>,
+        (x, y): (f64, f64),
+        expected: &[DisplayObject<'gc>],
+    ) -> bool {
+        let point = (Twips::from_pixels(x), Twips::from_pixels(y));
+        let objects = children_under_point(context, container, point);
+        objects.len() == expected.len()
+            && objects
+                .into_iter()
+                .zip(expected)
+                .all(|(object, expected)| DisplayObject::ptr_eq(object, *expected))
+    }
+
+    #[test]
+    fn objects_under_point_in_drawing_order() {
+        with_update_context(|context| {
+            let (container, a, b) = two_squares(context);
+            assert!(found(context, container, (5.0, 5.0), &[a]));
+            assert!(found(context, container, (15.0, 5.0), &[a, b]));
+            assert!(found(context, container, (25.0, 5.0), &[b]));
+            assert!(found(context, container, (35.0, 5.0), &[]));
+        });
+    }
+
+    #[test]
+    fn invisible_objects_are_not_under_point() {
+        with_update_context(|context| {
+            let (container, a, b) = two_squares(context);
+            b.set_visible(context.gc_context, false);
+            assert!(found(context, container, (15.0, 5.0), &[a]));
+
+            // Hiding a container hides its children too.
+            let (outer, _, _) = two_squares(context);
+            outer
+                .as_container()
+                .unwrap()
+                .replace_at_depth(context, container, 3);
+            container.set_visible(context.gc_context, false);
+            let (a, b) = {
+                let mut children = outer.as_container().unwrap().iter_render_list();
+                (children.next().unwrap(), children.next().unwrap())
+            };
+            assert!(found(context, outer, (15.0, 5.0), &[a, b]));
+        });
+    }
+
+    #[test]
+    fn scroll_rect_clips_objects_under_point() {
+        with_update_context(|context| {
+            let (container, a, b) = two_squares(context);
+            // Only the content between 5 and 15 pixels is shown, at 0 to 10 pixels.
+            set_scroll_rect(
+                context,
+                container,
+                Rectangle {
+                    x_min: Twips::from_pixels(5.0),
+                    y_min: Twips::from_pixels(5.0),
+                    x_max: Twips::from_pixels(15.0),
+                    y_max: Twips::from_pixels(15.0),
+                },
+            );
+            assert!(found(context, container, (2.0, 2.0), &[a]));
+            assert!(found(context, container, (7.0, 2.0), &[a, b]));
+            // Both squares are here, but scrolled out of view.
+            assert!(found(context, container, (12.0, 2.0), &[]));
+        });
+    }
+}
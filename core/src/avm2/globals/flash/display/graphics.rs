@@ -1,21 +1,49 @@
 //! `flash.display.Graphics` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::error::{argument_error, range_error, type_error};
+use crate::avm2::globals::flash::geom::transform::{matrix_to_object, object_to_matrix};
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
 use crate::avm2::method::{Method, NativeMethodImpl};
-use crate::avm2::object::{stage_allocator, Object, TObject};
+use crate::avm2::object::{stage_allocator, ArrayObject, Object, TObject, VectorObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
+use crate::avm2::vector::VectorStorage;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
 use crate::avm2::QName;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::drawing::Drawing;
 use crate::string::WStr;
 use gc_arena::{GcCell, MutationContext};
-use ruffle_render::shape_utils::DrawCommand;
+use ruffle_render::bitmap::{BitmapInfo, BitmapSource};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::shape_utils::{DrawCommand, DrawPath};
 use std::f64::consts::FRAC_1_SQRT_2;
-use swf::{Color, FillStyle, Fixed8, LineCapStyle, LineJoinStyle, LineStyle, Twips};
+use swf::{
+    Color, FillStyle, Fixed8, Gradient, GradientInterpolation, GradientRecord, GradientSpread,
+    LineCapStyle, LineJoinStyle, LineStyle, Twips,
+};
+
+/// The `GraphicsPathCommand` values understood by `drawPath`.
+const PATH_NO_OP: i32 = 0;
+const PATH_MOVE_TO: i32 = 1;
+const PATH_LINE_TO: i32 = 2;
+const PATH_CURVE_TO: i32 = 3;
+const PATH_WIDE_MOVE_TO: i32 = 4;
+const PATH_WIDE_LINE_TO: i32 = 5;
+const PATH_CUBIC_CURVE_TO: i32 = 6;
+
+fn ruffle_name<'gc>(local_name: &'static str) -> Multiname<'gc> {
+    Multiname::new(Namespace::Namespace("__ruffle__".into()), local_name)
+}
+
+fn bitmaps_name<'gc>() -> Multiname<'gc> {
+    Multiname::new(Namespace::private(NS_RUFFLE_INTERNAL), "bitmaps")
+}
 
 /// Implements `flash.display.Graphics`'s instance constructor.
 fn instance_init<'gc>(
@@ -80,23 +108,230 @@ fn begin_fill<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Register a `BitmapData` with the drawing of `this`, returning its id.
+///
+/// The `BitmapData` object itself is kept in a hidden array on the `Graphics`
+/// object, so that `readGraphicsData` can hand it back out.
+fn register_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    display_object: DisplayObject<'gc>,
+    bitmap_data: Object<'gc>,
+) -> Result<Option<u16>, Error<'gc>> {
+    let bitmap = match bitmap_data.as_bitmap_data() {
+        Some(bitmap) => bitmap,
+        None => return Ok(None),
+    };
+    let bitmap = {
+        let mut bitmap = bitmap.write(activation.context.gc_context);
+        let handle = match bitmap.bitmap_handle(activation.context.renderer) {
+            Some(handle) => handle,
+            None => return Ok(None),
+        };
+        BitmapInfo {
+            handle,
+            width: bitmap.width() as u16,
+            height: bitmap.height() as u16,
+        }
+    };
+
+    let id = match display_object.as_drawing(activation.context.gc_context) {
+        Some(mut draw) => draw.add_bitmap(bitmap),
+        None => return Ok(None),
+    };
+
+    let bitmaps = match this.get_property(&bitmaps_name(), activation)? {
+        Value::Object(bitmaps) => bitmaps,
+        _ => {
+            let bitmaps = ArrayObject::empty(activation)?;
+            this.set_property(&bitmaps_name(), bitmaps.into(), activation)?;
+            bitmaps
+        }
+    };
+    if let Some(mut bitmaps) = bitmaps.as_array_storage_mut(activation.context.gc_context) {
+        bitmaps.set(id as usize, bitmap_data.into());
+    }
+
+    Ok(Some(id))
+}
+
+/// Build a bitmap fill from the arguments of `beginBitmapFill` or
+/// `lineBitmapStyle`.
+fn bitmap_fill_from_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    display_object: DisplayObject<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Option<FillStyle>, Error<'gc>> {
+    let bitmap_data = match args.get(0) {
+        Some(Value::Object(bitmap_data)) => *bitmap_data,
+        _ => {
+            return Err(Error::AvmError(type_error(
+                activation,
+                "Error #2007: Parameter bitmap must be non-null.",
+                2007,
+            )?))
+        }
+    };
+    let mut matrix = match args.get(1) {
+        Some(Value::Object(matrix)) => object_to_matrix(*matrix, activation)?,
+        _ => Matrix::IDENTITY,
+    };
+    // Flash matrix is in pixels. Scale from pixels to twips.
+    matrix *= Matrix::scale(Twips::TWIPS_PER_PIXEL as f32, Twips::TWIPS_PER_PIXEL as f32);
+    let is_repeating = args
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| true.into())
+        .coerce_to_boolean();
+    let is_smoothed = args
+        .get(3)
+        .cloned()
+        .unwrap_or_else(|| false.into())
+        .coerce_to_boolean();
+
+    Ok(
+        register_bitmap(activation, this, display_object, bitmap_data)?.map(|id| {
+            FillStyle::Bitmap {
+                id,
+                matrix: matrix.into(),
+                is_smoothed,
+                is_repeating,
+            }
+        }),
+    )
+}
+
+/// Read the values of an `Array` passed to a native method.
+fn array_values<'gc>(value: Option<&Value<'gc>>) -> Vec<Value<'gc>> {
+    value
+        .and_then(|value| value.as_object())
+        .and_then(|object| {
+            object.as_array_storage().map(|storage| {
+                storage
+                    .iter()
+                    .map(|v| v.unwrap_or(Value::Undefined))
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Build a gradient fill from the arguments of `beginGradientFill` or
+/// `lineGradientStyle`.
+fn gradient_fill_from_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<FillStyle, Error<'gc>> {
+    let gradient_type = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let colors = array_values(args.get(1));
+    let alphas = array_values(args.get(2));
+    let ratios = array_values(args.get(3));
+
+    let mut records = Vec::with_capacity(colors.len());
+    for ((color, alpha), ratio) in colors.iter().zip(alphas.iter()).zip(ratios.iter()) {
+        let color = color.coerce_to_u32(activation)?;
+        let alpha = alpha.coerce_to_number(activation)?.clamp(0.0, 1.0);
+        let ratio = ratio.coerce_to_number(activation)?.clamp(0.0, 255.0);
+        records.push(GradientRecord {
+            ratio: ratio as u8,
+            color: color_from_args(color, alpha),
+        });
+    }
+
+    let matrix = match args.get(4) {
+        Some(Value::Object(matrix)) => object_to_matrix(*matrix, activation)?,
+        _ => Matrix::IDENTITY,
+    };
+    let spread = args
+        .get(5)
+        .cloned()
+        .unwrap_or_else(|| "pad".into())
+        .coerce_to_string(activation)?;
+    let spread = if &spread == b"reflect" {
+        GradientSpread::Reflect
+    } else if &spread == b"repeat" {
+        GradientSpread::Repeat
+    } else {
+        GradientSpread::Pad
+    };
+    let interpolation = args
+        .get(6)
+        .cloned()
+        .unwrap_or_else(|| "rgb".into())
+        .coerce_to_string(activation)?;
+    let interpolation = if &interpolation == b"linearRGB" {
+        GradientInterpolation::LinearRgb
+    } else {
+        GradientInterpolation::Rgb
+    };
+    let focal_point = args
+        .get(7)
+        .cloned()
+        .unwrap_or_else(|| 0.0.into())
+        .coerce_to_number(activation)?;
+
+    let gradient = Gradient {
+        matrix: matrix.into(),
+        spread,
+        interpolation,
+        records,
+    };
+    if &gradient_type == b"linear" {
+        Ok(FillStyle::LinearGradient(gradient))
+    } else if &gradient_type == b"radial" && focal_point == 0.0 {
+        Ok(FillStyle::RadialGradient(gradient))
+    } else if &gradient_type == b"radial" {
+        Ok(FillStyle::FocalGradient {
+            gradient,
+            focal_point: Fixed8::from_f64(focal_point.clamp(-1.0, 1.0)),
+        })
+    } else {
+        Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2008: Parameter type must be one of the accepted values.",
+            2008,
+        )?))
+    }
+}
+
 /// Implements `Graphics.beginBitmapFill`.
 fn begin_bitmap_fill<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    log::warn!("Graphics.beginBitmapFill: not yet implemented");
+    if let Some(this) = this {
+        if let Some(dobj) = this.as_display_object() {
+            let fill_style = bitmap_fill_from_args(activation, this, dobj, args)?;
+
+            if let Some(mut draw) = dobj.as_drawing(activation.context.gc_context) {
+                draw.set_fill_style(fill_style);
+            }
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
 /// Implements `Graphics.beginGradientFill`.
 fn begin_gradient_fill<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    log::warn!("Graphics.beginGradientFill: not yet implemented");
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let fill_style = gradient_fill_from_args(activation, args)?;
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.set_fill_style(Some(fill_style));
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -106,10 +341,14 @@ fn clear<'gc>(
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some(this) = this.and_then(|t| t.as_display_object()) {
-        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
-            draw.clear()
+    if let Some(mut this) = this {
+        if let Some(dobj) = this.as_display_object() {
+            if let Some(mut draw) = dobj.as_drawing(activation.context.gc_context) {
+                draw.clear()
+            }
         }
+
+        this.set_property(&bitmaps_name(), Value::Null, activation)?;
     }
 
     Ok(Value::Undefined)
@@ -786,6 +1025,835 @@ fn draw_ellipse<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Graphics.drawRoundRectComplex`.
+fn draw_round_rect_complex<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let mut values = [0.0; 8];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = args
+                .get(i)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_number(activation)?;
+        }
+        let [x, y, width, height, top_left, top_right, bottom_left, bottom_right] = values;
+
+        // Each corner can take up at most half of the shorter side.
+        let max_radius = width.min(height) / 2.0;
+        let top_left = top_left.clamp(0.0, max_radius);
+        let top_right = top_right.clamp(0.0, max_radius);
+        let bottom_left = bottom_left.clamp(0.0, max_radius);
+        let bottom_right = bottom_right.clamp(0.0, max_radius);
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            // Like `drawRoundRect`, we start from the bottom-right corner.
+            draw.draw_command(DrawCommand::MoveTo {
+                x: Twips::from_pixels(x + width),
+                y: Twips::from_pixels(y + height - bottom_right),
+            });
+            draw_corner(
+                &mut draw,
+                (x + width - bottom_right, y + height - bottom_right),
+                bottom_right,
+                (1.0, 1.0),
+                false,
+            );
+            draw.draw_command(DrawCommand::LineTo {
+                x: Twips::from_pixels(x + bottom_left),
+                y: Twips::from_pixels(y + height),
+            });
+            draw_corner(
+                &mut draw,
+                (x + bottom_left, y + height - bottom_left),
+                bottom_left,
+                (-1.0, 1.0),
+                true,
+            );
+            draw.draw_command(DrawCommand::LineTo {
+                x: Twips::from_pixels(x),
+                y: Twips::from_pixels(y + top_left),
+            });
+            draw_corner(
+                &mut draw,
+                (x + top_left, y + top_left),
+                top_left,
+                (-1.0, -1.0),
+                false,
+            );
+            draw.draw_command(DrawCommand::LineTo {
+                x: Twips::from_pixels(x + width - top_right),
+                y: Twips::from_pixels(y),
+            });
+            draw_corner(
+                &mut draw,
+                (x + width - top_right, y + top_right),
+                top_right,
+                (1.0, -1.0),
+                true,
+            );
+            draw.draw_command(DrawCommand::LineTo {
+                x: Twips::from_pixels(x + width),
+                y: Twips::from_pixels(y + height - bottom_right),
+            });
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Draw a quarter circle of the given radius around `center`.
+///
+/// The quadrant is chosen by reflecting the bottom-right quadrant of
+/// `UNIT_CIRCLE_POINTS` by `sign`, and `reverse` draws it from 90 degrees back
+/// to 0 degrees.
+fn draw_corner(
+    draw: &mut Drawing,
+    center: (f64, f64),
+    radius: f64,
+    sign: (f64, f64),
+    reverse: bool,
+) {
+    let point = |i: usize| {
+        let i = if reverse { 4 - i } else { i };
+        let (x, y) = UNIT_CIRCLE_POINTS[i];
+        (
+            Twips::from_pixels(center.0 + radius * x * sign.0),
+            Twips::from_pixels(center.1 + radius * y * sign.1),
+        )
+    };
+
+    for i in [1, 3] {
+        let (x1, y1) = point(i);
+        let (x2, y2) = point(i + 1);
+        draw.draw_command(DrawCommand::CurveTo { x1, y1, x2, y2 });
+    }
+}
+
+/// Approximate a cubic Bezier curve from the current pen position with a
+/// series of quadratic curves.
+///
+/// The curve is split into segments of roughly equal parameter length, and
+/// each segment is replaced by the quadratic that matches its end points and
+/// the midpoint of its control polygon.
+fn draw_cubic_curve(draw: &mut Drawing, c1: (f64, f64), c2: (f64, f64), end: (f64, f64)) {
+    let cursor = draw.cursor();
+    let start = (cursor.0.to_pixels(), cursor.1.to_pixels());
+
+    let point = |t: f64| {
+        let mt = 1.0 - t;
+        let a = mt * mt * mt;
+        let b = 3.0 * mt * mt * t;
+        let c = 3.0 * mt * t * t;
+        let d = t * t * t;
+        (
+            a * start.0 + b * c1.0 + c * c2.0 + d * end.0,
+            a * start.1 + b * c1.1 + c * c2.1 + d * end.1,
+        )
+    };
+    let derivative = |t: f64| {
+        let mt = 1.0 - t;
+        let a = 3.0 * mt * mt;
+        let b = 6.0 * mt * t;
+        let c = 3.0 * t * t;
+        (
+            a * (c1.0 - start.0) + b * (c2.0 - c1.0) + c * (end.0 - c2.0),
+            a * (c1.1 - start.1) + b * (c2.1 - c1.1) + c * (end.1 - c2.1),
+        )
+    };
+
+    // Use more segments for longer curves, measured along the control polygon.
+    let distance = |a: (f64, f64), b: (f64, f64)| (b.0 - a.0).hypot(b.1 - a.1);
+    let length = distance(start, c1) + distance(c1, c2) + distance(c2, end);
+    let segments = (length / 16.0).ceil().clamp(1.0, 32.0) as usize;
+
+    for i in 0..segments {
+        let t0 = i as f64 / segments as f64;
+        let t1 = (i + 1) as f64 / segments as f64;
+        let dt = (t1 - t0) / 3.0;
+
+        let p0 = point(t0);
+        let p3 = point(t1);
+        let d0 = derivative(t0);
+        let d3 = derivative(t1);
+        let p1 = (p0.0 + d0.0 * dt, p0.1 + d0.1 * dt);
+        let p2 = (p3.0 - d3.0 * dt, p3.1 - d3.1 * dt);
+
+        let control = (
+            (3.0 * (p1.0 + p2.0) - (p0.0 + p3.0)) / 4.0,
+            (3.0 * (p1.1 + p2.1) - (p0.1 + p3.1)) / 4.0,
+        );
+        draw.draw_command(DrawCommand::CurveTo {
+            x1: Twips::from_pixels(control.0),
+            y1: Twips::from_pixels(control.1),
+            x2: Twips::from_pixels(p3.0),
+            y2: Twips::from_pixels(p3.1),
+        });
+    }
+}
+
+/// Implements `Graphics.cubicCurveTo`.
+fn cubic_curve_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let mut values = [0.0; 6];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = args
+                .get(i)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_number(activation)?;
+        }
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw_cubic_curve(
+                &mut draw,
+                (values[0], values[1]),
+                (values[2], values[3]),
+                (values[4], values[5]),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.lineGradientStyle`.
+fn line_gradient_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let fill_style = gradient_fill_from_args(activation, args)?;
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.set_line_fill_style(fill_style);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.lineBitmapStyle`.
+fn line_bitmap_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(dobj) = this.as_display_object() {
+            let fill_style = bitmap_fill_from_args(activation, this, dobj, args)?;
+
+            if let (Some(fill_style), Some(mut draw)) =
+                (fill_style, dobj.as_drawing(activation.context.gc_context))
+            {
+                draw.set_line_fill_style(fill_style);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Read the values of a `Vector` passed to a native method, or `None` if
+/// the argument isn't a vector.
+fn vector_values<'gc>(value: Option<&Value<'gc>>) -> Option<Vec<Value<'gc>>> {
+    value
+        .and_then(|value| value.as_object())
+        .and_then(|object| {
+            object
+                .as_vector_storage()
+                .map(|storage| storage.iter().collect())
+        })
+}
+
+fn vector_numbers<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Option<&Value<'gc>>,
+) -> Result<Option<Vec<f64>>, Error<'gc>> {
+    vector_values(value)
+        .map(|values| {
+            values
+                .into_iter()
+                .map(|v| v.coerce_to_number(activation))
+                .collect()
+        })
+        .transpose()
+}
+
+fn vector_ints<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Option<&Value<'gc>>,
+) -> Result<Option<Vec<i32>>, Error<'gc>> {
+    vector_values(value)
+        .map(|values| {
+            values
+                .into_iter()
+                .map(|v| v.coerce_to_i32(activation))
+                .collect()
+        })
+        .transpose()
+}
+
+/// Implements `Graphics.drawPath`.
+///
+/// Both winding rules are drawn with the even-odd rule.
+fn draw_path<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let commands = vector_ints(activation, args.get(0))?.unwrap_or_default();
+        let data = vector_numbers(activation, args.get(1))?.unwrap_or_default();
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            let point = |i: usize| (Twips::from_pixels(data[i]), Twips::from_pixels(data[i + 1]));
+            let mut i = 0;
+
+            for command in commands {
+                let size = match command {
+                    PATH_MOVE_TO | PATH_LINE_TO => 2,
+                    PATH_CURVE_TO | PATH_WIDE_MOVE_TO | PATH_WIDE_LINE_TO => 4,
+                    PATH_CUBIC_CURVE_TO => 6,
+                    _ => 0,
+                };
+                if i + size > data.len() {
+                    break;
+                }
+
+                match command {
+                    PATH_MOVE_TO => {
+                        let (x, y) = point(i);
+                        draw.draw_command(DrawCommand::MoveTo { x, y });
+                    }
+                    PATH_LINE_TO => {
+                        let (x, y) = point(i);
+                        draw.draw_command(DrawCommand::LineTo { x, y });
+                    }
+                    PATH_CURVE_TO => {
+                        let (x1, y1) = point(i);
+                        let (x2, y2) = point(i + 2);
+                        draw.draw_command(DrawCommand::CurveTo { x1, y1, x2, y2 });
+                    }
+                    // The wide variants carry an unused extra point first.
+                    PATH_WIDE_MOVE_TO => {
+                        let (x, y) = point(i + 2);
+                        draw.draw_command(DrawCommand::MoveTo { x, y });
+                    }
+                    PATH_WIDE_LINE_TO => {
+                        let (x, y) = point(i + 2);
+                        draw.draw_command(DrawCommand::LineTo { x, y });
+                    }
+                    PATH_CUBIC_CURVE_TO => {
+                        draw_cubic_curve(
+                            &mut draw,
+                            (data[i], data[i + 1]),
+                            (data[i + 2], data[i + 3]),
+                            (data[i + 4], data[i + 5]),
+                        );
+                    }
+                    PATH_NO_OP => {}
+                    _ => {}
+                }
+
+                i += size;
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Calculate the bitmap matrix that maps the bitmap coordinates `uvs` of a
+/// triangle onto its vertices `points`, both in pixels.
+fn triangle_bitmap_matrix(points: [(f64, f64); 3], uvs: [(f64, f64); 3]) -> Option<Matrix> {
+    let (s1, s2) = (
+        (uvs[1].0 - uvs[0].0, uvs[1].1 - uvs[0].1),
+        (uvs[2].0 - uvs[0].0, uvs[2].1 - uvs[0].1),
+    );
+    let (d1, d2) = (
+        (points[1].0 - points[0].0, points[1].1 - points[0].1),
+        (points[2].0 - points[0].0, points[2].1 - points[0].1),
+    );
+
+    let determinant = s1.0 * s2.1 - s2.0 * s1.1;
+    if determinant.abs() < f64::EPSILON {
+        return None;
+    }
+
+    let a = (d1.0 * s2.1 - d2.0 * s1.1) / determinant;
+    let b = (d1.1 * s2.1 - d2.1 * s1.1) / determinant;
+    let c = (d2.0 * s1.0 - d1.0 * s2.0) / determinant;
+    let d = (d2.1 * s1.0 - d1.1 * s2.0) / determinant;
+    let tx = points[0].0 - (a * uvs[0].0 + c * uvs[0].1);
+    let ty = points[0].1 - (b * uvs[0].0 + d * uvs[0].1);
+
+    let mut matrix = Matrix {
+        a: a as f32,
+        b: b as f32,
+        c: c as f32,
+        d: d as f32,
+        tx: Twips::from_pixels(tx),
+        ty: Twips::from_pixels(ty),
+    };
+    matrix *= Matrix::scale(Twips::TWIPS_PER_PIXEL as f32, Twips::TWIPS_PER_PIXEL as f32);
+    Some(matrix)
+}
+
+/// Implements `Graphics.drawTriangles`.
+///
+/// If the current fill is a bitmap and `uvtData` is given, each triangle is
+/// filled with the bitmap mapped onto it. The `t` values of 3D UVT data are
+/// ignored.
+fn draw_triangles<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let vertices = vector_numbers(activation, args.get(0))?.unwrap_or_default();
+        let vertex_count = vertices.len() / 2;
+        let indices = match vector_ints(activation, args.get(1))? {
+            Some(indices) => indices,
+            None => (0..vertex_count as i32).collect(),
+        };
+        let uvt_data = vector_numbers(activation, args.get(2))?;
+        let culling = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| "none".into())
+            .coerce_to_string(activation)?;
+        let culling = if &culling == b"positive" {
+            1.0
+        } else if &culling == b"negative" {
+            -1.0
+        } else if &culling == b"none" {
+            0.0
+        } else {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2008: Parameter culling must be one of the accepted values.",
+                2008,
+            )?));
+        };
+
+        if indices
+            .iter()
+            .any(|&index| index < 0 || index as usize >= vertex_count)
+        {
+            return Err(Error::AvmError(range_error(
+                activation,
+                "Error #2004: One of the parameters is invalid.",
+                2004,
+            )?));
+        }
+
+        let uvt_stride = match &uvt_data {
+            Some(uvt_data) if uvt_data.len() == vertex_count * 3 => 3,
+            _ => 2,
+        };
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            let original_fill = draw.fill_style().cloned();
+            let bitmap_fill = match &original_fill {
+                Some(FillStyle::Bitmap {
+                    id,
+                    is_smoothed,
+                    is_repeating,
+                    ..
+                }) => draw
+                    .bitmap_size(*id)
+                    .map(|size| (*id, size, *is_smoothed, *is_repeating)),
+                _ => None,
+            };
+
+            for triangle in indices.chunks_exact(3) {
+                let mut points = [(0.0, 0.0); 3];
+                let mut uvs = [(0.0, 0.0); 3];
+                for (i, &index) in triangle.iter().enumerate() {
+                    let index = index as usize;
+                    points[i] = (vertices[index * 2], vertices[index * 2 + 1]);
+                    if let Some(uvt_data) = &uvt_data {
+                        let uv = index * uvt_stride;
+                        uvs[i] = (
+                            uvt_data.get(uv).copied().unwrap_or_default(),
+                            uvt_data.get(uv + 1).copied().unwrap_or_default(),
+                        );
+                    }
+                }
+
+                let cross = (points[1].0 - points[0].0) * (points[2].1 - points[0].1)
+                    - (points[1].1 - points[0].1) * (points[2].0 - points[0].0);
+                if cross * culling > 0.0 {
+                    continue;
+                }
+
+                if let (Some((id, size, is_smoothed, is_repeating)), Some(_)) =
+                    (bitmap_fill, &uvt_data)
+                {
+                    let (width, height) = (size.width as f64, size.height as f64);
+                    let uvs = uvs.map(|(u, v)| (u * width, v * height));
+                    if let Some(matrix) = triangle_bitmap_matrix(points, uvs) {
+                        draw.set_fill_style(Some(FillStyle::Bitmap {
+                            id,
+                            matrix: matrix.into(),
+                            is_smoothed,
+                            is_repeating,
+                        }));
+                    }
+                }
+
+                let [(x0, y0), (x1, y1), (x2, y2)] =
+                    points.map(|(x, y)| (Twips::from_pixels(x), Twips::from_pixels(y)));
+                draw.draw_command(DrawCommand::MoveTo { x: x0, y: y0 });
+                draw.draw_command(DrawCommand::LineTo { x: x1, y: y1 });
+                draw.draw_command(DrawCommand::LineTo { x: x2, y: y2 });
+                draw.draw_command(DrawCommand::LineTo { x: x0, y: y0 });
+            }
+
+            if bitmap_fill.is_some() && uvt_data.is_some() {
+                draw.set_fill_style(original_fill);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.copyFrom`.
+fn copy_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        let source = match args.get(0) {
+            Some(Value::Object(source)) => *source,
+            _ => {
+                return Err(Error::AvmError(type_error(
+                    activation,
+                    "Error #2007: Parameter sourceGraphics must be non-null.",
+                    2007,
+                )?))
+            }
+        };
+
+        // Copy the source first, in case it's the same drawing as ours.
+        let drawing = source
+            .as_display_object()
+            .and_then(|dobj| dobj.as_drawing_ref().map(|draw| draw.clone()))
+            .unwrap_or_default();
+        if let Some(dobj) = this.as_display_object() {
+            if let Some(mut draw) = dobj.as_drawing(activation.context.gc_context) {
+                draw.copy_from(&drawing);
+            }
+        }
+
+        let bitmaps = match source.get_property(&bitmaps_name(), activation)? {
+            Value::Object(bitmaps) => {
+                let values = array_values(Some(&bitmaps.into()));
+                ArrayObject::from_storage(activation, ArrayStorage::from_args(&values))?.into()
+            }
+            _ => Value::Null,
+        };
+        this.set_property(&bitmaps_name(), bitmaps, activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct one of the `flash.display` graphics data classes.
+fn construct_graphics_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: &'static str,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let class =
+        activation.resolve_class(&Multiname::new(Namespace::package("flash.display"), name))?;
+    Ok(class.construct(activation, args)?.into())
+}
+
+/// Convert a fill style of a drawing back into a `IGraphicsFill` object.
+///
+/// `bitmaps` holds the `BitmapData` objects registered with the drawing,
+/// indexed by bitmap id.
+fn fill_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    style: &FillStyle,
+    matrix: Matrix,
+    bitmaps: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (gradient_type, gradient, focal_point) = match style {
+        FillStyle::Color(color) => {
+            return construct_graphics_data(
+                activation,
+                "GraphicsSolidFill",
+                &[color.to_rgb().into(), (color.a as f64 / 255.0).into()],
+            );
+        }
+        FillStyle::Bitmap {
+            id,
+            matrix: bitmap_matrix,
+            is_smoothed,
+            is_repeating,
+        } => {
+            let mut bitmap_matrix = matrix * Matrix::from(*bitmap_matrix);
+            bitmap_matrix *= Matrix::scale(
+                1.0 / Twips::TWIPS_PER_PIXEL as f32,
+                1.0 / Twips::TWIPS_PER_PIXEL as f32,
+            );
+            let bitmap_matrix = matrix_to_object(bitmap_matrix, activation)?;
+            let bitmap_data = bitmaps.get(*id as usize).cloned().unwrap_or(Value::Null);
+            return construct_graphics_data(
+                activation,
+                "GraphicsBitmapFill",
+                &[
+                    bitmap_data,
+                    bitmap_matrix,
+                    (*is_repeating).into(),
+                    (*is_smoothed).into(),
+                ],
+            );
+        }
+        FillStyle::LinearGradient(gradient) => ("linear", gradient, 0.0),
+        FillStyle::RadialGradient(gradient) => ("radial", gradient, 0.0),
+        FillStyle::FocalGradient {
+            gradient,
+            focal_point,
+        } => ("radial", gradient, focal_point.to_f64()),
+    };
+
+    let mut colors = ArrayStorage::new(0);
+    let mut alphas = ArrayStorage::new(0);
+    let mut ratios = ArrayStorage::new(0);
+    for record in &gradient.records {
+        colors.push(record.color.to_rgb().into());
+        alphas.push((record.color.a as f64 / 255.0).into());
+        ratios.push(record.ratio.into());
+    }
+    let colors = ArrayObject::from_storage(activation, colors)?;
+    let alphas = ArrayObject::from_storage(activation, alphas)?;
+    let ratios = ArrayObject::from_storage(activation, ratios)?;
+    let gradient_matrix = matrix_to_object(matrix * Matrix::from(gradient.matrix), activation)?;
+    let spread = match gradient.spread {
+        GradientSpread::Pad => "pad",
+        GradientSpread::Reflect => "reflect",
+        GradientSpread::Repeat => "repeat",
+    };
+    let interpolation = match gradient.interpolation {
+        GradientInterpolation::Rgb => "rgb",
+        GradientInterpolation::LinearRgb => "linearRGB",
+    };
+
+    construct_graphics_data(
+        activation,
+        "GraphicsGradientFill",
+        &[
+            gradient_type.into(),
+            colors.into(),
+            alphas.into(),
+            ratios.into(),
+            gradient_matrix,
+            spread.into(),
+            interpolation.into(),
+            focal_point.into(),
+        ],
+    )
+}
+
+/// Convert the commands of a drawing path into a `GraphicsPath` object.
+fn commands_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    commands: &[DrawCommand],
+    matrix: Matrix,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut path_commands = Vec::with_capacity(commands.len());
+    let mut path_data = Vec::with_capacity(commands.len() * 2);
+    let mut push_point = |x: Twips, y: Twips| {
+        let (x, y) = matrix * (x, y);
+        path_data.push(x.to_pixels().into());
+        path_data.push(y.to_pixels().into());
+    };
+
+    for command in commands {
+        match *command {
+            DrawCommand::MoveTo { x, y } => {
+                path_commands.push(PATH_MOVE_TO.into());
+                push_point(x, y);
+            }
+            DrawCommand::LineTo { x, y } => {
+                path_commands.push(PATH_LINE_TO.into());
+                push_point(x, y);
+            }
+            DrawCommand::CurveTo { x1, y1, x2, y2 } => {
+                path_commands.push(PATH_CURVE_TO.into());
+                push_point(x1, y1);
+                push_point(x2, y2);
+            }
+        }
+    }
+
+    let int_class = activation.avm2().classes().int;
+    let number_class = activation.avm2().classes().number;
+    let path_commands = VectorStorage::from_values(path_commands, false, int_class);
+    let path_commands = VectorObject::from_vector(path_commands, activation)?;
+    let path_data = VectorStorage::from_values(path_data, false, number_class);
+    let path_data = VectorObject::from_vector(path_data, activation)?;
+
+    construct_graphics_data(
+        activation,
+        "GraphicsPath",
+        &[path_commands.into(), path_data.into()],
+    )
+}
+
+/// Convert a line style of a drawing into a `GraphicsStroke` object.
+fn stroke_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    style: &LineStyle,
+    matrix: Matrix,
+    bitmaps: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let scale_mode = match (style.allow_scale_x(), style.allow_scale_y()) {
+        (true, true) => "normal",
+        (true, false) => "horizontal",
+        (false, true) => "vertical",
+        (false, false) => "none",
+    };
+    let caps = match style.start_cap() {
+        LineCapStyle::None => "none",
+        LineCapStyle::Square => "square",
+        LineCapStyle::Round => "round",
+    };
+    let (joints, miter_limit) = match style.join_style() {
+        LineJoinStyle::Round => ("round", 3.0),
+        LineJoinStyle::Bevel => ("bevel", 3.0),
+        LineJoinStyle::Miter(miter_limit) => ("miter", miter_limit.to_f64()),
+    };
+    let fill = fill_to_object(activation, style.fill_style(), matrix, bitmaps)?;
+
+    construct_graphics_data(
+        activation,
+        "GraphicsStroke",
+        &[
+            style.width().to_pixels().into(),
+            style.is_pixel_hinted().into(),
+            scale_mode.into(),
+            caps.into(),
+            joints.into(),
+            miter_limit.into(),
+            fill,
+        ],
+    )
+}
+
+/// Append the graphics data of a display object, and optionally its
+/// children, to `output`.
+fn read_graphics_data_internal<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    display_object: DisplayObject<'gc>,
+    matrix: Matrix,
+    recurse: bool,
+    output: &mut Vec<Value<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let drawing = display_object.as_drawing_ref().map(|draw| draw.clone());
+
+    if let Some(drawing) = drawing {
+        // Bitmaps are only known if the `Graphics` object has been created.
+        let bitmaps = match display_object.object2() {
+            Value::Object(object) => match object.get_property(
+                &Multiname::new(Namespace::private(NS_RUFFLE_INTERNAL), "graphics"),
+                activation,
+            )? {
+                Value::Object(graphics) => {
+                    array_values(Some(&graphics.get_property(&bitmaps_name(), activation)?))
+                }
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+
+        for path in drawing.draw_paths() {
+            match path {
+                DrawPath::Fill { style, commands } => {
+                    output.push(fill_to_object(activation, style, matrix, &bitmaps)?);
+                    output.push(commands_to_object(activation, &commands, matrix)?);
+                    output.push(construct_graphics_data(activation, "GraphicsEndFill", &[])?);
+                }
+                DrawPath::Stroke {
+                    style, commands, ..
+                } => {
+                    output.push(stroke_to_object(activation, style, matrix, &bitmaps)?);
+                    output.push(commands_to_object(activation, &commands, matrix)?);
+                    output.push(construct_graphics_data(activation, "GraphicsStroke", &[])?);
+                }
+            }
+        }
+    }
+
+    if recurse {
+        if let Some(container) = display_object.as_container() {
+            for child in container.iter_render_list() {
+                let child_matrix = matrix * *child.base().matrix();
+                read_graphics_data_internal(activation, child, child_matrix, recurse, output)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Implements `Graphics.readGraphicsData`.
+fn read_graphics_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let recurse = args
+        .get(0)
+        .cloned()
+        .unwrap_or_else(|| true.into())
+        .coerce_to_boolean();
+
+    let mut data = Vec::new();
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        read_graphics_data_internal(activation, this, Matrix::IDENTITY, recurse, &mut data)?;
+    }
+
+    let data_class = activation.resolve_class(&Multiname::new(
+        Namespace::package("flash.display"),
+        "IGraphicsData",
+    ))?;
+    let storage = VectorStorage::from_values(data, false, data_class);
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
+
+/// Implements `Graphics.drawGraphicsData`.
+fn draw_graphics_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        for item in vector_values(args.get(0)).unwrap_or_default() {
+            // Each graphics data class knows how to draw itself.
+            if let Value::Object(item) = item {
+                item.call_property(&ruffle_name("draw"), &[this.into()], activation)?;
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `Graphics`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -820,8 +1888,114 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("drawRoundRect", draw_round_rect),
         ("drawCircle", draw_circle),
         ("drawEllipse", draw_ellipse),
+        ("drawRoundRectComplex", draw_round_rect_complex),
+        ("cubicCurveTo", cubic_curve_to),
+        ("lineGradientStyle", line_gradient_style),
+        ("lineBitmapStyle", line_bitmap_style),
+        ("drawPath", draw_path),
+        ("drawTriangles", draw_triangles),
+        ("copyFrom", copy_from),
+        ("readGraphicsData", read_graphics_data),
+        ("drawGraphicsData", draw_graphics_data),
     ];
     write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
 
+    // Slot for the `BitmapData` objects used by bitmap fills.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "bitmaps"),
+        Multiname::public("Array"),
+        None,
+    ));
+
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve_end_points(draw: &Drawing) -> Vec<(Twips, Twips)> {
+        let paths = draw.draw_paths();
+        let commands = match &paths[..] {
+            [DrawPath::Fill { commands, .. }] => commands,
+            _ => panic!("expected a single fill"),
+        };
+        commands
+            .iter()
+            .filter_map(|command| match *command {
+                DrawCommand::CurveTo { x2, y2, .. } => Some((x2, y2)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn filled_drawing() -> Drawing {
+        let mut draw = Drawing::new();
+        draw.set_fill_style(Some(FillStyle::Color(Color::from_rgb(0, 255))));
+        draw
+    }
+
+    #[test]
+    fn cubic_curve_segments_end_on_the_curve() {
+        let mut draw = filled_drawing();
+        let (start, c1, c2, end) = ((10.0, 10.0), (40.0, 90.0), (90.0, -30.0), (120.0, 40.0));
+        draw.draw_command(DrawCommand::MoveTo {
+            x: Twips::from_pixels(start.0),
+            y: Twips::from_pixels(start.1),
+        });
+        draw_cubic_curve(&mut draw, c1, c2, end);
+
+        let end_points = curve_end_points(&draw);
+        let segments = end_points.len();
+        assert!(segments > 1);
+        for (i, (x, y)) in end_points.into_iter().enumerate() {
+            let t = (i + 1) as f64 / segments as f64;
+            let mt = 1.0 - t;
+            let expected = |p0: f64, p1: f64, p2: f64, p3: f64| {
+                mt * mt * mt * p0 + 3.0 * mt * mt * t * p1 + 3.0 * mt * t * t * p2 + t * t * t * p3
+            };
+            let expected_x = Twips::from_pixels(expected(start.0, c1.0, c2.0, end.0));
+            let expected_y = Twips::from_pixels(expected(start.1, c1.1, c2.1, end.1));
+            assert!((x.get() - expected_x.get()).abs() <= 1, "segment {i}");
+            assert!((y.get() - expected_y.get()).abs() <= 1, "segment {i}");
+        }
+        assert_eq!(
+            draw.cursor(),
+            (Twips::from_pixels(end.0), Twips::from_pixels(end.1))
+        );
+    }
+
+    #[test]
+    fn short_cubic_curve_uses_one_segment() {
+        let mut draw = filled_drawing();
+        draw_cubic_curve(&mut draw, (2.0, 0.0), (4.0, 2.0), (6.0, 2.0));
+
+        assert_eq!(
+            curve_end_points(&draw),
+            vec![(Twips::from_pixels(6.0), Twips::from_pixels(2.0))]
+        );
+    }
+
+    #[test]
+    fn triangle_bitmap_matrix_maps_uvs_onto_points() {
+        let points = [(10.0, 20.0), (110.0, 20.0), (10.0, 70.0)];
+        let uvs = [(0.0, 0.0), (50.0, 0.0), (0.0, 100.0)];
+        let matrix = triangle_bitmap_matrix(points, uvs).unwrap();
+
+        for (point, uv) in points.into_iter().zip(uvs) {
+            // Bitmap fill matrices map bitmap pixels, with one pixel per unit.
+            let uv = (Twips::new(uv.0 as i32), Twips::new(uv.1 as i32));
+            assert_eq!(
+                matrix * uv,
+                (Twips::from_pixels(point.0), Twips::from_pixels(point.1))
+            );
+        }
+    }
+
+    #[test]
+    fn degenerate_triangle_uvs_have_no_bitmap_matrix() {
+        let points = [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)];
+        let uvs = [(0.0, 0.0), (5.0, 5.0), (10.0, 10.0)];
+        assert!(triangle_bitmap_matrix(points, uvs).is_none());
+    }
+}
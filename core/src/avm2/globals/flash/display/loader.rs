@@ -1,18 +1,21 @@
 //! `flash.display.Loader` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::error::security_error;
 use crate::avm2::object::LoaderInfoObject;
 use crate::avm2::object::TObject;
 use crate::avm2::value::Value;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
-use crate::avm2::{Error, Object};
+use crate::avm2::{Domain, Error, Object};
 use crate::backend::navigator::Request;
 use crate::display_object::LoaderDisplay;
 use crate::display_object::MovieClip;
-use crate::loader::MovieLoaderEventHandler;
+use crate::display_object::{TDisplayObject, TDisplayObjectContainer};
+use crate::loader::{MovieLoadOptions, MovieLoaderEventHandler};
 use crate::tag_utils::SwfMovie;
 use std::sync::Arc;
+use swf::read::read_compression_type;
 
 pub fn init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -49,6 +52,36 @@ pub fn init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Throw a `SecurityError` if the calling movie may not script this
+/// `Loader`'s content.
+pub fn check_content_access<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(content) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_container())
+        .and_then(|container| container.child_by_index(0))
+    {
+        let requester = activation.context.swf.url().map(|u| u.to_string());
+        if let Some(url) = activation
+            .context
+            .security_sandbox
+            .find_inaccessible_content(requester.as_deref(), content, false)
+        {
+            let message = format!(
+                "Error #2121: Security sandbox violation: Loader.content: {} cannot access {}. This may be worked around by calling Security.allowDomain.",
+                requester.unwrap_or_default(),
+                url
+            );
+            return Err(Error::AvmError(security_error(activation, &message, 2121)?));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn load<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -64,6 +97,9 @@ pub fn load<'gc>(
             .get_property(&Multiname::public("url"), activation)?
             .coerce_to_string(activation)?;
 
+        let domain = loader_context_domain(activation, context)?;
+        let options = loader_context_options(activation, context)?;
+
         // This is a dummy MovieClip, which will get overwritten in `Loader`
         let content = MovieClip::new(
             Arc::new(SwfMovie::empty(activation.context.swf.version())),
@@ -85,7 +121,8 @@ pub fn load<'gc>(
             Request::get(url.to_string()),
             Some(url.to_string()),
             Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)),
-            context,
+            Some(domain),
+            options,
         );
         activation.context.navigator.spawn_future(future);
     }
@@ -104,6 +141,17 @@ pub fn load_bytes<'gc>(
             .get(1)
             .and_then(|v| v.coerce_to_object(activation).ok());
 
+        let options = loader_context_options(activation, context)?;
+        if !options.allow_code_import && read_compression_type(bytearray.bytes()).is_ok() {
+            return Err(Error::AvmError(security_error(
+                activation,
+                "Error #3226: Cannot import a SWF file when LoaderContext.allowCodeImport is false.",
+                3226,
+            )?));
+        }
+
+        let domain = loader_context_domain(activation, context)?;
+
         // This is a dummy MovieClip, which will get overwritten in `Loader`
         let content = MovieClip::new(
             Arc::new(SwfMovie::empty(activation.context.swf.version())),
@@ -123,9 +171,117 @@ pub fn load_bytes<'gc>(
             content.into(),
             bytearray.bytes().to_vec(),
             Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)),
-            context,
+            Some(domain),
         );
         activation.context.navigator.spawn_future(future);
     }
     Ok(Value::Undefined)
 }
+
+/// Read the options of a `LoaderContext` that affect how content is loaded.
+///
+/// This throws if the calling movie is local and sets `securityDomain`.
+fn loader_context_options<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    context: Option<Object<'gc>>,
+) -> Result<MovieLoadOptions, Error<'gc>> {
+    let context = match context {
+        Some(context) => context,
+        None => return Ok(Default::default()),
+    };
+
+    let security_domain = context.get_property(&Multiname::public("securityDomain"), activation)?;
+    let is_local = activation
+        .caller_movie()
+        .url()
+        .map_or(true, |url| url.starts_with("file:"));
+    if is_local && !matches!(security_domain, Value::Null | Value::Undefined) {
+        return Err(Error::AvmError(security_error(
+            activation,
+            "Error #2142: Security sandbox violation: local SWF files cannot use the LoaderContext.securityDomain property.",
+            2142,
+        )?));
+    }
+
+    Ok(MovieLoadOptions {
+        check_policy_file: context
+            .get_property(&Multiname::public("checkPolicyFile"), activation)?
+            .coerce_to_boolean(),
+        allow_code_import: context
+            .get_property(&Multiname::public("allowCodeImport"), activation)?
+            .coerce_to_boolean(),
+        requester_url: activation.caller_movie().url().map(str::to_string),
+    })
+}
+
+/// Determine the application domain that a movie loaded with the given
+/// `LoaderContext` should run in.
+///
+/// Without an explicit `applicationDomain`, the movie gets a new child of the
+/// loading movie's domain.
+fn loader_context_domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    context: Option<Object<'gc>>,
+) -> Result<Domain<'gc>, Error<'gc>> {
+    let domain = match context {
+        Some(context) => context
+            .get_property(&Multiname::public("applicationDomain"), activation)?
+            .as_object()
+            .and_then(|o| o.as_application_domain()),
+        None => None,
+    };
+
+    Ok(domain.unwrap_or_else(|| {
+        let parent_domain = activation.caller_domain();
+        Domain::movie_domain(activation, parent_domain)
+    }))
+}
+
+pub fn unload<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        return unload_content(activation, this, false);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn unload_and_stop<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        return unload_content(activation, this, true);
+    }
+    Ok(Value::Undefined)
+}
+
+fn unload_content<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    and_stop: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let loader_info = match this
+        .get_property(
+            &Multiname::new(Namespace::private(""), "_contentLoaderInfo"),
+            activation,
+        )?
+        .as_object()
+    {
+        Some(loader_info) => loader_info,
+        // The loader hasn't been initialized, so there is nothing to unload.
+        None => return Ok(Value::Undefined),
+    };
+
+    if let Some(loader) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_loader_display())
+    {
+        loader.unload_content(&mut activation.context, loader_info, and_stop);
+    }
+
+    Ok(Value::Undefined)
+}
@@ -262,14 +262,80 @@ pub fn is_url_inaccessible<'gc>(
     Ok(false.into())
 }
 
-/// `parentAllowsChild` getter stub
+/// Determine the URLs of the movie that loaded a `LoaderInfo`'s content, and
+/// of that content.
+fn sandbox_urls<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> (Option<String>, Option<String>) {
+    let parent_url = activation.context.swf.url().map(|u| u.to_string());
+    let child_url = this
+        .as_loader_info_object()
+        .and_then(|o| o.as_loader_stream())
+        .and_then(|loader_stream| match &*loader_stream {
+            LoaderStream::NotYetLoaded(movie, _, _) | LoaderStream::Swf(movie, _) => {
+                movie.url().map(|u| u.to_string())
+            }
+        });
+
+    (parent_url, child_url)
+}
+
+/// `childAllowsParent` getter
+pub fn child_allows_parent<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (parent_url, child_url) = sandbox_urls(activation, this);
+        return Ok(activation
+            .context
+            .security_sandbox
+            .can_access_content(parent_url.as_deref(), child_url.as_deref())
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// `parentAllowsChild` getter
 pub fn parent_allows_child<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    log::warn!("LoaderInfo.parentAllowsChild is a stub");
-    Ok(false.into())
+    if let Some(this) = this {
+        let (parent_url, child_url) = sandbox_urls(activation, this);
+        return Ok(activation
+            .context
+            .security_sandbox
+            .can_access_content(child_url.as_deref(), parent_url.as_deref())
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// `sameDomain` getter
+pub fn same_domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (parent_url, child_url) = sandbox_urls(activation, this);
+        let is_cross_domain = match child_url {
+            Some(child_url) => activation
+                .context
+                .security_sandbox
+                .is_cross_domain(parent_url.as_deref(), &child_url),
+            None => false,
+        };
+        return Ok((!is_cross_domain).into());
+    }
+
+    Ok(Value::Undefined)
 }
 
 /// `swfVersion` getter
@@ -535,11 +601,13 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("bytesLoaded", Some(bytes_loaded), None),
         ("bytesTotal", Some(bytes_total), None),
         ("content", Some(content), None),
+        ("childAllowsParent", Some(child_allows_parent), None),
         ("contentType", Some(content_type), None),
         ("frameRate", Some(frame_rate), None),
         ("height", Some(height), None),
         ("isURLInaccessible", Some(is_url_inaccessible), None),
         ("parentAllowsChild", Some(parent_allows_child), None),
+        ("sameDomain", Some(same_domain), None),
         ("swfVersion", Some(swf_version), None),
         ("url", Some(url), None),
         ("width", Some(width), None),
@@ -0,0 +1,30 @@
+package flash.events
+{
+    public class PermissionEvent extends Event
+    {
+        public static const PERMISSION_STATUS:String = "permissionStatus"; // Defines the value of the type property of a permissionStatus event object.
+
+        private var _status: String; // The status of the requested permission.
+
+        public function PermissionEvent(type:String, bubbles:Boolean = false, cancelable:Boolean = false, status:String = "denied")
+        {
+            super(type,bubbles,cancelable);
+            this._status = status;
+        }
+
+        public function get status():String
+        {
+            return this._status;
+        }
+
+        override public function clone():Event
+        {
+            return new PermissionEvent(this.type, this.bubbles, this.cancelable, this.status);
+        }
+
+        override public function toString():String
+        {
+            return this.formatToString("PermissionEvent","type","bubbles","cancelable","eventPhase","status");
+        }
+    }
+}
@@ -38,6 +38,14 @@ package flash.geom {
         }
 
         public function appendRotation(degrees:Number, axis:Vector3D, pivotPoint:Vector3D = null):void {
+            this.append(getRotationMatrix(degrees, axis, pivotPoint));
+        }
+
+        public function prependRotation(degrees:Number, axis:Vector3D, pivotPoint:Vector3D = null):void {
+            this.prepend(getRotationMatrix(degrees, axis, pivotPoint));
+        }
+
+        private static function getRotationMatrix(degrees:Number, axis:Vector3D, pivotPoint:Vector3D):Matrix3D {
             var tx:Number, ty:Number, tz:Number;
             tx = ty = tz = 0;
 
@@ -85,7 +93,7 @@ package flash.geom {
             d[14] = (tz * (x2 + y2) - z * (tx * x + ty * y)) * ccos + (tx * y - ty * x) * sin;
 			m.rawData = d;
 
-            this.append(m);
+            return m;
         }
 
     public function copyRawDataFrom(vector:Vector.<Number>, index:uint = 0, transpose:Boolean = false):void {
@@ -183,5 +191,333 @@ package flash.geom {
 		return new Matrix3D(this.rawData.concat());
 	}
 
+        public function prepend(rhs:Matrix3D):void {
+            var result:Matrix3D = rhs.clone();
+            result.append(this);
+            this._rawData = result._rawData;
+        }
+
+        public function appendScale(xScale:Number, yScale:Number, zScale:Number):void {
+            this.append(new Matrix3D(new <Number>[
+                xScale, 0, 0, 0,
+                0, yScale, 0, 0,
+                0, 0, zScale, 0,
+                0, 0, 0, 1
+            ]));
+        }
+
+        public function prependScale(xScale:Number, yScale:Number, zScale:Number):void {
+            this.prepend(new Matrix3D(new <Number>[
+                xScale, 0, 0, 0,
+                0, yScale, 0, 0,
+                0, 0, zScale, 0,
+                0, 0, 0, 1
+            ]));
+        }
+
+        public function prependTranslation(x:Number, y:Number, z:Number):void {
+            var m:Matrix3D = new Matrix3D();
+            m.position = new Vector3D(x, y, z);
+            this.prepend(m);
+        }
+
+        public function get position():Vector3D {
+            return new Vector3D(this._rawData[12], this._rawData[13], this._rawData[14]);
+        }
+
+        public function set position(value:Vector3D):void {
+            this._rawData[12] = value.x;
+            this._rawData[13] = value.y;
+            this._rawData[14] = value.z;
+        }
+
+        public function get determinant():Number {
+            var m = this._rawData;
+            return (m[0] * m[5] - m[4] * m[1]) * (m[10] * m[15] - m[14] * m[11])
+                - (m[0] * m[9] - m[8] * m[1]) * (m[6] * m[15] - m[14] * m[7])
+                + (m[0] * m[13] - m[12] * m[1]) * (m[6] * m[11] - m[10] * m[7])
+                + (m[4] * m[9] - m[8] * m[5]) * (m[2] * m[15] - m[14] * m[3])
+                - (m[4] * m[13] - m[12] * m[5]) * (m[2] * m[11] - m[10] * m[3])
+                + (m[8] * m[13] - m[12] * m[9]) * (m[2] * m[7] - m[6] * m[3]);
+        }
+
+        public function invert():Boolean {
+            var d:Number = this.determinant;
+            if (Math.abs(d) < 0.00000000001) {
+                return false;
+            }
+            d = 1 / d;
+            var m = this._rawData.concat();
+            var m11 = m[0], m21 = m[4], m31 = m[8], m41 = m[12];
+            var m12 = m[1], m22 = m[5], m32 = m[9], m42 = m[13];
+            var m13 = m[2], m23 = m[6], m33 = m[10], m43 = m[14];
+            var m14 = m[3], m24 = m[7], m34 = m[11], m44 = m[15];
+
+            this._rawData[0] = d * (m22 * (m33 * m44 - m43 * m34) - m32 * (m23 * m44 - m43 * m24) + m42 * (m23 * m34 - m33 * m24));
+            this._rawData[1] = -d * (m12 * (m33 * m44 - m43 * m34) - m32 * (m13 * m44 - m43 * m14) + m42 * (m13 * m34 - m33 * m14));
+            this._rawData[2] = d * (m12 * (m23 * m44 - m43 * m24) - m22 * (m13 * m44 - m43 * m14) + m42 * (m13 * m24 - m23 * m14));
+            this._rawData[3] = -d * (m12 * (m23 * m34 - m33 * m24) - m22 * (m13 * m34 - m33 * m14) + m32 * (m13 * m24 - m23 * m14));
+            this._rawData[4] = -d * (m21 * (m33 * m44 - m43 * m34) - m31 * (m23 * m44 - m43 * m24) + m41 * (m23 * m34 - m33 * m24));
+            this._rawData[5] = d * (m11 * (m33 * m44 - m43 * m34) - m31 * (m13 * m44 - m43 * m14) + m41 * (m13 * m34 - m33 * m14));
+            this._rawData[6] = -d * (m11 * (m23 * m44 - m43 * m24) - m21 * (m13 * m44 - m43 * m14) + m41 * (m13 * m24 - m23 * m14));
+            this._rawData[7] = d * (m11 * (m23 * m34 - m33 * m24) - m21 * (m13 * m34 - m33 * m14) + m31 * (m13 * m24 - m23 * m14));
+            this._rawData[8] = d * (m21 * (m32 * m44 - m42 * m34) - m31 * (m22 * m44 - m42 * m24) + m41 * (m22 * m34 - m32 * m24));
+            this._rawData[9] = -d * (m11 * (m32 * m44 - m42 * m34) - m31 * (m12 * m44 - m42 * m14) + m41 * (m12 * m34 - m32 * m14));
+            this._rawData[10] = d * (m11 * (m22 * m44 - m42 * m24) - m21 * (m12 * m44 - m42 * m14) + m41 * (m12 * m24 - m22 * m14));
+            this._rawData[11] = -d * (m11 * (m22 * m34 - m32 * m24) - m21 * (m12 * m34 - m32 * m14) + m31 * (m12 * m24 - m22 * m14));
+            this._rawData[12] = -d * (m21 * (m32 * m43 - m42 * m33) - m31 * (m22 * m43 - m42 * m23) + m41 * (m22 * m33 - m32 * m23));
+            this._rawData[13] = d * (m11 * (m32 * m43 - m42 * m33) - m31 * (m12 * m43 - m42 * m13) + m41 * (m12 * m33 - m32 * m13));
+            this._rawData[14] = -d * (m11 * (m22 * m43 - m42 * m23) - m21 * (m12 * m43 - m42 * m13) + m41 * (m12 * m23 - m22 * m13));
+            this._rawData[15] = d * (m11 * (m22 * m33 - m32 * m23) - m21 * (m12 * m33 - m32 * m13) + m31 * (m12 * m23 - m22 * m13));
+            return true;
+        }
+
+        // Splits the matrix into a translation, a rotation and a scale, such that
+        // `recompose` applies the scale, then the rotation, then the translation.
+        public function decompose(orientationStyle:String = "eulerAngles"):Vector.<Vector3D> {
+            var mr = this._rawData.concat();
+
+            var pos = new Vector3D(mr[12], mr[13], mr[14]);
+
+            var scale = new Vector3D(
+                Math.sqrt(mr[0] * mr[0] + mr[1] * mr[1] + mr[2] * mr[2]),
+                Math.sqrt(mr[4] * mr[4] + mr[5] * mr[5] + mr[6] * mr[6]),
+                Math.sqrt(mr[8] * mr[8] + mr[9] * mr[9] + mr[10] * mr[10])
+            );
+            // A mirrored matrix is treated as having a negative Z scale.
+            if (mr[0] * (mr[5] * mr[10] - mr[6] * mr[9]) - mr[1] * (mr[4] * mr[10] - mr[6] * mr[8]) + mr[2] * (mr[4] * mr[9] - mr[5] * mr[8]) < 0) {
+                scale.z = -scale.z;
+            }
+
+            if (scale.x != 0) {
+                mr[0] /= scale.x;
+                mr[1] /= scale.x;
+                mr[2] /= scale.x;
+            }
+            if (scale.y != 0) {
+                mr[4] /= scale.y;
+                mr[5] /= scale.y;
+                mr[6] /= scale.y;
+            }
+            if (scale.z != 0) {
+                mr[8] /= scale.z;
+                mr[9] /= scale.z;
+                mr[10] /= scale.z;
+            }
+
+            var rot = new Vector3D();
+            switch (orientationStyle) {
+                case Orientation3D.AXIS_ANGLE:
+                    rot.w = Math.acos((mr[0] + mr[5] + mr[10] - 1) / 2);
+                    var len = Math.sqrt((mr[6] - mr[9]) * (mr[6] - mr[9]) + (mr[8] - mr[2]) * (mr[8] - mr[2]) + (mr[1] - mr[4]) * (mr[1] - mr[4]));
+                    if (len != 0) {
+                        rot.x = (mr[6] - mr[9]) / len;
+                        rot.y = (mr[8] - mr[2]) / len;
+                        rot.z = (mr[1] - mr[4]) / len;
+                    }
+                    break;
+                case Orientation3D.QUATERNION:
+                    var tr = mr[0] + mr[5] + mr[10];
+                    if (tr > 0) {
+                        rot.w = Math.sqrt(1 + tr) / 2;
+                        rot.x = (mr[6] - mr[9]) / (4 * rot.w);
+                        rot.y = (mr[8] - mr[2]) / (4 * rot.w);
+                        rot.z = (mr[1] - mr[4]) / (4 * rot.w);
+                    } else if (mr[0] > mr[5] && mr[0] > mr[10]) {
+                        rot.x = Math.sqrt(1 + mr[0] - mr[5] - mr[10]) / 2;
+                        rot.w = (mr[6] - mr[9]) / (4 * rot.x);
+                        rot.y = (mr[1] + mr[4]) / (4 * rot.x);
+                        rot.z = (mr[8] + mr[2]) / (4 * rot.x);
+                    } else if (mr[5] > mr[10]) {
+                        rot.y = Math.sqrt(1 + mr[5] - mr[0] - mr[10]) / 2;
+                        rot.x = (mr[1] + mr[4]) / (4 * rot.y);
+                        rot.w = (mr[8] - mr[2]) / (4 * rot.y);
+                        rot.z = (mr[6] + mr[9]) / (4 * rot.y);
+                    } else {
+                        rot.z = Math.sqrt(1 + mr[10] - mr[0] - mr[5]) / 2;
+                        rot.x = (mr[8] + mr[2]) / (4 * rot.z);
+                        rot.y = (mr[6] + mr[9]) / (4 * rot.z);
+                        rot.w = (mr[1] - mr[4]) / (4 * rot.z);
+                    }
+                    break;
+                default:
+                    // The rotation is Rz * Ry * Rx, whose bottom-left element is -sin(y).
+                    rot.y = Math.asin(Math.max(-1, Math.min(1, -mr[2])));
+                    if (Math.abs(Math.cos(rot.y)) > 0.000000001) {
+                        rot.x = Math.atan2(mr[6], mr[10]);
+                        rot.z = Math.atan2(mr[1], mr[0]);
+                    } else {
+                        // Gimbal lock: X and Z rotate around the same axis, so put it all in X.
+                        rot.x = Math.sin(rot.y) * Math.atan2(mr[4], mr[5]);
+                        rot.z = 0;
+                    }
+                    break;
+            }
+
+            return new <Vector3D>[pos, rot, scale];
+        }
+
+        public function recompose(components:Vector.<Vector3D>, orientationStyle:String = "eulerAngles"):Boolean {
+            if (components.length < 3) {
+                return false;
+            }
+            var pos:Vector3D = components[0];
+            var rot:Vector3D = components[1];
+            var scale:Vector3D = components[2];
+            if (scale.x == 0 || scale.y == 0 || scale.z == 0) {
+                return false;
+            }
+
+            this.identity();
+            this.appendScale(scale.x, scale.y, scale.z);
+            switch (orientationStyle) {
+                case Orientation3D.AXIS_ANGLE:
+                    this.appendRotation(rot.w * 180 / Math.PI, new Vector3D(rot.x, rot.y, rot.z));
+                    break;
+                case Orientation3D.QUATERNION:
+                    var x = rot.x, y = rot.y, z = rot.z, w = rot.w;
+                    this.append(new Matrix3D(new <Number>[
+                        1 - 2 * (y * y + z * z), 2 * (x * y + z * w), 2 * (x * z - y * w), 0,
+                        2 * (x * y - z * w), 1 - 2 * (x * x + z * z), 2 * (y * z + x * w), 0,
+                        2 * (x * z + y * w), 2 * (y * z - x * w), 1 - 2 * (x * x + y * y), 0,
+                        0, 0, 0, 1
+                    ]));
+                    break;
+                default:
+                    this.appendRotation(rot.x * 180 / Math.PI, Vector3D.X_AXIS);
+                    this.appendRotation(rot.y * 180 / Math.PI, Vector3D.Y_AXIS);
+                    this.appendRotation(rot.z * 180 / Math.PI, Vector3D.Z_AXIS);
+                    break;
+            }
+            this.appendTranslation(pos.x, pos.y, pos.z);
+            return true;
+        }
+
+        // Interpolates the translation and scale linearly, and the rotation spherically.
+        public static function interpolate(thisMat:Matrix3D, toMat:Matrix3D, percent:Number):Matrix3D {
+            var from = thisMat.decompose(Orientation3D.QUATERNION);
+            var to = toMat.decompose(Orientation3D.QUATERNION);
+
+            var lerp = function(a:Vector3D, b:Vector3D):Vector3D {
+                return new Vector3D(
+                    a.x + (b.x - a.x) * percent,
+                    a.y + (b.y - a.y) * percent,
+                    a.z + (b.z - a.z) * percent,
+                    a.w + (b.w - a.w) * percent
+                );
+            };
+
+            var q0:Vector3D = from[1];
+            var q1:Vector3D = to[1].clone();
+            var dot = q0.x * q1.x + q0.y * q1.y + q0.z * q1.z + q0.w * q1.w;
+            // Take the shorter way around.
+            if (dot < 0) {
+                dot = -dot;
+                q1 = new Vector3D(-q1.x, -q1.y, -q1.z, -q1.w);
+            }
+            var rot:Vector3D;
+            if (dot > 0.9995) {
+                rot = lerp(q0, q1);
+                var len = Math.sqrt(rot.x * rot.x + rot.y * rot.y + rot.z * rot.z + rot.w * rot.w);
+                rot = new Vector3D(rot.x / len, rot.y / len, rot.z / len, rot.w / len);
+            } else {
+                var angle = Math.acos(dot);
+                var sin = Math.sin(angle);
+                var k0 = Math.sin((1 - percent) * angle) / sin;
+                var k1 = Math.sin(percent * angle) / sin;
+                rot = new Vector3D(
+                    q0.x * k0 + q1.x * k1,
+                    q0.y * k0 + q1.y * k1,
+                    q0.z * k0 + q1.z * k1,
+                    q0.w * k0 + q1.w * k1
+                );
+            }
+
+            var result = new Matrix3D();
+            result.recompose(new <Vector3D>[lerp(from[0], to[0]), rot, lerp(from[2], to[2])], Orientation3D.QUATERNION);
+            return result;
+        }
+
+        public function interpolateTo(toMat:Matrix3D, percent:Number):void {
+            this._rawData = interpolate(this, toMat, percent)._rawData;
+        }
+
+        public function transformVector(v:Vector3D):Vector3D {
+            var m = this._rawData;
+            var x = v.x, y = v.y, z = v.z;
+            return new Vector3D(
+                x * m[0] + y * m[4] + z * m[8] + m[12],
+                x * m[1] + y * m[5] + z * m[9] + m[13],
+                x * m[2] + y * m[6] + z * m[10] + m[14],
+                x * m[3] + y * m[7] + z * m[11] + m[15]
+            );
+        }
+
+        public function deltaTransformVector(v:Vector3D):Vector3D {
+            var m = this._rawData;
+            var x = v.x, y = v.y, z = v.z;
+            return new Vector3D(
+                x * m[0] + y * m[4] + z * m[8],
+                x * m[1] + y * m[5] + z * m[9],
+                x * m[2] + y * m[6] + z * m[10],
+                0
+            );
+        }
+
+        public function transformVectors(vin:Vector.<Number>, vout:Vector.<Number>):void {
+            var m = this._rawData;
+            var i = 0;
+            while (i + 3 <= vin.length) {
+                var x = vin[i], y = vin[i + 1], z = vin[i + 2];
+                vout[i] = x * m[0] + y * m[4] + z * m[8] + m[12];
+                vout[i + 1] = x * m[1] + y * m[5] + z * m[9] + m[13];
+                vout[i + 2] = x * m[2] + y * m[6] + z * m[10] + m[14];
+                i += 3;
+            }
+        }
+
+        public function copyFrom(sourceMatrix3D:Matrix3D):void {
+            this._rawData = sourceMatrix3D._rawData.concat();
+        }
+
+        public function copyToMatrix3D(dest:Matrix3D):void {
+            dest._rawData = this._rawData.concat();
+        }
+
+        public function copyRawDataTo(vector:Vector.<Number>, index:uint = 0, transpose:Boolean = false):void {
+            for (var i = 0; i < 16; i++) {
+                var source = transpose ? (i % 4) * 4 + int(i / 4) : i;
+                vector[index + i] = this._rawData[source];
+            }
+        }
+
+        public function copyColumnFrom(column:uint, vector3D:Vector3D):void {
+            this._rawData[column * 4] = vector3D.x;
+            this._rawData[column * 4 + 1] = vector3D.y;
+            this._rawData[column * 4 + 2] = vector3D.z;
+            this._rawData[column * 4 + 3] = vector3D.w;
+        }
+
+        public function copyColumnTo(column:uint, vector3D:Vector3D):void {
+            vector3D.x = this._rawData[column * 4];
+            vector3D.y = this._rawData[column * 4 + 1];
+            vector3D.z = this._rawData[column * 4 + 2];
+            vector3D.w = this._rawData[column * 4 + 3];
+        }
+
+        public function copyRowFrom(row:uint, vector3D:Vector3D):void {
+            this._rawData[row] = vector3D.x;
+            this._rawData[row + 4] = vector3D.y;
+            this._rawData[row + 8] = vector3D.z;
+            this._rawData[row + 12] = vector3D.w;
+        }
+
+        public function copyRowTo(row:uint, vector3D:Vector3D):void {
+            vector3D.x = this._rawData[row];
+            vector3D.y = this._rawData[row + 4];
+            vector3D.z = this._rawData[row + 8];
+            vector3D.w = this._rawData[row + 12];
+        }
+
 }
 }
\ No newline at end of file
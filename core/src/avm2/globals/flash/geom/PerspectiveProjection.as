@@ -0,0 +1,61 @@
+package flash.geom {
+    import flash.display.DisplayObject;
+
+    public class PerspectiveProjection {
+        // The width of the stage, used to convert between `fieldOfView` and `focalLength`.
+        private var _width:Number = 500;
+        private var _fieldOfView:Number = 55;
+        private var _projectionCenter:Point = new Point(250, 250);
+
+        // The display object whose `transform.perspectiveProjection` returned this object, if any.
+        private var _displayObject:DisplayObject = null;
+
+        public function PerspectiveProjection() {
+        }
+
+        public function get fieldOfView():Number {
+            return this._fieldOfView;
+        }
+
+        public function set fieldOfView(value:Number):void {
+            if (!(value > 0 && value < 180)) {
+                throw new ArgumentError("Error #2182: Invalid fieldOfView value.  The value must be greater than 0 and less than 180.", 2182);
+            }
+            this._fieldOfView = value;
+            this.sync();
+        }
+
+        public function get focalLength():Number {
+            return this._width / 2 / Math.tan(this._fieldOfView * Math.PI / 360);
+        }
+
+        public function set focalLength(value:Number):void {
+            this.fieldOfView = Math.atan(this._width / 2 / value) * 360 / Math.PI;
+        }
+
+        public function get projectionCenter():Point {
+            return this._projectionCenter.clone();
+        }
+
+        public function set projectionCenter(value:Point):void {
+            this._projectionCenter = value.clone();
+            this.sync();
+        }
+
+        public function toMatrix3D():Matrix3D {
+            var f:Number = this.focalLength;
+            return new Matrix3D(new <Number>[
+                f, 0, 0, 0,
+                0, f, 0, 0,
+                0, 0, 1, 1,
+                0, 0, 0, 0
+            ]);
+        }
+
+        private function sync():void {
+            if (this._displayObject != null) {
+                this._displayObject.transform.perspectiveProjection = this;
+            }
+        }
+    }
+}
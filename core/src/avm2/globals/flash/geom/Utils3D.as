@@ -0,0 +1,22 @@
+package flash.geom {
+    public class Utils3D {
+        public static function projectVector(m:Matrix3D, v:Vector3D):Vector3D {
+            var result:Vector3D = m.transformVector(v);
+            result.x /= result.w;
+            result.y /= result.w;
+            result.z /= result.w;
+            return result;
+        }
+
+        public static function projectVectors(m:Matrix3D, verts:Vector.<Number>, projectedVerts:Vector.<Number>, uvts:Vector.<Number>):void {
+            var i:int = 0;
+            while (i + 3 <= verts.length) {
+                var v:Vector3D = m.transformVector(new Vector3D(verts[i], verts[i + 1], verts[i + 2]));
+                projectedVerts[i / 3 * 2] = v.x / v.w;
+                projectedVerts[i / 3 * 2 + 1] = v.y / v.w;
+                uvts[i + 2] = 1 / v.w;
+                i += 3;
+            }
+        }
+    }
+}
@@ -1,9 +1,12 @@
 #![allow(non_snake_case)]
 
+use crate::avm2::object::VectorObject;
+use crate::avm2::vector::VectorStorage;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Namespace, Object, TObject, Value};
-use crate::display_object::{StageQuality, TDisplayObject};
+use crate::display_object::{PerspectiveProjection, StageQuality, TDisplayObject};
 use crate::prelude::{ColorTransform, DisplayObject, Matrix, Twips};
+use ruffle_render::matrix3d::Matrix3D;
 use swf::Fixed8;
 
 fn get_display_object<'gc>(
@@ -53,9 +56,10 @@ pub fn set_color_transform<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.unwrap();
     let ct = object_to_color_transform(args[0].coerce_to_object(activation)?, activation)?;
-    get_display_object(this, activation)?
-        .base_mut(activation.context.gc_context)
+    let dobj = get_display_object(this, activation)?;
+    dobj.base_mut(activation.context.gc_context)
         .set_color_transform(ct);
+    dobj.invalidate_parent_cached_bitmap(activation.context.gc_context);
     Ok(Value::Undefined)
 }
 
@@ -76,9 +80,98 @@ pub fn set_matrix<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.unwrap();
     let matrix = object_to_matrix(args[0].coerce_to_object(activation)?, activation)?;
-    get_display_object(this, activation)?
-        .base_mut(activation.context.gc_context)
+    let dobj = get_display_object(this, activation)?;
+    dobj.base_mut(activation.context.gc_context)
         .set_matrix(matrix);
+    dobj.invalidate_parent_cached_bitmap(activation.context.gc_context);
+    Ok(Value::Undefined)
+}
+
+pub fn get_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.unwrap();
+    let dobj = get_display_object(this, activation)?;
+    if dobj.base().transform_3d().is_none() {
+        return Ok(Value::Null);
+    }
+    let matrix = dobj.base().matrix_3d();
+    matrix_3d_to_object(&matrix, activation)
+}
+
+pub fn set_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.unwrap();
+    let dobj = get_display_object(this, activation)?;
+    match args[0].as_object() {
+        Some(object) => {
+            let matrix = object_to_matrix_3d(object, activation)?;
+            dobj.base_mut(activation.context.gc_context)
+                .set_matrix_3d(&matrix);
+        }
+        None => {
+            // Flattening the object keeps its 2D transform.
+            dobj.base_mut(activation.context.gc_context)
+                .set_transform_3d(None);
+        }
+    }
+    dobj.invalidate_parent_cached_bitmap(activation.context.gc_context);
+    Ok(Value::Undefined)
+}
+
+pub fn get_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.unwrap();
+    let dobj = get_display_object(this, activation)?;
+    let projection = match dobj.base().perspective_projection() {
+        Some(projection) => projection,
+        None if dobj.parent().and_then(|p| p.as_stage()).is_some() => {
+            // The root movie always has a projection, centered on the stage.
+            let (width, height) = activation.context.stage.stage_size();
+            PerspectiveProjection {
+                field_of_view: PerspectiveProjection::DEFAULT_FIELD_OF_VIEW,
+                center: (f64::from(width) / 2.0, f64::from(height) / 2.0),
+            }
+        }
+        None => return Ok(Value::Null),
+    };
+    let object = perspective_projection_to_object(&projection, activation)?;
+    let stage_width = activation.context.stage.stage_size().0;
+    object.set_property(
+        &Multiname::new(Namespace::Private("".into()), "_width"),
+        stage_width.into(),
+        activation,
+    )?;
+    object.set_property(
+        &Multiname::new(Namespace::Private("".into()), "_displayObject"),
+        dobj.object2(),
+        activation,
+    )?;
+    Ok(object.into())
+}
+
+pub fn set_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.unwrap();
+    let dobj = get_display_object(this, activation)?;
+    let projection = match args[0].as_object() {
+        Some(object) => Some(object_to_perspective_projection(object, activation)?),
+        None => None,
+    };
+    dobj.base_mut(activation.context.gc_context)
+        .set_perspective_projection(projection);
+    dobj.invalidate_parent_cached_bitmap(activation.context.gc_context);
     Ok(Value::Undefined)
 }
 
@@ -242,3 +335,91 @@ pub fn object_to_matrix<'gc>(
 
     Ok(Matrix { a, b, c, d, tx, ty })
 }
+
+pub fn matrix_3d_to_object<'gc>(
+    matrix: &Matrix3D,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let raw_data = matrix.raw_data.iter().map(|n| (*n).into()).collect();
+    let number_class = activation.avm2().classes().number;
+    let raw_data = VectorStorage::from_values(raw_data, false, number_class);
+    let raw_data = VectorObject::from_vector(raw_data, activation)?;
+    let object = activation
+        .avm2()
+        .classes()
+        .matrix3d
+        .construct(activation, &[raw_data.into()])?;
+    Ok(object.into())
+}
+
+pub fn object_to_matrix_3d<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Matrix3D, Error<'gc>> {
+    let raw_data = object
+        .get_property(&Multiname::public("rawData"), activation)?
+        .coerce_to_object(activation)?;
+    let raw_data: Vec<Value<'gc>> = raw_data
+        .as_vector_storage()
+        .map(|v| v.iter().collect())
+        .unwrap_or_default();
+    let mut matrix = Matrix3D::IDENTITY;
+    for (n, value) in matrix.raw_data.iter_mut().zip(raw_data) {
+        *n = value.coerce_to_number(activation)?;
+    }
+    Ok(matrix)
+}
+
+pub fn perspective_projection_to_object<'gc>(
+    projection: &PerspectiveProjection,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let object = activation
+        .avm2()
+        .classes()
+        .perspectiveprojection
+        .construct(activation, &[])?;
+    let center = activation.avm2().classes().point.construct(
+        activation,
+        &[projection.center.0.into(), projection.center.1.into()],
+    )?;
+    object.set_property(
+        &Multiname::new(Namespace::Private("".into()), "_fieldOfView"),
+        projection.field_of_view.into(),
+        activation,
+    )?;
+    object.set_property(
+        &Multiname::new(Namespace::Private("".into()), "_projectionCenter"),
+        center.into(),
+        activation,
+    )?;
+    Ok(object)
+}
+
+pub fn object_to_perspective_projection<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<PerspectiveProjection, Error<'gc>> {
+    let field_of_view = object
+        .get_property(
+            &Multiname::new(Namespace::Private("".into()), "_fieldOfView"),
+            activation,
+        )?
+        .coerce_to_number(activation)?;
+    let center = object
+        .get_property(
+            &Multiname::new(Namespace::Private("".into()), "_projectionCenter"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let x = center
+        .get_property(&Multiname::public("x"), activation)?
+        .coerce_to_number(activation)?;
+    let y = center
+        .get_property(&Multiname::public("y"), activation)?
+        .coerce_to_number(activation)?;
+    Ok(PerspectiveProjection {
+        field_of_view,
+        center: (x, y),
+    })
+}
@@ -3,6 +3,8 @@
 use crate::avm2::object::TObject;
 use crate::avm2::{Activation, Error, Multiname, Object, Value};
 
+pub mod file_reference;
+pub mod file_reference_list;
 pub mod object_encoding;
 pub mod shared_object;
 pub mod url_loader;
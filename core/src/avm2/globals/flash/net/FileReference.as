@@ -1,89 +1,104 @@
 package flash.net
 {
+    import flash.errors.IllegalOperationError;
     import flash.events.EventDispatcher;
+    import flash.permissions.PermissionStatus;
     import flash.utils.ByteArray;
+
+    namespace ruffle = "__ruffle__";
+
     public class FileReference extends EventDispatcher
     {
-        private var _creationDate: Date;
-        private var _creator: String;
-        private var _data: ByteArray;
-        private var _extension: String;
-        private var _modificationDate: Date;
-        private var _name: String;
-        private static var _permissionStatus: String;
-        private var _size: Number;
-        private var _type: String;
+        // These are filled in by the native code once a file has been picked.
+        ruffle var _creationDate: Date;
+        ruffle var _data: ByteArray;
+        ruffle var _modificationDate: Date;
+        ruffle var _name: String;
+        ruffle var _path: String;
+        ruffle var _size: Number;
+        ruffle var _type: String;
 
         public function FileReference() {
-            
+
+        }
+
+        private function checkSelected(): void {
+            if (this.ruffle::_path == null) {
+                throw new IllegalOperationError("Error #2037: Functions called in incorrect sequence, or earlier call was unsuccessful.", 2037);
+            }
         }
 
         public function get creationDate(): Date {
-            return this._creationDate;
-        }   
+            this.checkSelected();
+            return this.ruffle::_creationDate;
+        }
 
+        // Only used by Mac OS 9 and earlier.
         public function get creator(): String {
-            retunr this._creator;
-        }   
+            this.checkSelected();
+            return null;
+        }
 
         public function get data(): ByteArray {
-            return this._data;
-        }   
+            return this.ruffle::_data;
+        }
 
         public function get extension(): String {
-            return this._extension;
-        }   
+            this.checkSelected();
+            var type: String = this.ruffle::_type;
+            return type == null ? null : type.substr(1);
+        }
 
         public function get modificationDate(): Date {
-            return this._modificationDate;
-        }   
+            this.checkSelected();
+            return this.ruffle::_modificationDate;
+        }
 
         public function get name(): String {
-            retunr this._name;
-        }   
+            this.checkSelected();
+            return this.ruffle::_name;
+        }
 
         public static function get permissionStatus(): String {
-            return FileReference._permissionStatus;
-        }   
+            return PermissionStatus.GRANTED;
+        }
 
         public function get size(): Number {
-            return this._size;
-        }   
+            this.checkSelected();
+            return this.ruffle::_size;
+        }
 
         public function get type(): String {
-            return this._type;
-        }   
+            this.checkSelected();
+            return this.ruffle::_type;
+        }
 
-        public function browse(typeFilter:Array = null):Boolean {
-            return false;
-        }   
+        native public function browse(typeFilter:Array = null):Boolean;
 
-        public function cancel():void { 
-            throw new Error("FileReference.cancel() is not yet implemented!");
-        }   
+        native public function cancel():void;
 
-        public function download(request:URLRequest, defaultFileName:String = null):void {  
-            throw new Error("FileReference.download() is not yet implemented!");
-        }   
+        native public function download(request:URLRequest, defaultFileName:String = null):void;
 
-        public function load():void {   
-            throw new Error("FileReference.load() is not yet implemented!");
-        }   
+        public function load():void {
+            this.checkSelected();
+            this.loadFile();
+        }
 
-        public function requestPermission():void {  
-            throw new Error("FileReference.requestPermission() is not yet implemented!");
-        }   
+        native public function requestPermission():void;
 
-        public function save(data:*, defaultFileName:String = null):void {  
-            throw new Error("FileReference.save() is not yet implemented!");
-        }   
+        native public function save(data:*, defaultFileName:String = null):void;
 
-        public function upload(request:URLRequest, uploadDataFieldName:String = "Filedata", testUpload:Boolean = false):void {  
-            throw new Error("FileReference.upload() is not yet implemented!");
-        }   
+        public function upload(request:URLRequest, uploadDataFieldName:String = "Filedata", testUpload:Boolean = false):void {
+            this.checkSelected();
+            this.uploadFile(request, uploadDataFieldName, true);
+        }
 
-        public function uploadUnencoded(request:URLRequest):void {  
-            throw new Error("FileReference.uploadUnencoded() is not yet implemented!");
+        public function uploadUnencoded(request:URLRequest):void {
+            this.checkSelected();
+            this.uploadFile(request, null, false);
         }
+
+        private native function loadFile():void;
+        private native function uploadFile(request:URLRequest, uploadDataFieldName:String, encoded:Boolean):void;
     }
-}
\ No newline at end of file
+}
@@ -1,22 +1,22 @@
 package flash.net
 {
     import flash.events.EventDispatcher;
+
+    namespace ruffle = "__ruffle__";
+
     public class FileReferenceList extends EventDispatcher
     {
-        private var _fileList:Array;
+        ruffle var _fileList:Array;
+
         public function FileReferenceList()
         {
-            _fileList = new Array();
         }
 
         public function get fileList():Array
         {
-            return this._fileList;
+            return this.ruffle::_fileList;
         }
 
-        public function browse(typeFilter:Array = null):Boolean
-        {
-            throw new Error("FileReferenceList.browse() is not yet implemented!");
-        }
+        native public function browse(typeFilter:Array = null):Boolean;
     }
-}
\ No newline at end of file
+}
@@ -18,17 +18,23 @@ package flash.net
       // to work with AMF0.
 
       native public static function getLocal(name:String, localPath:String = null, secure:Boolean = false): SharedObject;
+      native public static function getRemote(name:String, remotePath:String = null, persistence:Object = false, secure:Boolean = false): SharedObject;
 
       native public function flush(minDiskSpace:int = 0) : String;
       native public function close() : void;
       native public function clear() : void;
 
+      // Only remote shared objects track dirty properties, and Ruffle never
+      // synchronizes those with a server.
+      public function setDirty(propertyName:String):void {
+      }
+
       // note: this is supposed to be a read-only property
       public var data: Object;
-      
-      // note: this is supposed to be a read-only property
-      public var size: uint;
+
+      native public function get size() : uint;
 
       ruffle var _ruffleName: String;
+      ruffle var _rufflePersistent: Boolean = false;
    }
 }
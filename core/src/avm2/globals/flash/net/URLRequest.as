@@ -13,6 +13,7 @@ package flash.net {
 		public var digest:String;
 		private var _method:String = URLRequestMethod.GET;
 		private var _data:Object;
+		private var _requestHeaders:Array = [];
 
 		public function URLRequest(url:String = null) {
 			this.url = url;
@@ -48,5 +49,13 @@ package flash.net {
 		public function get contentType():String {
 			return this._contentType;
 		}
+
+		public function get requestHeaders():Array {
+			return this._requestHeaders;
+		}
+
+		public function set requestHeaders(value:Array):void {
+			this._requestHeaders = value;
+		}
 	}
 }
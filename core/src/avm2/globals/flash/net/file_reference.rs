@@ -0,0 +1,492 @@
+//! `flash.net.FileReference` native function definitions
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::error::{argument_error, illegal_operation_error};
+use crate::avm2::events::queue_dispatch_event;
+use crate::avm2::object::{ByteArrayObject, EventObject, TObject};
+use crate::avm2::{Activation, Error, Multiname, Namespace, Object, Value};
+use crate::backend::navigator::{NavigationMethod, Request};
+use crate::backend::ui::{DialogFile, FileFilter};
+use crate::loader::{multipart_upload_body, FileDialogPurpose, FileReferenceTarget, FileTransfer};
+use crate::string::AvmString;
+use chrono::{DateTime, Utc};
+use std::path::Path;
+use url::form_urlencoded;
+
+fn ruffle_name<'gc>(local_name: &'static str) -> Multiname<'gc> {
+    Multiname::new(Namespace::Namespace("__ruffle__".into()), local_name)
+}
+
+/// Throws if a file dialog is already open, as Flash Player only allows one
+/// at a time.
+pub fn check_no_file_dialog<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<(), Error<'gc>> {
+    if activation.context.load_manager.is_file_dialog_open() {
+        return Err(Error::AvmError(illegal_operation_error(
+            activation,
+            "Error #2041: Only one file browsing session may be performed at a time.",
+            2041,
+        )?));
+    }
+    Ok(())
+}
+
+/// Implements `FileReference.browse`
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        check_no_file_dialog(activation)?;
+        let filters = file_filters(activation, args.get(0))?;
+        let dialog = activation
+            .context
+            .ui
+            .display_file_open_dialog(filters, false);
+        let future = activation.context.load_manager.open_file_dialog(
+            activation.context.player.clone(),
+            FileReferenceTarget::Avm2(this),
+            dialog,
+            FileDialogPurpose::Browse,
+        );
+        activation.context.navigator.spawn_future(future);
+
+        return Ok(true.into());
+    }
+    Ok(false.into())
+}
+
+/// Implements `FileReference.cancel`
+pub fn cancel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation
+            .context
+            .load_manager
+            .cancel_file_reference_transfers(FileReferenceTarget::Avm2(this));
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.download`
+pub fn download<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let request = match args.get(0) {
+            Some(Value::Object(request)) => *request,
+            _ => {
+                return Err(Error::AvmError(argument_error(
+                    activation,
+                    "Error #2007: Parameter request must be non-null.",
+                    2007,
+                )?))
+            }
+        };
+        let (url, method) = url_request_parts(activation, request)?;
+
+        let default_name = match args.get(1) {
+            None | Some(Value::Undefined) | Some(Value::Null) => {
+                // Default to the last path segment of the URL.
+                let path = url.split(['?', '#']).next().unwrap_or_default();
+                path.rsplit('/').next().unwrap_or_default().to_string()
+            }
+            Some(name) => name.coerce_to_string(activation)?.to_string(),
+        };
+
+        check_no_file_dialog(activation)?;
+        let dialog = activation.context.ui.display_file_save_dialog(default_name);
+        let future = activation.context.load_manager.open_file_dialog(
+            activation.context.player.clone(),
+            FileReferenceTarget::Avm2(this),
+            Box::pin(async move { dialog.await.map(|file| vec![file]) }),
+            FileDialogPurpose::Download(Request::request(method, url, None)),
+        );
+        activation.context.navigator.spawn_future(future);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.save`
+pub fn save<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let data = match args.get(0) {
+            None | Some(Value::Undefined) | Some(Value::Null) => {
+                return Err(Error::AvmError(argument_error(
+                    activation,
+                    "Error #2007: Parameter data must be non-null.",
+                    2007,
+                )?))
+            }
+            Some(Value::Object(object)) if object.as_bytearray().is_some() => {
+                object.as_bytearray().unwrap().bytes().to_vec()
+            }
+            // Everything else, including XML, is saved as UTF-8 text.
+            Some(value) => value
+                .coerce_to_string(activation)?
+                .to_utf8_lossy()
+                .into_owned()
+                .into_bytes(),
+        };
+
+        let default_name = match args.get(1) {
+            None | Some(Value::Undefined) | Some(Value::Null) => String::new(),
+            Some(name) => name.coerce_to_string(activation)?.to_string(),
+        };
+
+        check_no_file_dialog(activation)?;
+        let dialog = activation.context.ui.display_file_save_dialog(default_name);
+        let future = activation.context.load_manager.open_file_dialog(
+            activation.context.player.clone(),
+            FileReferenceTarget::Avm2(this),
+            Box::pin(async move { dialog.await.map(|file| vec![file]) }),
+            FileDialogPurpose::Save(data),
+        );
+        activation.context.navigator.spawn_future(future);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.load`
+pub fn load_file<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        let path = this
+            .get_property(&ruffle_name("_path"), activation)?
+            .coerce_to_string(activation)?
+            .to_string();
+
+        match activation.context.ui.read_file(Path::new(&path)) {
+            Ok(bytes) => {
+                let size = bytes.len();
+                let storage = ByteArrayStorage::from_vec(bytes);
+                let data = ByteArrayObject::from_storage(activation, storage)?;
+                this.set_property(&ruffle_name("_data"), data.into(), activation)?;
+                this.set_property(&ruffle_name("_size"), size.into(), activation)?;
+                queue_transfer_events(activation, this, size)?;
+            }
+            Err(e) => {
+                log::warn!("FileReference.load: unable to read file: {}", e);
+                queue_io_error_event(activation, this)?;
+            }
+        }
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.requestPermission`
+///
+/// Access to picked files is never restricted, so this always reports that
+/// permission was granted.
+pub fn request_permission<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let permission_evt_cls = activation.resolve_class(&Multiname::new(
+            Namespace::package("flash.events"),
+            "PermissionEvent",
+        ))?;
+        let permission_evt = permission_evt_cls.construct(
+            activation,
+            &[
+                "permissionStatus".into(),
+                false.into(),
+                false.into(),
+                "granted".into(),
+            ],
+        )?;
+        queue_dispatch_event(activation, this, permission_evt)?;
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.upload` and `FileReference.uploadUnencoded`
+pub fn upload_file<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let request = match args.get(0) {
+            Some(Value::Object(request)) => *request,
+            _ => {
+                return Err(Error::AvmError(argument_error(
+                    activation,
+                    "Error #2007: Parameter request must be non-null.",
+                    2007,
+                )?))
+            }
+        };
+        let (mut url, method) = url_request_parts(activation, request)?;
+        let variables = url_request_variables(activation, request)?;
+        let encoded = args.get(2).unwrap_or(&Value::Undefined).coerce_to_boolean();
+
+        let path = this
+            .get_property(&ruffle_name("_path"), activation)?
+            .coerce_to_string(activation)?
+            .to_string();
+        let contents = match activation.context.ui.read_file(Path::new(&path)) {
+            Ok(contents) => contents,
+            Err(e) => {
+                log::warn!("FileReference.upload: unable to read file: {}", e);
+                queue_io_error_event(activation, this)?;
+                return Ok(Value::Undefined);
+            }
+        };
+        let file_size = contents.len();
+
+        // Variables go in the query string, unless they can be sent as form
+        // fields alongside the file.
+        let body = if encoded {
+            let name = this
+                .get_property(&ruffle_name("_name"), activation)?
+                .coerce_to_string(activation)?;
+            let field_name = args
+                .get(1)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_string(activation)?;
+            let form_variables = if matches!(method, NavigationMethod::Post) {
+                &variables[..]
+            } else {
+                append_query(&mut url, &variables);
+                &[]
+            };
+            multipart_upload_body(
+                &name.to_string(),
+                &field_name.to_string(),
+                &contents,
+                form_variables,
+            )
+        } else {
+            append_query(&mut url, &variables);
+            (contents, "application/octet-stream".to_string())
+        };
+
+        // Uploads are always sent with POST.
+        let future = activation.context.load_manager.transfer_file_reference(
+            activation.context.player.clone(),
+            FileReferenceTarget::Avm2(this),
+            Request::post(url, Some(body)),
+            FileTransfer::Upload { file_size },
+        );
+        activation.context.navigator.spawn_future(future);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Reads the `FileFilter`s from a `typeFilter` argument.
+pub fn file_filters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    type_filter: Option<&Value<'gc>>,
+) -> Result<Vec<FileFilter>, Error<'gc>> {
+    let items: Vec<_> = match type_filter {
+        Some(Value::Object(array)) => match array.as_array_storage() {
+            Some(array) => array.iter().collect(),
+            None => return Ok(Vec::new()),
+        },
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut filters = Vec::with_capacity(items.len());
+    for item in items.into_iter().flatten() {
+        let item = item.coerce_to_object(activation)?;
+        let description = item
+            .get_property(&Multiname::public("description"), activation)?
+            .coerce_to_string(activation)?;
+        let extension = item
+            .get_property(&Multiname::public("extension"), activation)?
+            .coerce_to_string(activation)?;
+        filters.push(FileFilter::new(
+            description.to_string(),
+            &extension.to_string(),
+        ));
+    }
+    Ok(filters)
+}
+
+/// Stores the details of a file picked in a dialog on a `FileReference`.
+pub fn set_selected_file<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut file_reference: Object<'gc>,
+    file: &DialogFile,
+) -> Result<(), Error<'gc>> {
+    let path = AvmString::new_utf8(activation.context.gc_context, file.path.to_string_lossy());
+    file_reference.set_property(&ruffle_name("_path"), path.into(), activation)?;
+
+    let name = AvmString::new_utf8(activation.context.gc_context, &file.name);
+    file_reference.set_property(&ruffle_name("_name"), name.into(), activation)?;
+
+    file_reference.set_property(&ruffle_name("_size"), (file.size as f64).into(), activation)?;
+
+    let file_type = match file.file_type() {
+        Some(file_type) => AvmString::new_utf8(activation.context.gc_context, file_type).into(),
+        None => Value::Null,
+    };
+    file_reference.set_property(&ruffle_name("_type"), file_type, activation)?;
+
+    let creation_date = date(activation, file.creation_date)?;
+    file_reference.set_property(&ruffle_name("_creationDate"), creation_date, activation)?;
+
+    let modification_date = date(activation, file.modification_date)?;
+    file_reference.set_property(
+        &ruffle_name("_modificationDate"),
+        modification_date,
+        activation,
+    )?;
+
+    // A newly picked file hasn't been loaded yet.
+    file_reference.set_property(&ruffle_name("_data"), Value::Null, activation)?;
+
+    Ok(())
+}
+
+fn date<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    date_time: Option<DateTime<Utc>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    match date_time {
+        Some(date_time) => {
+            let date_class = activation.avm2().classes().date;
+            let millis = date_time.timestamp_millis() as f64;
+            Ok(date_class.construct(activation, &[millis.into()])?.into())
+        }
+        None => Ok(Value::Null),
+    }
+}
+
+fn url_request_parts<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    url_request: Object<'gc>,
+) -> Result<(String, NavigationMethod), Error<'gc>> {
+    let url = url_request
+        .get_property(&Multiname::public("url"), activation)?
+        .coerce_to_string(activation)?;
+
+    let method_str = url_request
+        .get_property(&Multiname::public("method"), activation)?
+        .coerce_to_string(activation)?;
+
+    let method = NavigationMethod::from_method_str(&method_str).unwrap_or_else(|| {
+        log::error!("Unknown HTTP method type {:?}", method_str);
+        NavigationMethod::Get
+    });
+
+    Ok((url.to_string(), method))
+}
+
+/// Reads the variables of a `URLRequest` from its `data` property, which
+/// holds either a `URLVariables` object or a URL-encoded string.
+fn url_request_variables<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    url_request: Object<'gc>,
+) -> Result<Vec<(String, String)>, Error<'gc>> {
+    let data = url_request.get_property(&Multiname::public("data"), activation)?;
+    let mut variables = Vec::new();
+    match data {
+        Value::Undefined | Value::Null => {}
+        Value::Object(object) if object.as_bytearray().is_none() => {
+            let mut last_index = object.get_next_enumerant(0, activation)?;
+            while let Some(index) = last_index {
+                let name = object
+                    .get_enumerant_name(index, activation)?
+                    .coerce_to_string(activation)?;
+                let value = object
+                    .get_property(&Multiname::public(name), activation)?
+                    .coerce_to_string(activation)?;
+                variables.push((name.to_string(), value.to_string()));
+                last_index = object.get_next_enumerant(index, activation)?;
+            }
+        }
+        Value::Object(_) => {}
+        data => {
+            let data = data.coerce_to_string(activation)?.to_string();
+            variables.extend(form_urlencoded::parse(data.as_bytes()).into_owned());
+        }
+    }
+    Ok(variables)
+}
+
+/// Appends URL-encoded variables to the query string of a URL.
+fn append_query(url: &mut String, variables: &[(String, String)]) {
+    if variables.is_empty() {
+        return;
+    }
+    url.push(if url.contains('?') { '&' } else { '?' });
+    url.push_str(
+        &form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(variables)
+            .finish(),
+    );
+}
+
+/// Queues the `open`, `progress` and `complete` events of a finished transfer.
+fn queue_transfer_events<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    size: usize,
+) -> Result<(), Error<'gc>> {
+    queue_event(activation, this, "open")?;
+
+    let progress_evt_cls = activation.avm2().classes().progressevent;
+    let progress_evt = progress_evt_cls.construct(
+        activation,
+        &[
+            "progress".into(),
+            false.into(),
+            false.into(),
+            size.into(),
+            size.into(),
+        ],
+    )?;
+    queue_dispatch_event(activation, this, progress_evt)?;
+
+    queue_event(activation, this, "complete")
+}
+
+fn queue_io_error_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let io_error_evt_cls = activation.avm2().classes().ioerrorevent;
+    let io_error_evt = io_error_evt_cls.construct(
+        activation,
+        &[
+            "ioError".into(),
+            false.into(),
+            false.into(),
+            "Error #2038: File I/O Error.".into(),
+            2038.into(),
+        ],
+    )?;
+    queue_dispatch_event(activation, this, io_error_evt)
+}
+
+/// Queues a plain event of the given type.
+///
+/// File dialogs block, but Flash Player reports their results (and those of
+/// the file operations that follow) asynchronously, once the calling script
+/// has finished.
+pub fn queue_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    event_type: &'static str,
+) -> Result<(), Error<'gc>> {
+    let event = EventObject::bare_default_event(&mut activation.context, event_type);
+    queue_dispatch_event(activation, this, event)
+}
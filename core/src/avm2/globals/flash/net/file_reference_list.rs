@@ -0,0 +1,63 @@
+//! `flash.net.FileReferenceList` native function definitions
+
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::globals::flash::net::file_reference::{
+    check_no_file_dialog, file_filters, set_selected_file,
+};
+use crate::avm2::object::{ArrayObject, TObject};
+use crate::avm2::{Activation, Error, Multiname, Namespace, Object, Value};
+use crate::backend::ui::DialogFile;
+use crate::loader::{FileDialogPurpose, FileReferenceTarget};
+
+/// Implements `FileReferenceList.browse`
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        check_no_file_dialog(activation)?;
+        let filters = file_filters(activation, args.get(0))?;
+        let dialog = activation
+            .context
+            .ui
+            .display_file_open_dialog(filters, true);
+        let future = activation.context.load_manager.open_file_dialog(
+            activation.context.player.clone(),
+            FileReferenceTarget::Avm2(this),
+            dialog,
+            FileDialogPurpose::BrowseMultiple,
+        );
+        activation.context.navigator.spawn_future(future);
+
+        return Ok(true.into());
+    }
+    Ok(false.into())
+}
+
+/// Fills in the `fileList` of a `FileReferenceList` with the files picked in
+/// a dialog.
+pub fn set_file_list<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut file_reference_list: Object<'gc>,
+    files: Vec<DialogFile>,
+) -> Result<(), Error<'gc>> {
+    let file_reference_cls = activation.resolve_class(&Multiname::new(
+        Namespace::package("flash.net"),
+        "FileReference",
+    ))?;
+    let mut file_list = Vec::with_capacity(files.len());
+    for file in files {
+        let file_reference = file_reference_cls.construct(activation, &[])?;
+        set_selected_file(activation, file_reference, &file)?;
+        file_list.push(Some(file_reference.into()));
+    }
+
+    let file_list = ArrayObject::from_storage(activation, ArrayStorage::from_storage(file_list))?;
+    file_reference_list.set_property(
+        &Multiname::new(Namespace::Namespace("__ruffle__".into()), "_fileList"),
+        file_list.into(),
+        activation,
+    )?;
+    Ok(())
+}
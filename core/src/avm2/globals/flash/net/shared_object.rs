@@ -1,5 +1,7 @@
 //! `flash.net.SharedObject` builtin/prototype
 
+use crate::avm2::error::error;
+use crate::avm2::events::queue_dispatch_event;
 use crate::avm2::object::TObject;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Namespace, Object, Value};
@@ -130,13 +132,75 @@ pub fn get_local<'gc>(
         return Ok(Value::Null);
     }
 
+    let sharedobject_cls = this.unwrap(); // `this` of a static method is the class
+    let this = shared_object_for_name(activation, sharedobject_cls, full_name, true)?;
+    Ok(this.into())
+}
+
+pub fn get_remote<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Ruffle has no Flash Media Server support, so remote shared objects are
+    // emulated with local ones. They never synchronize with the server, but
+    // persistent ones survive between sessions like they would on the server.
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned();
+    let remote_path = match args.get(1) {
+        Some(Value::String(remote_path)) => remote_path.to_utf8_lossy().into_owned(),
+        _ => String::new(),
+    };
+    let persistent = !matches!(
+        args.get(2).unwrap_or(&Value::Bool(false)),
+        Value::Bool(false) | Value::Null | Value::Undefined
+    );
+
+    let remote_url = url::Url::parse(&remote_path).ok();
+    let remote_host = remote_url
+        .as_ref()
+        .and_then(|url| url.host_str())
+        .unwrap_or("localhost");
+    let remote_app = remote_url
+        .as_ref()
+        .map(|url| url.path().trim_matches('/'))
+        .unwrap_or_default();
+
+    let full_name = if remote_app.is_empty() {
+        format!("{remote_host}/#remote/{name}")
+    } else {
+        format!("{remote_host}/#remote/{remote_app}/{name}")
+    };
+    if full_name.split('/').any(|s| s.starts_with('.')) {
+        log::error!("SharedObject.get_remote: Invalid path with .. segments");
+        return Ok(Value::Null);
+    }
+
+    let sharedobject_cls = this.unwrap(); // `this` of a static method is the class
+    let this = shared_object_for_name(activation, sharedobject_cls, full_name, persistent)?;
+    Ok(this.into())
+}
+
+/// Get the shared object stored under `full_name`, creating it if it isn't
+/// already loaded.
+///
+/// Non-persistent shared objects are never loaded from or saved to storage.
+fn shared_object_for_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    sharedobject_cls: Object<'gc>,
+    full_name: String,
+    persistent: bool,
+) -> Result<Object<'gc>, Error<'gc>> {
     // Check if this is referencing an existing shared object
     if let Some(so) = activation.context.avm2_shared_objects.get(&full_name) {
-        return Ok((*so).into());
+        return Ok(*so);
     }
 
     // Data property only should exist when created with getLocal/Remote
-    let sharedobject_cls = this.unwrap(); // `this` of a static method is the class
     let mut this = sharedobject_cls.construct(activation, &[])?;
 
     // Set the internal name
@@ -146,13 +210,20 @@ pub fn get_local<'gc>(
         AvmString::new_utf8(activation.context.gc_context, &full_name).into(),
         activation,
     )?;
+    let ruffle_persistent = Multiname::new(
+        Namespace::Namespace("__ruffle__".into()),
+        "_rufflePersistent",
+    );
+    this.set_property(&ruffle_persistent, persistent.into(), activation)?;
 
     let mut data = Value::Undefined;
 
     // Load the data object from storage if it existed prior
-    if let Some(saved) = activation.context.storage.get(&full_name) {
-        if let Ok(lso) = flash_lso::read::Reader::default().parse(&saved) {
-            data = crate::avm2::amf::deserialize_lso(activation, &lso)?.into();
+    if persistent {
+        if let Some(saved) = activation.context.storage.get(&full_name) {
+            if let Ok(lso) = flash_lso::read::Reader::default().parse(&saved) {
+                data = crate::avm2::amf::deserialize_lso(activation, &lso)?.into();
+            }
         }
     }
 
@@ -172,58 +243,206 @@ pub fn get_local<'gc>(
         .avm2_shared_objects
         .insert(full_name, this);
 
-    Ok(this.into())
+    Ok(this)
+}
+
+/// Get the storage name of a shared object, or `None` if it is not persisted.
+fn storage_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<Option<String>, Error<'gc>> {
+    let ruffle_persistent = Multiname::new(
+        Namespace::Namespace("__ruffle__".into()),
+        "_rufflePersistent",
+    );
+    if !this
+        .get_property(&ruffle_persistent, activation)?
+        .coerce_to_boolean()
+    {
+        return Ok(None);
+    }
+
+    let ruffle_name = Multiname::new(Namespace::Namespace("__ruffle__".into()), "_ruffleName");
+    let name = this
+        .get_property(&ruffle_name, activation)?
+        .coerce_to_string(activation)?;
+    Ok(Some(name.to_utf8_lossy().into_owned()))
+}
+
+/// Serialize the data of a shared object into `.sol` file contents.
+///
+/// Returns `None` if the shared object holds no data.
+fn serialize<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    name: &str,
+) -> Result<Option<Vec<u8>>, Error<'gc>> {
+    let data = this
+        .get_property(&Multiname::public("data"), activation)?
+        .coerce_to_object(activation)?;
+
+    let mut elements = Vec::new();
+    crate::avm2::amf::recursive_serialize(activation, data, &mut elements, AMFVersion::AMF3)?;
+    if elements.is_empty() {
+        return Ok(None);
+    }
+
+    let mut lso = Lso::new(
+        elements,
+        name.split('/').last().unwrap_or("<unknown>"),
+        AMFVersion::AMF3,
+    );
+
+    Ok(Some(
+        flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default(),
+    ))
+}
+
+/// Dispatch a `netStatus` event on a shared object once the current script
+/// has finished running.
+fn queue_net_status_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    code: &str,
+    level: &str,
+) -> Result<(), Error<'gc>> {
+    let mut info = activation
+        .avm2()
+        .classes()
+        .object
+        .construct(activation, &[])?;
+    info.set_property(
+        &Multiname::public("code"),
+        AvmString::new_utf8(activation.context.gc_context, code).into(),
+        activation,
+    )?;
+    info.set_property(
+        &Multiname::public("level"),
+        AvmString::new_utf8(activation.context.gc_context, level).into(),
+        activation,
+    )?;
+
+    let net_status_event_cls = activation.resolve_class(&Multiname::new(
+        Namespace::package("flash.events"),
+        "NetStatusEvent",
+    ))?;
+    let event = net_status_event_cls.construct(
+        activation,
+        &["netStatus".into(), false.into(), false.into(), info.into()],
+    )?;
+
+    queue_dispatch_event(activation, this, event)
 }
 
 pub fn flush<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let name = match storage_name(activation, this)? {
+            Some(name) => name,
+            None => return Ok("flushed".into()),
+        };
+
+        let bytes = match serialize(activation, this, &name)? {
+            Some(bytes) => bytes,
+            None => {
+                // Flash doesn't write out empty shared objects.
+                activation.context.storage.remove_key(&name);
+                return Ok("flushed".into());
+            }
+        };
+
+        let min_disk_space = args
+            .get(0)
+            .unwrap_or(&Value::Integer(0))
+            .coerce_to_i32(activation)?
+            .max(0) as usize;
+
+        if let Some(quota) = activation.context.storage.quota() {
+            let domain = name.split('/').next().unwrap_or_default();
+            let storage = &activation.context.storage;
+            let used_by_others = storage
+                .used_space(&format!("{domain}/"))
+                .saturating_sub(storage.get_size(&name).unwrap_or_default());
+
+            if used_by_others + bytes.len().max(min_disk_space) > quota {
+                // Flash Player would now ask the user for more space. There's
+                // nobody to ask, so the request is denied, which is reported
+                // once the calling script has finished.
+                queue_net_status_event(activation, this, "SharedObject.Flush.Failed", "error")?;
+                return Ok("pending".into());
+            }
+        }
+
+        if !activation.context.storage.put(&name, &bytes) {
+            return Err(Error::AvmError(error(
+                activation,
+                "Error #2130: Unable to flush SharedObject.",
+                2130,
+            )?));
+        }
+
+        return Ok("flushed".into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn close<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
-        let data = this
-            .get_property(&Multiname::public("data"), activation)?
-            .coerce_to_object(activation)?;
+        // Closing a local shared object writes it out, and the next call to
+        // `getLocal` will read it back from storage.
+        flush(activation, Some(this), &[])?;
 
         let ruffle_name = Multiname::new(Namespace::Namespace("__ruffle__".into()), "_ruffleName");
         let name = this
             .get_property(&ruffle_name, activation)?
             .coerce_to_string(activation)?;
-        let name = name.to_utf8_lossy();
-
-        let mut elements = Vec::new();
-        crate::avm2::amf::recursive_serialize(activation, data, &mut elements, AMFVersion::AMF3)?;
-        let mut lso = Lso::new(
-            elements,
-            &name
-                .split('/')
-                .last()
-                .map(|e| e.to_string())
-                .unwrap_or_else(|| "<unknown>".to_string()),
-            AMFVersion::AMF3,
-        );
-
-        let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
-
-        return Ok(activation.context.storage.put(&name, &bytes).into());
+        activation
+            .context
+            .avm2_shared_objects
+            .remove(&*name.to_utf8_lossy());
     }
     Ok(Value::Undefined)
 }
 
-pub fn close<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    log::warn!("SharedObject.close - not yet implemented");
+    if let Some(mut this) = this {
+        let data = activation
+            .avm2()
+            .classes()
+            .object
+            .construct(activation, &[])?;
+        this.set_property(&Multiname::public("data"), data.into(), activation)?;
+
+        if let Some(name) = storage_name(activation, this)? {
+            activation.context.storage.remove_key(&name);
+        }
+    }
     Ok(Value::Undefined)
 }
 
-pub fn clear<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+pub fn get_size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    log::warn!("SharedObject.clear - not yet implemented");
+    if let Some(this) = this {
+        let ruffle_name = Multiname::new(Namespace::Namespace("__ruffle__".into()), "_ruffleName");
+        let name = this
+            .get_property(&ruffle_name, activation)?
+            .coerce_to_string(activation)?;
+        let size = serialize(activation, this, &name.to_utf8_lossy())?.map_or(0, |b| b.len());
+        return Ok((size as u32).into());
+    }
     Ok(Value::Undefined)
 }
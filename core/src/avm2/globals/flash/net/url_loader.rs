@@ -59,15 +59,46 @@ fn spawn_fetch<'gc>(
         NavigationMethod::Get
     });
 
-    // FIXME - set options from the `URLRequest`
-    let request = Request::request(method, url.to_string(), None);
+    // FIXME - set the body from the `URLRequest`
+    let mut request = Request::request(method, url.to_string(), None);
+    request.set_headers(request_headers(activation, url_request)?);
 
+    let requester_url = activation.caller_movie().url().map(str::to_string);
     let future = activation.context.load_manager.load_data_into_url_loader(
         activation.context.player.clone(),
         loader_object,
         request,
         data_format,
+        requester_url,
     );
     activation.context.navigator.spawn_future(future);
     Ok(Value::Undefined)
 }
+
+/// Reads the `URLRequestHeader`s in `URLRequest.requestHeaders` as name/value pairs.
+fn request_headers<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    url_request: &Object<'gc>,
+) -> Result<Vec<(String, String)>, Error<'gc>> {
+    let items: Vec<_> =
+        match url_request.get_property(&Multiname::public("requestHeaders"), activation)? {
+            Value::Object(array) => match array.as_array_storage() {
+                Some(array) => array.iter().collect(),
+                None => return Ok(Vec::new()),
+            },
+            _ => return Ok(Vec::new()),
+        };
+
+    let mut headers = Vec::with_capacity(items.len());
+    for item in items.into_iter().flatten() {
+        let item = item.coerce_to_object(activation)?;
+        let name = item
+            .get_property(&Multiname::public("name"), activation)?
+            .coerce_to_string(activation)?;
+        let value = item
+            .get_property(&Multiname::public("value"), activation)?
+            .coerce_to_string(activation)?;
+        headers.push((name.to_string(), value.to_string()));
+    }
+    Ok(headers)
+}
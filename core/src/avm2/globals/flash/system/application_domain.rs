@@ -2,9 +2,11 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
+use crate::avm2::domain::Domain;
 use crate::avm2::method::{Method, NativeMethodImpl};
-use crate::avm2::object::{appdomain_allocator, DomainObject, Object, TObject};
+use crate::avm2::object::{appdomain_allocator, DomainObject, Object, TObject, VectorObject};
 use crate::avm2::value::Value;
+use crate::avm2::vector::VectorStorage;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
@@ -12,7 +14,37 @@ use crate::avm2::QName;
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `flash.system.ApplicationDomain`'s instance constructor.
+///
+/// This creates a new domain whose parent is the given domain, or the system
+/// domain if none was given.
 pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        let parent_domain = match args.get(0) {
+            Some(Value::Object(parent)) => parent.as_application_domain(),
+            _ => None,
+        }
+        .unwrap_or_else(|| activation.avm2().global_domain());
+        let domain = Domain::movie_domain(activation, parent_domain);
+
+        if let Object::DomainObject(this) = this {
+            this.init_domain(activation.context.gc_context, domain);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.ApplicationDomain`'s native instance initializer.
+///
+/// Native code wraps existing domains with `DomainObject::from_domain`, so
+/// this leaves the wrapped domain alone.
+pub fn native_instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
@@ -72,23 +104,9 @@ pub fn get_definition<'gc>(
             .unwrap_or_else(|| "".into())
             .coerce_to_string(activation)?;
         let name = QName::from_qualified_name(name, activation.context.gc_context);
-        let (qname, mut defined_script) = match appdomain.get_defining_script(&name.into())? {
-            Some(data) => data,
-            None => {
-                return Err(Error::AvmError(crate::avm2::error::reference_error(
-                    activation,
-                    &format!(
-                        "Error #1065: Variable {} is not defined.",
-                        name.local_name()
-                    ),
-                    1065,
-                )?))
-            }
-        };
-        let globals = defined_script.globals(&mut activation.context)?;
-        let definition = globals.get_property(&qname.into(), activation)?;
 
-        return Ok(definition);
+        // Only this domain and its parents are searched, never a sibling.
+        return appdomain.get_defined_value(activation, name);
     }
 
     Ok(Value::Undefined)
@@ -115,6 +133,26 @@ pub fn has_definition<'gc>(
     Ok(Value::Undefined)
 }
 
+/// `getQualifiedDefinitionNames` method
+pub fn get_qualified_definition_names<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(appdomain) = this.and_then(|this| this.as_application_domain()) {
+        let names = appdomain
+            .package_definition_names()
+            .into_iter()
+            .map(|name| name.to_qualified_name(activation.context.gc_context).into())
+            .collect();
+
+        let storage = VectorStorage::from_values(names, false, activation.avm2().classes().string);
+        return Ok(VectorObject::from_vector(storage, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// `domainMemory` property setter
 pub fn set_domain_memory<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -123,6 +161,15 @@ pub fn set_domain_memory<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(Value::Object(arg)) = args.get(0) {
         if let Some(bytearray_obj) = arg.as_bytearray_object() {
+            let length = arg.as_bytearray().map_or(0, |bytes| bytes.len());
+            if length < Domain::MIN_DOMAIN_MEMORY_LENGTH {
+                return Err(Error::AvmError(crate::avm2::error::range_error(
+                    activation,
+                    "Error #1506: The specified range is invalid.",
+                    1506,
+                )?));
+            }
+
             if let Some(appdomain) = this.and_then(|this| this.as_application_domain()) {
                 appdomain.set_domain_memory(activation.context.gc_context, bytearray_obj);
             }
@@ -162,6 +209,17 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     let mut write = class.write(mc);
     write.set_instance_allocator(appdomain_allocator);
+    write.set_native_instance_init(Method::from_builtin(
+        native_instance_init,
+        "<ApplicationDomain native instance initializer>",
+        mc,
+    ));
+
+    const CLASS_CONSTANTS: &[(&str, u32)] = &[(
+        "MIN_DOMAIN_MEMORY_LENGTH",
+        Domain::MIN_DOMAIN_MEMORY_LENGTH as u32,
+    )];
+    write.define_public_constant_uint_class_traits(CLASS_CONSTANTS);
 
     const PUBLIC_CLASS_PROPERTIES: &[(&str, Option<NativeMethodImpl>, Option<NativeMethodImpl>)] =
         &[("currentDomain", Some(current_domain), None)];
@@ -179,9 +237,128 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
         ("getDefinition", get_definition),
+        (
+            "getQualifiedDefinitionNames",
+            get_qualified_definition_names,
+        ),
         ("hasDefinition", has_definition),
     ];
     write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::script::Script;
+    use crate::player::PlayerBuilder;
+    use crate::string::AvmString;
+    use crate::tag_utils::SwfMovie;
+
+    fn with_activation<F>(test: F)
+    where
+        F: for<'gc> FnOnce(&mut Activation<'_, 'gc, '_>),
+    {
+        let player = PlayerBuilder::new().with_movie(SwfMovie::empty(10)).build();
+        let mut player = player.lock().unwrap();
+        player.mutate_with_update_context(|context| {
+            test(&mut Activation::from_nothing(context.reborrow()))
+        });
+    }
+
+    /// Export a public `Thing` definition into `domain`.
+    fn export_thing<'gc>(activation: &mut Activation<'_, 'gc, '_>, mut domain: Domain<'gc>) {
+        let mut globals = activation
+            .avm2()
+            .classes()
+            .object
+            .construct(activation, &[])
+            .unwrap();
+        globals
+            .set_property(&Multiname::public("Thing"), true.into(), activation)
+            .unwrap();
+        let script = Script::empty_script(activation.context.gc_context, globals, domain);
+        domain
+            .export_definition(
+                QName::new(Namespace::public(), "Thing"),
+                script,
+                true,
+                activation.context.gc_context,
+            )
+            .unwrap();
+    }
+
+    fn domain_object<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        domain: Domain<'gc>,
+    ) -> Object<'gc> {
+        DomainObject::from_domain(activation, domain).unwrap()
+    }
+
+    #[test]
+    fn sibling_domain_definitions_are_not_found() {
+        with_activation(|activation| {
+            let system = activation.avm2().global_domain();
+            let first = Domain::movie_domain(activation, system);
+            let sibling = Domain::movie_domain(activation, system);
+            let child = Domain::movie_domain(activation, first);
+            export_thing(activation, first);
+
+            let args = ["Thing".into()];
+            let first = domain_object(activation, first);
+            let sibling = domain_object(activation, sibling);
+            let child = domain_object(activation, child);
+
+            for found in [first, child] {
+                assert!(matches!(
+                    has_definition(activation, Some(found), &args),
+                    Ok(Value::Bool(true))
+                ));
+                assert!(matches!(
+                    get_definition(activation, Some(found), &args),
+                    Ok(Value::Bool(true))
+                ));
+            }
+
+            assert!(matches!(
+                has_definition(activation, Some(sibling), &args),
+                Ok(Value::Bool(false))
+            ));
+            assert!(get_definition(activation, Some(sibling), &args).is_err());
+        });
+    }
+
+    #[test]
+    fn qualified_definition_names_lists_package_definitions() {
+        with_activation(|activation| {
+            let system = activation.avm2().global_domain();
+            let mut domain = Domain::movie_domain(activation, system);
+            export_thing(activation, domain);
+
+            let script = Script::empty_script(
+                activation.context.gc_context,
+                activation.avm2().classes().object.into(),
+                domain,
+            );
+            domain
+                .export_definition(
+                    QName::new(
+                        Namespace::Namespace("http://www.adobe.com/2006/flex/mx/internal".into()),
+                        "Hidden",
+                    ),
+                    script,
+                    false,
+                    activation.context.gc_context,
+                )
+                .unwrap();
+
+            let names: Vec<_> = domain
+                .package_definition_names()
+                .into_iter()
+                .map(|name| name.to_qualified_name(activation.context.gc_context))
+                .collect();
+            assert_eq!(names, vec![AvmString::from("Thing")]);
+        });
+    }
+}
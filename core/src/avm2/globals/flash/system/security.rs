@@ -4,7 +4,10 @@ use crate::avm2::activation::Activation;
 use crate::avm2::object::Object;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::sandbox::SecuritySandbox;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
+use url::Url;
 
 pub fn get_sandbox_type<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -16,29 +19,84 @@ pub fn get_sandbox_type<'gc>(
 }
 
 pub fn allow_domain<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    log::warn!("Security.allowDomain not implemented");
-    Ok(Value::Undefined)
+    grant_domains(activation, args, false)
 }
 
 pub fn allow_insecure_domain<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    log::warn!("Security.allowInsecureDomain not implemented");
+    grant_domains(activation, args, true)
+}
+
+/// Grant the given domains script access to the calling movie.
+///
+/// Insecure grants also allow movies served over HTTP to access a movie
+/// served over HTTPS.
+fn grant_domains<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    insecure: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut domains = Vec::with_capacity(args.len());
+    for domain in args {
+        domains.push(domain.coerce_to_string(activation)?.to_string());
+    }
+
+    let movie = activation.caller_movie();
+    grant_domains_to_movie(
+        activation.context.security_sandbox,
+        &movie,
+        &domains,
+        insecure,
+    );
+
     Ok(Value::Undefined)
 }
 
+/// Grant the given domains script access to `movie`, if it was served from a URL.
+fn grant_domains_to_movie(
+    sandbox: &mut SecuritySandbox,
+    movie: &SwfMovie,
+    domains: &[String],
+    insecure: bool,
+) {
+    if let Some(movie_url) = movie.url() {
+        for domain in domains {
+            sandbox.allow_domain(movie_url, domain, insecure);
+        }
+    }
+}
+
+/// Resolve a policy file URL given by `movie`, which may be relative to the movie.
+fn policy_file_url(movie: &SwfMovie, url: &str) -> Result<Url, url::ParseError> {
+    let base = movie.url().and_then(|u| Url::parse(u).ok());
+    Url::options().base_url(base.as_ref()).parse(url)
+}
+
 pub fn load_policy_file<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    log::warn!("Security.loadPolicyFile not implemented");
+    let url = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_string();
+
+    // Relative policy file URLs are resolved against the calling movie.
+    match policy_file_url(&activation.caller_movie(), &url) {
+        Ok(url) => activation.context.security_sandbox.load_policy_file(url),
+        Err(e) => log::warn!("Security.loadPolicyFile: invalid URL {}: {}", url, e),
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -50,3 +108,50 @@ pub fn show_settings<'gc>(
     log::warn!("Security.showSettings not implemented");
     Ok(Value::Undefined)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROOT_URL: &str = "https://root.test/main.swf";
+    const CHILD_URL: &str = "https://child.test/swfs/child.swf";
+
+    fn movie(url: &str) -> SwfMovie {
+        let mut movie = SwfMovie::empty(10);
+        movie.set_url(Some(url.to_string()));
+        movie
+    }
+
+    #[test]
+    fn policy_file_url_is_relative_to_calling_movie() {
+        let child = movie(CHILD_URL);
+        assert_eq!(
+            policy_file_url(&child, "policy.xml").unwrap().as_str(),
+            "https://child.test/swfs/policy.xml"
+        );
+        assert_eq!(
+            policy_file_url(&child, "https://cdn.test/crossdomain.xml")
+                .unwrap()
+                .as_str(),
+            "https://cdn.test/crossdomain.xml"
+        );
+        assert!(policy_file_url(&SwfMovie::empty(10), "policy.xml").is_err());
+    }
+
+    #[test]
+    fn loaded_movie_grants_access_to_itself() {
+        let mut sandbox = SecuritySandbox::new(true);
+        let requester = Some("https://other.test/loader.swf");
+        assert!(!sandbox.can_access_content(requester, Some(CHILD_URL)));
+
+        // A child loaded from another origin only grants access to its own content.
+        grant_domains_to_movie(
+            &mut sandbox,
+            &movie(CHILD_URL),
+            &["other.test".to_string()],
+            false,
+        );
+        assert!(sandbox.can_access_content(requester, Some(CHILD_URL)));
+        assert!(!sandbox.can_access_content(requester, Some(ROOT_URL)));
+    }
+}
@@ -0,0 +1,63 @@
+package flash.text {
+    import flash.events.EventDispatcher;
+
+    namespace ruffle = "__ruffle__";
+
+    public dynamic class StyleSheet extends EventDispatcher {
+        // Maps lowercased style names to objects holding their properties.
+        ruffle var _styles: Object = {};
+
+        public function StyleSheet() {
+
+        }
+
+        public function get styleNames(): Array {
+            var names: Array = [];
+            for (var name: String in this.ruffle::_styles) {
+                names.push(name);
+            }
+            return names;
+        }
+
+        public function clear(): void {
+            this.ruffle::_styles = {};
+        }
+
+        public function getStyle(styleName: String): Object {
+            return copyStyle(this.ruffle::_styles[styleName.toLowerCase()]);
+        }
+
+        public function parseCSS(CSSText: String): void {
+            var styles: Object = this.innerParseCSS(CSSText);
+            if (styles == null) {
+                return;
+            }
+            for (var name: String in styles) {
+                this.setStyle(name, styles[name]);
+            }
+        }
+
+        public function setStyle(styleName: String, styleObject: Object): void {
+            if (styleObject == null) {
+                delete this.ruffle::_styles[styleName.toLowerCase()];
+            } else {
+                this.ruffle::_styles[styleName.toLowerCase()] = copyStyle(styleObject);
+            }
+        }
+
+        public native function transform(formatObject: Object): TextFormat;
+
+        private native function innerParseCSS(CSSText: String): Object;
+
+        private static function copyStyle(style: Object): Object {
+            if (style == null) {
+                return null;
+            }
+            var copy: Object = {};
+            for (var key: String in style) {
+                copy[key] = style[key];
+            }
+            return copy;
+        }
+    }
+}
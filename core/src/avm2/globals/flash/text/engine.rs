@@ -0,0 +1,5 @@
+//! `flash.text.engine` namespace
+
+pub mod element_format;
+pub mod text_block;
+pub mod text_line;
@@ -0,0 +1,8 @@
+package flash.text.engine {
+    public final class BreakOpportunity {
+        public static const AUTO: String = "auto";
+        public static const ANY: String = "any";
+        public static const NONE: String = "none";
+        public static const ALL: String = "all";
+    }
+}
@@ -0,0 +1,6 @@
+package flash.text.engine {
+    public final class CFFHinting {
+        public static const NONE: String = "none";
+        public static const HORIZONTAL_STEM: String = "horizontalStem";
+    }
+}
@@ -0,0 +1,101 @@
+package flash.text.engine {
+    import flash.events.EventDispatcher;
+
+    namespace ruffle = "__ruffle__";
+
+    public class ContentElement {
+        public static const GRAPHIC_ELEMENT: uint = 0xFDEF;
+
+        public var userData: *;
+
+        private var _elementFormat: ElementFormat;
+        private var _eventMirror: EventDispatcher;
+        private var _textRotation: String;
+
+        // These are filled in by the owning `TextBlock` and `GroupElement`.
+        ruffle var _textBlock: TextBlock = null;
+        ruffle var _textBlockBeginIndex: int = 0;
+        ruffle var _groupElement: GroupElement = null;
+
+        public function ContentElement(elementFormat: ElementFormat = null, eventMirror: EventDispatcher = null, textRotation: String = "rotate0") {
+            this._elementFormat = elementFormat;
+            this._eventMirror = eventMirror;
+            this._textRotation = textRotation;
+        }
+
+        public function get elementFormat(): ElementFormat {
+            return this._elementFormat;
+        }
+
+        public function set elementFormat(value: ElementFormat): void {
+            this._elementFormat = value;
+            this.ruffle::invalidate();
+        }
+
+        public function get eventMirror(): EventDispatcher {
+            return this._eventMirror;
+        }
+
+        public function set eventMirror(value: EventDispatcher): void {
+            this._eventMirror = value;
+        }
+
+        public function get textRotation(): String {
+            return this._textRotation;
+        }
+
+        public function set textRotation(value: String): void {
+            this._textRotation = value;
+            this.ruffle::invalidate();
+        }
+
+        public function get groupElement(): GroupElement {
+            return this.ruffle::_groupElement;
+        }
+
+        public function get textBlock(): TextBlock {
+            return this.ruffle::_textBlock;
+        }
+
+        public function get textBlockBeginIndex(): int {
+            return this.ruffle::_textBlockBeginIndex;
+        }
+
+        public function get rawText(): String {
+            return null;
+        }
+
+        public function get text(): String {
+            var rawText: String = this.rawText;
+            if (rawText == null) {
+                return null;
+            }
+            return rawText.split(String.fromCharCode(GRAPHIC_ELEMENT)).join("");
+        }
+
+        // Record which text block (and where in it) this element lives.
+        ruffle function attach(textBlock: TextBlock, beginIndex: int): void {
+            this.ruffle::_textBlock = textBlock;
+            this.ruffle::_textBlockBeginIndex = beginIndex;
+        }
+
+        // The element's effective format, inheriting from enclosing groups.
+        ruffle function resolvedFormat(): ElementFormat {
+            if (this._elementFormat != null) {
+                return this._elementFormat;
+            }
+            if (this.ruffle::_groupElement != null) {
+                return this.ruffle::_groupElement.ruffle::resolvedFormat();
+            }
+            return null;
+        }
+
+        // Called whenever this element's content or format changes, so that
+        // lines already created from it are marked invalid.
+        ruffle function invalidate(): void {
+            if (this.ruffle::_textBlock != null) {
+                this.ruffle::_textBlock.ruffle::contentChanged();
+            }
+        }
+    }
+}
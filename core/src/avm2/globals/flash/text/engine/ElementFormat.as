@@ -0,0 +1,244 @@
+package flash.text.engine {
+    import flash.errors.IllegalOperationError;
+    import flash.geom.Rectangle;
+
+    public final class ElementFormat {
+        private var _alignmentBaseline: String;
+        private var _alpha: Number;
+        private var _baselineShift: Number;
+        private var _breakOpportunity: String;
+        private var _color: uint;
+        private var _digitCase: String;
+        private var _digitWidth: String;
+        private var _dominantBaseline: String;
+        private var _fontDescription: FontDescription;
+        private var _fontSize: Number;
+        private var _kerning: String;
+        private var _ligatureLevel: String;
+        private var _locale: String;
+        private var _textRotation: String;
+        private var _trackingLeft: Number;
+        private var _trackingRight: Number;
+        private var _typographicCase: String;
+        private var _locked: Boolean = false;
+
+        public function ElementFormat(fontDescription: FontDescription = null, fontSize: Number = 12.0, color: uint = 0x000000, alpha: Number = 1.0,
+                                      textRotation: String = "auto", dominantBaseline: String = "roman", alignmentBaseline: String = "useDominantBaseline",
+                                      baselineShift: Number = 0.0, kerning: String = "on", trackingRight: Number = 0.0, trackingLeft: Number = 0.0,
+                                      locale: String = "en", breakOpportunity: String = "auto", digitCase: String = "default",
+                                      digitWidth: String = "default", ligatureLevel: String = "common", typographicCase: String = "default") {
+            this._fontDescription = fontDescription != null ? fontDescription : new FontDescription();
+            this.fontSize = fontSize;
+            this.color = color;
+            this.alpha = alpha;
+            this.textRotation = textRotation;
+            this.dominantBaseline = dominantBaseline;
+            this.alignmentBaseline = alignmentBaseline;
+            this.baselineShift = baselineShift;
+            this.kerning = kerning;
+            this.trackingRight = trackingRight;
+            this.trackingLeft = trackingLeft;
+            this.locale = locale;
+            this.breakOpportunity = breakOpportunity;
+            this.digitCase = digitCase;
+            this.digitWidth = digitWidth;
+            this.ligatureLevel = ligatureLevel;
+            this.typographicCase = typographicCase;
+        }
+
+        private function checkLocked(): void {
+            if (this._locked) {
+                throw new IllegalOperationError("Error #2185: The ElementFormat object is locked and cannot be modified.", 2185);
+            }
+        }
+
+        public function get alignmentBaseline(): String {
+            return this._alignmentBaseline;
+        }
+
+        public function set alignmentBaseline(value: String): void {
+            this.checkLocked();
+            this._alignmentBaseline = value;
+        }
+
+        public function get alpha(): Number {
+            return this._alpha;
+        }
+
+        public function set alpha(value: Number): void {
+            this.checkLocked();
+            this._alpha = Math.max(0.0, Math.min(1.0, value));
+        }
+
+        public function get baselineShift(): Number {
+            return this._baselineShift;
+        }
+
+        public function set baselineShift(value: Number): void {
+            this.checkLocked();
+            this._baselineShift = value;
+        }
+
+        public function get breakOpportunity(): String {
+            return this._breakOpportunity;
+        }
+
+        public function set breakOpportunity(value: String): void {
+            this.checkLocked();
+            this._breakOpportunity = value;
+        }
+
+        public function get color(): uint {
+            return this._color;
+        }
+
+        public function set color(value: uint): void {
+            this.checkLocked();
+            this._color = value;
+        }
+
+        public function get digitCase(): String {
+            return this._digitCase;
+        }
+
+        public function set digitCase(value: String): void {
+            this.checkLocked();
+            this._digitCase = value;
+        }
+
+        public function get digitWidth(): String {
+            return this._digitWidth;
+        }
+
+        public function set digitWidth(value: String): void {
+            this.checkLocked();
+            this._digitWidth = value;
+        }
+
+        public function get dominantBaseline(): String {
+            return this._dominantBaseline;
+        }
+
+        public function set dominantBaseline(value: String): void {
+            this.checkLocked();
+            this._dominantBaseline = value;
+        }
+
+        public function get fontDescription(): FontDescription {
+            return this._fontDescription;
+        }
+
+        public function set fontDescription(value: FontDescription): void {
+            this.checkLocked();
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter fontDescription must be non-null.", 2007);
+            }
+            this._fontDescription = value;
+        }
+
+        public function get fontSize(): Number {
+            return this._fontSize;
+        }
+
+        public function set fontSize(value: Number): void {
+            this.checkLocked();
+            if (!(value >= 0.0 && value <= 720.0)) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            this._fontSize = value;
+        }
+
+        public function get kerning(): String {
+            return this._kerning;
+        }
+
+        public function set kerning(value: String): void {
+            this.checkLocked();
+            this._kerning = value;
+        }
+
+        public function get ligatureLevel(): String {
+            return this._ligatureLevel;
+        }
+
+        public function set ligatureLevel(value: String): void {
+            this.checkLocked();
+            this._ligatureLevel = value;
+        }
+
+        public function get locale(): String {
+            return this._locale;
+        }
+
+        public function set locale(value: String): void {
+            this.checkLocked();
+            this._locale = value;
+        }
+
+        public function get locked(): Boolean {
+            return this._locked;
+        }
+
+        public function set locked(value: Boolean): void {
+            this._locked = value;
+        }
+
+        public function get textRotation(): String {
+            return this._textRotation;
+        }
+
+        public function set textRotation(value: String): void {
+            this.checkLocked();
+            this._textRotation = value;
+        }
+
+        public function get trackingLeft(): Number {
+            return this._trackingLeft;
+        }
+
+        public function set trackingLeft(value: Number): void {
+            this.checkLocked();
+            this._trackingLeft = value;
+        }
+
+        public function get trackingRight(): Number {
+            return this._trackingRight;
+        }
+
+        public function set trackingRight(value: Number): void {
+            this.checkLocked();
+            this._trackingRight = value;
+        }
+
+        public function get typographicCase(): String {
+            return this._typographicCase;
+        }
+
+        public function set typographicCase(value: String): void {
+            this.checkLocked();
+            this._typographicCase = value;
+        }
+
+        public function clone(): ElementFormat {
+            return new ElementFormat(this.fontDescription.clone(), this.fontSize, this.color, this.alpha, this.textRotation,
+                                     this.dominantBaseline, this.alignmentBaseline, this.baselineShift, this.kerning,
+                                     this.trackingRight, this.trackingLeft, this.locale, this.breakOpportunity, this.digitCase,
+                                     this.digitWidth, this.ligatureLevel, this.typographicCase);
+        }
+
+        public function getFontMetrics(): FontMetrics {
+            // Returns the ascent, descent and leading of the resolved font, in pixels.
+            var metrics: Array = this.measureFont();
+            var ascent: Number = metrics[0];
+            var descent: Number = metrics[1];
+            var size: Number = this.fontSize;
+
+            // Only the vertical extents are known for our fonts, so the
+            // decoration and script metrics are derived from them.
+            var emBox: Rectangle = new Rectangle(0, descent - size, size, size);
+            return new FontMetrics(emBox, -ascent / 3, size / 20, descent / 2, size / 20, descent / 2, 0.7, -ascent / 2, 0.7, metrics[2]);
+        }
+
+        private native function measureFont(): Array;
+    }
+}
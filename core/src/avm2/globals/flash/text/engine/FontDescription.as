@@ -0,0 +1,121 @@
+package flash.text.engine {
+    import flash.errors.IllegalOperationError;
+
+    public final class FontDescription {
+        private var _fontName: String;
+        private var _fontWeight: String;
+        private var _fontPosture: String;
+        private var _fontLookup: String;
+        private var _renderingMode: String;
+        private var _cffHinting: String;
+        private var _locked: Boolean = false;
+
+        public function FontDescription(fontName: String = "_serif", fontWeight: String = "normal", fontPosture: String = "normal",
+                                        fontLookup: String = "device", renderingMode: String = "cff", cffHinting: String = "horizontalStem") {
+            this.fontName = fontName;
+            this.fontWeight = fontWeight;
+            this.fontPosture = fontPosture;
+            this.fontLookup = fontLookup;
+            this.renderingMode = renderingMode;
+            this.cffHinting = cffHinting;
+        }
+
+        public static function isFontCompatible(fontName: String, fontWeight: String, fontPosture: String): Boolean {
+            return false;
+        }
+
+        public static function isDeviceFontCompatible(fontName: String, fontWeight: String, fontPosture: String): Boolean {
+            return true;
+        }
+
+        private function checkLocked(): void {
+            if (this._locked) {
+                throw new IllegalOperationError("Error #2185: The FontDescription object is locked and cannot be modified.", 2185);
+            }
+        }
+
+        public function get fontName(): String {
+            return this._fontName;
+        }
+
+        public function set fontName(value: String): void {
+            this.checkLocked();
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter fontName must be non-null.", 2007);
+            }
+            this._fontName = value;
+        }
+
+        public function get fontWeight(): String {
+            return this._fontWeight;
+        }
+
+        public function set fontWeight(value: String): void {
+            this.checkLocked();
+            if (value != FontWeight.NORMAL && value != FontWeight.BOLD) {
+                throw new ArgumentError("Error #2008: Parameter fontWeight must be one of the accepted values.", 2008);
+            }
+            this._fontWeight = value;
+        }
+
+        public function get fontPosture(): String {
+            return this._fontPosture;
+        }
+
+        public function set fontPosture(value: String): void {
+            this.checkLocked();
+            if (value != FontPosture.NORMAL && value != FontPosture.ITALIC) {
+                throw new ArgumentError("Error #2008: Parameter fontPosture must be one of the accepted values.", 2008);
+            }
+            this._fontPosture = value;
+        }
+
+        public function get fontLookup(): String {
+            return this._fontLookup;
+        }
+
+        public function set fontLookup(value: String): void {
+            this.checkLocked();
+            if (value != FontLookup.DEVICE && value != FontLookup.EMBEDDED_CFF) {
+                throw new ArgumentError("Error #2008: Parameter fontLookup must be one of the accepted values.", 2008);
+            }
+            this._fontLookup = value;
+        }
+
+        public function get renderingMode(): String {
+            return this._renderingMode;
+        }
+
+        public function set renderingMode(value: String): void {
+            this.checkLocked();
+            if (value != RenderingMode.NORMAL && value != RenderingMode.CFF) {
+                throw new ArgumentError("Error #2008: Parameter renderingMode must be one of the accepted values.", 2008);
+            }
+            this._renderingMode = value;
+        }
+
+        public function get cffHinting(): String {
+            return this._cffHinting;
+        }
+
+        public function set cffHinting(value: String): void {
+            this.checkLocked();
+            if (value != CFFHinting.NONE && value != CFFHinting.HORIZONTAL_STEM) {
+                throw new ArgumentError("Error #2008: Parameter cffHinting must be one of the accepted values.", 2008);
+            }
+            this._cffHinting = value;
+        }
+
+        public function get locked(): Boolean {
+            return this._locked;
+        }
+
+        public function set locked(value: Boolean): void {
+            this._locked = value;
+        }
+
+        public function clone(): FontDescription {
+            return new FontDescription(this.fontName, this.fontWeight, this.fontPosture, this.fontLookup, this.renderingMode, this.cffHinting);
+        }
+    }
+}
@@ -0,0 +1,6 @@
+package flash.text.engine {
+    public final class FontLookup {
+        public static const DEVICE: String = "device";
+        public static const EMBEDDED_CFF: String = "embeddedCFF";
+    }
+}
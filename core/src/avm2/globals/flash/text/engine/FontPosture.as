@@ -0,0 +1,6 @@
+package flash.text.engine {
+    public final class FontPosture {
+        public static const NORMAL: String = "normal";
+        public static const ITALIC: String = "italic";
+    }
+}
@@ -0,0 +1,6 @@
+package flash.text.engine {
+    public final class FontWeight {
+        public static const NORMAL: String = "normal";
+        public static const BOLD: String = "bold";
+    }
+}
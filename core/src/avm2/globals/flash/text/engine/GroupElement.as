@@ -0,0 +1,100 @@
+package flash.text.engine {
+    import flash.events.EventDispatcher;
+
+    namespace ruffle = "__ruffle__";
+
+    public final class GroupElement extends ContentElement {
+        private var _elements: Vector.<ContentElement> = new Vector.<ContentElement>();
+
+        public function GroupElement(elements: Vector.<ContentElement> = null, elementFormat: ElementFormat = null, eventMirror: EventDispatcher = null,
+                                     textRotation: String = "rotate0") {
+            super(elementFormat, eventMirror, textRotation);
+            if (elements != null) {
+                this.setElements(elements);
+            }
+        }
+
+        override public function get rawText(): String {
+            var rawText: String = "";
+            for each (var element: ContentElement in this._elements) {
+                var elementText: String = element.rawText;
+                if (elementText != null) {
+                    rawText += elementText;
+                }
+            }
+            return rawText;
+        }
+
+        public function get elementCount(): int {
+            return this._elements.length;
+        }
+
+        public function getElementAt(index: int): ContentElement {
+            if (index < 0 || index >= this._elements.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            return this._elements[index];
+        }
+
+        public function getElementIndex(element: ContentElement): int {
+            return this._elements.indexOf(element);
+        }
+
+        public function getElementAtCharIndex(charIndex: int): ContentElement {
+            var start: int = 0;
+            for each (var element: ContentElement in this._elements) {
+                var elementText: String = element.rawText;
+                var end: int = start + (elementText != null ? elementText.length : 0);
+                if (charIndex >= start && charIndex < end) {
+                    return element;
+                }
+                start = end;
+            }
+            return null;
+        }
+
+        public function setElements(value: Vector.<ContentElement>): void {
+            this.replaceElements(0, this._elements.length, value);
+        }
+
+        public function replaceElements(beginIndex: int, endIndex: int, newElements: Vector.<ContentElement>): Vector.<ContentElement> {
+            if (beginIndex < 0 || endIndex > this._elements.length || beginIndex > endIndex) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+
+            var removed: Vector.<ContentElement> = this._elements.slice(beginIndex, endIndex);
+            for each (var oldElement: ContentElement in removed) {
+                oldElement.ruffle::_groupElement = null;
+                oldElement.ruffle::attach(null, 0);
+            }
+
+            var tail: Vector.<ContentElement> = this._elements.slice(endIndex);
+            this._elements.length = beginIndex;
+            if (newElements != null) {
+                for each (var newElement: ContentElement in newElements) {
+                    if (newElement.groupElement != null || newElement.textBlock != null) {
+                        throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+                    }
+                    newElement.ruffle::_groupElement = this;
+                    this._elements.push(newElement);
+                }
+            }
+            for each (var element: ContentElement in tail) {
+                this._elements.push(element);
+            }
+
+            this.ruffle::attach(this.textBlock, this.textBlockBeginIndex);
+            this.ruffle::invalidate();
+            return removed;
+        }
+
+        override ruffle function attach(textBlock: TextBlock, beginIndex: int): void {
+            super.ruffle::attach(textBlock, beginIndex);
+            for each (var element: ContentElement in this._elements) {
+                element.ruffle::attach(textBlock, beginIndex);
+                var elementText: String = element.rawText;
+                beginIndex += elementText != null ? elementText.length : 0;
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+package flash.text.engine {
+    public final class Kerning {
+        public static const ON: String = "on";
+        public static const OFF: String = "off";
+        public static const AUTO: String = "auto";
+    }
+}
@@ -0,0 +1,6 @@
+package flash.text.engine {
+    public final class RenderingMode {
+        public static const NORMAL: String = "normal";
+        public static const CFF: String = "cff";
+    }
+}
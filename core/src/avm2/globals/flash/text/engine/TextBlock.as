@@ -0,0 +1,297 @@
+package flash.text.engine {
+    import flash.display.DisplayObject;
+
+    namespace ruffle = "__ruffle__";
+
+    public final class TextBlock {
+        public var userData: *;
+
+        public var applyNonLinearFontScaling: Boolean;
+        public var baselineFontDescription: FontDescription;
+        public var baselineFontSize: Number;
+        public var baselineZero: String;
+        public var bidiLevel: int;
+        public var lineRotation: String;
+
+        private var _content: ContentElement;
+        private var _firstLine: TextLine = null;
+        private var _lastLine: TextLine = null;
+        private var _textLineCreationResult: String = null;
+
+        public function TextBlock(content: ContentElement = null, tabStops: * = null, textJustifier: * = null, lineRotation: String = "rotate0",
+                                  baselineZero: String = "roman", bidiLevel: int = 0, applyNonLinearFontScaling: Boolean = true,
+                                  baselineFontDescription: FontDescription = null, baselineFontSize: Number = 12.0) {
+            this.lineRotation = lineRotation;
+            this.baselineZero = baselineZero;
+            this.bidiLevel = bidiLevel;
+            this.applyNonLinearFontScaling = applyNonLinearFontScaling;
+            this.baselineFontDescription = baselineFontDescription;
+            this.baselineFontSize = baselineFontSize;
+            this.content = content;
+        }
+
+        public function get content(): ContentElement {
+            return this._content;
+        }
+
+        public function set content(value: ContentElement): void {
+            if (value != null && value.groupElement != null) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (this._content != null) {
+                this._content.ruffle::attach(null, 0);
+            }
+            this._content = value;
+            this.ruffle::contentChanged();
+        }
+
+        public function get firstLine(): TextLine {
+            return this._firstLine;
+        }
+
+        public function get lastLine(): TextLine {
+            return this._lastLine;
+        }
+
+        public function get firstInvalidLine(): TextLine {
+            for (var line: TextLine = this._firstLine; line != null; line = line.nextLine) {
+                if (line.validity != TextLineValidity.VALID) {
+                    return line;
+                }
+            }
+            return null;
+        }
+
+        public function get textLineCreationResult(): String {
+            return this._textLineCreationResult;
+        }
+
+        public function createTextLine(previousLine: TextLine = null, width: Number = 1000000, lineOffset: Number = 0.0, fitSomething: Boolean = false): TextLine {
+            if (previousLine != null && previousLine.textBlock != this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            return this.layoutLine(null, previousLine, width, fitSomething);
+        }
+
+        public function recreateTextLine(textLine: TextLine, previousLine: TextLine = null, width: Number = 1000000, lineOffset: Number = 0.0,
+                                         fitSomething: Boolean = false): TextLine {
+            if (textLine == null || (previousLine != null && previousLine.textBlock != this)) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (textLine.textBlock != null) {
+                textLine.textBlock.releaseLines(textLine, textLine);
+            }
+
+            return this.layoutLine(textLine, previousLine, width, fitSomething);
+        }
+
+        private function layoutLine(textLine: TextLine, previousLine: TextLine, width: Number, fitSomething: Boolean): TextLine {
+            if (!(width >= 0 && width <= TextLine.MAX_LINE_WIDTH)) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var beginIndex: int = previousLine != null ? previousLine.textBlockBeginIndex + previousLine.rawTextLength : 0;
+            var rawText: String = this._content != null ? this._content.rawText : null;
+            if (rawText == null || beginIndex >= rawText.length) {
+                this._textLineCreationResult = TextLineCreationResult.COMPLETE;
+                return null;
+            }
+
+            var texts: Array = [];
+            var formats: Array = [];
+            var graphics: Array = [];
+            this.collectRuns(this._content, texts, formats, graphics);
+
+            var line: TextLine = this.createLine(textLine, texts, formats, graphics, beginIndex, width, fitSomething);
+            if (line == null) {
+                this._textLineCreationResult = TextLineCreationResult.INSUFFICIENT_WIDTH;
+                return null;
+            }
+
+            line.ruffle::_textBlock = this;
+            line.ruffle::_textBlockBeginIndex = beginIndex;
+            line.ruffle::_specifiedWidth = width;
+            line.ruffle::_validity = TextLineValidity.VALID;
+            for each (var oldGraphic: DisplayObject in line.ruffle::_atomGraphics) {
+                if (oldGraphic != null && oldGraphic.parent == line) {
+                    line.removeChild(oldGraphic);
+                }
+            }
+            line.ruffle::_atomGraphics = [];
+            this.placeGraphics(line, this._content);
+
+            // Lines that followed `previousLine` no longer match the new line.
+            var nextLine: TextLine = previousLine != null ? previousLine.nextLine : this._firstLine;
+            if (nextLine != null) {
+                this.releaseLines(nextLine, this._lastLine);
+            }
+
+            line.ruffle::_previousLine = previousLine;
+            line.ruffle::_nextLine = null;
+            if (previousLine != null) {
+                previousLine.ruffle::_nextLine = line;
+            } else {
+                this._firstLine = line;
+            }
+            this._lastLine = line;
+
+            this._textLineCreationResult = TextLineCreationResult.SUCCESS;
+            return line;
+        }
+
+        // Flatten the content tree into parallel arrays of leaf element text,
+        // resolved formats, and graphic elements (null for text runs).
+        private function collectRuns(element: ContentElement, texts: Array, formats: Array, graphics: Array): void {
+            if (element is GroupElement) {
+                var group: GroupElement = element as GroupElement;
+                for (var i: int = 0; i < group.elementCount; i++) {
+                    this.collectRuns(group.getElementAt(i), texts, formats, graphics);
+                }
+                return;
+            }
+
+            var format: ElementFormat = element.ruffle::resolvedFormat();
+            texts.push(element.rawText != null ? element.rawText : "");
+            formats.push(format != null ? format : new ElementFormat());
+            graphics.push(element as GraphicElement);
+        }
+
+        private function placeGraphics(line: TextLine, element: ContentElement): void {
+            if (element is GroupElement) {
+                var group: GroupElement = element as GroupElement;
+                for (var i: int = 0; i < group.elementCount; i++) {
+                    this.placeGraphics(line, group.getElementAt(i));
+                }
+                return;
+            }
+
+            var graphicElement: GraphicElement = element as GraphicElement;
+            if (graphicElement == null || graphicElement.graphic == null) {
+                return;
+            }
+
+            var atomIndex: int = line.getAtomIndexAtCharIndex(graphicElement.textBlockBeginIndex);
+            if (atomIndex < 0) {
+                return;
+            }
+
+            // Graphics sit on the roman baseline.
+            var graphic: DisplayObject = graphicElement.graphic;
+            graphic.x = line.getAtomBounds(atomIndex).x;
+            graphic.y = -graphicElement.elementHeight;
+            line.addChild(graphic);
+            line.ruffle::_atomGraphics[atomIndex] = graphic;
+        }
+
+        public function releaseLines(firstLine: TextLine, lastLine: TextLine): void {
+            if (firstLine == null || lastLine == null || firstLine.textBlock != this || lastLine.textBlock != this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var before: TextLine = firstLine.previousLine;
+            var after: TextLine = lastLine.nextLine;
+            var line: TextLine = firstLine;
+            while (line != null) {
+                var next: TextLine = line.nextLine;
+                line.ruffle::_textBlock = null;
+                line.ruffle::_previousLine = null;
+                line.ruffle::_nextLine = null;
+                if (line.validity == TextLineValidity.VALID) {
+                    line.ruffle::_validity = TextLineValidity.INVALID;
+                }
+                if (line == lastLine) {
+                    break;
+                }
+                line = next;
+            }
+
+            if (before != null) {
+                before.ruffle::_nextLine = after;
+            } else {
+                this._firstLine = after;
+            }
+            if (after != null) {
+                after.ruffle::_previousLine = before;
+            } else {
+                this._lastLine = before;
+            }
+        }
+
+        public function releaseLineCreationData(): void {
+            // We don't keep any data around between line creations.
+        }
+
+        public function findTextLineAtCharIndex(charIndex: int): TextLine {
+            var rawText: String = this._content != null ? this._content.rawText : "";
+            if (charIndex < 0 || charIndex >= rawText.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            for (var line: TextLine = this._firstLine; line != null; line = line.nextLine) {
+                if (charIndex >= line.textBlockBeginIndex && charIndex < line.textBlockBeginIndex + line.rawTextLength) {
+                    return line;
+                }
+            }
+            return null;
+        }
+
+        public function findNextAtomBoundary(afterCharIndex: int): int {
+            this.checkCharIndex(afterCharIndex);
+            return afterCharIndex + 1;
+        }
+
+        public function findPreviousAtomBoundary(beforeCharIndex: int): int {
+            this.checkCharIndex(beforeCharIndex);
+            return beforeCharIndex - 1;
+        }
+
+        public function findNextWordBoundary(afterCharIndex: int): int {
+            this.checkCharIndex(afterCharIndex);
+            var rawText: String = this._content.rawText;
+            var index: int = afterCharIndex + 1;
+            while (index < rawText.length && !this.isWordBoundary(rawText, index)) {
+                index++;
+            }
+            return index;
+        }
+
+        public function findPreviousWordBoundary(beforeCharIndex: int): int {
+            this.checkCharIndex(beforeCharIndex);
+            var rawText: String = this._content.rawText;
+            var index: int = beforeCharIndex - 1;
+            while (index > 0 && !this.isWordBoundary(rawText, index)) {
+                index--;
+            }
+            return index;
+        }
+
+        private function isWordBoundary(rawText: String, index: int): Boolean {
+            return (rawText.charAt(index - 1) == " ") != (rawText.charAt(index) == " ");
+        }
+
+        private function checkCharIndex(charIndex: int): void {
+            var rawText: String = this._content != null ? this._content.rawText : "";
+            if (charIndex < 0 || charIndex > rawText.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+        }
+
+        public function dump(): String {
+            return "";
+        }
+
+        // Called when the content changes, invalidating any existing lines.
+        ruffle function contentChanged(): void {
+            if (this._content != null) {
+                this._content.ruffle::attach(this, 0);
+            }
+            for (var line: TextLine = this._firstLine; line != null; line = line.nextLine) {
+                if (line.validity == TextLineValidity.VALID) {
+                    line.ruffle::_validity = TextLineValidity.INVALID;
+                }
+            }
+        }
+
+        private native function createLine(textLine: TextLine, texts: Array, formats: Array, graphics: Array, beginIndex: int, width: Number,
+                                           fitSomething: Boolean): TextLine;
+    }
+}
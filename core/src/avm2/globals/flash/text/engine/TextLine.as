@@ -0,0 +1,227 @@
+package flash.text.engine {
+    import flash.display.DisplayObject;
+    import flash.display.DisplayObjectContainer;
+    import flash.geom.Point;
+    import flash.geom.Rectangle;
+    import __ruffle__.stub_method;
+
+    namespace ruffle = "__ruffle__";
+
+    [Ruffle(NativeInstanceInit)]
+    public final class TextLine extends DisplayObjectContainer {
+        public static const MAX_LINE_WIDTH: int = 1000000;
+
+        public var userData: *;
+
+        // These are filled in by the native code that lays out the line.
+        ruffle var _rawTextLength: int = 0;
+        ruffle var _ascent: Number = 0;
+        ruffle var _descent: Number = 0;
+        ruffle var _textWidth: Number = 0;
+
+        // Four entries per atom: the begin and end indices of the atom in the
+        // text block, followed by its horizontal position and width.
+        ruffle var _atomData: Array = [];
+
+        // These are filled in by the owning `TextBlock`.
+        ruffle var _textBlock: TextBlock = null;
+        ruffle var _textBlockBeginIndex: int = 0;
+        ruffle var _specifiedWidth: Number = 0;
+        ruffle var _previousLine: TextLine = null;
+        ruffle var _nextLine: TextLine = null;
+        ruffle var _validity: String = "valid";
+        ruffle var _atomGraphics: Array = [];
+
+        public function TextLine() {
+            throw new ArgumentError("Error #2012: TextLine$ class cannot be instantiated.", 2012);
+        }
+
+        public function get ascent(): Number {
+            return this.ruffle::_ascent;
+        }
+
+        public function get descent(): Number {
+            return this.ruffle::_descent;
+        }
+
+        public function get totalAscent(): Number {
+            return this.ruffle::_ascent;
+        }
+
+        public function get totalDescent(): Number {
+            return this.ruffle::_descent;
+        }
+
+        public function get totalHeight(): Number {
+            return this.ruffle::_ascent + this.ruffle::_descent;
+        }
+
+        public function get textHeight(): Number {
+            return this.ruffle::_ascent + this.ruffle::_descent;
+        }
+
+        public function get textWidth(): Number {
+            return this.ruffle::_textWidth;
+        }
+
+        public function get unjustifiedTextWidth(): Number {
+            return this.ruffle::_textWidth;
+        }
+
+        public function get specifiedWidth(): Number {
+            return this.ruffle::_specifiedWidth;
+        }
+
+        public function get rawTextLength(): int {
+            return this.ruffle::_rawTextLength;
+        }
+
+        public function get textBlock(): TextBlock {
+            return this.ruffle::_textBlock;
+        }
+
+        public function get textBlockBeginIndex(): int {
+            return this.ruffle::_textBlockBeginIndex;
+        }
+
+        public function get previousLine(): TextLine {
+            return this.ruffle::_previousLine;
+        }
+
+        public function get nextLine(): TextLine {
+            return this.ruffle::_nextLine;
+        }
+
+        public function get validity(): String {
+            return this.ruffle::_validity;
+        }
+
+        public function set validity(value: String): void {
+            if (value != TextLineValidity.VALID && value != TextLineValidity.POSSIBLY_INVALID &&
+                value != TextLineValidity.INVALID && value != TextLineValidity.STATIC) {
+                throw new ArgumentError("Error #2008: Parameter validity must be one of the accepted values.", 2008);
+            }
+            this.ruffle::_validity = value;
+        }
+
+        public function get hasGraphicElement(): Boolean {
+            for each (var graphic: DisplayObject in this.ruffle::_atomGraphics) {
+                if (graphic != null) {
+                    return true;
+                }
+            }
+            return false;
+        }
+
+        public function get hasTabs(): Boolean {
+            return false;
+        }
+
+        public function get atomCount(): int {
+            return this.ruffle::_atomData.length / 4;
+        }
+
+        private function checkAtomIndex(atomIndex: int): void {
+            if (atomIndex < 0 || atomIndex >= this.atomCount) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+        }
+
+        public function getAtomBounds(atomIndex: int): Rectangle {
+            this.checkAtomIndex(atomIndex);
+            var atomData: Array = this.ruffle::_atomData;
+            return new Rectangle(atomData[atomIndex * 4 + 2], -this.ascent, atomData[atomIndex * 4 + 3], this.ascent + this.descent);
+        }
+
+        public function getAtomCenter(atomIndex: int): Number {
+            this.checkAtomIndex(atomIndex);
+            var atomData: Array = this.ruffle::_atomData;
+            return atomData[atomIndex * 4 + 2] + atomData[atomIndex * 4 + 3] / 2;
+        }
+
+        public function getAtomTextBlockBeginIndex(atomIndex: int): int {
+            this.checkAtomIndex(atomIndex);
+            return this.ruffle::_atomData[atomIndex * 4];
+        }
+
+        public function getAtomTextBlockEndIndex(atomIndex: int): int {
+            this.checkAtomIndex(atomIndex);
+            return this.ruffle::_atomData[atomIndex * 4 + 1];
+        }
+
+        public function getAtomBidiLevel(atomIndex: int): int {
+            this.checkAtomIndex(atomIndex);
+            return 0;
+        }
+
+        public function getAtomTextRotation(atomIndex: int): String {
+            this.checkAtomIndex(atomIndex);
+            return TextRotation.ROTATE_0;
+        }
+
+        public function getAtomGraphic(atomIndex: int): DisplayObject {
+            this.checkAtomIndex(atomIndex);
+            return this.ruffle::_atomGraphics[atomIndex];
+        }
+
+        public function getAtomWordBoundaryOnLeft(atomIndex: int): Boolean {
+            this.checkAtomIndex(atomIndex);
+            var begin: int = this.getAtomTextBlockBeginIndex(atomIndex);
+            if (begin == 0 || this.textBlock == null || this.textBlock.content == null) {
+                return true;
+            }
+            var rawText: String = this.textBlock.content.rawText;
+            return rawText.charAt(begin - 1) == " " || rawText.charAt(begin) == " ";
+        }
+
+        public function getAtomIndexAtCharIndex(charIndex: int): int {
+            var atomData: Array = this.ruffle::_atomData;
+            for (var i: int = 0; i < atomData.length; i += 4) {
+                if (charIndex >= atomData[i] && charIndex < atomData[i + 1]) {
+                    return i / 4;
+                }
+            }
+            return -1;
+        }
+
+        public function getAtomIndexAtPoint(stageX: Number, stageY: Number): int {
+            var local: Point = this.globalToLocal(new Point(stageX, stageY));
+            if (local.y < -this.ascent || local.y > this.descent) {
+                return -1;
+            }
+            var atomData: Array = this.ruffle::_atomData;
+            for (var i: int = 0; i < atomData.length; i += 4) {
+                if (local.x >= atomData[i + 2] && local.x < atomData[i + 2] + atomData[i + 3]) {
+                    return i / 4;
+                }
+            }
+            return -1;
+        }
+
+        public function getBaselinePosition(baseline: String): Number {
+            switch (baseline) {
+                case TextBaseline.ROMAN:
+                    return 0;
+                case TextBaseline.ASCENT:
+                case TextBaseline.IDEOGRAPHIC_TOP:
+                    return -this.ascent;
+                case TextBaseline.DESCENT:
+                case TextBaseline.IDEOGRAPHIC_BOTTOM:
+                    return this.descent;
+                case TextBaseline.IDEOGRAPHIC_CENTER:
+                    return (this.descent - this.ascent) / 2;
+                default:
+                    throw new ArgumentError("Error #2008: Parameter baseline must be one of the accepted values.", 2008);
+            }
+        }
+
+        public function flushAtomData(): void {
+            stub_method("flash.text.engine.TextLine", "flushAtomData");
+        }
+
+        public function dump(): String {
+            stub_method("flash.text.engine.TextLine", "dump");
+            return "";
+        }
+    }
+}
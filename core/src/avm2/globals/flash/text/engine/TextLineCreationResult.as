@@ -0,0 +1,8 @@
+package flash.text.engine {
+    public final class TextLineCreationResult {
+        public static const SUCCESS: String = "success";
+        public static const EMERGENCY: String = "emergency";
+        public static const COMPLETE: String = "complete";
+        public static const INSUFFICIENT_WIDTH: String = "insufficientWidth";
+    }
+}
@@ -0,0 +1,8 @@
+package flash.text.engine {
+    public final class TextLineValidity {
+        public static const VALID: String = "valid";
+        public static const POSSIBLY_INVALID: String = "possiblyInvalid";
+        public static const INVALID: String = "invalid";
+        public static const STATIC: String = "static";
+    }
+}
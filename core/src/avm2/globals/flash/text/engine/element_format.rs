@@ -0,0 +1,127 @@
+//! `flash.text.engine.ElementFormat` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::avm2::Multiname;
+use crate::font::{EvalParameters, Font};
+use swf::{Color, Twips};
+
+/// The parts of an `ElementFormat` that affect how its text is laid out and
+/// drawn.
+pub struct ResolvedFormat<'gc> {
+    /// The font to render with, if any font could be found at all.
+    pub font: Option<Font<'gc>>,
+
+    /// The size, tracking and kerning of the text.
+    pub params: EvalParameters,
+
+    /// The color and alpha of the text.
+    pub color: Color,
+}
+
+impl<'gc> ResolvedFormat<'gc> {
+    /// Read an `ElementFormat` and look up the font its `FontDescription`
+    /// refers to.
+    ///
+    /// Device fonts (and embedded fonts that can't be found) are rendered
+    /// with our built-in device font.
+    pub fn from_format(
+        activation: &mut Activation<'_, 'gc, '_>,
+        format: Object<'gc>,
+    ) -> Result<Self, Error<'gc>> {
+        let font_size = format
+            .get_property(&Multiname::public("fontSize"), activation)?
+            .coerce_to_number(activation)?;
+        let color = format
+            .get_property(&Multiname::public("color"), activation)?
+            .coerce_to_u32(activation)?;
+        let alpha = format
+            .get_property(&Multiname::public("alpha"), activation)?
+            .coerce_to_number(activation)?;
+        let kerning = format
+            .get_property(&Multiname::public("kerning"), activation)?
+            .coerce_to_string(activation)?;
+        let tracking = format
+            .get_property(&Multiname::public("trackingLeft"), activation)?
+            .coerce_to_number(activation)?
+            + format
+                .get_property(&Multiname::public("trackingRight"), activation)?
+                .coerce_to_number(activation)?;
+
+        let mut font = None;
+        if let Value::Object(description) =
+            format.get_property(&Multiname::public("fontDescription"), activation)?
+        {
+            let lookup = description
+                .get_property(&Multiname::public("fontLookup"), activation)?
+                .coerce_to_string(activation)?;
+            if &lookup == b"embeddedCFF" {
+                let name = description
+                    .get_property(&Multiname::public("fontName"), activation)?
+                    .coerce_to_string(activation)?;
+                let is_bold = &description
+                    .get_property(&Multiname::public("fontWeight"), activation)?
+                    .coerce_to_string(activation)?
+                    == b"bold";
+                let is_italic = &description
+                    .get_property(&Multiname::public("fontPosture"), activation)?
+                    .coerce_to_string(activation)?
+                    == b"italic";
+
+                let movie = activation.context.swf.clone();
+                font = activation
+                    .context
+                    .library
+                    .library_for_movie_mut(movie)
+                    .get_font_by_name(&name.to_utf8_lossy(), is_bold, is_italic)
+                    .filter(|f| f.has_glyphs());
+            }
+        }
+
+        Ok(Self {
+            font: font.or_else(|| activation.context.library.device_font()),
+            params: EvalParameters::from_parts(
+                Twips::from_pixels(font_size),
+                Twips::from_pixels(tracking),
+                &kerning != b"off",
+            ),
+            color: Color::from_rgb(color, (alpha * 255.0) as u8),
+        })
+    }
+
+    /// The ascent, descent and leading of this format's font.
+    pub fn vertical_metrics(&self) -> (Twips, Twips, Twips) {
+        let height = self.params.height();
+        match self.font {
+            Some(font) => (
+                font.get_baseline_for_height(height),
+                font.get_descent_for_height(height),
+                font.get_leading_for_height(height),
+            ),
+            None => (height, Twips::ZERO, Twips::ZERO),
+        }
+    }
+}
+
+/// Implements `ElementFormat.measureFont`
+pub fn measure_font<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (ascent, descent, leading) =
+            ResolvedFormat::from_format(activation, this)?.vertical_metrics();
+        let storage = ArrayStorage::from_args(&[
+            ascent.to_pixels().into(),
+            descent.to_pixels().into(),
+            leading.to_pixels().into(),
+        ]);
+        return Ok(ArrayObject::from_storage(activation, storage)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,494 @@
+//! `flash.text.engine.TextBlock` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::error::type_error;
+use crate::avm2::globals::flash::text::engine::element_format::ResolvedFormat;
+use crate::avm2::object::{ArrayObject, Object, StageObject, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::avm2::{Multiname, Namespace};
+use crate::display_object::{MovieClip, TDisplayObject};
+use crate::string::{utils as string_utils, AvmString};
+use crate::tag_utils::SwfMovie;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use ruffle_render::transform::Transform;
+use std::sync::Arc;
+use swf::{Color, FillStyle, Twips};
+
+/// The line separator, which always ends the line it is on.
+const LINE_SEPARATOR: u16 = 0x2028;
+
+fn ruffle_name<'gc>(local_name: &'static str) -> Multiname<'gc> {
+    Multiname::new(Namespace::Namespace("__ruffle__".into()), local_name)
+}
+
+/// One leaf element of a text block's content, as flattened by
+/// `TextBlock.collectRuns`.
+struct Run<'gc> {
+    /// The index of the run's first character within the text block.
+    start: usize,
+
+    /// The raw text of the element.
+    text: AvmString<'gc>,
+
+    /// The element's resolved format.
+    format: ResolvedFormat<'gc>,
+
+    /// The width and height of a `GraphicElement`, or `None` for text.
+    graphic_size: Option<(Twips, Twips)>,
+}
+
+/// A single indivisible unit of a laid out line.
+struct Atom {
+    /// The text block indices covered by this atom.
+    begin: usize,
+    end: usize,
+
+    /// The horizontal position and width of the atom on its line.
+    x: Twips,
+    width: Twips,
+}
+
+/// The result of laying out a single line of a text block.
+#[derive(Default)]
+struct LineLayout {
+    /// The text block index just past the last character on the line.
+    end: usize,
+
+    atoms: Vec<Atom>,
+
+    /// Glyph outlines to draw, in line coordinates.
+    fills: Vec<(Color, Vec<DrawCommand>)>,
+
+    ascent: Twips,
+    descent: Twips,
+    width: Twips,
+}
+
+/// Read the values of an array passed to a native method.
+fn array_values<'gc>(value: Option<&Value<'gc>>) -> Vec<Value<'gc>> {
+    value
+        .and_then(|value| value.as_object())
+        .and_then(|object| {
+            object.as_array_storage().map(|storage| {
+                storage
+                    .iter()
+                    .map(|v| v.unwrap_or(Value::Undefined))
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+fn collect_runs<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    texts: &[Value<'gc>],
+    formats: &[Value<'gc>],
+    graphics: &[Value<'gc>],
+) -> Result<Vec<Run<'gc>>, Error<'gc>> {
+    let mut runs = Vec::with_capacity(texts.len());
+    let mut start = 0;
+
+    for (i, text) in texts.iter().enumerate() {
+        let text = text.coerce_to_string(activation)?;
+        let format = match formats.get(i) {
+            Some(Value::Object(format)) => ResolvedFormat::from_format(activation, *format)?,
+            _ => {
+                return Err(Error::AvmError(type_error(
+                    activation,
+                    "Error #2007: Parameter elementFormat must be non-null.",
+                    2007,
+                )?))
+            }
+        };
+        let graphic_size = match graphics.get(i) {
+            Some(Value::Object(graphic)) => {
+                let width = graphic
+                    .get_property(&Multiname::public("elementWidth"), activation)?
+                    .coerce_to_number(activation)?;
+                let height = graphic
+                    .get_property(&Multiname::public("elementHeight"), activation)?
+                    .coerce_to_number(activation)?;
+                Some((Twips::from_pixels(width), Twips::from_pixels(height)))
+            }
+            _ => None,
+        };
+
+        runs.push(Run {
+            start,
+            text,
+            format,
+            graphic_size,
+        });
+        start += text.len();
+    }
+
+    Ok(runs)
+}
+
+/// Fit as much of the text block as possible, starting at `begin`, onto a
+/// line of the given width.
+///
+/// Lines are broken using the same rules as `TextField` word wrapping. If not
+/// even a single atom fits, this yields `None` unless `fit_something` is set,
+/// in which case the first atom is placed regardless.
+fn layout_line(
+    runs: &[Run<'_>],
+    begin: usize,
+    width: Twips,
+    fit_something: bool,
+) -> Option<LineLayout> {
+    let mut layout = LineLayout {
+        end: begin,
+        ..Default::default()
+    };
+
+    for run in runs {
+        let run_end = run.start + run.text.len();
+        if run_end <= begin {
+            continue;
+        }
+
+        let offset = begin.saturating_sub(run.start);
+        let is_start_of_line = layout.end == begin;
+
+        if let Some((graphic_width, graphic_height)) = run.graphic_size {
+            if layout.width + graphic_width > width && !is_start_of_line {
+                break;
+            } else if graphic_width > width && !fit_something {
+                return None;
+            }
+
+            layout.atoms.push(Atom {
+                begin: run.start,
+                end: run_end,
+                x: layout.width,
+                width: graphic_width,
+            });
+            layout.width += graphic_width;
+            layout.ascent = layout.ascent.max(graphic_height);
+            layout.end = run_end;
+            continue;
+        }
+
+        let text = &run.text[offset..];
+        let (text, mut is_broken) = match text.iter().position(|c| c == LINE_SEPARATOR) {
+            Some(i) => (&text[..=i], true),
+            None => (text, false),
+        };
+
+        let params = run.format.params;
+        let font = match run.format.font {
+            Some(font) => font,
+            None => {
+                // Without any font there's nothing to draw or measure.
+                layout.end = run.start + offset + text.len();
+                if is_broken {
+                    break;
+                }
+                continue;
+            }
+        };
+
+        let mut end = text.len();
+        if let Some(breakpoint) =
+            font.wrap_line(text, params, width, layout.width, is_start_of_line)
+        {
+            if breakpoint == 0 && is_start_of_line {
+                if !fit_something {
+                    return None;
+                }
+                end = string_utils::next_char_boundary(text, 0);
+            } else if breakpoint == 0 {
+                break;
+            } else if text.get(breakpoint) == Some(b' '.into()) {
+                // This ensures that the space causing the line break is
+                // included in the line it broke.
+                end = string_utils::next_char_boundary(text, breakpoint);
+            } else {
+                end = breakpoint;
+            }
+            is_broken = true;
+        }
+
+        let text = &text[..end];
+        let (ascent, descent, _) = run.format.vertical_metrics();
+        layout.ascent = layout.ascent.max(ascent);
+        layout.descent = layout.descent.max(descent);
+
+        // Glyphs are drawn with their baseline on the line's origin.
+        let transform = Transform {
+            matrix: Matrix {
+                tx: layout.width,
+                ty: -params.height(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let text_start = run.start + offset;
+        let line_x = layout.width;
+        let mut text_width = Twips::ZERO;
+        font.evaluate(
+            text,
+            transform,
+            params,
+            |pos, transform, glyph, advance, x| {
+                layout.atoms.push(Atom {
+                    begin: text_start + pos,
+                    end: text_start + string_utils::next_char_boundary(text, pos),
+                    x: line_x + x,
+                    width: advance,
+                });
+                text_width = x + advance;
+
+                let shape = glyph.as_shape();
+                let shape: DistilledShape = (&*shape).into();
+                for path in shape.paths {
+                    if let DrawPath::Fill { commands, .. } = path {
+                        let commands = commands
+                            .into_iter()
+                            .map(|command| transform_command(&transform.matrix, command))
+                            .collect();
+                        layout.fills.push((run.format.color.clone(), commands));
+                    }
+                }
+            },
+        );
+
+        layout.width += text_width;
+        layout.end = text_start + end;
+        if is_broken {
+            break;
+        }
+    }
+
+    Some(layout)
+}
+
+fn transform_command(matrix: &Matrix, command: DrawCommand) -> DrawCommand {
+    match command {
+        DrawCommand::MoveTo { x, y } => {
+            let (x, y) = *matrix * (x, y);
+            DrawCommand::MoveTo { x, y }
+        }
+        DrawCommand::LineTo { x, y } => {
+            let (x, y) = *matrix * (x, y);
+            DrawCommand::LineTo { x, y }
+        }
+        DrawCommand::CurveTo { x1, y1, x2, y2 } => {
+            let (x1, y1) = *matrix * (x1, y1);
+            let (x2, y2) = *matrix * (x2, y2);
+            DrawCommand::CurveTo { x1, y1, x2, y2 }
+        }
+    }
+}
+
+/// Allocate a new, empty `TextLine`.
+fn new_text_line<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<Object<'gc>, Error<'gc>> {
+    let class = activation.avm2().classes().textline;
+    let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+    let mut line = MovieClip::new(movie, activation.context.gc_context);
+    line.set_avm2_class(activation.context.gc_context, Some(class));
+
+    let object: Object<'gc> =
+        StageObject::for_display_object(activation, line.into(), class)?.into();
+    line.set_object2(activation.context.gc_context, object);
+    class.call_native_init(Some(object), &[], activation)?;
+
+    Ok(object)
+}
+
+/// Implements `TextBlock.createLine`
+///
+/// Lays out the next line of the block, starting at `beginIndex`, into
+/// either the given `TextLine` or a new one.
+pub fn create_line<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let texts = array_values(args.get(1));
+    let formats = array_values(args.get(2));
+    let graphics = array_values(args.get(3));
+    let begin = args
+        .get(4)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_u32(activation)? as usize;
+    let width = args
+        .get(5)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_number(activation)?;
+    let fit_something = args.get(6).unwrap_or(&Value::Undefined).coerce_to_boolean();
+
+    let runs = collect_runs(activation, &texts, &formats, &graphics)?;
+    let layout = match layout_line(&runs, begin, Twips::from_pixels(width), fit_something) {
+        Some(layout) if layout.end > begin => layout,
+        _ => return Ok(Value::Null),
+    };
+
+    let mut line = match args.get(0) {
+        Some(Value::Object(line)) => *line,
+        _ => new_text_line(activation)?,
+    };
+
+    if let Some(display_object) = line.as_display_object() {
+        if let Some(mut drawing) = display_object.as_drawing(activation.context.gc_context) {
+            drawing.clear();
+            for (color, commands) in layout.fills {
+                drawing.set_fill_style(Some(FillStyle::Color(color)));
+                for command in commands {
+                    drawing.draw_command(command);
+                }
+                drawing.set_fill_style(None);
+            }
+        }
+    }
+
+    let mut atom_data = ArrayStorage::new(0);
+    for atom in &layout.atoms {
+        atom_data.push((atom.begin as f64).into());
+        atom_data.push((atom.end as f64).into());
+        atom_data.push(atom.x.to_pixels().into());
+        atom_data.push(atom.width.to_pixels().into());
+    }
+    let atom_data = ArrayObject::from_storage(activation, atom_data)?;
+
+    line.set_property(
+        &ruffle_name("_rawTextLength"),
+        ((layout.end - begin) as f64).into(),
+        activation,
+    )?;
+    line.set_property(
+        &ruffle_name("_ascent"),
+        layout.ascent.to_pixels().into(),
+        activation,
+    )?;
+    line.set_property(
+        &ruffle_name("_descent"),
+        layout.descent.to_pixels().into(),
+        activation,
+    )?;
+    line.set_property(
+        &ruffle_name("_textWidth"),
+        layout.width.to_pixels().into(),
+        activation,
+    )?;
+    line.set_property(&ruffle_name("_atomData"), atom_data.into(), activation)?;
+
+    Ok(line.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font::{EvalParameters, Font};
+    use crate::player::Player;
+    use gc_arena::{rootless_arena, MutationContext};
+    use ruffle_render::backend::{null::NullRenderer, ViewportDimensions};
+
+    fn with_device_font<F>(callback: F)
+    where
+        F: for<'gc> FnOnce(MutationContext<'gc, '_>, Font<'gc>),
+    {
+        rootless_arena(|mc| {
+            let mut renderer = NullRenderer::new(ViewportDimensions {
+                width: 0,
+                height: 0,
+                scale_factor: 1.0,
+            });
+            let device_font = Player::load_device_font(mc, &mut renderer);
+
+            callback(mc, device_font);
+        })
+    }
+
+    fn text_run<'gc>(
+        mc: MutationContext<'gc, '_>,
+        font: Font<'gc>,
+        start: usize,
+        text: &str,
+    ) -> Run<'gc> {
+        Run {
+            start,
+            text: AvmString::new_utf8(mc, text),
+            format: ResolvedFormat {
+                font: Some(font),
+                params: EvalParameters::from_parts(
+                    Twips::from_pixels(12.0),
+                    Twips::from_pixels(0.0),
+                    true,
+                ),
+                color: Color::from_rgb(0, 255),
+            },
+            graphic_size: None,
+        }
+    }
+
+    #[test]
+    fn layout_line_breaks_after_space() {
+        with_device_font(|mc, font| {
+            let runs = [text_run(mc, font, 0, "abcd efgh ijkl")];
+
+            let line = layout_line(&runs, 0, Twips::from_pixels(35.0), false).unwrap();
+            assert_eq!(line.end, 5);
+            assert_eq!(line.atoms.first().map(|atom| atom.begin), Some(0));
+
+            let line = layout_line(&runs, 5, Twips::from_pixels(35.0), false).unwrap();
+            assert_eq!(line.end, 10);
+            assert_eq!(line.atoms.first().map(|atom| atom.begin), Some(5));
+        });
+    }
+
+    #[test]
+    fn layout_line_ends_at_line_separator() {
+        with_device_font(|mc, font| {
+            let runs = [
+                text_run(mc, font, 0, "ab\u{2028}cd"),
+                text_run(mc, font, 5, "ef"),
+            ];
+
+            let line = layout_line(&runs, 0, Twips::from_pixels(200.0), false).unwrap();
+            assert_eq!(line.end, 3);
+
+            let line = layout_line(&runs, 3, Twips::from_pixels(200.0), false).unwrap();
+            assert_eq!(line.end, 7);
+        });
+    }
+
+    #[test]
+    fn layout_line_places_text_after_graphic() {
+        with_device_font(|mc, font| {
+            let mut graphic = text_run(mc, font, 0, "\u{FDEF}");
+            graphic.graphic_size = Some((Twips::from_pixels(10.0), Twips::from_pixels(20.0)));
+            let runs = [graphic, text_run(mc, font, 1, "ab")];
+
+            let line = layout_line(&runs, 0, Twips::from_pixels(200.0), false).unwrap();
+            assert_eq!(line.end, 3);
+            assert_eq!(line.atoms[0].width, Twips::from_pixels(10.0));
+            assert!(line.atoms[1..]
+                .iter()
+                .all(|atom| atom.x >= Twips::from_pixels(10.0)));
+            assert!(line.ascent >= Twips::from_pixels(20.0));
+        });
+    }
+
+    #[test]
+    fn layout_line_fit_something() {
+        with_device_font(|mc, font| {
+            let mut graphic = text_run(mc, font, 0, "\u{FDEF}");
+            graphic.graphic_size = Some((Twips::from_pixels(100.0), Twips::from_pixels(20.0)));
+            let runs = [graphic, text_run(mc, font, 1, "abc")];
+
+            assert!(layout_line(&runs, 0, Twips::from_pixels(50.0), false).is_none());
+            let line = layout_line(&runs, 0, Twips::from_pixels(50.0), true).unwrap();
+            assert_eq!(line.end, 1);
+            assert_eq!(line.atoms.len(), 1);
+
+            assert!(layout_line(&runs, 1, Twips::from_pixels(1.0), false).is_none());
+            let line = layout_line(&runs, 1, Twips::from_pixels(1.0), true).unwrap();
+            assert_eq!(line.end, 2);
+        });
+    }
+}
@@ -0,0 +1,16 @@
+//! `flash.text.engine.TextLine` native methods
+
+use crate::avm2::{Activation, Error, Object, Value};
+
+/// Implements `flash.text.engine.TextLine`'s native instance constructor.
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, args)?;
+    }
+
+    Ok(Value::Undefined)
+}
@@ -1,4 +1,6 @@
-use crate::avm2::{Activation, Error, Object, Value};
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::display_object::TDisplayObject;
+use crate::string::AvmString;
 
 pub fn native_instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -13,11 +15,17 @@ pub fn native_instance_init<'gc>(
 }
 /// Implements `StaticText.text`
 pub fn get_text<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Err(Error::RustError(
-        "StaticText.text: not yet implemented".into(),
-    ))
+    if let Some(text) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_text())
+    {
+        let text = text.text(activation.context.library);
+        return Ok(AvmString::new(activation.context.gc_context, text).into());
+    }
+
+    Ok(Value::Undefined)
 }
@@ -0,0 +1,118 @@
+//! `flash.text.StyleSheet` native methods
+
+use crate::avm2::object::{Object, TObject, TextFormatObject};
+use crate::avm2::value::Value;
+use crate::avm2::{Activation, Error, Multiname, Namespace};
+use crate::html::{CssStyle, StyleSheet};
+use crate::string::AvmString;
+
+fn styles_name<'gc>() -> Multiname<'gc> {
+    Multiname::new(Namespace::Namespace("__ruffle__".into()), "_styles")
+}
+
+/// Reads the dynamic properties of a style object as CSS declarations.
+fn style_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Result<CssStyle, Error<'gc>> {
+    let mut style = CssStyle::new();
+    let mut last_index = object.get_next_enumerant(0, activation)?;
+    while let Some(index) = last_index {
+        let name = object
+            .get_enumerant_name(index, activation)?
+            .coerce_to_string(activation)?;
+        let value = object
+            .get_property(&Multiname::public(name), activation)?
+            .coerce_to_string(activation)?;
+        style.push((
+            name.to_utf8_lossy().into_owned(),
+            value.to_utf8_lossy().into_owned(),
+        ));
+        last_index = object.get_next_enumerant(index, activation)?;
+    }
+    Ok(style)
+}
+
+/// Builds a snapshot of the styles held by an AS3 `StyleSheet`, for use by
+/// text fields.
+pub fn style_sheet_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<StyleSheet, Error<'gc>> {
+    let mut style_sheet = StyleSheet::new();
+    let styles = match this.get_property(&styles_name(), activation)?.as_object() {
+        Some(styles) => styles,
+        None => return Ok(style_sheet),
+    };
+
+    let mut last_index = styles.get_next_enumerant(0, activation)?;
+    while let Some(index) = last_index {
+        let name = styles
+            .get_enumerant_name(index, activation)?
+            .coerce_to_string(activation)?;
+        let style = styles.get_property(&Multiname::public(name), activation)?;
+        if let Some(style) = style.as_object() {
+            let style = style_from_object(activation, style)?;
+            style_sheet.set_style(&name.to_utf8_lossy(), style);
+        }
+        last_index = styles.get_next_enumerant(index, activation)?;
+    }
+    Ok(style_sheet)
+}
+
+/// Implements `StyleSheet.transform`
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let style = match args.get(0).and_then(|value| value.as_object()) {
+        Some(object) => style_from_object(activation, object)?,
+        None => return Ok(Value::Null),
+    };
+
+    let text_format = StyleSheet::style_to_text_format(&style);
+    Ok(TextFormatObject::from_text_format(activation, text_format)?.into())
+}
+
+/// Implements `StyleSheet.innerParseCSS`
+///
+/// Returns an object mapping each selector to its declarations, or `null` if
+/// the CSS couldn't be parsed.
+pub fn inner_parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    let rules = match StyleSheet::parse_css(&css.to_utf8_lossy()) {
+        Some(rules) => rules,
+        None => return Ok(Value::Null),
+    };
+
+    let object_class = activation.avm2().classes().object;
+    let mut result = object_class.construct(activation, &[])?;
+    for (selector, declarations) in rules {
+        let selector = AvmString::new_utf8(activation.context.gc_context, selector);
+        let selector_name = Multiname::public(selector);
+
+        // Later rules for the same selector add to the earlier ones.
+        let mut style = match result.get_property(&selector_name, activation)? {
+            Value::Object(style) => style,
+            _ => object_class.construct(activation, &[])?,
+        };
+        for (name, value) in declarations {
+            let name = AvmString::new_utf8(activation.context.gc_context, name);
+            let value = AvmString::new_utf8(activation.context.gc_context, value);
+            style.set_property(&Multiname::public(name), value.into(), activation)?;
+        }
+        result.set_property(&selector_name, style.into(), activation)?;
+    }
+
+    Ok(result.into())
+}
@@ -2,6 +2,8 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::error::{error, range_error};
+use crate::avm2::globals::flash::text::style_sheet::style_sheet_from_object;
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::object::{Object, TObject, TextFormatObject};
 use crate::avm2::value::Value;
@@ -13,9 +15,10 @@ use crate::display_object::{AutoSizeMode, EditText, TDisplayObject, TextSelectio
 use crate::html::TextFormat;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
+use crate::vminterface::AvmObject;
 use gc_arena::{GcCell, MutationContext};
 use std::sync::Arc;
-use swf::Color;
+use swf::{Color, Twips};
 
 /// Implements `flash.text.TextField`'s instance constructor.
 pub fn instance_init<'gc>(
@@ -261,6 +264,8 @@ pub fn set_default_text_format<'gc>(
         .and_then(|this| this.as_display_object())
         .and_then(|this| this.as_edit_text())
     {
+        check_no_style_sheet(activation, this)?;
+
         let new_text_format = args.get(0).unwrap_or(&Value::Undefined).as_object();
 
         if let Some(new_text_format) = new_text_format {
@@ -381,6 +386,62 @@ pub fn set_html_text<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn style_sheet<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        if let Some(AvmObject::Avm2(object)) = this.style_sheet_object() {
+            return Ok(object.into());
+        }
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_style_sheet<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let style_sheet = match args.get(0).unwrap_or(&Value::Undefined).as_object() {
+            Some(object) => {
+                let style_sheet = style_sheet_from_object(activation, object)?;
+                Some((AvmObject::Avm2(object), style_sheet))
+            }
+            None => None,
+        };
+        this.set_style_sheet(&mut activation.context, style_sheet);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Fields with a style sheet can't have their formatting or text changed
+/// directly.
+fn check_no_style_sheet<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: EditText<'gc>,
+) -> Result<(), Error<'gc>> {
+    if this.style_sheet_object().is_some() {
+        return Err(Error::AvmError(error(
+            activation,
+            "Error #2009: This method cannot be used on a text field with a style sheet.",
+            2009,
+        )?));
+    }
+    Ok(())
+}
+
 pub fn length<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -432,6 +493,116 @@ pub fn set_multiline<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn condense_white<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.condense_white().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_condense_white<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let condense_white = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        this.set_condense_white(condense_white, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn max_chars<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.max_chars().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_max_chars<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let max_chars = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        this.set_max_chars(max_chars, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn restrict<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return match this.restrict() {
+            Some(restrict) => Ok(AvmString::new(activation.context.gc_context, restrict).into()),
+            None => Ok(Value::Null),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_restrict<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let restrict = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            value => Some(value.coerce_to_string(activation)?),
+        };
+
+        this.set_restrict(restrict.as_deref(), activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn selectable<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -738,6 +909,8 @@ pub fn replace_selected_text<'gc>(
         .and_then(|this| this.as_display_object())
         .and_then(|this| this.as_edit_text())
     {
+        check_no_style_sheet(activation, this)?;
+
         let value = args
             .get(0)
             .cloned()
@@ -767,6 +940,8 @@ pub fn replace_text<'gc>(
         .and_then(|this| this.as_display_object())
         .and_then(|this| this.as_edit_text())
     {
+        check_no_style_sheet(activation, this)?;
+
         let begin_index = args
             .get(0)
             .cloned()
@@ -794,6 +969,96 @@ pub fn replace_text<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn always_show_selection<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.always_show_selection().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_always_show_selection<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let always_show_selection = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        this.set_always_show_selection(always_show_selection, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn caret_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this
+            .selection()
+            .map_or(0, |selection| selection.to())
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn selection_begin_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this
+            .selection()
+            .map_or(0, |selection| selection.start())
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn selection_end_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this
+            .selection()
+            .map_or(0, |selection| selection.end())
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn set_selection<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -835,6 +1100,8 @@ pub fn set_text_format<'gc>(
         .and_then(|this| this.as_display_object())
         .and_then(|this| this.as_edit_text())
     {
+        check_no_style_sheet(activation, this)?;
+
         let tf = args.get(0).unwrap_or(&Value::Undefined).as_object();
         if let Some(tf) = tf {
             if let Some(tf) = tf.as_text_format() {
@@ -1122,6 +1389,250 @@ pub fn get_line_metrics<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Reads a character or line index argument, which is None when negative.
+fn index_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Option<usize>, Error<'gc>> {
+    let index = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    Ok(usize::try_from(index).ok())
+}
+
+/// Reads a point argument in pixels.
+fn point_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<(Twips, Twips), Error<'gc>> {
+    let x = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+    let y = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+    Ok((Twips::from_pixels(x), Twips::from_pixels(y)))
+}
+
+fn line_index_out_of_bounds<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Error<'gc> {
+    match range_error(
+        activation,
+        "Error #2006: The supplied index is out of bounds.",
+        2006,
+    ) {
+        Ok(error) => Error::AvmError(error),
+        Err(error) => error,
+    }
+}
+
+pub fn get_char_boundaries<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let bounds = index_arg(activation, args)?.and_then(|index| this.char_bounds(index));
+
+        if let Some(bounds) = bounds {
+            let rectangle_class = activation.avm2().classes().rectangle;
+            return Ok(rectangle_class
+                .construct(
+                    activation,
+                    &[
+                        bounds.offset_x().to_pixels().into(),
+                        bounds.offset_y().to_pixels().into(),
+                        bounds.width().to_pixels().into(),
+                        bounds.height().to_pixels().into(),
+                    ],
+                )?
+                .into());
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_char_index_at_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let position = point_args(activation, args)?;
+        return Ok(this
+            .char_index_at_point(position)
+            .map_or(-1, |index| index as i32)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_first_char_in_paragraph<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(index_arg(activation, args)?
+            .and_then(|index| this.first_char_in_paragraph(index))
+            .map_or(-1, |index| index as i32)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_index_at_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let position = point_args(activation, args)?;
+        return Ok(this
+            .line_index_at_point(position)
+            .map_or(-1, |line| line as i32)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_index_of_char<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(index_arg(activation, args)?
+            .and_then(|index| this.line_index_of_char(index))
+            .map_or(-1, |line| line as i32)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return match index_arg(activation, args)?.and_then(|line| this.line_length(line)) {
+            Some(length) => Ok(length.into()),
+            None => Err(line_index_out_of_bounds(activation)),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_offset<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return match index_arg(activation, args)?.and_then(|line| this.line_offset(line)) {
+            Some(offset) => Ok(offset.into()),
+            None => Err(line_index_out_of_bounds(activation)),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_image_reference<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let id = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        return Ok(this
+            .image_by_id(&id, true)
+            .map(|image| image.object2())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return match index_arg(activation, args)?.and_then(|line| this.line_text(line)) {
+            Some(text) => Ok(AvmString::new(activation.context.gc_context, text).into()),
+            None => Err(line_index_out_of_bounds(activation)),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_paragraph_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(index_arg(activation, args)?
+            .and_then(|index| this.paragraph_length(index))
+            .map_or(-1, |length| length as i32)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn bottom_scroll_v<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -1263,6 +1774,11 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         Option<NativeMethodImpl>,
         Option<NativeMethodImpl>,
     )] = &[
+        (
+            "alwaysShowSelection",
+            Some(always_show_selection),
+            Some(set_always_show_selection),
+        ),
         ("autoSize", Some(autosize), Some(set_autosize)),
         ("background", Some(background), Some(set_background)),
         (
@@ -1273,6 +1789,12 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("border", Some(border), Some(set_border)),
         ("borderColor", Some(border_color), Some(set_border_color)),
         ("bottomScrollV", Some(bottom_scroll_v), None),
+        ("caretIndex", Some(caret_index), None),
+        (
+            "condenseWhite",
+            Some(condense_white),
+            Some(set_condense_white),
+        ),
         (
             "defaultTextFormat",
             Some(default_text_format),
@@ -1286,12 +1808,17 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("embedFonts", Some(embed_fonts), Some(set_embed_fonts)),
         ("htmlText", Some(html_text), Some(set_html_text)),
         ("length", Some(length), None),
+        ("maxChars", Some(max_chars), Some(set_max_chars)),
         ("maxScrollH", Some(max_scroll_h), None),
         ("maxScrollV", Some(max_scroll_v), None),
         ("multiline", Some(multiline), Some(set_multiline)),
+        ("restrict", Some(restrict), Some(set_restrict)),
         ("scrollH", Some(scroll_h), Some(set_scroll_h)),
         ("scrollV", Some(scroll_v), Some(set_scroll_v)),
         ("selectable", Some(selectable), Some(set_selectable)),
+        ("selectionBeginIndex", Some(selection_begin_index), None),
+        ("selectionEndIndex", Some(selection_end_index), None),
+        ("styleSheet", Some(style_sheet), Some(set_style_sheet)),
         ("text", Some(text), Some(set_text)),
         ("textColor", Some(text_color), Some(set_text_color)),
         ("textHeight", Some(text_height), None),
@@ -1318,6 +1845,16 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("setSelection", set_selection),
         ("setTextFormat", set_text_format),
         ("getLineMetrics", get_line_metrics),
+        ("getCharBoundaries", get_char_boundaries),
+        ("getCharIndexAtPoint", get_char_index_at_point),
+        ("getFirstCharInParagraph", get_first_char_in_paragraph),
+        ("getImageReference", get_image_reference),
+        ("getLineIndexAtPoint", get_line_index_at_point),
+        ("getLineIndexOfChar", get_line_index_of_char),
+        ("getLineLength", get_line_length),
+        ("getLineOffset", get_line_offset),
+        ("getLineText", get_line_text),
+        ("getParagraphLength", get_paragraph_length),
     ];
     write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
 
@@ -22,6 +22,7 @@ include "flash/utils/IDataInput.as"
 include "flash/utils/IDataOutput.as"
 include "flash/utils/ByteArray.as"
 include "flash/utils/Dictionary.as"
+include "flash/desktop/Clipboard.as"
 include "flash/desktop/ClipboardFormats.as"
 include "flash/desktop/ClipboardTransferMode.as"
 include "flash/display/ActionScriptVersion.as"
@@ -34,8 +35,19 @@ include "flash/display/ColorCorrectionSupport.as"
 include "flash/display/FocusDirection.as"
 include "flash/display/FrameLabel.as"
 include "flash/display/GradientType.as"
+include "flash/display/IGraphicsData.as"
+include "flash/display/IGraphicsFill.as"
+include "flash/display/IGraphicsPath.as"
+include "flash/display/IGraphicsStroke.as"
+include "flash/display/GraphicsBitmapFill.as"
+include "flash/display/GraphicsEndFill.as"
+include "flash/display/GraphicsGradientFill.as"
+include "flash/display/GraphicsPath.as"
 include "flash/display/GraphicsPathCommand.as"
 include "flash/display/GraphicsPathWinding.as"
+include "flash/display/GraphicsSolidFill.as"
+include "flash/display/GraphicsStroke.as"
+include "flash/display/GraphicsTrianglePath.as"
 include "flash/display/InterpolationMethod.as"
 include "flash/display/JointStyle.as"
 include "flash/display/JPEGEncoderOptions.as"
@@ -105,6 +117,7 @@ include "flash/events/IOErrorEvent.as"
 include "flash/events/KeyboardEvent.as"
 include "flash/events/NetDataEvent.as"
 include "flash/events/NetStatusEvent.as"
+include "flash/events/PermissionEvent.as"
 include "flash/events/ProgressEvent.as"
 include "flash/events/SampleDataEvent.as"
 include "flash/events/SecurityErrorEvent.as"
@@ -144,9 +157,11 @@ include "flash/geom/ColorTransform.as"
 include "flash/geom/Matrix.as"
 include "flash/geom/Matrix3D.as"
 include "flash/geom/Orientation3D.as"
+include "flash/geom/PerspectiveProjection.as"
 include "flash/geom/Point.as"
 include "flash/geom/Rectangle.as"
 include "flash/geom/Transform.as"
+include "flash/geom/Utils3D.as"
 include "flash/geom/Vector3D.as"
 include "flash/globalization/CollatorMode.as"
 include "flash/globalization/DateTimeNameContext.as"
@@ -189,6 +204,7 @@ include "flash/net/URLRequestHeader.as"
 include "flash/net/URLRequestMethod.as"
 include "flash/net/URLVariables.as"
 
+include "flash/permissions/PermissionStatus.as"
 include "flash/printing/PrintJobOrientation.as"
 include "flash/security/CertificateStatus.as"
 include "flash/system/Capabilities.as"
@@ -207,6 +223,7 @@ include "flash/text/FontStyle.as"
 include "flash/text/FontType.as"
 include "flash/text/GridFitType.as"
 include "flash/text/StaticText.as"
+include "flash/text/StyleSheet.as"
 include "flash/text/TextColorType.as"
 include "flash/text/TextDisplayMode.as"
 include "flash/text/TextExtent.as"
@@ -215,6 +232,26 @@ include "flash/text/TextFieldType.as"
 include "flash/text/TextFormatAlign.as"
 include "flash/text/TextInteractionMode.as"
 include "flash/text/TextLineMetrics.as"
+include "flash/text/engine/BreakOpportunity.as"
+include "flash/text/engine/CFFHinting.as"
+include "flash/text/engine/ContentElement.as"
+include "flash/text/engine/ElementFormat.as"
+include "flash/text/engine/FontDescription.as"
+include "flash/text/engine/FontLookup.as"
+include "flash/text/engine/FontMetrics.as"
+include "flash/text/engine/FontPosture.as"
+include "flash/text/engine/FontWeight.as"
+include "flash/text/engine/GraphicElement.as"
+include "flash/text/engine/GroupElement.as"
+include "flash/text/engine/Kerning.as"
+include "flash/text/engine/RenderingMode.as"
+include "flash/text/engine/TextBaseline.as"
+include "flash/text/engine/TextBlock.as"
+include "flash/text/engine/TextElement.as"
+include "flash/text/engine/TextLine.as"
+include "flash/text/engine/TextLineCreationResult.as"
+include "flash/text/engine/TextLineValidity.as"
+include "flash/text/engine/TextRotation.as"
 
 include "flash/ui/ContextMenu.as"
 include "flash/ui/ContextMenuBuiltInItems.as"
@@ -12,6 +12,7 @@ include "flash/display/BitmapData.as"
 include "flash/display/DisplayObject.as"
 include "flash/display/InteractiveObject.as"
 include "flash/display/DisplayObjectContainer.as"
+include "flash/display/Graphics.as"
 
 include "flash/display/LoaderInfo.as"
 include "flash/events/EventDispatcher.as"
@@ -54,6 +54,31 @@ pub fn log_warn<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Log a warning that a script called a method we only have a stub for.
+pub fn stub_method<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let class_name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let method_name = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    log::warn!(
+        "Method {}.{} is not yet implemented",
+        class_name.to_utf8_lossy(),
+        method_name.to_utf8_lossy()
+    );
+
+    Ok(Value::Undefined)
+}
+
 pub fn is_finite<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
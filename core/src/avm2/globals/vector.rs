@@ -120,7 +120,7 @@ pub fn class_init<'gc>(
             int_vector_class.into(),
             class_class,
         );
-        domain.export_definition(int_vector_name, script, activation.context.gc_context)?;
+        domain.export_definition(int_vector_name, script, true, activation.context.gc_context)?;
 
         let uint_class = activation.avm2().classes().uint;
         let uint_vector_class = this.apply(activation, &[uint_class.into()])?;
@@ -136,7 +136,12 @@ pub fn class_init<'gc>(
             uint_vector_class.into(),
             class_class,
         );
-        domain.export_definition(uint_vector_name, script, activation.context.gc_context)?;
+        domain.export_definition(
+            uint_vector_name,
+            script,
+            true,
+            activation.context.gc_context,
+        )?;
 
         let number_class = activation.avm2().classes().number;
         let number_vector_class = this.apply(activation, &[number_class.into()])?;
@@ -152,7 +157,12 @@ pub fn class_init<'gc>(
             number_vector_class.into(),
             class_class,
         );
-        domain.export_definition(number_vector_name, script, activation.context.gc_context)?;
+        domain.export_definition(
+            number_vector_name,
+            script,
+            true,
+            activation.context.gc_context,
+        )?;
 
         let object_vector_class = this.apply(activation, &[Value::Null])?;
         let object_vector_name = QName::new(Namespace::internal(NS_VECTOR), "Vector$object");
@@ -167,7 +177,12 @@ pub fn class_init<'gc>(
             object_vector_class.into(),
             class_class,
         );
-        domain.export_definition(object_vector_name, script, activation.context.gc_context)?;
+        domain.export_definition(
+            object_vector_name,
+            script,
+            true,
+            activation.context.gc_context,
+        )?;
     }
 
     Ok(Value::Undefined)
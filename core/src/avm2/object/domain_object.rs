@@ -65,10 +65,17 @@ impl<'gc> DomainObject<'gc> {
         .into();
         this.install_instance_slots(activation);
 
-        class.call_init(Some(this), &[], activation)?;
+        class.call_native_init(Some(this), &[], activation)?;
 
         Ok(this)
     }
+
+    /// Replace the domain held by this object.
+    ///
+    /// This is only intended for use by the `ApplicationDomain` constructor.
+    pub fn init_domain(&self, mc: MutationContext<'gc, '_>, domain: Domain<'gc>) {
+        self.0.write(mc).domain = domain;
+    }
 }
 
 impl<'gc> TObject<'gc> for DomainObject<'gc> {
@@ -251,6 +251,39 @@ impl<'gc> LoaderInfoObject<'gc> {
         }
     }
 
+    /// Reset this loader info after its content has been unloaded, and fire
+    /// the `unload` event.
+    ///
+    /// The loader info goes back to reporting an empty, not-yet-loaded movie,
+    /// so that a later load through the same `Loader` fires `init` and
+    /// `complete` again.
+    pub fn unload(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let version = match self.0.read().loaded_stream {
+            Some(LoaderStream::Swf(ref movie, _)) => movie.version(),
+            Some(LoaderStream::NotYetLoaded(ref movie, _, _)) => movie.version(),
+            None => context.swf.version(),
+        };
+
+        let mut write = self.0.write(context.gc_context);
+        write.loaded_stream = Some(LoaderStream::NotYetLoaded(
+            Arc::new(SwfMovie::empty(version)),
+            None,
+            false,
+        ));
+        write.init_event_fired = false;
+        write.complete_event_fired = false;
+        drop(write);
+
+        let unload_evt = EventObject::bare_default_event(context, "unload");
+
+        if let Err(e) = Avm2::dispatch_event(context, unload_evt, (*self).into()) {
+            log::error!(
+                "Encountered AVM2 error when broadcasting `unload` event: {}",
+                e
+            );
+        }
+    }
+
     /// Unwrap this object's loader stream
     pub fn as_loader_stream(&self) -> Option<Ref<LoaderStream<'gc>>> {
         if self.0.read().loaded_stream.is_some() {
@@ -13,12 +13,15 @@ use crate::avm2::Multiname;
 use crate::avm2::{Avm2, Error};
 use crate::context::UpdateContext;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use std::cell::Ref;
 use std::mem::drop;
 use std::rc::Rc;
+use std::sync::Arc;
 use swf::avm2::types::{
-    AbcFile, Index, Method as AbcMethod, Multiname as AbcMultiname, Script as AbcScript,
+    AbcFile, Index, Method as AbcMethod, Multiname as AbcMultiname, Namespace as AbcNamespace,
+    Script as AbcScript, Trait as AbcTrait,
 };
 
 #[derive(Copy, Clone, Collect)]
@@ -47,6 +50,10 @@ pub struct TranslationUnitData<'gc> {
     #[collect(require_static)]
     abc: Rc<AbcFile>,
 
+    /// The SWF movie that the ABC file was embedded in.
+    #[collect(require_static)]
+    movie: Arc<SwfMovie>,
+
     /// All classes loaded from the ABC's class list.
     classes: Vec<Option<GcCell<'gc, Class<'gc>>>>,
 
@@ -86,7 +93,12 @@ pub struct TranslationUnitData<'gc> {
 impl<'gc> TranslationUnit<'gc> {
     /// Construct a new `TranslationUnit` for a given ABC file intended to
     /// execute within a particular domain.
-    pub fn from_abc(abc: AbcFile, domain: Domain<'gc>, mc: MutationContext<'gc, '_>) -> Self {
+    pub fn from_abc(
+        abc: AbcFile,
+        domain: Domain<'gc>,
+        movie: Arc<SwfMovie>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Self {
         let classes = vec![None; abc.classes.len()];
         let methods = vec![None; abc.methods.len()];
         let scripts = vec![None; abc.scripts.len()];
@@ -99,6 +111,7 @@ impl<'gc> TranslationUnit<'gc> {
             TranslationUnitData {
                 domain,
                 abc: Rc::new(abc),
+                movie,
                 classes,
                 methods,
                 scripts,
@@ -113,6 +126,11 @@ impl<'gc> TranslationUnit<'gc> {
         self.0.read().domain
     }
 
+    /// Retrieve the movie that this translation unit was loaded from.
+    pub fn movie(self) -> Arc<SwfMovie> {
+        self.0.read().movie.clone()
+    }
+
     /// Retrieve the underlying `AbcFile` for this translation unit.
     pub fn abc(self) -> Rc<AbcFile> {
         self.0.read().abc.clone()
@@ -461,10 +479,20 @@ impl<'gc> Script<'gc> {
                     .write(activation.context.gc_context)
                     .private_trait_scripts
                     .insert(name, *self);
+            } else if write.domain.has_definition(name) {
+                // Definitions already visible from this domain take priority
+                // over ours, so that a movie loaded into the same or a child
+                // domain can't replace classes of its loader.
+                avm_debug!(
+                    activation.avm2(),
+                    "Definition {} is shadowed by an existing definition",
+                    name.local_name()
+                );
             } else {
                 write.domain.export_definition(
                     newtrait.name(),
                     *self,
+                    is_package_trait(&abc, abc_trait),
                     activation.context.gc_context,
                 )?;
             }
@@ -532,3 +560,24 @@ impl<'gc> Script<'gc> {
         Ok(Ref::map(read, |read| &read.traits[..]))
     }
 }
+
+/// Whether a script trait is named in a package namespace, as opposed to a
+/// user-defined namespace such as `mx_internal`.
+///
+/// Our `Namespace` doesn't keep these apart, so this reads the ABC directly.
+fn is_package_trait(abc: &AbcFile, abc_trait: &AbcTrait) -> bool {
+    let namespace = match (abc_trait.name.0 as usize)
+        .checked_sub(1)
+        .and_then(|index| abc.constant_pool.multinames.get(index))
+    {
+        Some(AbcMultiname::QName { namespace, .. }) => namespace,
+        _ => return false,
+    };
+
+    matches!(
+        (namespace.0 as usize)
+            .checked_sub(1)
+            .and_then(|index| abc.constant_pool.namespaces.get(index)),
+        Some(AbcNamespace::Package(_))
+    )
+}
@@ -2,10 +2,12 @@ use crate::{
     avm1::SoundObject,
     avm2::SoundChannelObject,
     display_object::{self, DisplayObject, MovieClip, TDisplayObject},
+    tag_utils::SwfMovie,
 };
 use downcast_rs::Downcast;
 use gc_arena::Collect;
 use generational_arena::{Arena, Index};
+use std::sync::Arc;
 
 #[cfg(feature = "audio")]
 pub mod decoders;
@@ -452,6 +454,19 @@ impl<'gc> AudioManager<'gc> {
         });
     }
 
+    /// Stop all sounds playing in display objects that belong to the given movie.
+    pub fn stop_sounds_with_movie(&mut self, audio: &mut dyn AudioBackend, movie: &Arc<SwfMovie>) {
+        self.sounds.retain(move |sound| {
+            if let Some(other) = sound.display_object {
+                if Arc::ptr_eq(&other.movie(), movie) {
+                    audio.stop_sound(sound.instance);
+                    return false;
+                }
+            }
+            true
+        });
+    }
+
     pub fn stop_all_sounds(&mut self, audio: &mut dyn AudioBackend) {
         self.sounds.clear();
         audio.stop_all_sounds();
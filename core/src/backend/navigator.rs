@@ -53,6 +53,9 @@ pub struct Request {
     ///
     /// The body consists of data and a mime type.
     body: Option<(Vec<u8>, String)>,
+
+    /// Custom HTTP headers to send with the request, as name/value pairs.
+    headers: Vec<(String, String)>,
 }
 
 impl Request {
@@ -62,6 +65,7 @@ impl Request {
             url,
             method: NavigationMethod::Get,
             body: None,
+            headers: Vec::new(),
         }
     }
 
@@ -71,13 +75,19 @@ impl Request {
             url,
             method: NavigationMethod::Post,
             body,
+            headers: Vec::new(),
         }
     }
 
     /// Construct a request with the given method and data
     #[allow(clippy::self_named_constructors)]
     pub fn request(method: NavigationMethod, url: String, body: Option<(Vec<u8>, String)>) -> Self {
-        Self { url, method, body }
+        Self {
+            url,
+            method,
+            body,
+            headers: Vec::new(),
+        }
     }
 
     /// Retrieve the URL of this request.
@@ -94,6 +104,16 @@ impl Request {
     pub fn body(&self) -> &Option<(Vec<u8>, String)> {
         &self.body
     }
+
+    /// Retrieve the custom HTTP headers of this request.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Set the custom HTTP headers to send with this request.
+    pub fn set_headers(&mut self, headers: Vec<(String, String)>) {
+        self.headers = headers;
+    }
 }
 
 /// A response to a fetch request.
@@ -103,6 +123,9 @@ pub struct Response {
 
     /// The contents of the response body.
     pub body: Vec<u8>,
+
+    /// The `Content-Type` the response was served with, if known.
+    pub content_type: Option<String>,
 }
 
 /// Type alias for pinned, boxed, and owned futures that output a falliable
@@ -293,7 +316,11 @@ impl NavigatorBackend for NullNavigatorBackend {
 
             let body = std::fs::read(path).map_err(|e| Error::FetchError(e.to_string()))?;
 
-            Ok(Response { url, body })
+            Ok(Response {
+                url,
+                body,
+                content_type: None,
+            })
         })
     }
 
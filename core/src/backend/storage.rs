@@ -10,17 +10,46 @@ pub trait StorageBackend {
     }
 
     fn remove_key(&mut self, name: &str);
+
+    /// List the names of all stored values.
+    ///
+    /// Backends that can't enumerate their contents report no values.
+    fn keys(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// The maximum number of bytes that may be stored for a single domain,
+    /// or `None` if storage is unlimited.
+    fn quota(&self) -> Option<usize> {
+        None
+    }
+
+    /// The total number of bytes stored under names starting with `prefix`.
+    fn used_space(&self, prefix: &str) -> usize {
+        self.keys()
+            .iter()
+            .filter(|name| name.starts_with(prefix))
+            .filter_map(|name| self.get_size(name))
+            .sum()
+    }
 }
 
 #[derive(Default)]
 pub struct MemoryStorageBackend {
     map: HashMap<String, Vec<u8>>,
+    quota: Option<usize>,
 }
 
 impl MemoryStorageBackend {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Limit the number of bytes that may be stored for a single domain.
+    pub fn with_quota(mut self, quota: usize) -> Self {
+        self.quota = Some(quota);
+        self
+    }
 }
 
 impl StorageBackend for MemoryStorageBackend {
@@ -36,4 +65,61 @@ impl StorageBackend for MemoryStorageBackend {
     fn remove_key(&mut self, name: &str) {
         self.map.remove(name);
     }
+
+    fn keys(&self) -> Vec<String> {
+        self.map.keys().cloned().collect()
+    }
+
+    fn quota(&self) -> Option<usize> {
+        self.quota
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A backend that relies on the default `keys` implementation.
+    #[derive(Default)]
+    struct OpaqueStorageBackend(HashMap<String, Vec<u8>>);
+
+    impl StorageBackend for OpaqueStorageBackend {
+        fn get(&self, name: &str) -> Option<Vec<u8>> {
+            self.0.get(name).cloned()
+        }
+
+        fn put(&mut self, name: &str, value: &[u8]) -> bool {
+            self.0.insert(name.into(), value.to_vec());
+            true
+        }
+
+        fn remove_key(&mut self, name: &str) {
+            self.0.remove(name);
+        }
+    }
+
+    #[test]
+    fn used_space_counts_prefixed_values() {
+        let mut storage = MemoryStorageBackend::new().with_quota(100);
+        storage.put("example.com/a", &[0; 10]);
+        storage.put("example.com/b", &[0; 20]);
+        storage.put("other.test/a", &[0; 40]);
+
+        assert_eq!(storage.quota(), Some(100));
+        assert_eq!(storage.used_space("example.com/"), 30);
+        assert_eq!(storage.used_space("other.test/"), 40);
+
+        storage.remove_key("example.com/a");
+        assert_eq!(storage.used_space("example.com/"), 20);
+    }
+
+    #[test]
+    fn default_keys_is_empty() {
+        let mut storage = OpaqueStorageBackend::default();
+        storage.put("example.com/a", &[0; 10]);
+
+        assert!(storage.keys().is_empty());
+        assert_eq!(storage.used_space("example.com/"), 0);
+        assert_eq!(storage.quota(), None);
+    }
 }
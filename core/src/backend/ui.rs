@@ -1,9 +1,18 @@
 use crate::events::{KeyCode, PlayerEvent};
+use chrono::{DateTime, Utc};
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
 
 pub type FullscreenError = Cow<'static, str>;
 
+/// The result of a file dialog, resolving once the user has closed it.
+///
+/// The output is `None` if the user cancelled the dialog.
+pub type DialogResultFuture<T> = Pin<Box<dyn Future<Output = Option<T>> + 'static>>;
+
 pub trait UiBackend {
     fn mouse_visible(&self) -> bool;
 
@@ -15,6 +24,10 @@ pub trait UiBackend {
     /// Sets the clipboard to the given content.
     fn set_clipboard_content(&mut self, content: String);
 
+    /// Reads the text content of the clipboard.
+    /// Returns an empty string if the clipboard doesn't hold any text.
+    fn clipboard_content(&mut self) -> String;
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError>;
 
     /// Displays a warning about unsupported content in Ruffle.
@@ -28,6 +41,125 @@ pub trait UiBackend {
 
     // Unused, but kept in case we need it later.
     fn message(&self, message: &str);
+
+    /// Displays a dialog for picking one (or, if `multiple` is set, several)
+    /// files to open.
+    ///
+    /// The dialog must not block the player; its result is reported through
+    /// the returned future instead.
+    fn display_file_open_dialog(
+        &mut self,
+        filters: Vec<FileFilter>,
+        multiple: bool,
+    ) -> DialogResultFuture<Vec<DialogFile>>;
+
+    /// Displays a dialog for choosing where to save a file.
+    ///
+    /// The dialog must not block the player; its result is reported through
+    /// the returned future instead.
+    fn display_file_save_dialog(&mut self, default_name: String) -> DialogResultFuture<DialogFile>;
+
+    /// Reads the contents of a file picked in a file dialog.
+    fn read_file(&mut self, path: &Path) -> std::io::Result<Vec<u8>> {
+        std::fs::read(path)
+    }
+
+    /// Writes to a file picked in a file dialog, replacing its contents.
+    fn write_file(&mut self, path: &Path, data: &[u8]) -> std::io::Result<()> {
+        std::fs::write(path, data)
+    }
+}
+
+/// A filter restricting which files are offered by a file dialog.
+/// Equivalent to AS3 `FileFilter`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileFilter {
+    /// The description displayed to the user, e.g. "Images".
+    pub description: String,
+
+    /// The extensions accepted by this filter, without the leading dot.
+    pub extensions: Vec<String>,
+}
+
+impl FileFilter {
+    /// Builds a filter from a semicolon-separated list of patterns, such as
+    /// `*.jpg;*.png`, as used by `FileFilter.extension`.
+    pub fn new(description: String, patterns: &str) -> Self {
+        let extensions = patterns
+            .split(';')
+            .map(|pattern| {
+                pattern
+                    .trim()
+                    .trim_start_matches('*')
+                    .trim_start_matches('.')
+            })
+            .filter(|extension| !extension.is_empty())
+            .map(str::to_owned)
+            .collect();
+        Self {
+            description,
+            extensions,
+        }
+    }
+
+    /// Whether the given file is accepted by this filter.
+    pub fn matches(&self, path: &Path) -> bool {
+        self.extensions.iter().any(|extension| {
+            extension == "*"
+                || path.extension().map_or(false, |e| {
+                    e.to_string_lossy().eq_ignore_ascii_case(extension)
+                })
+        })
+    }
+}
+
+/// A file picked by the user in a file dialog.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DialogFile {
+    /// The location of the file. This is only meaningful to the UI backend.
+    pub path: PathBuf,
+
+    /// The name of the file, including its extension.
+    pub name: String,
+
+    /// The size of the file in bytes, or 0 if it doesn't exist yet.
+    pub size: u64,
+
+    pub creation_date: Option<DateTime<Utc>>,
+
+    pub modification_date: Option<DateTime<Utc>>,
+}
+
+impl DialogFile {
+    /// Describes the file at `path`, reading its size and dates from the
+    /// file system where possible.
+    pub fn from_path(path: PathBuf) -> Self {
+        let metadata = std::fs::metadata(&path).ok();
+        Self {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            size: metadata.as_ref().map_or(0, |m| m.len()),
+            creation_date: metadata
+                .as_ref()
+                .and_then(|m| m.created().ok())
+                .map(DateTime::from),
+            modification_date: metadata
+                .as_ref()
+                .and_then(|m| m.modified().ok())
+                .map(DateTime::from),
+            path,
+        }
+    }
+
+    /// The extension of the file, including the leading dot.
+    /// Equivalent to AS3 `FileReference.type`.
+    pub fn file_type(&self) -> Option<String> {
+        Path::new(&self.name)
+            .extension()
+            .map(|extension| format!(".{}", extension.to_string_lossy()))
+    }
 }
 
 /// A mouse cursor icon displayed by the Flash Player.
@@ -120,11 +252,29 @@ impl Default for InputManager {
 }
 
 /// UiBackend that does nothing.
-pub struct NullUiBackend {}
+///
+/// File dialogs are answered from a queue of preconfigured paths, and the
+/// clipboard is kept in memory, which makes it usable for headless runs and
+/// tests.
+pub struct NullUiBackend {
+    file_dialog_results: VecDeque<Vec<PathBuf>>,
+    clipboard: String,
+}
 
 impl NullUiBackend {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            file_dialog_results: VecDeque::new(),
+            clipboard: String::new(),
+        }
+    }
+
+    /// Queues the paths to be "picked" by the next file dialog.
+    /// An empty list causes the dialog to be cancelled, as does running out
+    /// of queued results.
+    pub fn with_file_dialog_result(mut self, paths: Vec<PathBuf>) -> Self {
+        self.file_dialog_results.push_back(paths);
+        self
     }
 }
 
@@ -137,7 +287,13 @@ impl UiBackend for NullUiBackend {
 
     fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
 
-    fn set_clipboard_content(&mut self, _content: String) {}
+    fn set_clipboard_content(&mut self, content: String) {
+        self.clipboard = content;
+    }
+
+    fn clipboard_content(&mut self) -> String {
+        self.clipboard.clone()
+    }
 
     fn set_fullscreen(&mut self, _is_full: bool) -> Result<(), FullscreenError> {
         Ok(())
@@ -148,6 +304,35 @@ impl UiBackend for NullUiBackend {
     fn display_root_movie_download_failed_message(&self) {}
 
     fn message(&self, _message: &str) {}
+
+    fn display_file_open_dialog(
+        &mut self,
+        filters: Vec<FileFilter>,
+        multiple: bool,
+    ) -> DialogResultFuture<Vec<DialogFile>> {
+        let mut paths = self.file_dialog_results.pop_front().unwrap_or_default();
+        if !filters.is_empty() {
+            paths.retain(|path| filters.iter().any(|filter| filter.matches(path)));
+        }
+        if !multiple {
+            paths.truncate(1);
+        }
+        let result =
+            (!paths.is_empty()).then(|| paths.into_iter().map(DialogFile::from_path).collect());
+        Box::pin(std::future::ready(result))
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        _default_name: String,
+    ) -> DialogResultFuture<DialogFile> {
+        let result = self
+            .file_dialog_results
+            .pop_front()
+            .and_then(|paths| paths.into_iter().next())
+            .map(DialogFile::from_path);
+        Box::pin(std::future::ready(result))
+    }
 }
 
 impl Default for NullUiBackend {
@@ -155,3 +340,64 @@ impl Default for NullUiBackend {
         NullUiBackend::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    fn names(files: Option<Vec<DialogFile>>) -> Option<Vec<String>> {
+        files.map(|files| files.into_iter().map(|file| file.name).collect())
+    }
+
+    #[test]
+    fn open_dialog_picks_queued_paths() {
+        let mut ui = NullUiBackend::new()
+            .with_file_dialog_result(vec!["a.png".into(), "b.txt".into()])
+            .with_file_dialog_result(vec!["a.png".into(), "b.txt".into()]);
+
+        let single = block_on(ui.display_file_open_dialog(vec![], false));
+        assert_eq!(names(single), Some(vec!["a.png".to_string()]));
+
+        let multiple = block_on(ui.display_file_open_dialog(vec![], true));
+        assert_eq!(
+            names(multiple),
+            Some(vec!["a.png".to_string(), "b.txt".to_string()])
+        );
+    }
+
+    #[test]
+    fn open_dialog_applies_filters() {
+        let mut ui = NullUiBackend::new()
+            .with_file_dialog_result(vec!["a.png".into(), "b.TXT".into()])
+            .with_file_dialog_result(vec!["a.png".into()]);
+        let filters = vec![FileFilter::new("Text".to_string(), "*.txt; *.md")];
+
+        let files = block_on(ui.display_file_open_dialog(filters.clone(), true));
+        assert_eq!(names(files), Some(vec!["b.TXT".to_string()]));
+
+        // Nothing left after filtering counts as a cancelled dialog.
+        let files = block_on(ui.display_file_open_dialog(filters, true));
+        assert_eq!(names(files), None);
+    }
+
+    #[test]
+    fn dialogs_cancel_without_queued_paths() {
+        let mut ui = NullUiBackend::new().with_file_dialog_result(vec![]);
+        assert_eq!(block_on(ui.display_file_open_dialog(vec![], false)), None);
+        assert_eq!(
+            block_on(ui.display_file_save_dialog("a.txt".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn save_dialog_picks_first_queued_path() {
+        let mut ui = NullUiBackend::new()
+            .with_file_dialog_result(vec!["dir/saved.bin".into(), "other.bin".into()]);
+        let file = block_on(ui.display_file_save_dialog("a.txt".to_string())).unwrap();
+        assert_eq!(file.name, "saved.bin");
+        assert_eq!(file.path, PathBuf::from("dir/saved.bin"));
+        assert_eq!(file.file_type().as_deref(), Some(".bin"));
+    }
+}
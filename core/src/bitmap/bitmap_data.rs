@@ -13,7 +13,9 @@ use ruffle_render::color_transform::ColorTransform;
 use ruffle_render::commands::{CommandHandler, CommandList};
 use ruffle_render::transform::Transform;
 use std::ops::Range;
+use std::str::FromStr;
 use swf::BlendMode;
+use thiserror::Error;
 
 /// An implementation of the Lehmer/Park-Miller random number generator
 /// Uses the fixed parameters m = 2,147,483,647 and a = 16,807
@@ -38,6 +40,13 @@ impl LehmerRng {
     }
 }
 
+/// The taps of a maximal-length Galois linear feedback shift register for each bit width,
+/// up to the number of bits needed to index every pixel of the largest `BitmapData`.
+const LFSR_TAPS: [u32; 25] = [
+    0, 0x1, 0x3, 0x6, 0xC, 0x14, 0x30, 0x60, 0xB8, 0x110, 0x240, 0x500, 0x829, 0x100D, 0x2015,
+    0x6000, 0xD008, 0x12000, 0x20400, 0x40023, 0x90000, 0x140000, 0x300000, 0x420000, 0xE10000,
+];
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Collect)]
 #[collect(no_drop)]
 pub struct Color(i32);
@@ -157,12 +166,82 @@ bitflags! {
     }
 }
 
+/// The comparison used by `BitmapData.threshold`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ThresholdOperation {
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl ThresholdOperation {
+    fn matches(self, value: u32, threshold: u32) -> bool {
+        match self {
+            Self::LessThan => value < threshold,
+            Self::LessThanOrEqual => value <= threshold,
+            Self::GreaterThan => value > threshold,
+            Self::GreaterThanOrEqual => value >= threshold,
+            Self::Equal => value == threshold,
+            Self::NotEqual => value != threshold,
+        }
+    }
+}
+
+impl FromStr for ThresholdOperation {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "<" => Self::LessThan,
+            "<=" => Self::LessThanOrEqual,
+            ">" => Self::GreaterThan,
+            ">=" => Self::GreaterThanOrEqual,
+            "==" => Self::Equal,
+            "!=" => Self::NotEqual,
+            _ => return Err(()),
+        })
+    }
+}
+
+/// An image format that a `BitmapData` can be compressed to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitmapEncoding {
+    Png {
+        fast_compression: bool,
+    },
+
+    /// `quality` ranges from 1 to 100.
+    Jpeg {
+        quality: u8,
+    },
+}
+
+#[derive(Debug, Error)]
+pub enum EncodeError {
+    #[error("PNG encoding failed: {0}")]
+    Png(#[from] png::EncodingError),
+
+    #[error("JPEG encoding failed: {0}")]
+    Jpeg(#[from] jpeg_encoder::EncodingError),
+
+    #[error("Image of {0}x{1} pixels is too large for JPEG")]
+    TooLargeForJpeg(u32, u32),
+}
+
 #[derive(Clone, Collect, Default)]
 #[collect(no_drop)]
 pub struct BitmapData<'gc> {
     /// The pixels in the bitmap, stored as a array of pre-multiplied ARGB colour values
     pixels: Vec<Color>,
     dirty: bool,
+
+    /// Incremented whenever the pixels change, so that cached renderings of display objects
+    /// showing this bitmap can tell when they've gone stale.
+    version: u32,
+
     width: u32,
     height: u32,
     transparency: bool,
@@ -208,6 +287,7 @@ impl<'gc> BitmapData<'gc> {
         BitmapData {
             pixels: Vec::new(),
             dirty: false,
+            version: 0,
             width: 0,
             height: 0,
             transparency: false,
@@ -225,7 +305,7 @@ impl<'gc> BitmapData<'gc> {
             Color(fill_color).to_premultiplied_alpha(self.transparency());
             width as usize * height as usize
         ];
-        self.dirty = true;
+        self.set_changed();
     }
 
     pub fn check_valid(
@@ -255,6 +335,7 @@ impl<'gc> BitmapData<'gc> {
         self.bitmap_handle = None;
         // There's no longer a handle to update
         self.dirty = false;
+        self.version = self.version.wrapping_add(1);
         self.disposed = true;
     }
 
@@ -292,6 +373,16 @@ impl<'gc> BitmapData<'gc> {
         self.dirty = dirty;
     }
 
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Marks the pixels as changed, both for the texture and for any cached renderings.
+    fn set_changed(&mut self) {
+        self.dirty = true;
+        self.version = self.version.wrapping_add(1);
+    }
+
     pub fn pixels(&self) -> &[Color] {
         &self.pixels
     }
@@ -301,7 +392,7 @@ impl<'gc> BitmapData<'gc> {
         self.height = height;
         self.transparency = transparency;
         self.pixels = pixels;
-        self.dirty = true;
+        self.set_changed();
     }
 
     pub fn pixels_rgba(&self) -> Vec<u8> {
@@ -363,7 +454,7 @@ impl<'gc> BitmapData<'gc> {
     pub fn set_pixel32_raw(&mut self, x: u32, y: u32, color: Color) {
         let width = self.width();
         self.pixels[(x + y * width) as usize] = color;
-        self.dirty = true;
+        self.set_changed();
     }
 
     pub fn set_pixel32(&mut self, x: i32, y: i32, color: Color) {
@@ -376,6 +467,144 @@ impl<'gc> BitmapData<'gc> {
         }
     }
 
+    /// The part of the given rectangle that lies within this bitmap, as
+    /// `(x_min, y_min, x_max, y_max)` with exclusive maximums.
+    fn clip_rect(&self, x: i32, y: i32, width: i32, height: i32) -> (u32, u32, u32, u32) {
+        let x_min = x.clamp(0, self.width() as i32) as u32;
+        let y_min = y.clamp(0, self.height() as i32) as u32;
+        let x_max = x.saturating_add(width.max(0)).clamp(0, self.width() as i32) as u32;
+        let y_max = y
+            .saturating_add(height.max(0))
+            .clamp(0, self.height() as i32) as u32;
+        (x_min, y_min, x_max.max(x_min), y_max.max(y_min))
+    }
+
+    /// The non-premultiplied ARGB values of the pixels within a rectangle, row by row.
+    /// Only the part of the rectangle that lies within this bitmap is returned.
+    pub fn get_pixels_rect(&self, x: i32, y: i32, width: i32, height: i32) -> Vec<u32> {
+        let (x_min, y_min, x_max, y_max) = self.clip_rect(x, y, width, height);
+        let mut pixels = Vec::with_capacity(((x_max - x_min) * (y_max - y_min)) as usize);
+        for y in y_min..y_max {
+            for x in x_min..x_max {
+                pixels.push(self.get_pixel32(x as i32, y as i32).into());
+            }
+        }
+        pixels
+    }
+
+    /// Sets the pixels within a rectangle, row by row, from non-premultiplied ARGB values.
+    /// Only the part of the rectangle that lies within this bitmap is set.
+    ///
+    /// Returns `false` if `pixels` ran out before the whole rectangle was set.
+    pub fn set_pixels_rect(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        pixels: impl IntoIterator<Item = u32>,
+    ) -> bool {
+        let (x_min, y_min, x_max, y_max) = self.clip_rect(x, y, width, height);
+        let mut pixels = pixels.into_iter();
+        for y in y_min..y_max {
+            for x in x_min..x_max {
+                match pixels.next() {
+                    Some(color) => self.set_pixel32(x as i32, y as i32, Color(color as i32)),
+                    None => return false,
+                }
+            }
+        }
+        true
+    }
+
+    /// Compresses the pixels within a rectangle into an image file.
+    /// Only the part of the rectangle that lies within this bitmap is encoded.
+    pub fn encode(
+        &self,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        encoding: BitmapEncoding,
+    ) -> Result<Vec<u8>, EncodeError> {
+        let (x_min, y_min, x_max, y_max) = self.clip_rect(x, y, width, height);
+        let (encoded_width, encoded_height) = (x_max - x_min, y_max - y_min);
+        let mut bytes = vec![];
+
+        match encoding {
+            BitmapEncoding::Png { fast_compression } => {
+                // PNG stores colors without premultiplied alpha, so they're converted back.
+                let pixels = self.get_pixels_rect(x, y, width, height);
+                let (color_type, data): (_, Vec<u8>) = if self.transparency() {
+                    let data = pixels
+                        .into_iter()
+                        .flat_map(|argb| {
+                            let [alpha, red, green, blue] = argb.to_be_bytes();
+                            [red, green, blue, alpha]
+                        })
+                        .collect();
+                    (png::ColorType::Rgba, data)
+                } else {
+                    let data = pixels
+                        .into_iter()
+                        .flat_map(|argb| {
+                            let [_, red, green, blue] = argb.to_be_bytes();
+                            [red, green, blue]
+                        })
+                        .collect();
+                    (png::ColorType::Rgb, data)
+                };
+
+                let mut encoder = png::Encoder::new(&mut bytes, encoded_width, encoded_height);
+                encoder.set_color(color_type);
+                encoder.set_depth(png::BitDepth::Eight);
+                encoder.set_compression(if fast_compression {
+                    png::Compression::Fast
+                } else {
+                    png::Compression::Best
+                });
+                encoder.write_header()?.write_image_data(&data)?;
+            }
+            BitmapEncoding::Jpeg { quality } => {
+                // JPEG has no alpha channel. Using the premultiplied colors makes transparent
+                // pixels appear on black, rather than showing whatever color they hide.
+                let mut data = Vec::with_capacity((encoded_width * encoded_height * 3) as usize);
+                for y in y_min..y_max {
+                    for x in x_min..x_max {
+                        let color = self.get_pixel_raw(x, y).unwrap_or_else(|| 0.into());
+                        data.extend_from_slice(&[color.red(), color.green(), color.blue()]);
+                    }
+                }
+
+                let too_large = || EncodeError::TooLargeForJpeg(encoded_width, encoded_height);
+                let jpeg_width = u16::try_from(encoded_width).map_err(|_| too_large())?;
+                let jpeg_height = u16::try_from(encoded_height).map_err(|_| too_large())?;
+                jpeg_encoder::Encoder::new(&mut bytes, quality.clamp(1, 100)).encode(
+                    &data,
+                    jpeg_width,
+                    jpeg_height,
+                    jpeg_encoder::ColorType::Rgb,
+                )?;
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    /// The number of pixels within a rectangle with each value of each channel, in the
+    /// order red, green, blue and alpha.
+    pub fn histogram(&self, x: i32, y: i32, width: i32, height: i32) -> [[u32; 256]; 4] {
+        let mut histogram = [[0; 256]; 4];
+        for color in self.get_pixels_rect(x, y, width, height) {
+            let color = Color(color as i32);
+            histogram[0][color.red() as usize] += 1;
+            histogram[1][color.green() as usize] += 1;
+            histogram[2][color.blue() as usize] += 1;
+            histogram[3][color.alpha() as usize] += 1;
+        }
+        histogram
+    }
+
     pub fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: Color) {
         for x_offset in 0..width {
             for y_offset in 0..height {
@@ -936,6 +1165,186 @@ impl<'gc> BitmapData<'gc> {
         }
     }
 
+    /// Tests the pixels of `source_bitmap` within `src_rect` against `threshold`, replacing
+    /// the matching pixels at `dest_point` with `color`. The rest are replaced with the source
+    /// pixels if `copy_source` is set, and left alone otherwise.
+    ///
+    /// Both the pixels and the threshold are masked with `mask` before being compared as
+    /// unsigned, non-premultiplied ARGB values. Returns the number of matching pixels.
+    #[allow(clippy::too_many_arguments)]
+    pub fn threshold(
+        &mut self,
+        source_bitmap: &Self,
+        src_rect: (i32, i32, i32, i32),
+        dest_point: (i32, i32),
+        operation: ThresholdOperation,
+        threshold: u32,
+        color: u32,
+        mask: u32,
+        copy_source: bool,
+    ) -> u32 {
+        let (src_min_x, src_min_y, src_width, src_height) = src_rect;
+        let (dest_min_x, dest_min_y) = dest_point;
+        let color = Color(color as i32).to_premultiplied_alpha(self.transparency);
+        let mut count = 0;
+
+        for src_y in src_min_y..(src_min_y + src_height) {
+            for src_x in src_min_x..(src_min_x + src_width) {
+                let dest_x = src_x - src_min_x + dest_min_x;
+                let dest_y = src_y - src_min_y + dest_min_y;
+
+                if !self.is_point_in_bounds(dest_x, dest_y)
+                    || !source_bitmap.is_point_in_bounds(src_x, src_y)
+                {
+                    continue;
+                }
+
+                let source_color = source_bitmap
+                    .get_pixel_raw(src_x as u32, src_y as u32)
+                    .unwrap();
+                let value = u32::from(source_color.to_un_multiplied_alpha());
+
+                if operation.matches(value & mask, threshold & mask) {
+                    count += 1;
+                    self.set_pixel32_raw(dest_x as u32, dest_y as u32, color);
+                } else if copy_source {
+                    let source_color = if self.transparency {
+                        source_color
+                    } else {
+                        source_color.with_alpha(0xFF)
+                    };
+                    self.set_pixel32_raw(dest_x as u32, dest_y as u32, source_color);
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Whether the alpha of the pixel at `test_point` is at least `alpha_threshold`.
+    /// The point is relative to the top-left corner of this bitmap.
+    pub fn hit_test_point(&self, alpha_threshold: u32, test_point: (i32, i32)) -> bool {
+        let (x, y) = test_point;
+        self.is_point_in_bounds(x, y)
+            && u32::from(self.get_pixel_raw(x as u32, y as u32).unwrap().alpha()) >= alpha_threshold
+    }
+
+    /// Whether the alpha of any pixel in the given rectangle is at least `alpha_threshold`.
+    /// The rectangle is relative to the top-left corner of this bitmap.
+    pub fn hit_test_rectangle(
+        &self,
+        alpha_threshold: u32,
+        top_left: (i32, i32),
+        size: (i32, i32),
+    ) -> bool {
+        let x_min = top_left.0.max(0);
+        let y_min = top_left.1.max(0);
+        let x_max = (top_left.0 + size.0).min(self.width() as i32);
+        let y_max = (top_left.1 + size.1).min(self.height() as i32);
+
+        (y_min..y_max).any(|y| (x_min..x_max).any(|x| self.hit_test_point(alpha_threshold, (x, y))))
+    }
+
+    /// Whether this bitmap, placed at `self_point`, overlaps `test` placed at `test_point`
+    /// on any pixel whose alpha passes the threshold of both bitmaps.
+    pub fn hit_test_bitmapdata(
+        &self,
+        self_point: (i32, i32),
+        self_threshold: u32,
+        test: &Self,
+        test_point: (i32, i32),
+        test_threshold: u32,
+    ) -> bool {
+        let x_min = self_point.0.max(test_point.0);
+        let y_min = self_point.1.max(test_point.1);
+        let x_max = (self_point.0 + self.width() as i32).min(test_point.0 + test.width() as i32);
+        let y_max = (self_point.1 + self.height() as i32).min(test_point.1 + test.height() as i32);
+
+        (y_min..y_max).any(|y| {
+            (x_min..x_max).any(|x| {
+                self.hit_test_point(self_threshold, (x - self_point.0, y - self_point.1))
+                    && test.hit_test_point(test_threshold, (x - test_point.0, y - test_point.1))
+            })
+        })
+    }
+
+    /// Copies `num_pixels` pixels within `src_rect` of `source_bitmap` to `dest_point`, in
+    /// a pseudo-random order determined by `random_seed`. If `source_bitmap` is `None`, this
+    /// bitmap is used as the source, and the pixels are replaced with `fill_color` instead.
+    ///
+    /// The order comes from a linear feedback shift register, which visits every pixel
+    /// exactly once before repeating. Returns the seed to pass to the next call to continue
+    /// the dissolve where this one stopped.
+    pub fn pixel_dissolve(
+        &mut self,
+        source_bitmap: Option<&Self>,
+        src_rect: (i32, i32, i32, i32),
+        dest_point: (i32, i32),
+        random_seed: i32,
+        num_pixels: i32,
+        fill_color: u32,
+    ) -> i32 {
+        let (src_min_x, src_min_y, src_width, src_height) = src_rect;
+        let (dest_min_x, dest_min_y) = dest_point;
+        if src_width <= 0 || src_height <= 0 {
+            return random_seed;
+        }
+        let pixel_count = src_width as u32 * src_height as u32;
+
+        // The register holds values from 1 to `2^bits - 1`, each standing for one pixel.
+        let bits = (32 - pixel_count.leading_zeros()) as usize;
+        let taps = match LFSR_TAPS.get(bits) {
+            Some(taps) => *taps,
+            None => return random_seed,
+        };
+        let register_mask = ((1u64 << bits) - 1) as u32;
+        let mut register = (random_seed as u32) & register_mask;
+        if register == 0 || register > pixel_count {
+            register = register % pixel_count + 1;
+        }
+
+        let fill_color = Color(fill_color as i32).to_premultiplied_alpha(self.transparency);
+        for _ in 0..num_pixels.clamp(0, pixel_count as i32) {
+            let index = register - 1;
+            let src_x = src_min_x + (index % src_width as u32) as i32;
+            let src_y = src_min_y + (index / src_width as u32) as i32;
+            let dest_x = src_x - src_min_x + dest_min_x;
+            let dest_y = src_y - src_min_y + dest_min_y;
+
+            if self.is_point_in_bounds(dest_x, dest_y) {
+                let color = match source_bitmap {
+                    Some(source_bitmap) => source_bitmap
+                        .get_pixel_raw(src_x as u32, src_y as u32)
+                        .map(|color| {
+                            if self.transparency {
+                                color
+                            } else {
+                                color.with_alpha(0xFF)
+                            }
+                        }),
+                    None => Some(fill_color),
+                };
+                if let Some(color) = color {
+                    self.set_pixel32_raw(dest_x as u32, dest_y as u32, color);
+                }
+            }
+
+            // Skip the values that don't stand for a pixel.
+            loop {
+                register = if register & 1 != 0 {
+                    (register >> 1) ^ taps
+                } else {
+                    register >> 1
+                };
+                if register <= pixel_count {
+                    break;
+                }
+            }
+        }
+
+        register as i32
+    }
+
     // Updates the data stored with our `BitmapHandle` if this `BitmapData`
     // is dirty
     pub fn update_dirty_texture(&mut self, context: &mut RenderContext) {
@@ -995,6 +1404,7 @@ impl<'gc> BitmapData<'gc> {
             Some(Self {
                 pixels,
                 dirty: false,
+                version: 0,
                 width: bitmap.width,
                 height: bitmap.height,
                 transparency: true,
@@ -1040,6 +1450,9 @@ impl<'gc> BitmapData<'gc> {
             library: &context.library,
             transform_stack: &mut transform_stack,
             is_offscreen: true,
+            cull_bounds: Default::default(),
+            dirty_region: None,
+            known_viewport_bounds: Default::default(),
             stage: context.stage,
             clip_depth_stack: vec![],
             allow_mask: true,
@@ -1073,7 +1486,11 @@ impl<'gc> BitmapData<'gc> {
             render_context.commands
         } else {
             let mut commands = CommandList::new();
-            commands.blend(&render_context.commands, blend_mode);
+            commands.blend(
+                &render_context.commands,
+                blend_mode,
+                &ColorTransform::IDENTITY,
+            );
             commands
         };
 
@@ -1127,3 +1544,214 @@ fn copy_pixels_to_bitmapdata(write: &mut BitmapData, bytes: &[u8]) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitmap(width: u32, height: u32, transparency: bool, fill_color: u32) -> BitmapData<'static> {
+        let mut bitmap = BitmapData::default();
+        bitmap.init_pixels(width, height, transparency, fill_color as i32);
+        bitmap
+    }
+
+    fn count_pixels(bitmap: &BitmapData<'_>, color: u32) -> usize {
+        let color = Color(color as i32);
+        bitmap
+            .pixels()
+            .iter()
+            .filter(|pixel| **pixel == color)
+            .count()
+    }
+
+    #[test]
+    fn lfsr_taps_have_maximal_length() {
+        for (bits, taps) in LFSR_TAPS.iter().enumerate().skip(1).take(20) {
+            let mut register = 1u32;
+            let mut period = 0u32;
+            loop {
+                register = if register & 1 != 0 {
+                    (register >> 1) ^ taps
+                } else {
+                    register >> 1
+                };
+                period += 1;
+                if register == 1 {
+                    break;
+                }
+            }
+            assert_eq!(period, (1 << bits) - 1, "{bits} bits");
+        }
+    }
+
+    #[test]
+    fn pixel_dissolve_visits_every_pixel_once() {
+        const FILL: u32 = 0xFFFF0000;
+        let mut target = bitmap(7, 5, false, 0xFF000000);
+        let mut seed = 12345;
+        for dissolved in 1..=35 {
+            seed = target.pixel_dissolve(None, (0, 0, 7, 5), (0, 0), seed, 1, FILL);
+            assert_eq!(count_pixels(&target, FILL), dissolved);
+        }
+
+        // Dissolving everything at once reaches the same seed.
+        let mut target = bitmap(7, 5, false, 0xFF000000);
+        assert_eq!(
+            target.pixel_dissolve(None, (0, 0, 7, 5), (0, 0), 12345, 35, FILL),
+            seed
+        );
+        assert_eq!(count_pixels(&target, FILL), 35);
+    }
+
+    #[test]
+    fn pixel_dissolve_copies_from_source() {
+        let source = bitmap(4, 4, true, 0x80FF0000);
+        let mut target = bitmap(4, 4, false, 0xFF000000);
+        target.pixel_dissolve(Some(&source), (1, 1, 2, 2), (0, 0), 1, 4, 0);
+        // The source's alpha is dropped by an opaque target.
+        assert_eq!(count_pixels(&target, 0xFF000000), 12);
+        assert_eq!(
+            target.get_pixel_raw(1, 1),
+            Some(source.pixels()[0].with_alpha(0xFF))
+        );
+        assert_eq!(
+            target.get_pixel_raw(2, 2),
+            Some(Color(0xFF000000u32 as i32))
+        );
+    }
+
+    #[test]
+    fn threshold_replaces_matching_pixels() {
+        let mut source = bitmap(3, 1, true, 0xFF000000);
+        source.set_pixel32_raw(1, 0, Color(0xFF000080u32 as i32));
+        source.set_pixel32_raw(2, 0, Color(0xFF0000FFu32 as i32));
+        let mut target = bitmap(3, 1, true, 0);
+
+        let matched = target.threshold(
+            &source,
+            (0, 0, 3, 1),
+            (0, 0),
+            ThresholdOperation::GreaterThanOrEqual,
+            0x80,
+            0xFF00FF00,
+            0xFF,
+            false,
+        );
+        assert_eq!(matched, 2);
+        assert_eq!(target.get_pixel_raw(0, 0), Some(Color(0)));
+        assert_eq!(
+            target.get_pixel_raw(2, 0),
+            Some(Color(0xFF00FF00u32 as i32))
+        );
+
+        // Pixels that don't match are copied from the source when asked to.
+        let matched = target.threshold(
+            &source,
+            (0, 0, 3, 1),
+            (0, 0),
+            ThresholdOperation::Equal,
+            0x80,
+            0xFF00FF00,
+            0xFF,
+            true,
+        );
+        assert_eq!(matched, 1);
+        assert_eq!(
+            target.get_pixel_raw(0, 0),
+            Some(Color(0xFF000000u32 as i32))
+        );
+        assert_eq!(
+            target.get_pixel_raw(2, 0),
+            Some(Color(0xFF0000FFu32 as i32))
+        );
+    }
+
+    #[test]
+    fn hit_test_uses_alpha_threshold() {
+        let mut first = bitmap(4, 4, true, 0);
+        first.set_pixel32_raw(3, 3, Color(0x80FFFFFFu32 as i32));
+        assert!(first.hit_test_point(0x80, (3, 3)));
+        assert!(!first.hit_test_point(0x81, (3, 3)));
+        assert!(!first.hit_test_point(0, (4, 3)));
+        assert!(first.hit_test_rectangle(0x80, (2, 2), (2, 2)));
+        assert!(!first.hit_test_rectangle(0x80, (0, 0), (3, 3)));
+
+        // Only the bottom-right pixel of `first` overlaps `second`.
+        let second = bitmap(4, 4, true, 0xFF000000);
+        assert!(first.hit_test_bitmapdata((0, 0), 0x80, &second, (3, 3), 0xFF));
+        assert!(!first.hit_test_bitmapdata((0, 0), 0x80, &second, (4, 4), 0xFF));
+        assert!(!first.hit_test_bitmapdata((0, 0), 0x81, &second, (3, 3), 0xFF));
+    }
+
+    #[test]
+    fn pixels_rect_is_clipped_to_bitmap() {
+        let mut target = bitmap(3, 2, true, 0);
+        // Only the right column of the rectangle lies within the bitmap, and only that
+        // many pixels are read.
+        assert!(target.set_pixels_rect(-1, 0, 2, 2, [0xFF0000FF, 0xFF000000]));
+        assert_eq!(target.get_pixels_rect(-5, -5, 20, 20).len(), 6);
+        assert_eq!(target.get_pixels_rect(0, 0, 1, 2), [0xFF0000FF, 0xFF000000]);
+        assert_eq!(target.get_pixels_rect(0, 0, -1, 2), []);
+
+        // Running out of pixels leaves the rest alone.
+        assert!(!target.set_pixels_rect(1, 0, 2, 2, [0xFFFFFFFF; 3]));
+        assert_eq!(
+            target.get_pixels_rect(0, 0, 3, 2),
+            [0xFF0000FF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFF000000, 0xFFFFFFFF, 0]
+        );
+    }
+
+    #[test]
+    fn histogram_counts_channels_in_rect() {
+        let mut target = bitmap(2, 2, true, 0xFF102030);
+        target.set_pixel32(1, 1, Color(0x00000000));
+        let histogram = target.histogram(0, 0, 2, 3);
+        assert_eq!(histogram[0][0x10], 3);
+        assert_eq!(histogram[1][0x20], 3);
+        assert_eq!(histogram[2][0x30], 3);
+        assert_eq!(histogram[3][0xFF], 3);
+        for channel in histogram {
+            assert_eq!(channel[0], 1);
+            assert_eq!(channel.iter().sum::<u32>(), 4);
+        }
+    }
+
+    #[test]
+    fn png_round_trip_keeps_partial_alpha() {
+        let mut source = bitmap(3, 2, true, 0);
+        let colors = [
+            0x80FF8000, 0x40123456, 0xFFABCDEF, 0x01FFFFFF, 0, 0xFF000000,
+        ];
+        assert!(source.set_pixels_rect(0, 0, 3, 2, colors));
+        let encoding = BitmapEncoding::Png {
+            fast_compression: false,
+        };
+        let bytes = source.encode(1, 0, 5, 5, encoding).unwrap();
+
+        let decoder = png::Decoder::new(&bytes[..]);
+        let mut reader = decoder.read_info().unwrap();
+        let mut data = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut data).unwrap();
+        assert_eq!((info.width, info.height), (2, 2));
+        assert_eq!(info.color_type, png::ColorType::Rgba);
+
+        // The colors come back as `getPixel32` sees them, without premultiplied alpha.
+        let expected: Vec<u8> = source
+            .get_pixels_rect(1, 0, 2, 2)
+            .into_iter()
+            .flat_map(|argb| {
+                let [alpha, red, green, blue] = argb.to_be_bytes();
+                [red, green, blue, alpha]
+            })
+            .collect();
+        assert_eq!(data, expected);
+
+        // Partial alpha survives, without darkening the colors it applies to.
+        let alphas: Vec<u8> = data.chunks(4).map(|pixel| pixel[3]).collect();
+        assert_eq!(alphas, [0x40, 0xFF, 0, 0xFF]);
+        for (channel, expected) in data[..3].iter().zip([0x12, 0x34, 0x56]) {
+            assert!(channel.abs_diff(expected) <= 2, "{channel} != {expected}");
+        }
+        assert_eq!(&data[4..8], [0xAB, 0xCD, 0xEF, 0xFF]);
+    }
+}
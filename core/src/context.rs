@@ -12,7 +12,10 @@ use crate::backend::{
     ui::{InputManager, UiBackend},
 };
 use crate::context_menu::ContextMenuState;
-use crate::display_object::{EditText, InteractiveObject, MovieClip, SoundTransform, Stage};
+use crate::dirty_region::DirtyRegion;
+use crate::display_object::{
+    DisplayObjectPtr, EditText, InteractiveObject, MovieClip, SoundTransform, Stage,
+};
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
 use crate::frame_lifecycle::FramePhase;
@@ -20,6 +23,7 @@ use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::player::Player;
 use crate::prelude::*;
+use crate::sandbox::SecuritySandbox;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::timer::Timers;
 use core::fmt;
@@ -124,6 +128,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The system properties
     pub system: &'a mut SystemProperties,
 
+    /// The cross-domain security sandbox.
+    pub security_sandbox: &'a mut SecuritySandbox,
+
     /// The current instance ID. Used to generate default `instanceN` names.
     pub instance_counter: &'a mut i32,
 
@@ -322,6 +329,7 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             player: self.player.clone(),
             load_manager: self.load_manager,
             system: self.system,
+            security_sandbox: self.security_sandbox,
             instance_counter: self.instance_counter,
             avm1_shared_objects: self.avm1_shared_objects,
             avm2_shared_objects: self.avm2_shared_objects,
@@ -444,6 +452,19 @@ pub struct RenderContext<'a, 'gc, 'gc_context> {
     /// Whether we're rendering offscreen. This can disable some logic like Ruffle-side render culling
     pub is_offscreen: bool,
 
+    /// The visible area of the viewport, in twips. Objects entirely outside of it aren't drawn,
+    /// unless rendering offscreen.
+    pub cull_bounds: BoundingBox,
+
+    /// Finds the parts of the viewport that changed since the last frame, if enabled.
+    /// Always `None` when rendering offscreen.
+    pub dirty_region: Option<&'a mut DirtyRegion>,
+
+    /// The viewport bounds of objects that were found along with those of an ancestor,
+    /// and the matrix they were found with. This saves finding them again when the
+    /// objects themselves are rendered.
+    pub known_viewport_bounds: HashMap<*const DisplayObjectPtr, (Matrix, Option<BoundingBox>)>,
+
     /// The current player's stage (including all loaded levels)
     pub stage: Stage<'gc>,
 
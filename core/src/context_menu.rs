@@ -6,8 +6,10 @@
 
 use crate::avm1;
 use crate::avm2;
+use crate::display_object::EditText;
 use crate::display_object::Stage;
 use crate::display_object::TDisplayObject;
+use crate::events::TextControlCode;
 use gc_arena::Collect;
 use serde::Serialize;
 
@@ -80,6 +82,44 @@ impl<'gc> ContextMenuState<'gc> {
             );
         }
     }
+
+    /// Adds the editing commands of the given text field.
+    pub fn build_text_items(&mut self, text: EditText<'gc>) {
+        let is_editable = text.is_editable();
+        let has_selection = text
+            .selection()
+            .map_or(false, |selection| !selection.is_caret());
+        let can_copy = has_selection && !text.is_password();
+
+        let items = [
+            ("Cut", can_copy && is_editable, TextControlCode::Cut),
+            ("Copy", can_copy, TextControlCode::Copy),
+            ("Paste", is_editable, TextControlCode::Paste),
+            (
+                "Delete",
+                has_selection && is_editable,
+                TextControlCode::Delete,
+            ),
+            (
+                "Select All",
+                text.is_selectable(),
+                TextControlCode::SelectAll,
+            ),
+        ];
+        for (caption, enabled, code) in items {
+            self.push(
+                ContextMenuItem {
+                    enabled,
+                    // Separate the editing commands from the other items.
+                    separator_before: code == TextControlCode::Cut
+                        || code == TextControlCode::SelectAll,
+                    caption: caption.to_string(),
+                    checked: false,
+                },
+                ContextMenuCallback::TextControl { code, text },
+            );
+        }
+    }
 }
 
 #[derive(Collect, Clone, Serialize)]
@@ -110,6 +150,11 @@ pub enum ContextMenuCallback<'gc> {
     Avm2 {
         item: avm2::Object<'gc>,
     },
+    TextControl {
+        #[collect(require_static)]
+        code: TextControlCode,
+        text: EditText<'gc>,
+    },
 }
 
 pub struct BuiltInItemFlags {
@@ -0,0 +1,267 @@
+//! Tracking of the parts of the viewport that changed between frames.
+
+use crate::display_object::DisplayObjectPtr;
+use ruffle_render::bounding_box::BoundingBox;
+use ruffle_render::color_transform::ColorTransform;
+use ruffle_render::matrix::Matrix;
+use std::collections::HashMap;
+use swf::{BlendMode, Color, Rectangle, Twips};
+
+/// The most rectangles handed to the renderer for a single frame.
+/// Beyond this, they're merged into one.
+const MAX_DIRTY_RECTS: usize = 8;
+
+/// How a display object was drawn on a frame.
+///
+/// If anything but `bounds` changes between frames, so may every pixel within the old and
+/// new bounds. Changes to `bounds` alone come from children, which are recorded themselves.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderRecord {
+    /// The bounds of the object and its children on the viewport, in twips.
+    pub bounds: BoundingBox,
+
+    /// The bounds of what the object draws itself, without its children.
+    pub own_bounds: BoundingBox,
+
+    /// The concatenated matrix of the object, including the stage's view matrix.
+    pub matrix: Matrix,
+
+    /// The concatenated color transform of the object.
+    pub color_transform: ColorTransform,
+
+    pub blend_mode: BlendMode,
+
+    pub scroll_rect: Option<Rectangle<Twips>>,
+
+    /// The matrix from the object's mask to the viewport, if it has a mask.
+    pub mask_matrix: Option<Matrix>,
+
+    /// The version of the pixels shown by a `Bitmap`.
+    pub bitmap_data_version: Option<u32>,
+}
+
+impl RenderRecord {
+    /// Whether the object looks the same in both records, ignoring its children.
+    fn looks_same(&self, other: &Self) -> bool {
+        self.own_bounds == other.own_bounds
+            && self.matrix == other.matrix
+            && self.color_transform == other.color_transform
+            && self.blend_mode == other.blend_mode
+            && self.scroll_rect == other.scroll_rect
+            && self.mask_matrix == other.mask_matrix
+            && self.bitmap_data_version == other.bitmap_data_version
+    }
+}
+
+/// Everything drawn on a frame that isn't part of any display object.
+#[derive(Clone, Debug, PartialEq)]
+struct FrameSettings {
+    background: Color,
+    viewport: BoundingBox,
+    view_matrix: Matrix,
+}
+
+/// Finds the regions of the viewport that need to be redrawn, by comparing how each
+/// display object is drawn on this frame with how it was drawn on the last one.
+///
+/// Objects that are drawn for the first time, disappear, or change in any way mark both
+/// their old and new bounds as dirty.
+#[derive(Debug, Default)]
+pub struct DirtyRegion {
+    /// The objects drawn on the last frame.
+    previous: HashMap<*const DisplayObjectPtr, RenderRecord>,
+
+    /// The objects drawn on this frame so far.
+    current: HashMap<*const DisplayObjectPtr, RenderRecord>,
+
+    /// The dirty rectangles found so far, in twips on the viewport.
+    rects: Vec<BoundingBox>,
+
+    /// The settings of the last frame, or `None` if it has to be redrawn completely.
+    last_frame: Option<FrameSettings>,
+}
+
+impl DirtyRegion {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Makes the next frame be redrawn completely.
+    ///
+    /// This is needed when something other than a display object changes what's on screen,
+    /// such as Stage3D content.
+    pub fn invalidate_all(&mut self) {
+        self.last_frame = None;
+    }
+
+    /// Records how an object is drawn on this frame.
+    ///
+    /// `changed` is set if the contents of the object changed since it was last drawn, in a
+    /// way that `record` doesn't show.
+    pub fn record(&mut self, object: *const DisplayObjectPtr, record: RenderRecord, changed: bool) {
+        // Objects such as timeline masks may be drawn several times per frame.
+        if self.current.get(&object) == Some(&record) {
+            return;
+        }
+        match self.previous.remove(&object) {
+            Some(previous) if !changed && previous.looks_same(&record) => {}
+            Some(previous) => {
+                self.rects.push(previous.bounds);
+                self.rects.push(record.bounds.clone());
+            }
+            None => self.rects.push(record.bounds.clone()),
+        }
+        self.current.insert(object, record);
+    }
+
+    /// Finishes the current frame, returning the rectangles of the viewport that have to
+    /// be redrawn, in twips. Returns `None` if the whole frame has to be redrawn.
+    pub fn finish_frame(
+        &mut self,
+        background: Color,
+        viewport: BoundingBox,
+        view_matrix: Matrix,
+    ) -> Option<Vec<BoundingBox>> {
+        // Anything that was drawn on the last frame but not on this one has disappeared.
+        self.rects
+            .extend(self.previous.drain().map(|(_, record)| record.bounds));
+        std::mem::swap(&mut self.previous, &mut self.current);
+        let mut rects = std::mem::take(&mut self.rects);
+
+        let settings = FrameSettings {
+            background,
+            viewport: viewport.clone(),
+            view_matrix,
+        };
+        if self.last_frame.replace(settings.clone()) != Some(settings) {
+            return None;
+        }
+
+        rects.retain_mut(|rect| {
+            *rect = intersection(rect, &viewport);
+            rect.valid
+        });
+        Some(merge_rects(rects))
+    }
+}
+
+/// The overlap of two bounding boxes, which is invalid if they don't overlap.
+fn intersection(a: &BoundingBox, b: &BoundingBox) -> BoundingBox {
+    if !a.intersects(b) {
+        return Default::default();
+    }
+    BoundingBox {
+        x_min: a.x_min.max(b.x_min),
+        y_min: a.y_min.max(b.y_min),
+        x_max: a.x_max.min(b.x_max),
+        y_max: a.y_max.min(b.y_max),
+        valid: true,
+    }
+}
+
+/// Merges overlapping rectangles, so that no pixel is drawn twice.
+fn merge_rects(mut rects: Vec<BoundingBox>) -> Vec<BoundingBox> {
+    let mut merged: Vec<BoundingBox> = Vec::with_capacity(rects.len());
+    while let Some(mut rect) = rects.pop() {
+        // Growing a rectangle may make it overlap ones that were already merged.
+        while let Some(i) = merged.iter().position(|other| other.intersects(&rect)) {
+            rect.union(&merged.swap_remove(i));
+        }
+        merged.push(rect);
+    }
+    if merged.len() > MAX_DIRTY_RECTS {
+        let mut union = BoundingBox::default();
+        for rect in &merged {
+            union.union(rect);
+        }
+        merged = vec![union];
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x_min: i32, y_min: i32, x_max: i32, y_max: i32) -> BoundingBox {
+        BoundingBox {
+            x_min: Twips::from_pixels_i32(x_min),
+            y_min: Twips::from_pixels_i32(y_min),
+            x_max: Twips::from_pixels_i32(x_max),
+            y_max: Twips::from_pixels_i32(y_max),
+            valid: true,
+        }
+    }
+
+    fn record(bounds: BoundingBox) -> RenderRecord {
+        RenderRecord {
+            own_bounds: bounds.clone(),
+            bounds,
+            matrix: Matrix::IDENTITY,
+            color_transform: ColorTransform::IDENTITY,
+            blend_mode: BlendMode::Normal,
+            scroll_rect: None,
+            mask_matrix: None,
+            bitmap_data_version: None,
+        }
+    }
+
+    fn finish(region: &mut DirtyRegion) -> Option<Vec<BoundingBox>> {
+        region.finish_frame(Color::WHITE, rect(0, 0, 100, 100), Matrix::IDENTITY)
+    }
+
+    #[test]
+    fn dirty_rects() {
+        // Only the addresses of the objects matter.
+        let objects = [0u8; 3];
+        let object = |i: usize| -> *const DisplayObjectPtr { (&objects[i] as *const u8).cast() };
+        let (a, b, c) = (object(0), object(1), object(2));
+        let mut region = DirtyRegion::new();
+
+        // The first frame is always drawn completely.
+        region.record(a, record(rect(0, 0, 10, 10)), false);
+        region.record(b, record(rect(50, 50, 60, 60)), false);
+        assert_eq!(finish(&mut region), None);
+
+        // Nothing changed.
+        region.record(a, record(rect(0, 0, 10, 10)), false);
+        region.record(b, record(rect(50, 50, 60, 60)), false);
+        assert_eq!(finish(&mut region), Some(vec![]));
+
+        // `a` moves, so both its old and new positions are dirty.
+        region.record(a, record(rect(5, 0, 15, 10)), false);
+        region.record(b, record(rect(50, 50, 60, 60)), false);
+        assert_eq!(finish(&mut region), Some(vec![rect(0, 0, 15, 10)]));
+
+        // `b` changes contents, and `a` disappears.
+        region.record(b, record(rect(50, 50, 60, 60)), true);
+        let mut rects = finish(&mut region).unwrap();
+        rects.sort_by_key(|rect| rect.x_min);
+        assert_eq!(rects, vec![rect(5, 0, 15, 10), rect(50, 50, 60, 60)]);
+
+        // Rectangles are clipped to the viewport.
+        region.record(b, record(rect(90, 90, 120, 120)), false);
+        let mut rects = finish(&mut region).unwrap();
+        rects.sort_by_key(|rect| rect.x_min);
+        assert_eq!(rects, vec![rect(50, 50, 60, 60), rect(90, 90, 100, 100)]);
+
+        // A container whose children move isn't dirty itself.
+        let container = |bounds| RenderRecord {
+            bounds,
+            ..record(rect(0, 0, 0, 0))
+        };
+        region.record(b, record(rect(90, 90, 120, 120)), false);
+        region.record(c, container(rect(0, 0, 50, 50)), false);
+        assert_eq!(finish(&mut region), Some(vec![rect(0, 0, 50, 50)]));
+        region.record(b, record(rect(90, 90, 120, 120)), false);
+        region.record(c, container(rect(0, 0, 80, 80)), false);
+        assert_eq!(finish(&mut region), Some(vec![]));
+
+        // Changing the background redraws everything.
+        region.record(b, record(rect(90, 90, 120, 120)), false);
+        assert_eq!(
+            region.finish_frame(Color::BLACK, rect(0, 0, 100, 100), Matrix::IDENTITY),
+            None
+        );
+    }
+}
@@ -4,7 +4,9 @@ use crate::avm2::{
     EventObject as Avm2EventObject, Multiname as Avm2Multiname, Object as Avm2Object,
     TObject as Avm2TObject, Value as Avm2Value,
 };
+use crate::bitmap::bitmap_data::BitmapData;
 use crate::context::{RenderContext, UpdateContext};
+use crate::dirty_region::RenderRecord;
 use crate::drawing::Drawing;
 use crate::player::NEWEST_PLAYER_VERSION;
 use crate::prelude::*;
@@ -13,10 +15,14 @@ use crate::tag_utils::SwfMovie;
 use crate::types::{Degrees, Percent};
 use crate::vminterface::Instantiator;
 use bitflags::bitflags;
-use gc_arena::{Collect, MutationContext};
+use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_macros::enum_trait_object;
-use ruffle_render::transform::Transform;
+use ruffle_render::bitmap::{Bitmap as RenderBitmap, BitmapFormat, BitmapHandle};
+use ruffle_render::commands::CommandList;
+use ruffle_render::matrix3d::{Components3D, Matrix3D};
+use ruffle_render::transform::{Transform, TransformStack};
 use std::cell::{Ref, RefMut};
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 use swf::{BlendMode, Fixed8, Rectangle};
@@ -51,7 +57,7 @@ pub use morph_shape::{MorphShape, MorphShapeStatic};
 pub use movie_clip::{MovieClip, Scene};
 use ruffle_render::commands::CommandHandler;
 pub use stage::{Stage, StageAlign, StageDisplayState, StageQuality, StageScaleMode, WindowMode};
-pub use text::Text;
+pub use text::{StaticGlyph, Text};
 pub use video::Video;
 
 #[derive(Clone, Collect)]
@@ -117,6 +123,25 @@ pub struct DisplayObjectBase<'gc> {
     /// changes immediately (without needing wait for a render)
     #[collect(require_static)]
     next_scroll_rect: Rectangle<Twips>,
+
+    /// The matrix used to draw this object into its cached bitmap, set by the AVM2
+    /// `cacheAsBitmapMatrix` property. When unset, the object is cached at its on-screen scale.
+    #[collect(require_static)]
+    cache_as_bitmap_matrix: Option<Matrix>,
+
+    /// The bitmap this object was last rendered into, if it is bitmap cached.
+    bitmap_cache: Option<BitmapCache<'gc>>,
+
+    /// The 3D part of this object's transform, set by the AVM2 `z`, `rotationX`, `rotationY`,
+    /// `scaleZ` and `transform.matrix3D` properties. Objects with a 3D transform are drawn in
+    /// perspective; everything else (bounds, hit testing) still uses the 2D matrix.
+    #[collect(require_static)]
+    transform_3d: Option<Transform3D>,
+
+    /// The projection used to draw this object's 3D children, set by the AVM2
+    /// `transform.perspectiveProjection` property.
+    #[collect(require_static)]
+    perspective_projection: Option<PerspectiveProjection>,
 }
 
 impl<'gc> Default for DisplayObjectBase<'gc> {
@@ -142,6 +167,10 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             flags: DisplayObjectFlags::VISIBLE,
             scroll_rect: None,
             next_scroll_rect: Default::default(),
+            cache_as_bitmap_matrix: None,
+            bitmap_cache: None,
+            transform_3d: None,
+            perspective_projection: None,
         }
     }
 }
@@ -457,6 +486,31 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.flags.set(DisplayObjectFlags::CACHE_AS_BITMAP, value);
     }
 
+    fn cache_as_bitmap_matrix(&self) -> Option<Matrix> {
+        self.cache_as_bitmap_matrix
+    }
+
+    fn set_cache_as_bitmap_matrix(&mut self, matrix: Option<Matrix>) {
+        self.cache_as_bitmap_matrix = matrix;
+    }
+
+    /// Marks the cached bitmap of this object as stale, if there is one.
+    fn invalidate_cached_bitmap(&mut self) {
+        if let Some(cache) = &mut self.bitmap_cache {
+            cache.dirty = true;
+        }
+    }
+
+    fn changed_since_render(&self) -> bool {
+        self.flags
+            .contains(DisplayObjectFlags::CHANGED_SINCE_RENDER)
+    }
+
+    fn set_changed_since_render(&mut self, value: bool) {
+        self.flags
+            .set(DisplayObjectFlags::CHANGED_SINCE_RENDER, value);
+    }
+
     fn instantiated_by_timeline(&self) -> bool {
         self.flags
             .contains(DisplayObjectFlags::INSTANTIATED_BY_TIMELINE)
@@ -498,16 +552,617 @@ impl<'gc> DisplayObjectBase<'gc> {
     fn set_maskee(&mut self, node: Option<DisplayObject<'gc>>) {
         self.maskee = node;
     }
+
+    pub fn transform_3d(&self) -> Option<Transform3D> {
+        self.transform_3d
+    }
+
+    pub fn set_transform_3d(&mut self, transform_3d: Option<Transform3D>) {
+        self.set_transformed_by_script(true);
+        self.transform_3d = transform_3d;
+    }
+
+    fn transform_3d_mut(&mut self) -> &mut Transform3D {
+        self.set_transformed_by_script(true);
+        self.transform_3d.get_or_insert_with(Default::default)
+    }
+
+    /// The 3D matrix of this object, in pixels.
+    ///
+    /// Objects without a 3D transform return their 2D matrix, including any skew.
+    pub fn matrix_3d(&self) -> Matrix3D {
+        let transform_3d = match self.transform_3d {
+            Some(transform_3d) => transform_3d,
+            None => return Matrix3D::from_matrix(&self.transform.matrix),
+        };
+        let (rotation, scale_x, scale_y) = if self.scale_rotation_cached() {
+            (
+                self.rotation.into_radians(),
+                self.scale_x.unit(),
+                self.scale_y.unit(),
+            )
+        } else {
+            // Same as `cache_scale_rotation`, without the skew.
+            let Matrix { a, b, c, d, .. } = self.transform.matrix;
+            let (a, b, c, d) = (f64::from(a), f64::from(b), f64::from(c), f64::from(d));
+            (b.atan2(a), a.hypot(b), c.hypot(d))
+        };
+        Matrix3D::recompose(&Components3D {
+            translation: [self.x(), self.y(), transform_3d.z],
+            rotation: [
+                transform_3d.rotation_x.into_radians(),
+                transform_3d.rotation_y.into_radians(),
+                rotation,
+            ],
+            scale: [scale_x, scale_y, transform_3d.scale_z],
+        })
+    }
+
+    /// Sets the 3D matrix of this object, which gives it a 3D transform.
+    ///
+    /// The matrix is split into separate translations, rotations and scales, so any skew
+    /// or perspective in it is lost.
+    pub fn set_matrix_3d(&mut self, matrix: &Matrix3D) {
+        let components = match matrix.decompose() {
+            Some(components) => components,
+            None => {
+                // There's no rotation to extract from a flattened matrix.
+                self.set_matrix(matrix.to_matrix());
+                self.transform_3d_mut().z = matrix.get(2, 3);
+                return;
+            }
+        };
+        let [x, y, z] = components.translation;
+        let [rotation_x, rotation_y, rotation_z] = components.rotation;
+        let [scale_x, scale_y, scale_z] = components.scale;
+        let (sin, cos) = rotation_z.sin_cos();
+        self.transform.matrix = Matrix {
+            a: (scale_x * cos) as f32,
+            b: (scale_x * sin) as f32,
+            c: (scale_y * -sin) as f32,
+            d: (scale_y * cos) as f32,
+            tx: Twips::from_pixels(x),
+            ty: Twips::from_pixels(y),
+        };
+        self.rotation = Degrees::from_radians(rotation_z);
+        self.scale_x = Percent::from_unit(scale_x);
+        self.scale_y = Percent::from_unit(scale_y);
+        self.skew = 0.0;
+        self.set_scale_rotation_cached(true);
+        self.set_transform_3d(Some(Transform3D {
+            z,
+            rotation_x: Degrees::from_radians(rotation_x),
+            rotation_y: Degrees::from_radians(rotation_y),
+            scale_z,
+        }));
+    }
+
+    pub fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.perspective_projection
+    }
+
+    pub fn set_perspective_projection(&mut self, projection: Option<PerspectiveProjection>) {
+        self.perspective_projection = projection;
+    }
+}
+
+/// The parts of a display object's transform that move it out of the XY plane.
+///
+/// The rest of the transform comes from the object's 2D position, rotation and scale.
+#[derive(Clone, Copy, Debug)]
+pub struct Transform3D {
+    /// The position on the Z axis in pixels, where positive values are further away.
+    pub z: f64,
+    pub rotation_x: Degrees,
+    pub rotation_y: Degrees,
+    pub scale_z: f64,
+}
+
+impl Default for Transform3D {
+    fn default() -> Self {
+        Self {
+            z: 0.0,
+            rotation_x: Degrees::from(0.0),
+            rotation_y: Degrees::from(0.0),
+            scale_z: 1.0,
+        }
+    }
+}
+
+/// How 3D objects are projected onto the screen, as set by `flash.geom.PerspectiveProjection`.
+#[derive(Clone, Copy, Debug)]
+pub struct PerspectiveProjection {
+    /// The angle of the viewer's field of view, in degrees.
+    pub field_of_view: f64,
+
+    /// The point that 3D objects vanish towards, in pixels in the local space of the
+    /// object this projection belongs to.
+    pub center: (f64, f64),
+}
+
+impl PerspectiveProjection {
+    /// The field of view of the default projection of the root movie.
+    pub const DEFAULT_FIELD_OF_VIEW: f64 = 55.0;
+
+    /// The distance from the viewer to the XY plane, in pixels.
+    ///
+    /// Like Flash Player, this is chosen so that the field of view spans `width` pixels.
+    pub fn focal_length(&self, width: f64) -> f64 {
+        width / 2.0 / (self.field_of_view.to_radians() / 2.0).tan()
+    }
+}
+
+/// The largest width or height of a cached bitmap, in pixels.
+/// Objects any larger than this are rendered directly.
+const MAX_BITMAP_CACHE_DIMENSION: u32 = 8191;
+
+/// The largest number of pixels in a cached bitmap.
+const MAX_BITMAP_CACHE_PIXELS: u32 = 16_777_215;
+
+/// A rendering of a display object and its children, reused between frames for as long as
+/// its contents don't change.
+///
+/// The bitmap is drawn with the non-translation part of the object's concatenated matrix,
+/// so that moving the object only moves the bitmap. Changes to the object's children or
+/// content mark the cache as dirty through `TDisplayObject::invalidate_cached_bitmap`.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct BitmapCache<'gc> {
+    /// Whether the contents of the bitmap are stale and must be redrawn before use.
+    dirty: bool,
+
+    /// The matrix the bitmap was drawn with, without its translation.
+    #[collect(require_static)]
+    matrix: Matrix,
+
+    /// The position of the top-left corner of the bitmap, in pixels, relative to the
+    /// origin of the object under `matrix`.
+    x: i32,
+    y: i32,
+
+    width: u32,
+    height: u32,
+
+    /// The bitmap holding the rendered object.
+    /// `None` if the render backend is unable to draw offscreen.
+    #[collect(require_static)]
+    handle: Option<BitmapHandle>,
+
+    /// Every `BitmapData` displayed by a `Bitmap` in this object, along with the version
+    /// of its pixels that was drawn into the cache.
+    bitmap_data: Vec<(GcCell<'gc, BitmapData<'gc>>, u32)>,
+}
+
+impl<'gc> BitmapCache<'gc> {
+    /// Whether the contents of this cache are out of date, regardless of how it's drawn.
+    fn is_stale(&self) -> bool {
+        self.dirty
+            || self.bitmap_data.iter().any(|(bitmap_data, version)| {
+                // A `BitmapData` that's locked is being drawn into, which can't change
+                // what's displayed until it's done.
+                bitmap_data
+                    .try_read()
+                    .map_or(false, |bitmap_data| bitmap_data.version() != *version)
+            })
+    }
+
+    /// Whether this cache can be drawn as-is for an object with the given matrix and bounds.
+    fn is_valid(&self, matrix: &Matrix, x: i32, y: i32, width: u32, height: u32) -> bool {
+        !self.is_stale()
+            && self.matrix == *matrix
+            && (self.x, self.y, self.width, self.height) == (x, y, width, height)
+    }
+}
+
+/// Collects the `BitmapData` displayed in an object and its children, for
+/// `BitmapCache::bitmap_data`.
+fn collect_bitmap_data<'gc>(
+    object: DisplayObject<'gc>,
+    bitmap_data: &mut Vec<(GcCell<'gc, BitmapData<'gc>>, u32)>,
+) {
+    if let Some(bitmap) = object.as_bitmap() {
+        let data = bitmap.bitmap_data();
+        if let Ok(read) = data.try_read() {
+            bitmap_data.push((data, read.version()));
+        }
+    }
+    if let Some(container) = object.as_container() {
+        for child in container.iter_render_list() {
+            collect_bitmap_data(child, bitmap_data);
+        }
+    }
+}
+
+/// The state of an object's cached bitmap after bringing it up to date.
+enum CachedBitmap {
+    /// The object doesn't draw anything.
+    Empty,
+
+    /// The object is drawn in `handle`, whose top-left corner is at `(x, y)` pixels from
+    /// the origin of the object under the cache matrix.
+    Drawn {
+        handle: BitmapHandle,
+        x: i32,
+        y: i32,
+    },
+
+    /// The object is too large to cache, or the renderer can't draw offscreen.
+    Uncached,
+}
+
+/// Redraws the cached bitmap of an object if it is stale or was drawn with a different
+/// matrix. `matrix` must not have a translation.
+fn update_bitmap_cache<'gc>(
+    this: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc, '_>,
+    matrix: Matrix,
+) -> CachedBitmap {
+    // The bounds of everything drawn by `render_self`. Unlike `bounds_with_transform`,
+    // this ignores the scroll rect, which is applied when the bitmap is drawn.
+    let mut bounds = this.self_bounds().transform(&matrix);
+    if let Some(container) = this.as_container() {
+        for child in container.iter_render_list() {
+            bounds.union(&child.bounds_with_transform(&(matrix * *child.base().matrix())));
+        }
+    }
+    if !bounds.valid {
+        return CachedBitmap::Empty;
+    }
+    let x = bounds.x_min.to_pixels().floor();
+    let y = bounds.y_min.to_pixels().floor();
+    let width = (bounds.x_max.to_pixels().ceil() - x) as u32;
+    let height = (bounds.y_max.to_pixels().ceil() - y) as u32;
+    if width == 0 || height == 0 {
+        return CachedBitmap::Empty;
+    }
+    if width > MAX_BITMAP_CACHE_DIMENSION
+        || height > MAX_BITMAP_CACHE_DIMENSION
+        || width * height > MAX_BITMAP_CACHE_PIXELS
+    {
+        return CachedBitmap::Uncached;
+    }
+    let (x, y) = (x as i32, y as i32);
+
+    let valid_handle = this
+        .base()
+        .bitmap_cache
+        .as_ref()
+        .filter(|cache| cache.is_valid(&matrix, x, y, width, height))
+        .map(|cache| cache.handle.clone());
+    let handle = match valid_handle {
+        Some(handle) => handle,
+        None => {
+            // The old bitmap can be reused if it's the same size.
+            let old_handle = this
+                .base_mut(context.gc_context)
+                .bitmap_cache
+                .take()
+                .filter(|cache| (cache.width, cache.height) == (width, height))
+                .and_then(|cache| cache.handle);
+            let handle =
+                redraw_bitmap_cache(this, context, old_handle, &matrix, x, y, width, height);
+            let mut bitmap_data = vec![];
+            collect_bitmap_data(this, &mut bitmap_data);
+            this.base_mut(context.gc_context).bitmap_cache = Some(BitmapCache {
+                dirty: false,
+                matrix,
+                x,
+                y,
+                width,
+                height,
+                handle: handle.clone(),
+                bitmap_data,
+            });
+            handle
+        }
+    };
+
+    match handle {
+        Some(handle) => CachedBitmap::Drawn { handle, x, y },
+        None => CachedBitmap::Uncached,
+    }
+}
+
+/// Renders an object through its cached bitmap, redrawing the bitmap first if it is stale.
+///
+/// Returns `false` if the object can't be cached, in which case it should be rendered
+/// directly instead.
+fn render_bitmap_cached<'gc>(
+    this: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc, '_>,
+) -> bool {
+    let transform = context.transform_stack.transform().clone();
+    let cache_as_bitmap_matrix = this.base().cache_as_bitmap_matrix();
+    let matrix = Matrix {
+        tx: Twips::ZERO,
+        ty: Twips::ZERO,
+        ..cache_as_bitmap_matrix.unwrap_or(transform.matrix)
+    };
+    let (handle, x, y) = match update_bitmap_cache(this, context, matrix) {
+        CachedBitmap::Empty => return true,
+        CachedBitmap::Drawn { handle, x, y } => (handle, x, y),
+        CachedBitmap::Uncached => return false,
+    };
+
+    let offset = Matrix::translate(Twips::from_pixels(x.into()), Twips::from_pixels(y.into()));
+    let bitmap_matrix = if cache_as_bitmap_matrix.is_some() {
+        let mut inverse = matrix;
+        inverse.invert();
+        transform.matrix * inverse * offset
+    } else {
+        // Cached bitmaps are snapped to whole pixels.
+        Matrix::translate(
+            Twips::from_pixels(transform.matrix.tx.to_pixels().round()),
+            Twips::from_pixels(transform.matrix.ty.to_pixels().round()),
+        ) * offset
+    };
+    context.commands.render_bitmap(
+        &handle,
+        &Transform {
+            matrix: bitmap_matrix,
+            color_transform: transform.color_transform,
+        },
+        true,
+    );
+    true
+}
+
+/// Renders an object with a 3D transform in perspective, through its cached bitmap.
+///
+/// The bitmap is drawn at the scale the object would have without any rotation out of
+/// the XY plane. Returns `false` if the object can't be cached, in which case it should
+/// be rendered flat instead.
+fn render_3d<'gc>(
+    this: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc, '_>,
+    parent_matrix: &Matrix,
+) -> bool {
+    let own_matrix = this.base().matrix_3d();
+    let axis_scale = |column| {
+        (0..3)
+            .map(|row| own_matrix.get(row, column).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    };
+    let scale_x = f64::from(parent_matrix.a).hypot(parent_matrix.b.into()) * axis_scale(0);
+    let scale_y = f64::from(parent_matrix.c).hypot(parent_matrix.d.into()) * axis_scale(1);
+    if !(scale_x > 0.0 && scale_y > 0.0 && scale_x.is_finite() && scale_y.is_finite()) {
+        return true;
+    }
+
+    let cache_matrix = Matrix::scale(scale_x as f32, scale_y as f32);
+    let (handle, x, y) = match update_bitmap_cache(this, context, cache_matrix) {
+        CachedBitmap::Empty => return true,
+        CachedBitmap::Drawn { handle, x, y } => (handle, x, y),
+        CachedBitmap::Uncached => return false,
+    };
+
+    // The scroll rect is applied to the bitmap by the caller, not by the cached contents.
+    let mut global_matrix = this.local_to_global_matrix_3d();
+    if let Some(rect) = this.scroll_rect() {
+        global_matrix = global_matrix
+            * Matrix3D::translate(rect.x_min.to_pixels(), rect.y_min.to_pixels(), 0.0);
+    }
+    let view_matrix = Matrix3D::from_matrix(context.stage.base().matrix());
+    let matrix = view_matrix
+        * this.projection_matrix(context.stage)
+        * global_matrix
+        * Matrix3D::scale(1.0 / scale_x, 1.0 / scale_y, 1.0)
+        * Matrix3D::translate(x.into(), y.into(), 0.0);
+    let color_transform = context.transform_stack.transform().color_transform;
+    context
+        .commands
+        .render_bitmap_3d(&handle, &matrix, &color_transform, true);
+    true
+}
+
+/// Draws an object into a new cached bitmap, or into `old_handle` if given.
+#[allow(clippy::too_many_arguments)]
+fn redraw_bitmap_cache<'gc>(
+    this: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc, '_>,
+    old_handle: Option<BitmapHandle>,
+    matrix: &Matrix,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+) -> Option<BitmapHandle> {
+    let clear = vec![0; width as usize * height as usize * 4];
+    let handle = match old_handle {
+        Some(handle) => match context
+            .renderer
+            .update_texture(&handle, width, height, clear)
+        {
+            Ok(()) => handle,
+            Err(e) => {
+                log::error!("Failed to clear cached bitmap: {:?}", e);
+                return None;
+            }
+        },
+        None => {
+            let bitmap = RenderBitmap::new(width, height, BitmapFormat::Rgba, clear);
+            match context.renderer.register_bitmap(bitmap) {
+                Ok(handle) => handle,
+                Err(e) => {
+                    log::warn!("Failed to register cached bitmap: {:?}", e);
+                    return None;
+                }
+            }
+        }
+    };
+
+    let mut transform_stack = TransformStack::new();
+    transform_stack.push(&Transform {
+        matrix: Matrix::translate(
+            Twips::from_pixels((-x).into()),
+            Twips::from_pixels((-y).into()),
+        ) * *matrix,
+        color_transform: Default::default(),
+    });
+    let mut offscreen_context = RenderContext {
+        renderer: context.renderer,
+        commands: CommandList::new(),
+        gc_context: context.gc_context,
+        ui: context.ui,
+        library: context.library,
+        transform_stack: &mut transform_stack,
+        is_offscreen: true,
+        cull_bounds: Default::default(),
+        dirty_region: None,
+        known_viewport_bounds: Default::default(),
+        stage: context.stage,
+        clip_depth_stack: vec![],
+        allow_mask: true,
+    };
+    this.render_self(&mut offscreen_context);
+    let commands = offscreen_context.commands;
+
+    match context
+        .renderer
+        .render_offscreen(handle.clone(), width, height, commands)
+    {
+        Ok(_) => Some(handle),
+        Err(ruffle_render::error::Error::Unimplemented) => None,
+        Err(e) => {
+            log::error!("Failed to render cached bitmap: {:?}", e);
+            None
+        }
+    }
+}
+
+/// The bounds of an object and its children on the viewport, given its concatenated matrix.
+///
+/// Returns `None` if the object or any of its children has a 3D transform, in which case
+/// its bounds aren't known until it's projected.
+///
+/// The bounds found for each descendant are recorded in `known`, so that they don't have to
+/// be found again when that descendant is rendered.
+fn viewport_bounds(
+    this: DisplayObject<'_>,
+    matrix: &Matrix,
+    known: &mut HashMap<*const DisplayObjectPtr, (Matrix, Option<BoundingBox>)>,
+) -> Option<BoundingBox> {
+    if this.base().transform_3d().is_some() {
+        return None;
+    }
+    match this.as_container() {
+        Some(container) if this.scroll_rect().is_none() => {
+            let mut bounds = Some(this.self_bounds().transform(matrix));
+            for child in container.iter_render_list() {
+                let matrix = *matrix * *child.base().matrix();
+                let child_bounds = viewport_bounds(child, &matrix, known);
+                known.insert(child.as_ptr(), (matrix, child_bounds.clone()));
+                bounds = bounds.zip(child_bounds).map(|(mut bounds, child_bounds)| {
+                    bounds.union(&child_bounds);
+                    bounds
+                });
+            }
+            bounds
+        }
+        _ => Some(this.bounds_with_transform(matrix)),
+    }
+}
+
+/// Whether the contents of an object changed since it was last rendered, clearing its flag.
+/// With `recursive`, this also checks and clears the flags of all of its children.
+fn take_changed_since_render<'gc>(
+    this: DisplayObject<'gc>,
+    gc_context: MutationContext<'gc, '_>,
+    recursive: bool,
+) -> bool {
+    let mut changed = this.base().changed_since_render();
+    if changed {
+        this.base_mut(gc_context).set_changed_since_render(false);
+    }
+    if let Some(container) = this.as_container().filter(|_| recursive) {
+        for child in container.iter_render_list() {
+            changed |= take_changed_since_render(child, gc_context, true);
+        }
+    }
+    changed
 }
 
 pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_, 'gc, '_>) {
     if this.maskee().is_some() {
         return;
     }
+    let parent_matrix = context.transform_stack.transform().matrix;
     context.transform_stack.push(this.base().transform());
+    let transform = context.transform_stack.transform().clone();
+    let mask = this.masker();
+
+    // Objects outside of the viewport are skipped entirely, along with their children.
+    // This can't be done for anything drawn offscreen, whose bounds aren't on the viewport.
+    // The stage is never culled nor marked as dirty itself, as that would redraw everything.
+    let on_screen = !context.is_offscreen && this.as_stage().is_none();
+    let bounds = if on_screen {
+        // An ancestor usually found these bounds already while finding its own.
+        let known = &mut context.known_viewport_bounds;
+        let bounds = match known.remove(&this.as_ptr()) {
+            Some((matrix, bounds)) if matrix == transform.matrix => bounds,
+            _ => viewport_bounds(this, &transform.matrix, known),
+        };
+        let view_matrix = *context.stage.base().matrix();
+        let culled = bounds
+            .as_ref()
+            .map_or(false, |bounds| !bounds.intersects(&context.cull_bounds))
+            || mask.map_or(false, |m| {
+                let matrix = view_matrix * m.local_to_global_matrix();
+                !viewport_bounds(m, &matrix, &mut context.known_viewport_bounds)
+                    .map_or(true, |bounds| bounds.intersects(&context.cull_bounds))
+            });
+        if culled {
+            context.transform_stack.pop();
+            return;
+        }
+        bounds
+    } else {
+        None
+    };
+
+    // Gather what's needed to tell whether this object looks any different from the last
+    // frame. A stale cached bitmap means that the contents of a child changed.
+    let record = if !on_screen || context.dirty_region.is_none() {
+        None
+    } else {
+        let cache_stale = |object: DisplayObject<'gc>| {
+            object
+                .base()
+                .bitmap_cache
+                .as_ref()
+                .map_or(false, |cache| cache.is_stale())
+        };
+        // The flags of children are cleared as they're rendered, except for masks, whose
+        // children aren't rendered on their own.
+        let changed = take_changed_since_render(this, context.gc_context, false)
+            || cache_stale(this)
+            || mask.map_or(false, |m| {
+                take_changed_since_render(m, context.gc_context, true) || cache_stale(m)
+            });
+        let record = bounds.map(|bounds| RenderRecord {
+            bounds,
+            own_bounds: this.self_bounds().transform(&transform.matrix),
+            matrix: transform.matrix,
+            color_transform: transform.color_transform,
+            blend_mode: this.blend_mode(),
+            scroll_rect: this.scroll_rect(),
+            mask_matrix: mask.map(|m| *context.stage.base().matrix() * m.local_to_global_matrix()),
+            bitmap_data_version: this
+                .as_bitmap()
+                .and_then(|bitmap| bitmap.bitmap_data().try_read().ok().map(|b| b.version())),
+        });
+        Some((record, changed))
+    };
+
     let blend_mode = this.blend_mode();
+    // Objects with a blend mode are drawn into their own transparent layer, which
+    // is then blended as a whole. The color transform is applied once to the layer,
+    // so overlapping children don't show through each other.
     let original_commands = if blend_mode != BlendMode::Normal {
-        Some(std::mem::take(&mut context.commands))
+        let color_transform = context.transform_stack.transform().color_transform;
+        context.transform_stack.push_layer();
+        Some((std::mem::take(&mut context.commands), color_transform))
     } else {
         None
     };
@@ -535,11 +1190,24 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         });
     }
 
-    let mask = this.masker();
+    // Objects with `cacheAsBitmap` or filters are drawn through a bitmap that persists
+    // between frames. Nothing is cached while drawing a mask, which needs the actual shapes.
+    let cache_as_bitmap = this.is_bitmap_cached() || this.base().filters.length() > 0;
+
+    // When both the mask and the maskee are cached as bitmaps, the maskee is blended
+    // with the alpha channel of the mask instead of being clipped to the mask's shape.
+    let alpha_mask = mask.filter(|m| cache_as_bitmap && context.allow_mask && m.is_bitmap_cached());
+    let maskee_original_commands = if alpha_mask.is_some() {
+        Some(std::mem::take(&mut context.commands))
+    } else {
+        None
+    };
     let mut mask_transform = ruffle_render::transform::Transform::default();
     if let Some(m) = mask {
         mask_transform.matrix = this.global_to_local_matrix();
         mask_transform.matrix *= m.local_to_global_matrix();
+    }
+    if let Some(m) = mask.filter(|_| alpha_mask.is_none()) {
         context.commands.push_mask();
         context.allow_mask = false;
         context.transform_stack.push(&mask_transform);
@@ -567,7 +1235,21 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         context.commands.activate_mask();
     }
 
-    this.render_self(context);
+    // Objects with a 3D transform are drawn in perspective through a cached bitmap.
+    // This needs the full path to the stage, so anything drawn offscreen, such as
+    // into the cached bitmap of an ancestor, is drawn flat instead.
+    let is_3d = this.base().transform_3d().is_some();
+    if !cache_as_bitmap && !is_3d && this.base().bitmap_cache.is_some() {
+        this.base_mut(context.gc_context).bitmap_cache = None;
+    }
+    let rendered = if is_3d {
+        !context.is_offscreen && context.allow_mask && render_3d(this, context, &parent_matrix)
+    } else {
+        cache_as_bitmap && context.allow_mask && render_bitmap_cached(this, context)
+    };
+    if !rendered {
+        this.render_self(context);
+    }
 
     if let Some(rect_mat) = scroll_rect_matrix {
         // Draw the rectangle again after deactivating the mask,
@@ -577,7 +1259,7 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         context.commands.pop_mask();
     }
 
-    if let Some(m) = mask {
+    if let Some(m) = mask.filter(|_| alpha_mask.is_none()) {
         context.commands.deactivate_mask();
         context.allow_mask = false;
         context.transform_stack.push(&mask_transform);
@@ -587,9 +1269,17 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         context.commands.pop_mask();
     }
 
-    if let Some(original_commands) = original_commands {
-        let sub_commands = std::mem::replace(&mut context.commands, original_commands);
-        context.commands.blend(&sub_commands, blend_mode);
+    if let (Some(m), Some(original_commands)) = (alpha_mask, maskee_original_commands) {
+        let maskee_commands = std::mem::take(&mut context.commands);
+        context.transform_stack.push(&mask_transform);
+        if !render_bitmap_cached(m, context) {
+            m.render_self(context);
+        }
+        context.transform_stack.pop();
+        let mask_commands = std::mem::replace(&mut context.commands, original_commands);
+        context
+            .commands
+            .alpha_mask(&maskee_commands, &mask_commands);
     }
 
     if scroll_rect_matrix.is_some() {
@@ -597,7 +1287,23 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         context.transform_stack.pop();
     }
 
+    if let Some((original_commands, color_transform)) = original_commands {
+        let sub_commands = std::mem::replace(&mut context.commands, original_commands);
+        context
+            .commands
+            .blend(&sub_commands, blend_mode, &color_transform);
+        context.transform_stack.pop();
+    }
+
     context.transform_stack.pop();
+
+    if let (Some((record, changed)), Some(dirty_region)) = (record, &mut context.dirty_region) {
+        match record {
+            Some(record) => dirty_region.record(this.as_ptr(), record, changed),
+            // Where a 3D object ends up isn't known, so the whole frame has to be redrawn.
+            None => dirty_region.invalidate_all(),
+        }
+    }
 }
 
 #[enum_trait_object(
@@ -703,6 +1409,7 @@ pub trait TDisplayObject<'gc>:
 
     fn set_matrix(&self, gc_context: MutationContext<'gc, '_>, matrix: Matrix) {
         self.base_mut(gc_context).set_matrix(matrix);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     fn set_color_transform(
@@ -711,7 +1418,36 @@ pub trait TDisplayObject<'gc>:
         color_transform: ColorTransform,
     ) {
         self.base_mut(gc_context)
-            .set_color_transform(color_transform)
+            .set_color_transform(color_transform);
+        self.invalidate_parent_cached_bitmap(gc_context);
+    }
+
+    /// Marks the cached bitmaps of this object and all of its ancestors as stale, so that
+    /// they're redrawn the next time they're rendered.
+    ///
+    /// This must be called whenever the content of this object changes.
+    fn invalidate_cached_bitmap(&self, gc_context: MutationContext<'gc, '_>) {
+        let mut base = self.base_mut(gc_context);
+        base.invalidate_cached_bitmap();
+        base.set_changed_since_render(true);
+        drop(base);
+        self.invalidate_parent_cached_bitmap(gc_context);
+    }
+
+    /// Marks the cached bitmaps of the ancestors of this object as stale.
+    ///
+    /// This must be called whenever a property that's applied on top of this object's own
+    /// cached bitmap changes, such as its transform.
+    fn invalidate_parent_cached_bitmap(&self, gc_context: MutationContext<'gc, '_>) {
+        // Only this object is redrawn on screen; its ancestors just need new cached bitmaps.
+        self.base_mut(gc_context).set_changed_since_render(true);
+        let mut node = self.parent();
+        while let Some(display_object) = node {
+            display_object
+                .base_mut(gc_context)
+                .invalidate_cached_bitmap();
+            node = display_object.parent();
+        }
     }
 
     /// Should only be used to implement 'Transform.concatenatedMatrix'
@@ -773,6 +1509,7 @@ pub trait TDisplayObject<'gc>:
     /// Set by the `_x`/`x` ActionScript properties.
     fn set_x(&self, gc_context: MutationContext<'gc, '_>, value: f64) {
         self.base_mut(gc_context).set_x(value);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// The `y` position in pixels of this display object in local space.
@@ -785,6 +1522,7 @@ pub trait TDisplayObject<'gc>:
     /// Set by the `_y`/`y` ActionScript properties.
     fn set_y(&self, gc_context: MutationContext<'gc, '_>, value: f64) {
         self.base_mut(gc_context).set_y(value);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// The rotation in degrees this display object in local space.
@@ -800,6 +1538,7 @@ pub trait TDisplayObject<'gc>:
     fn set_rotation(&self, gc_context: MutationContext<'gc, '_>, radians: Degrees) {
         self.base_mut(gc_context).set_rotation(radians);
         self.set_scale_rotation_cached(gc_context);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// The X axis scale for this display object in local space.
@@ -815,6 +1554,7 @@ pub trait TDisplayObject<'gc>:
     fn set_scale_x(&self, gc_context: MutationContext<'gc, '_>, value: Percent) {
         self.base_mut(gc_context).set_scale_x(value);
         self.set_scale_rotation_cached(gc_context);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// The Y axis scale for this display object in local space.
@@ -830,6 +1570,122 @@ pub trait TDisplayObject<'gc>:
     fn set_scale_y(&self, gc_context: MutationContext<'gc, '_>, value: Percent) {
         self.base_mut(gc_context).set_scale_y(value);
         self.set_scale_rotation_cached(gc_context);
+        self.invalidate_parent_cached_bitmap(gc_context);
+    }
+
+    /// The `z` position in pixels of this display object in local space.
+    /// Returned by the AVM2 `z` property.
+    fn z(&self) -> f64 {
+        self.base().transform_3d().map_or(0.0, |t| t.z)
+    }
+
+    /// Sets the `z` position in pixels of this display object in local space.
+    /// Set by the AVM2 `z` property. This gives the object a 3D transform.
+    fn set_z(&self, gc_context: MutationContext<'gc, '_>, value: f64) {
+        self.base_mut(gc_context).transform_3d_mut().z = value;
+        self.invalidate_parent_cached_bitmap(gc_context);
+    }
+
+    /// The rotation in degrees of this display object around the X axis.
+    /// Returned by the AVM2 `rotationX` property.
+    fn rotation_x(&self) -> Degrees {
+        self.base()
+            .transform_3d()
+            .map_or(Degrees::from(0.0), |t| t.rotation_x)
+    }
+
+    /// Sets the rotation in degrees of this display object around the X axis.
+    /// Set by the AVM2 `rotationX` property. This gives the object a 3D transform.
+    fn set_rotation_x(&self, gc_context: MutationContext<'gc, '_>, value: Degrees) {
+        self.base_mut(gc_context).transform_3d_mut().rotation_x = value;
+        self.invalidate_parent_cached_bitmap(gc_context);
+    }
+
+    /// The rotation in degrees of this display object around the Y axis.
+    /// Returned by the AVM2 `rotationY` property.
+    fn rotation_y(&self) -> Degrees {
+        self.base()
+            .transform_3d()
+            .map_or(Degrees::from(0.0), |t| t.rotation_y)
+    }
+
+    /// Sets the rotation in degrees of this display object around the Y axis.
+    /// Set by the AVM2 `rotationY` property. This gives the object a 3D transform.
+    fn set_rotation_y(&self, gc_context: MutationContext<'gc, '_>, value: Degrees) {
+        self.base_mut(gc_context).transform_3d_mut().rotation_y = value;
+        self.invalidate_parent_cached_bitmap(gc_context);
+    }
+
+    /// The Z axis scale for this display object in local space.
+    /// Returned by the AVM2 `scaleZ` property.
+    fn scale_z(&self) -> f64 {
+        self.base().transform_3d().map_or(1.0, |t| t.scale_z)
+    }
+
+    /// Sets the Z axis scale for this display object in local space.
+    /// Set by the AVM2 `scaleZ` property. This gives the object a 3D transform.
+    fn set_scale_z(&self, gc_context: MutationContext<'gc, '_>, value: f64) {
+        self.base_mut(gc_context).transform_3d_mut().scale_z = value;
+        self.invalidate_parent_cached_bitmap(gc_context);
+    }
+
+    /// Returns the 3D matrix for transforming from this object's local space to global
+    /// stage space, in pixels. Perspective isn't included; see `projection_matrix`.
+    fn local_to_global_matrix_3d(&self) -> Matrix3D {
+        let mut matrix = self.base().matrix_3d();
+        if let Some(rect) = self.scroll_rect() {
+            matrix =
+                matrix * Matrix3D::translate(-rect.x_min.to_pixels(), -rect.y_min.to_pixels(), 0.0);
+        }
+        let mut node = self.parent();
+        while let Some(display_object) = node {
+            if display_object.as_stage().is_some() {
+                break;
+            }
+            if let Some(rect) = display_object.scroll_rect() {
+                matrix = Matrix3D::translate(-rect.x_min.to_pixels(), -rect.y_min.to_pixels(), 0.0)
+                    * matrix;
+            }
+            matrix = display_object.base().matrix_3d() * matrix;
+            node = display_object.parent();
+        }
+        matrix
+    }
+
+    /// Returns the perspective projection that applies to this object, in global stage space.
+    ///
+    /// This is the projection of the nearest ancestor that has one, or else a default
+    /// projection centered on the stage.
+    fn projection_matrix(&self, stage: Stage<'gc>) -> Matrix3D {
+        let stage_width = f64::from(stage.stage_size().0);
+        let mut node = self.parent();
+        while let Some(display_object) = node {
+            if display_object.as_stage().is_some() {
+                break;
+            }
+            if let Some(projection) = display_object.base().perspective_projection() {
+                let (x, y) = display_object.local_to_global((
+                    Twips::from_pixels(projection.center.0),
+                    Twips::from_pixels(projection.center.1),
+                ));
+                return Matrix3D::perspective(
+                    projection.focal_length(stage_width),
+                    x.to_pixels(),
+                    y.to_pixels(),
+                );
+            }
+            node = display_object.parent();
+        }
+        let (width, height) = stage.stage_size();
+        let projection = PerspectiveProjection {
+            field_of_view: PerspectiveProjection::DEFAULT_FIELD_OF_VIEW,
+            center: (f64::from(width) / 2.0, f64::from(height) / 2.0),
+        };
+        Matrix3D::perspective(
+            projection.focal_length(stage_width),
+            projection.center.0,
+            projection.center.1,
+        )
     }
 
     /// Gets the pixel width of the AABB containing this display object in local space.
@@ -938,7 +1794,8 @@ pub trait TDisplayObject<'gc>:
     /// 1 is fully opaque.
     /// Set by the `_alpha`/`alpha` ActionScript properties.
     fn set_alpha(&self, gc_context: MutationContext<'gc, '_>, value: f64) {
-        self.base_mut(gc_context).set_alpha(value)
+        self.base_mut(gc_context).set_alpha(value);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     fn name(&self) -> AvmString<'gc> {
@@ -953,7 +1810,8 @@ pub trait TDisplayObject<'gc>:
     }
 
     fn set_filters(&self, gc_context: MutationContext<'gc, '_>, filters: Avm2ArrayStorage<'gc>) {
-        self.base_mut(gc_context).set_filters(filters)
+        self.base_mut(gc_context).set_filters(filters);
+        self.invalidate_cached_bitmap(gc_context);
     }
 
     /// Returns the dot-syntax path to this display object, e.g. `_level0.foo.clip`
@@ -1002,6 +1860,7 @@ pub trait TDisplayObject<'gc>:
     }
     fn set_clip_depth(&self, gc_context: MutationContext<'gc, '_>, depth: Depth) {
         self.base_mut(gc_context).set_clip_depth(depth);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// Retrieve the parent of this display object.
@@ -1059,6 +1918,7 @@ pub trait TDisplayObject<'gc>:
             }
         }
         self.base_mut(gc_context).set_masker(node);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
     fn maskee(&self) -> Option<DisplayObject<'gc>> {
         self.base().maskee()
@@ -1075,6 +1935,7 @@ pub trait TDisplayObject<'gc>:
             }
         }
         self.base_mut(gc_context).set_maskee(node);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     fn scroll_rect(&self) -> Option<Rectangle<Twips>> {
@@ -1091,6 +1952,7 @@ pub trait TDisplayObject<'gc>:
         rectangle: Rectangle<Twips>,
     ) {
         self.base_mut(gc_context).next_scroll_rect = rectangle;
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     fn removed(&self) -> bool {
@@ -1112,6 +1974,7 @@ pub trait TDisplayObject<'gc>:
     /// Returned by the `_visible`/`visible` ActionScript properties.
     fn set_visible(&self, gc_context: MutationContext<'gc, '_>, value: bool) {
         self.base_mut(gc_context).set_visible(value);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// The blend mode used when rendering this display object.
@@ -1124,6 +1987,7 @@ pub trait TDisplayObject<'gc>:
     /// Values other than the defualt `BlendMode::Normal` implicitly cause cache-as-bitmap behavior.
     fn set_blend_mode(&self, gc_context: MutationContext<'gc, '_>, value: BlendMode) {
         self.base_mut(gc_context).set_blend_mode(value);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// The opaque background color of this display object.
@@ -1137,6 +2001,7 @@ pub trait TDisplayObject<'gc>:
     /// is ignored.
     fn set_opaque_background(&self, gc_context: MutationContext<'gc, '_>, value: Option<Color>) {
         self.base_mut(gc_context).set_opaque_background(value);
+        self.invalidate_cached_bitmap(gc_context);
     }
 
     /// Whether this display object represents the root of loaded content.
@@ -1194,7 +2059,26 @@ pub trait TDisplayObject<'gc>:
     /// Explicilty sets this display object to be cached into a bitmap rendering.
     /// Note that the object will still be bitmap cached if a filter or blend mode is active.
     fn set_is_bitmap_cached(&self, gc_context: MutationContext<'gc, '_>, value: bool) {
-        self.base_mut(gc_context).set_is_bitmap_cached(value)
+        self.base_mut(gc_context).set_is_bitmap_cached(value);
+        self.invalidate_parent_cached_bitmap(gc_context);
+    }
+
+    /// The matrix used to draw this object into its cached bitmap, in place of the
+    /// object's own scale, rotation and skew.
+    /// Returned by the `cacheAsBitmapMatrix` ActionScript property.
+    fn cache_as_bitmap_matrix(&self) -> Option<Matrix> {
+        self.base().cache_as_bitmap_matrix()
+    }
+
+    /// Sets the matrix used to draw this object into its cached bitmap.
+    /// Set by the `cacheAsBitmapMatrix` ActionScript property.
+    fn set_cache_as_bitmap_matrix(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        matrix: Option<Matrix>,
+    ) {
+        self.base_mut(gc_context).set_cache_as_bitmap_matrix(matrix);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// Whether this display object has a scroll rectangle applied.
@@ -1204,7 +2088,8 @@ pub trait TDisplayObject<'gc>:
 
     /// Sets whether this display object has a scroll rectangle applied.
     fn set_has_scroll_rect(&self, gc_context: MutationContext<'gc, '_>, value: bool) {
-        self.base_mut(gc_context).set_has_scroll_rect(value)
+        self.base_mut(gc_context).set_has_scroll_rect(value);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// Called whenever the focus tracker has deemed this display object worthy, or no longer worthy,
@@ -1483,6 +2368,9 @@ pub trait TDisplayObject<'gc>:
     fn as_edit_text(&self) -> Option<EditText<'gc>> {
         None
     }
+    fn as_text(&self) -> Option<Text<'gc>> {
+        None
+    }
     fn as_morph_shape(&self) -> Option<MorphShape<'gc>> {
         None
     }
@@ -1492,12 +2380,20 @@ pub trait TDisplayObject<'gc>:
     fn as_video(self) -> Option<Video<'gc>> {
         None
     }
+    /// The drawing of this object, for changing it. This marks the object as changed.
     fn as_drawing(&self, _gc_context: MutationContext<'gc, '_>) -> Option<RefMut<'_, Drawing>> {
         None
     }
+    /// The drawing of this object, for reading it without marking the object as changed.
+    fn as_drawing_ref(&self) -> Option<Ref<'_, Drawing>> {
+        None
+    }
     fn as_bitmap(self) -> Option<Bitmap<'gc>> {
         None
     }
+    fn as_loader_display(self) -> Option<LoaderDisplay<'gc>> {
+        None
+    }
     fn as_interactive(self) -> Option<InteractiveObject<'gc>> {
         None
     }
@@ -1789,6 +2685,10 @@ bitflags! {
 
         /// Whether this object has an explicit name.
         const HAS_EXPLICIT_NAME        = 1 << 10;
+
+        /// Whether the contents of this object changed since it was last rendered.
+        /// Used to find the dirty regions of the viewport.
+        const CHANGED_SINCE_RENDER     = 1 << 11;
     }
 }
 
@@ -1961,3 +2861,317 @@ impl Default for SoundTransform {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dirty_region::DirtyRegion;
+    use crate::player::PlayerBuilder;
+    use ruffle_render::backend::null::NullRenderer;
+    use ruffle_render::backend::{
+        Context3D, Context3DCommand, RenderBackend, ShapeHandle, ViewportDimensions,
+    };
+    use ruffle_render::bitmap::BitmapSource;
+    use ruffle_render::commands::Command;
+    use ruffle_render::error::Error as RenderError;
+    use ruffle_render::shape_utils::{DistilledShape, DrawCommand};
+    use std::borrow::Cow;
+    use swf::FillStyle;
+
+    /// A renderer that draws nothing, but counts how often it's asked to draw offscreen.
+    struct OffscreenCountingRenderer {
+        inner: NullRenderer,
+        offscreen_renders: usize,
+    }
+
+    impl RenderBackend for OffscreenCountingRenderer {
+        fn viewport_dimensions(&self) -> ViewportDimensions {
+            self.inner.viewport_dimensions()
+        }
+
+        fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+            self.inner.set_viewport_dimensions(dimensions)
+        }
+
+        fn register_shape(
+            &mut self,
+            shape: DistilledShape,
+            bitmap_source: &dyn BitmapSource,
+        ) -> ShapeHandle {
+            self.inner.register_shape(shape, bitmap_source)
+        }
+
+        fn replace_shape(
+            &mut self,
+            shape: DistilledShape,
+            bitmap_source: &dyn BitmapSource,
+            handle: ShapeHandle,
+        ) {
+            self.inner.replace_shape(shape, bitmap_source, handle)
+        }
+
+        fn register_glyph_shape(&mut self, shape: &swf::Glyph) -> ShapeHandle {
+            self.inner.register_glyph_shape(shape)
+        }
+
+        fn render_offscreen(
+            &mut self,
+            _handle: BitmapHandle,
+            width: u32,
+            height: u32,
+            _commands: CommandList,
+        ) -> Result<RenderBitmap, RenderError> {
+            self.offscreen_renders += 1;
+            let rgba = vec![0; width as usize * height as usize * 4];
+            Ok(RenderBitmap::new(width, height, BitmapFormat::Rgba, rgba))
+        }
+
+        fn submit_frame(&mut self, clear: Color, commands: CommandList) {
+            self.inner.submit_frame(clear, commands)
+        }
+
+        fn register_bitmap(&mut self, bitmap: RenderBitmap) -> Result<BitmapHandle, RenderError> {
+            self.inner.register_bitmap(bitmap)
+        }
+
+        fn update_texture(
+            &mut self,
+            bitmap: &BitmapHandle,
+            width: u32,
+            height: u32,
+            rgba: Vec<u8>,
+        ) -> Result<(), RenderError> {
+            self.inner.update_texture(bitmap, width, height, rgba)
+        }
+
+        fn create_context3d(&mut self) -> Result<Box<dyn Context3D>, RenderError> {
+            self.inner.create_context3d()
+        }
+
+        fn context3d_present<'gc>(
+            &mut self,
+            context: &mut dyn Context3D,
+            commands: Vec<Context3DCommand<'gc>>,
+            mc: MutationContext<'gc, '_>,
+        ) -> Result<(), RenderError> {
+            self.inner.context3d_present(context, commands, mc)
+        }
+
+        fn debug_info(&self) -> Cow<'static, str> {
+            self.inner.debug_info()
+        }
+    }
+
+    /// Render with a fresh render context whose transform is `matrix`, returning the
+    /// commands that were drawn.
+    fn render_with<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        matrix: Matrix,
+        render: impl FnOnce(&mut RenderContext<'_, 'gc, '_>),
+    ) -> CommandList {
+        let mut transform_stack = TransformStack::new();
+        transform_stack.push(&Transform {
+            matrix,
+            color_transform: Default::default(),
+        });
+        let mut render_context = RenderContext {
+            renderer: &mut *context.renderer,
+            commands: CommandList::new(),
+            gc_context: context.gc_context,
+            ui: &mut *context.ui,
+            library: &*context.library,
+            transform_stack: &mut transform_stack,
+            is_offscreen: false,
+            cull_bounds: Default::default(),
+            dirty_region: None,
+            known_viewport_bounds: Default::default(),
+            stage: context.stage,
+            clip_depth_stack: vec![],
+            allow_mask: true,
+        };
+        render(&mut render_context);
+        render_context.commands
+    }
+
+    /// Render `object` through its cached bitmap with its own matrix.
+    fn render_cached<'gc>(context: &mut UpdateContext<'_, 'gc, '_>, object: DisplayObject<'gc>) {
+        let matrix = *object.base().matrix();
+        render_with(context, matrix, |context| {
+            assert!(render_bitmap_cached(object, context));
+        });
+    }
+
+    fn offscreen_renders(context: &mut UpdateContext<'_, '_, '_>) -> usize {
+        context
+            .renderer
+            .downcast_ref::<OffscreenCountingRenderer>()
+            .unwrap()
+            .offscreen_renders
+    }
+
+    fn with_player<F>(test: F)
+    where
+        F: for<'a, 'gc> FnOnce(&mut UpdateContext<'a, 'gc, '_>),
+    {
+        let renderer = OffscreenCountingRenderer {
+            inner: NullRenderer::new(ViewportDimensions {
+                width: 100,
+                height: 100,
+                scale_factor: 1.0,
+            }),
+            offscreen_renders: 0,
+        };
+        let player = PlayerBuilder::new()
+            .with_renderer(renderer)
+            .with_movie(SwfMovie::empty(10))
+            .build();
+        let mut player = player.lock().unwrap();
+        player.mutate_with_update_context(|context| test(context));
+    }
+
+    /// Create a clip with a filled 10x10 pixel square drawn in it.
+    fn square_clip<'gc>(context: &mut UpdateContext<'_, 'gc, '_>) -> DisplayObject<'gc> {
+        let clip = MovieClip::new(context.swf.clone(), context.gc_context);
+        let mut drawing = clip.drawing(context.gc_context);
+        drawing.set_fill_style(Some(FillStyle::Color(Color::from_rgb(0xFF0000, 255))));
+        for (x, y) in [(10.0, 0.0), (10.0, 10.0), (0.0, 10.0), (0.0, 0.0)] {
+            drawing.draw_command(DrawCommand::LineTo {
+                x: Twips::from_pixels(x),
+                y: Twips::from_pixels(y),
+            });
+        }
+        drop(drawing);
+        clip.into()
+    }
+
+    #[test]
+    fn translation_does_not_redraw_cached_bitmap() {
+        with_player(|context| {
+            let clip = square_clip(context);
+            clip.set_is_bitmap_cached(context.gc_context, true);
+
+            render_cached(context, clip);
+            assert_eq!(offscreen_renders(context), 1);
+
+            clip.set_x(context.gc_context, 25.5);
+            clip.set_y(context.gc_context, -10.0);
+            render_cached(context, clip);
+            assert_eq!(offscreen_renders(context), 1);
+
+            clip.set_rotation(context.gc_context, Degrees::from(45.0));
+            render_cached(context, clip);
+            assert_eq!(offscreen_renders(context), 2);
+
+            clip.set_x(context.gc_context, 0.0);
+            render_cached(context, clip);
+            assert_eq!(offscreen_renders(context), 2);
+        });
+    }
+
+    #[test]
+    fn reading_drawing_does_not_redraw_cached_bitmap() {
+        with_player(|context| {
+            let clip = square_clip(context);
+            clip.set_is_bitmap_cached(context.gc_context, true);
+
+            render_cached(context, clip);
+            assert!(clip.as_drawing_ref().is_some());
+            render_cached(context, clip);
+            assert_eq!(offscreen_renders(context), 1);
+
+            drop(clip.as_drawing(context.gc_context));
+            render_cached(context, clip);
+            assert_eq!(offscreen_renders(context), 2);
+        });
+    }
+
+    /// Render `object` as if it were drawn offscreen, returning the commands.
+    fn render_offscreen<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: DisplayObject<'gc>,
+    ) -> Vec<Command> {
+        render_with(context, Matrix::IDENTITY, |context| {
+            context.is_offscreen = true;
+            object.render(context);
+        })
+        .commands
+    }
+
+    #[test]
+    fn cached_mask_and_maskee_use_alpha_mask() {
+        with_player(|context| {
+            let maskee = square_clip(context);
+            let mask = square_clip(context);
+            maskee.set_masker(context.gc_context, Some(mask), true);
+            mask.set_maskee(context.gc_context, Some(maskee), true);
+            let is_alpha_mask = |command: &Command| matches!(command, Command::AlphaMask { .. });
+            let is_push_mask = |command: &Command| matches!(command, Command::PushMask);
+
+            // A mask is only soft when both it and its maskee are cached.
+            maskee.set_is_bitmap_cached(context.gc_context, true);
+            let commands = render_offscreen(context, maskee);
+            assert!(commands.iter().any(is_push_mask));
+            assert!(!commands.iter().any(is_alpha_mask));
+
+            mask.set_is_bitmap_cached(context.gc_context, true);
+            let commands = render_offscreen(context, maskee);
+            assert!(!commands.iter().any(is_push_mask));
+            assert!(commands.iter().any(is_alpha_mask));
+
+            maskee.set_is_bitmap_cached(context.gc_context, false);
+            let commands = render_offscreen(context, maskee);
+            assert!(commands.iter().any(is_push_mask));
+            assert!(!commands.iter().any(is_alpha_mask));
+        });
+    }
+
+    fn pixel_bounds(x_min: f64, y_min: f64, x_max: f64, y_max: f64) -> BoundingBox {
+        BoundingBox {
+            x_min: Twips::from_pixels(x_min),
+            y_min: Twips::from_pixels(y_min),
+            x_max: Twips::from_pixels(x_max),
+            y_max: Twips::from_pixels(y_max),
+            valid: true,
+        }
+    }
+
+    /// Render `object` on the viewport as a whole frame, returning the rectangles that
+    /// `region` found to be dirty.
+    fn render_frame<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: DisplayObject<'gc>,
+        region: &mut DirtyRegion,
+    ) -> Option<Vec<BoundingBox>> {
+        let viewport = pixel_bounds(0.0, 0.0, 100.0, 100.0);
+        render_with(context, Matrix::IDENTITY, |context| {
+            context.cull_bounds = viewport.clone();
+            context.dirty_region = Some(&mut *region);
+            object.render(context);
+        });
+        region.finish_frame(Color::WHITE, viewport, Matrix::IDENTITY)
+    }
+
+    #[test]
+    fn moving_child_dirties_its_old_and_new_bounds() {
+        with_player(|context| {
+            let container = MovieClip::new(context.swf.clone(), context.gc_context);
+            let (a, b) = (square_clip(context), square_clip(context));
+            container.replace_at_depth(context, a, 1);
+            container.replace_at_depth(context, b, 2);
+            b.set_x(context.gc_context, 50.0);
+            let mut region = DirtyRegion::new();
+
+            assert_eq!(render_frame(context, container.into(), &mut region), None);
+            assert_eq!(
+                render_frame(context, container.into(), &mut region),
+                Some(vec![])
+            );
+
+            a.set_y(context.gc_context, 5.0);
+            assert_eq!(
+                render_frame(context, container.into(), &mut region),
+                Some(vec![pixel_bounds(0.0, 0.0, 10.0, 15.0)])
+            );
+        });
+    }
+}
@@ -150,8 +150,9 @@ impl<'gc> Avm1Button<'gc> {
         // Children that exist in both states should persist and not be recreated.
         // TODO: This behavior probably differs in AVM2 (I suspect they always get recreated).
         let mut children = Vec::new();
+        let static_data = self.0.read().static_data;
 
-        for record in &self.0.read().static_data.read().records {
+        for record in &static_data.read().records {
             if record.states.contains(state.into()) {
                 // State contains this depth, so we don't have to remove it.
                 removed_depths.remove(&record.depth.into());
@@ -265,6 +265,7 @@ impl<'gc> Avm2Button<'gc> {
 
     /// Change the rendered state of the button.
     pub fn set_state(self, context: &mut UpdateContext<'_, 'gc, '_>, state: ButtonState) {
+        self.invalidate_cached_bitmap(context.gc_context);
         self.0.write(context.gc_context).state = state;
         let button = self.0.read();
         if let Some(state) = button.up_state {
@@ -302,6 +303,7 @@ impl<'gc> Avm2Button<'gc> {
         state: swf::ButtonState,
         child: Option<DisplayObject<'gc>>,
     ) {
+        self.invalidate_cached_bitmap(context.gc_context);
         let child_was_on_stage = child.map(|c| c.is_on_stage(context)).unwrap_or(false);
         let old_state_child = self.get_state_child(state);
         let is_cur_state = swf::ButtonState::from(self.0.read().state) == state;
@@ -176,6 +176,7 @@ impl<'gc> Bitmap<'gc> {
         bitmap_data: GcCell<'gc, crate::bitmap::bitmap_data::BitmapData<'gc>>,
     ) {
         self.0.write(context.gc_context).bitmap_data = bitmap_data;
+        self.invalidate_cached_bitmap(context.gc_context);
     }
 
     pub fn avm2_bitmapdata_class(self) -> Option<Avm2ClassObject<'gc>> {
@@ -214,6 +215,15 @@ impl<'gc> Bitmap<'gc> {
 
     pub fn set_smoothing(self, mc: MutationContext<'gc, '_>, smoothing: bool) {
         self.0.write(mc).smoothing = smoothing;
+        self.invalidate_cached_bitmap(mc);
+    }
+
+    /// Set the movie this `Bitmap` is attributed to.
+    ///
+    /// Images loaded by a `Loader` are attributed to a placeholder movie
+    /// carrying the image's URL, so that its origin can be checked later.
+    pub fn set_movie(self, mc: MutationContext<'gc, '_>, movie: Arc<SwfMovie>) {
+        self.0.write(mc).movie = movie;
     }
 }
 
@@ -284,11 +294,6 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext<'_, 'gc, '_>) {
-        if !context.is_offscreen && !self.world_bounds().intersects(&context.stage.view_bounds()) {
-            // Off-screen; culled
-            return;
-        }
-
         let bitmap_data = self.0.read();
         let inner_bitmap_data = bitmap_data.bitmap_data.try_write(context.gc_context);
         if let Ok(mut inner_bitmap_data) = inner_bitmap_data {
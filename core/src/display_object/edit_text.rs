@@ -8,18 +8,24 @@ use crate::avm1::{
     Value as Avm1Value,
 };
 use crate::avm2::{
-    Activation as Avm2Activation, Object as Avm2Object, StageObject as Avm2StageObject,
+    Activation as Avm2Activation, Domain as Avm2Domain, Error as Avm2Error,
+    Multiname as Avm2Multiname, Namespace as Avm2Namespace, Object as Avm2Object,
+    QName as Avm2QName, StageObject as Avm2StageObject, TObject as Avm2TObject,
 };
+use crate::backend::navigator::Request;
 use crate::backend::ui::MouseCursor;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::interactive::{
     InteractiveObject, InteractiveObjectBase, TInteractiveObject,
 };
-use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject};
+use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, MovieClip, TDisplayObject};
 use crate::drawing::Drawing;
-use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode};
+use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, TextControlCode};
 use crate::font::{round_down_to_pixel, Glyph, TextRenderSettings};
-use crate::html::{BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, TextFormat};
+use crate::html::{
+    BoxBounds, FormatSpans, HtmlImage, LayoutBox, LayoutContent, LayoutMetrics, Size, StyleSheet,
+    TextFormat,
+};
 use crate::prelude::*;
 use crate::string::{utils as string_utils, AvmString, WStr, WString};
 use crate::tag_utils::SwfMovie;
@@ -30,7 +36,7 @@ use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use ruffle_render::commands::CommandHandler;
 use ruffle_render::shape_utils::DrawCommand;
 use ruffle_render::transform::Transform;
-use std::{cell::Ref, cell::RefMut, sync::Arc};
+use std::{cell::Ref, cell::RefMut, ops::Range, ops::RangeInclusive, sync::Arc};
 use swf::{Color, Twips};
 
 /// The kind of autosizing behavior an `EditText` should have, if any
@@ -135,17 +141,58 @@ pub struct EditTextData<'gc> {
 
     /// Flags indicating the text field's settings.
     flags: EditTextFlag,
+
+    /// The style sheet applied to HTML text, if any.
+    #[collect(require_static)]
+    style_sheet: Option<StyleSheet>,
+
+    /// The AVM object the style sheet was created from.
+    style_sheet_object: Option<AvmObject<'gc>>,
+
+    /// The HTML last assigned to this text field, before it was lowered into
+    /// text spans. Used to restyle the text when the style sheet changes.
+    html_source: Option<WString>,
+
+    /// The range of the link under the mouse cursor, which is styled with the
+    /// style sheet's `a:hover` style.
+    #[collect(require_static)]
+    hovered_link: Option<(usize, usize)>,
+
+    /// The text spans as they were before `a:hover` was applied.
+    unhovered_text_spans: Option<FormatSpans>,
+
+    /// The images embedded in the HTML text, in the same order as
+    /// `text_spans.images()`.
+    ///
+    /// This is `None` for library prototypes, which never display images.
+    images: Option<Vec<EditTextImage<'gc>>>,
+
+    /// The characters that the user is allowed to enter, if restricted.
+    restrict: Option<EditTextRestrict>,
+
+    /// The maximum number of characters that the user can enter, or 0 if
+    /// unlimited.
+    max_chars: i32,
+
+    /// The last time the user clicked on the text, used to detect double and
+    /// triple clicks.
+    #[collect(require_static)]
+    last_click: Option<TextClick>,
 }
 
 // TODO: would be nicer to compute (and return) this during layout, instead of afterwards
 /// Compute line (index, offset, extent) from the layout data.
 fn get_line_data(layout: &[LayoutBox]) -> Vec<LineData> {
-    // if there are no boxes, there are no lines
-    if layout.is_empty() {
-        return Vec::new();
-    }
+    // Images float beside the lines rather than being part of them.
+    let mut boxes = layout
+        .iter()
+        .filter(|layout_box| layout_box.as_image().is_none());
 
-    let first_box = &layout[0];
+    // if there are no boxes, there are no lines
+    let first_box = match boxes.next() {
+        Some(first_box) => first_box,
+        None => return Vec::new(),
+    };
 
     let mut index = 1;
     let mut offset = first_box.bounds().offset_y();
@@ -153,7 +200,7 @@ fn get_line_data(layout: &[LayoutBox]) -> Vec<LineData> {
 
     let mut line_data = Vec::new();
 
-    for layout_box in layout.get(1..).unwrap() {
+    for layout_box in boxes {
         let bounds = layout_box.bounds();
 
         // if the top of the new box is lower than the bottom of the old box, it's a new line
@@ -198,7 +245,7 @@ impl<'gc> EditText<'gc> {
 
         let text = WString::from_utf8(&text.to_str_lossy(encoding));
         let mut text_spans = if swf_tag.is_html() {
-            FormatSpans::from_html(&text, default_format, swf_tag.is_multiline())
+            FormatSpans::from_html(&text, default_format, None, swf_tag.is_multiline(), false)
         } else {
             FormatSpans::from_text(text, default_format)
         };
@@ -222,6 +269,8 @@ impl<'gc> EditText<'gc> {
             bounds.width() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0),
             swf_tag.is_word_wrap(),
             !swf_tag.use_outlines(),
+            // Images are created once this text field has been instantiated.
+            &[],
         );
         let line_data = get_line_data(&layout);
 
@@ -278,6 +327,15 @@ impl<'gc> EditText<'gc> {
                 hscroll: 0.0,
                 line_data,
                 scroll: 1,
+                style_sheet: None,
+                style_sheet_object: None,
+                html_source: None,
+                hovered_link: None,
+                unhovered_text_spans: None,
+                images: None,
+                restrict: None,
+                max_chars: swf_tag.max_length().map_or(0, i32::from),
+                last_click: None,
             },
         ));
 
@@ -312,6 +370,7 @@ impl<'gc> EditText<'gc> {
             .with_is_read_only(true)
             .with_is_selectable(true);
         let text_field = Self::from_swf_tag(context, swf_movie, swf_tag);
+        text_field.0.write(context.gc_context).images = Some(Vec::new());
 
         // Set position.
         {
@@ -332,6 +391,9 @@ impl<'gc> EditText<'gc> {
         let mut edit_text = self.0.write(context.gc_context);
         let default_format = edit_text.text_spans.default_format().clone();
         edit_text.text_spans = FormatSpans::from_text(text.into(), default_format);
+        edit_text.html_source = None;
+        edit_text.hovered_link = None;
+        edit_text.unhovered_text_spans = None;
         drop(edit_text);
 
         self.relayout(context);
@@ -339,7 +401,12 @@ impl<'gc> EditText<'gc> {
 
     pub fn html_text(self) -> WString {
         if self.is_html() {
-            self.0.read().text_spans.to_html()
+            let edit_text = self.0.read();
+            match &edit_text.html_source {
+                // Styled text is returned as it was assigned.
+                Some(html_source) if edit_text.style_sheet.is_some() => html_source.clone(),
+                _ => edit_text.text_spans.to_html(),
+            }
         } else {
             // Non-HTML text fields always return plain text.
             self.text()
@@ -353,8 +420,13 @@ impl<'gc> EditText<'gc> {
             write.text_spans = FormatSpans::from_html(
                 text,
                 default_format,
+                write.style_sheet.as_ref(),
                 write.flags.contains(EditTextFlag::MULTILINE),
+                write.flags.contains(EditTextFlag::CONDENSE_WHITE),
             );
+            write.html_source = Some(text.into());
+            write.hovered_link = None;
+            write.unhovered_text_spans = None;
             drop(write);
 
             self.relayout(context);
@@ -363,6 +435,221 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    pub fn style_sheet_object(self) -> Option<AvmObject<'gc>> {
+        self.0.read().style_sheet_object
+    }
+
+    /// Applies a style sheet to the HTML text of this field, restyling any
+    /// HTML that has already been assigned.
+    ///
+    /// Like Flash Player, text fields with a style sheet can't be edited.
+    pub fn set_style_sheet(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        style_sheet: Option<(AvmObject<'gc>, StyleSheet)>,
+    ) {
+        let mut write = self.0.write(context.gc_context);
+        match style_sheet {
+            Some((object, style_sheet)) => {
+                write.flags.insert(EditTextFlag::READ_ONLY);
+                write.style_sheet_object = Some(object);
+                write.style_sheet = Some(style_sheet);
+            }
+            None => {
+                write.style_sheet_object = None;
+                write.style_sheet = None;
+            }
+        }
+        let html_source = write.html_source.clone();
+        drop(write);
+
+        if let Some(html_source) = html_source {
+            self.set_html_text(&html_source, context);
+        }
+    }
+
+    /// The image embedded in the HTML text with the given `id`.
+    pub fn image_by_id(self, id: &WStr, case_sensitive: bool) -> Option<DisplayObject<'gc>> {
+        self.0
+            .read()
+            .images
+            .iter()
+            .flatten()
+            .find(|image| match &image.source.id {
+                Some(image_id) if case_sensitive => image_id.as_wstr() == id,
+                Some(image_id) => image_id.eq_ignore_case(id),
+                None => false,
+            })
+            .and_then(|image| image.display_object)
+    }
+
+    /// Creates display objects for the images embedded in the HTML text, if
+    /// they've changed since the last time this was called.
+    fn update_images(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let sources = self.0.read().text_spans.images().to_vec();
+        let old_images = {
+            let mut write = self.0.write(context.gc_context);
+            let images = match &mut write.images {
+                Some(images) => images,
+                None => return,
+            };
+            let is_unchanged = images.len() == sources.len()
+                && images
+                    .iter()
+                    .zip(&sources)
+                    .all(|(image, source)| image.source.same_content(source));
+            if is_unchanged {
+                // Images may have moved around in the text.
+                for (image, source) in images.iter_mut().zip(sources) {
+                    image.source = source;
+                }
+                return;
+            }
+            std::mem::take(images)
+        };
+
+        for image in old_images {
+            if let Some(display_object) = image.display_object {
+                context.load_manager.cancel_loads_into_clip(display_object);
+                display_object.unload(context);
+                display_object.set_parent(context.gc_context, None);
+            }
+        }
+
+        let mut images = Vec::with_capacity(sources.len());
+        for source in sources {
+            let display_object = self.create_image(context, &source);
+            if let Some(display_object) = display_object {
+                display_object.set_parent(context.gc_context, Some(self.into()));
+                if let Some(id) = &source.id {
+                    display_object.set_name(
+                        context.gc_context,
+                        AvmString::new(context.gc_context, id.clone()),
+                    );
+                }
+            }
+            images.push(EditTextImage {
+                source,
+                display_object,
+            });
+        }
+        self.0.write(context.gc_context).images = Some(images);
+    }
+
+    /// Creates the display object for an image embedded in the HTML text.
+    ///
+    /// The image's `src` is first looked up as a library symbol, by its class
+    /// name in AVM2 or its export name in AVM1. Otherwise, it's loaded from
+    /// the URL it names.
+    fn create_image(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        image: &HtmlImage,
+    ) -> Option<DisplayObject<'gc>> {
+        let movie = self.movie();
+        let src = AvmString::new(context.gc_context, image.src.clone());
+
+        if movie.is_action_script_3() {
+            let domain = context
+                .library
+                .library_for_movie(movie)
+                .map(|library| library.avm2_domain());
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+
+            let qname = Avm2QName::from_qualified_name(src, activation.context.gc_context);
+            let image = match domain.filter(|domain| domain.has_definition(qname)) {
+                Some(domain) => instantiate_avm2_symbol(&mut activation, domain, qname),
+                None => load_avm2_image(&mut activation, src),
+            };
+            match image {
+                Ok(image) => image.as_display_object(),
+                Err(e) => {
+                    log::error!("Error when creating image {} for text field: {}", src, e);
+                    None
+                }
+            }
+        } else {
+            let library = context.library.library_for_movie_mut(movie.clone());
+            let display_object = if library.character_by_export_name(src).is_some() {
+                library
+                    .instantiate_by_export_name(src, context.gc_context)
+                    .ok()?
+            } else {
+                let clip = MovieClip::new(
+                    Arc::new(SwfMovie::empty(movie.version())),
+                    context.gc_context,
+                );
+                let future = context.load_manager.load_movie_into_clip(
+                    context.player.clone(),
+                    clip.into(),
+                    Request::get(src.to_utf8_lossy().into_owned()),
+                    None,
+                    None,
+                    None,
+                    Default::default(),
+                );
+                context.navigator.spawn_future(future);
+                clip.into()
+            };
+            display_object.post_instantiation(context, None, Instantiator::Movie, false);
+            Some(display_object)
+        }
+    }
+
+    /// The size to lay out each of the images in the HTML text at.
+    fn image_sizes(self) -> Vec<Size<Twips>> {
+        self.0
+            .read()
+            .images
+            .iter()
+            .flatten()
+            .map(|image| {
+                let bounds = image
+                    .display_object
+                    .map(|display_object| display_object.bounds())
+                    .unwrap_or_default();
+                let width = image
+                    .source
+                    .width
+                    .map(Twips::from_pixels)
+                    .unwrap_or_else(|| bounds.width());
+                let height = image
+                    .source
+                    .height
+                    .map(Twips::from_pixels)
+                    .unwrap_or_else(|| bounds.height());
+                Size::from((width, height))
+            })
+            .collect()
+    }
+
+    /// Lays out the text again if an image without an explicit size has
+    /// changed size, such as when it finishes loading.
+    fn relayout_if_images_resized(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let image_sizes = self.image_sizes();
+        let is_resized = self.0.read().layout.iter().any(|layout_box| {
+            layout_box.as_image().map_or(false, |index| {
+                let bounds = layout_box.bounds();
+                image_sizes.get(index) != Some(&Size::from((bounds.width(), bounds.height())))
+            })
+        });
+
+        if is_resized {
+            self.relayout(context);
+        }
+    }
+
+    /// The display objects of the images embedded in the HTML text.
+    fn image_objects(self) -> Vec<DisplayObject<'gc>> {
+        self.0
+            .read()
+            .images
+            .iter()
+            .flatten()
+            .filter_map(|image| image.display_object)
+            .collect()
+    }
+
     pub fn text_length(self) -> usize {
         self.0.read().text_spans.text().len()
     }
@@ -433,6 +720,39 @@ impl<'gc> EditText<'gc> {
         self.relayout(context);
     }
 
+    pub fn condense_white(self) -> bool {
+        self.0.read().flags.contains(EditTextFlag::CONDENSE_WHITE)
+    }
+
+    /// Sets whether whitespace is condensed in HTML text. This only affects
+    /// HTML text assigned afterwards.
+    pub fn set_condense_white(self, condense_white: bool, gc_context: MutationContext<'gc, '_>) {
+        self.0
+            .write(gc_context)
+            .flags
+            .set(EditTextFlag::CONDENSE_WHITE, condense_white);
+    }
+
+    pub fn restrict(self) -> Option<WString> {
+        self.0
+            .read()
+            .restrict
+            .as_ref()
+            .map(|restrict| restrict.value().into())
+    }
+
+    pub fn set_restrict(self, restrict: Option<&WStr>, gc_context: MutationContext<'gc, '_>) {
+        self.0.write(gc_context).restrict = restrict.map(EditTextRestrict::from_pattern);
+    }
+
+    pub fn max_chars(self) -> i32 {
+        self.0.read().max_chars
+    }
+
+    pub fn set_max_chars(self, max_chars: i32, gc_context: MutationContext<'gc, '_>) {
+        self.0.write(gc_context).max_chars = max_chars.max(0);
+    }
+
     pub fn is_selectable(self) -> bool {
         !self.0.read().flags.contains(EditTextFlag::NO_SELECT)
     }
@@ -545,10 +865,12 @@ impl<'gc> EditText<'gc> {
         text: &WStr,
         context: &mut UpdateContext<'_, 'gc, '_>,
     ) {
-        self.0
-            .write(context.gc_context)
-            .text_spans
-            .replace_text(from, to, text, None);
+        let mut edit_text = self.0.write(context.gc_context);
+        edit_text.text_spans.replace_text(from, to, text, None);
+        edit_text.html_source = None;
+        edit_text.hovered_link = None;
+        edit_text.unhovered_text_spans = None;
+        drop(edit_text);
         self.relayout(context);
     }
 
@@ -637,6 +959,7 @@ impl<'gc> EditText<'gc> {
 
     /// Redraw the border of this `EditText`.
     fn redraw_border(self, gc_context: MutationContext<'gc, '_>) {
+        self.invalidate_cached_bitmap(gc_context);
         let mut write = self.0.write(gc_context);
 
         write.drawing.clear();
@@ -687,6 +1010,17 @@ impl<'gc> EditText<'gc> {
     /// Applies to each side.
     const INTERNAL_PADDING: f64 = 2.0;
 
+    /// The longest time between clicks for them to count as a double or
+    /// triple click, in milliseconds.
+    const MULTI_CLICK_INTERVAL_MS: i64 = 500;
+
+    /// How many pixels the text scrolls horizontally per frame while the
+    /// user drags a selection past the edge of the text field.
+    const AUTO_SCROLL_STEP: f64 = 8.0;
+
+    /// The color of the selection highlight when the text field isn't focused.
+    const INACTIVE_SELECTION_COLOR: Color = Color::from_rgb(0xA0A0A0, 0xFF);
+
     /// Relayout the `EditText`.
     ///
     /// This function operats exclusively with the text-span representation of
@@ -694,6 +1028,10 @@ impl<'gc> EditText<'gc> {
     /// have already been calculated and applied to HTML trees lowered into the
     /// text-span representation.
     fn relayout(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        self.invalidate_cached_bitmap(context.gc_context);
+        self.update_images(context);
+        let image_sizes = self.image_sizes();
+
         let mut edit_text = self.0.write(context.gc_context);
         let autosize = edit_text.autosize;
         let is_word_wrap = edit_text.flags.contains(EditTextFlag::WORD_WRAP);
@@ -715,6 +1053,7 @@ impl<'gc> EditText<'gc> {
             edit_text.bounds.width() - padding,
             is_word_wrap,
             !edit_text.flags.contains(EditTextFlag::USE_OUTLINES),
+            &image_sizes,
         );
 
         edit_text.line_data = get_line_data(&new_layout);
@@ -839,11 +1178,19 @@ impl<'gc> EditText<'gc> {
         });
 
         let edit_text = self.0.read();
-        let selection = edit_text.selection;
+        let has_focus = edit_text.flags.contains(EditTextFlag::HAS_FOCUS);
+        // Unfocused text fields only show their selection with `alwaysShowSelection`.
+        let selection = edit_text.selection.filter(|_| {
+            has_focus
+                || edit_text
+                    .flags
+                    .contains(EditTextFlag::ALWAYS_SHOW_SELECTION)
+        });
 
         let caret = if let LayoutContent::Text { start, end, .. } = &lbox.content() {
             if let Some(selection) = selection {
                 if selection.is_caret()
+                    && has_focus
                     && !edit_text.flags.contains(EditTextFlag::READ_ONLY)
                     && selection.start() >= *start
                     && selection.end() <= *end
@@ -883,7 +1230,6 @@ impl<'gc> EditText<'gc> {
                     // If it's highlighted, override the color.
                     match selection {
                         Some(selection) if selection.contains(start + pos) => {
-                            // Draw black selection rect
                             let selection_box = context.transform_stack.transform().matrix
                                 * Matrix::create_box(
                                     advance.to_pixels() as f32,
@@ -892,13 +1238,20 @@ impl<'gc> EditText<'gc> {
                                     x + Twips::from_pixels(-1.0),
                                     Twips::from_pixels(2.0),
                                 );
-                            context.commands.draw_rect(Color::BLACK, &selection_box);
-
-                            // Set text color to white
-                            context.transform_stack.push(&Transform {
-                                matrix: transform.matrix,
-                                color_transform: ColorTransform::default(),
-                            });
+                            if has_focus {
+                                // Draw black selection rect, with white text.
+                                context.commands.draw_rect(Color::BLACK, &selection_box);
+                                context.transform_stack.push(&Transform {
+                                    matrix: transform.matrix,
+                                    color_transform: ColorTransform::default(),
+                                });
+                            } else {
+                                // Inactive selections are drawn in gray.
+                                context
+                                    .commands
+                                    .draw_rect(Self::INACTIVE_SELECTION_COLOR, &selection_box);
+                                context.transform_stack.push(transform);
+                            }
                         }
                         _ => {
                             context.transform_stack.push(transform);
@@ -906,10 +1259,8 @@ impl<'gc> EditText<'gc> {
                     }
 
                     // Render glyph.
-                    let glyph_shape_handle = glyph.shape_handle(context.renderer);
-                    context
-                        .commands
-                        .render_shape(glyph_shape_handle, context.transform_stack.transform());
+                    let glyph_transform = context.transform_stack.transform().clone();
+                    font.render_glyph(context, glyph, &glyph_transform, &edit_text.render_settings);
                     context.transform_stack.pop();
 
                     if let Some((caret_pos, length)) = caret {
@@ -943,6 +1294,31 @@ impl<'gc> EditText<'gc> {
             drawing.render(context);
         }
 
+        if let Some(image) = lbox
+            .as_image()
+            .and_then(|index| edit_text.images.iter().flatten().nth(index))
+            .and_then(|image| image.display_object)
+        {
+            // Images given an explicit size are stretched to fit it.
+            let natural_bounds = image.bounds();
+            let scale = |size: Twips, natural_size: Twips| {
+                if natural_size > Twips::ZERO {
+                    size.to_pixels() / natural_size.to_pixels()
+                } else {
+                    1.0
+                }
+            };
+            context.transform_stack.push(&Transform {
+                matrix: Matrix::scale(
+                    scale(lbox.bounds().width(), natural_bounds.width()) as f32,
+                    scale(lbox.bounds().height(), natural_bounds.height()) as f32,
+                ) * Matrix::translate(-natural_bounds.x_min, -natural_bounds.y_min),
+                ..Default::default()
+            });
+            image.render(context);
+            context.transform_stack.pop();
+        }
+
         context.transform_stack.pop();
     }
 
@@ -1084,6 +1460,7 @@ impl<'gc> EditText<'gc> {
         selection: Option<TextSelection>,
         gc_context: MutationContext<'gc, '_>,
     ) {
+        self.invalidate_cached_bitmap(gc_context);
         let mut text = self.0.write(gc_context);
         if let Some(mut selection) = selection {
             selection.clamp(text.text_spans.text().len());
@@ -1102,6 +1479,7 @@ impl<'gc> EditText<'gc> {
         gc_context: MutationContext<'gc, '_>,
         settings: TextRenderSettings,
     ) {
+        self.invalidate_cached_bitmap(gc_context);
         self.0.write(gc_context).render_settings = settings
     }
 
@@ -1111,6 +1489,7 @@ impl<'gc> EditText<'gc> {
 
     pub fn set_hscroll(self, hscroll: f64, context: &mut UpdateContext<'_, 'gc, '_>) {
         self.0.write(context.gc_context).hscroll = hscroll;
+        self.invalidate_cached_bitmap(context.gc_context);
     }
 
     pub fn scroll(self) -> usize {
@@ -1128,53 +1507,113 @@ impl<'gc> EditText<'gc> {
         };
         let clamped = scroll_lines.clamp(1, self.maxscroll());
         self.0.write(context.gc_context).scroll = clamped;
-    }
+        self.invalidate_cached_bitmap(context.gc_context);
+    }
+
+    /// The range of the link containing the character at the given index.
+    fn link_range(text_spans: &FormatSpans, index: usize) -> Option<(usize, usize)> {
+        let spans: Vec<_> = text_spans
+            .iter_spans()
+            .map(|(start, end, _, span)| (start, end, &span.url))
+            .collect();
+        let i = spans
+            .iter()
+            .position(|(start, end, url)| (*start..*end).contains(&index) && !url.is_empty())?;
+        let url = spans[i].2;
+        let first = spans[..i]
+            .iter()
+            .rposition(|(_, _, other)| *other != url)
+            .map_or(0, |other| other + 1);
+        let last = spans[i..]
+            .iter()
+            .position(|(_, _, other)| *other != url)
+            .map_or(spans.len(), |other| i + other);
+        Some((spans[first].0, spans[last - 1].1))
+    }
+
+    /// Styles the link under the given screen position with the style sheet's
+    /// `a:hover` style, restoring the link that was previously hovered.
+    fn update_hovered_link(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        position: Option<(Twips, Twips)>,
+    ) {
+        let hover_format = self
+            .0
+            .read()
+            .style_sheet
+            .as_ref()
+            .and_then(StyleSheet::hover_format);
+        let hover_format = match hover_format {
+            Some(hover_format) => hover_format,
+            None => return,
+        };
 
-    pub fn screen_position_to_index(self, position: (Twips, Twips)) -> Option<usize> {
-        let text = self.0.read();
-        let position = self.global_to_local(position);
-        let position = (
-            position.0 + Twips::from_pixels(Self::INTERNAL_PADDING),
-            position.1 + Twips::from_pixels(Self::INTERNAL_PADDING),
-        );
+        let index =
+            position.and_then(|position| self.char_index_at_point(self.global_to_local(position)));
 
-        for layout_box in text.layout.iter() {
-            let origin = layout_box.bounds().origin();
-            let mut matrix = Matrix::translate(origin.x(), origin.y());
-            matrix.invert();
-            let local_position = matrix * position;
+        let mut edit_text = self.0.write(context.gc_context);
+        let link = index.and_then(|index| {
+            let text_spans = edit_text
+                .unhovered_text_spans
+                .as_ref()
+                .unwrap_or(&edit_text.text_spans);
+            Self::link_range(text_spans, index)
+        });
+        if link == edit_text.hovered_link {
+            return;
+        }
 
-            if let Some((text, _tf, font, params, color)) =
-                layout_box.as_renderable_text(text.text_spans.text())
-            {
-                let mut result = None;
-                let baseline_adjustment =
-                    font.get_baseline_for_height(params.height()) - params.height();
-                font.evaluate(
-                    text,
-                    self.text_transform(color, baseline_adjustment),
-                    params,
-                    |pos, _transform, _glyph: &Glyph, advance, x| {
-                        if local_position.0 >= x
-                            && local_position.0 <= x + advance
-                            && local_position.1 >= Twips::ZERO
-                            && local_position.1 <= params.height()
-                        {
-                            if local_position.0 >= x + (advance / 2) {
-                                result = Some(string_utils::next_char_boundary(text, pos));
-                            } else {
-                                result = Some(pos);
-                            }
-                        }
-                    },
-                );
-                if result.is_some() {
-                    return result;
-                }
-            }
+        if let Some(text_spans) = edit_text.unhovered_text_spans.take() {
+            edit_text.text_spans = text_spans;
         }
+        if let Some((from, to)) = link {
+            edit_text.unhovered_text_spans = Some(edit_text.text_spans.clone());
+            edit_text
+                .text_spans
+                .set_text_format(from, to, &hover_format);
+        }
+        edit_text.hovered_link = link;
 
-        None
+        // Hovering doesn't scroll the text.
+        let (hscroll, scroll) = (edit_text.hscroll, edit_text.scroll);
+        drop(edit_text);
+        self.relayout(context);
+        let mut edit_text = self.0.write(context.gc_context);
+        edit_text.hscroll = hscroll;
+        edit_text.scroll = scroll;
+    }
+
+    /// Filters text that the user is entering in place of `selection`, so
+    /// that it only has characters allowed by `restrict`, and fits within
+    /// `maxChars`.
+    fn filter_input(self, text: &WStr, selection: TextSelection) -> WString {
+        let edit_text = self.0.read();
+        let mut available = if edit_text.max_chars > 0 {
+            let kept_length =
+                edit_text.text_spans.text().len() - (selection.end() - selection.start());
+            (edit_text.max_chars as usize).saturating_sub(kept_length)
+        } else {
+            usize::MAX
+        };
+
+        let mut filtered = WString::new();
+        for c in text.chars() {
+            let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
+            let c = match &edit_text.restrict {
+                Some(restrict) => match restrict.to_allowed(c) {
+                    Some(c) => c,
+                    None => continue,
+                },
+                None => c,
+            };
+            if c.len_utf16() > available {
+                break;
+            }
+            available -= c.len_utf16();
+            filtered.push_char(c);
+        }
+        filtered
     }
 
     pub fn text_input(self, character: char, context: &mut UpdateContext<'_, 'gc, '_>) {
@@ -1220,34 +1659,118 @@ impl<'gc> EditText<'gc> {
                     }
                 }
                 code if !(code as char).is_control() => {
-                    self.replace_text(
-                        selection.start(),
-                        selection.end(),
-                        &WString::from_char(character),
-                        context,
-                    );
-                    let new_start = selection.start() + character.len_utf8();
-                    self.set_selection(
-                        Some(TextSelection::for_position(new_start)),
-                        context.gc_context,
-                    );
-                    changed = true;
+                    let text = self.filter_input(&WString::from_char(character), selection);
+                    if !text.is_empty() {
+                        self.replace_text(selection.start(), selection.end(), &text, context);
+                        let new_start = selection.start() + text.len();
+                        self.set_selection(
+                            Some(TextSelection::for_position(new_start)),
+                            context.gc_context,
+                        );
+                        changed = true;
+                    }
                 }
                 _ => {}
             }
 
             if changed {
-                let mut activation = Avm1Activation::from_nothing(
-                    context.reborrow(),
-                    ActivationIdentifier::root("[Propagate Text Binding]"),
-                    self.into(),
+                self.on_user_input(context);
+            }
+        }
+    }
+
+    /// Handles an editing command, such as from a keyboard shortcut or the
+    /// context menu.
+    pub fn text_control_input(
+        self,
+        control_code: TextControlCode,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) {
+        let selection = match self.selection() {
+            Some(selection) => selection,
+            None => return,
+        };
+        let is_editable = self.is_editable();
+        let can_copy = !selection.is_caret() && !self.is_password();
+
+        match control_code {
+            TextControlCode::SelectAll => {
+                if self.is_selectable() {
+                    self.set_selection(
+                        Some(TextSelection::for_range(0, self.text_length())),
+                        context.gc_context,
+                    );
+                }
+            }
+            TextControlCode::Copy => {
+                if can_copy {
+                    self.copy_selection(selection, context);
+                }
+            }
+            TextControlCode::Cut => {
+                if can_copy && is_editable {
+                    self.copy_selection(selection, context);
+                    self.delete_selection(selection, context);
+                }
+            }
+            TextControlCode::Delete => {
+                if is_editable && !selection.is_caret() {
+                    self.delete_selection(selection, context);
+                }
+            }
+            TextControlCode::Paste => {
+                if !is_editable {
+                    return;
+                }
+                let mut content = context.ui.clipboard_content().replace("\r\n", "\n");
+                if !self.is_multiline() {
+                    // Only the first line is pasted into single line text fields.
+                    if let Some(end) = content.find(&['\r', '\n'][..]) {
+                        content.truncate(end);
+                    }
+                }
+                let text = self.filter_input(&WString::from_utf8(&content), selection);
+                if text.is_empty() {
+                    return;
+                }
+                self.replace_text(selection.start(), selection.end(), &text, context);
+                self.set_selection(
+                    Some(TextSelection::for_position(selection.start() + text.len())),
+                    context.gc_context,
                 );
-                self.propagate_text_binding(&mut activation);
-                self.on_changed(&mut activation);
+                self.on_user_input(context);
             }
         }
     }
 
+    fn copy_selection(self, selection: TextSelection, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let text = self.text();
+        let selected_text = &text[selection.start()..selection.end()];
+        context
+            .ui
+            .set_clipboard_content(selected_text.to_utf8_lossy().into_owned());
+    }
+
+    fn delete_selection(self, selection: TextSelection, context: &mut UpdateContext<'_, 'gc, '_>) {
+        self.replace_text(selection.start(), selection.end(), WStr::empty(), context);
+        self.set_selection(
+            Some(TextSelection::for_position(selection.start())),
+            context.gc_context,
+        );
+        self.on_user_input(context);
+    }
+
+    /// Notifies ActionScript of a change the user made to the text.
+    fn on_user_input(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let mut activation = Avm1Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[Propagate Text Binding]"),
+            self.into(),
+        );
+        self.propagate_text_binding(&mut activation);
+        self.on_changed(&mut activation);
+    }
+
     /// Listens for keyboard text control commands.
     ///
     /// TODO: Add explicit text control events (#4452).
@@ -1256,51 +1779,239 @@ impl<'gc> EditText<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         event: ClipEvent,
     ) -> ClipEventResult {
-        if let ClipEvent::KeyPress { key_code } = event {
-            let mut edit_text = self.0.write(context.gc_context);
-            let selection = edit_text.selection;
-            if let Some(mut selection) = selection {
-                let text = edit_text.text_spans.text();
-                let length = text.len();
-                match key_code {
-                    ButtonKeyCode::Left => {
-                        if (context.input.is_key_down(KeyCode::Shift) || selection.is_caret())
-                            && selection.to > 0
-                        {
-                            selection.to = string_utils::prev_char_boundary(text, selection.to);
-                            if !context.input.is_key_down(KeyCode::Shift) {
-                                selection.from = selection.to;
-                            }
-                        } else if !context.input.is_key_down(KeyCode::Shift) {
-                            selection.to = selection.start();
-                            selection.from = selection.to;
-                        }
-                        selection.clamp(length);
-                        edit_text.selection = Some(selection);
-                        return ClipEventResult::Handled;
-                    }
-                    ButtonKeyCode::Right => {
-                        if (context.input.is_key_down(KeyCode::Shift) || selection.is_caret())
-                            && selection.to < length
-                        {
-                            selection.to = string_utils::next_char_boundary(text, selection.to);
-                            if !context.input.is_key_down(KeyCode::Shift) {
-                                selection.from = selection.to;
-                            }
-                        } else if !context.input.is_key_down(KeyCode::Shift) {
-                            selection.to = selection.end();
-                            selection.from = selection.to;
-                        }
-                        selection.clamp(length);
-                        edit_text.selection = Some(selection);
-                        return ClipEventResult::Handled;
-                    }
-                    _ => (),
+        let key_code = match event {
+            ClipEvent::KeyPress { key_code } => key_code,
+            _ => return ClipEventResult::NotHandled,
+        };
+        let selection = match self.selection() {
+            Some(selection) => selection,
+            None => return ClipEventResult::NotHandled,
+        };
+
+        let is_shift_down = context.input.is_key_down(KeyCode::Shift);
+        let is_ctrl_down = context.input.is_key_down(KeyCode::Control);
+        let text = self.text();
+        let caret = selection.to();
+        let new_caret = match key_code {
+            // Without shift, a selection collapses towards the arrow.
+            ButtonKeyCode::Left if !is_shift_down && !selection.is_caret() => selection.start(),
+            ButtonKeyCode::Right if !is_shift_down && !selection.is_caret() => selection.end(),
+            ButtonKeyCode::Left if is_ctrl_down => prev_word_boundary(&text, caret),
+            ButtonKeyCode::Right if is_ctrl_down => next_word_boundary(&text, caret),
+            ButtonKeyCode::Left => string_utils::prev_char_boundary(&text, caret),
+            ButtonKeyCode::Right => string_utils::next_char_boundary(&text, caret),
+            ButtonKeyCode::Home if is_ctrl_down => 0,
+            ButtonKeyCode::End if is_ctrl_down => text.len(),
+            ButtonKeyCode::Home => self.line_bounds_of_caret(caret).0,
+            ButtonKeyCode::End => self.line_bounds_of_caret(caret).1,
+            ButtonKeyCode::Up => self.caret_on_other_line(caret, -1),
+            ButtonKeyCode::Down => self.caret_on_other_line(caret, 1),
+            ButtonKeyCode::PgUp | ButtonKeyCode::PgDown => {
+                let page = self.bottom_scroll().saturating_sub(self.scroll()) + 1;
+                let page = page as isize;
+                let lines = if key_code == ButtonKeyCode::PgUp {
+                    -page
+                } else {
+                    page
+                };
+                self.set_scroll(self.scroll() as f64 + lines as f64, context);
+                self.caret_on_other_line(caret, lines)
+            }
+            _ => return ClipEventResult::NotHandled,
+        };
+
+        let anchor = if is_shift_down {
+            selection.from()
+        } else {
+            new_caret
+        };
+        self.set_selection(
+            Some(TextSelection::for_range(anchor, new_caret)),
+            context.gc_context,
+        );
+        self.scroll_to_caret(context);
+        ClipEventResult::Handled
+    }
+
+    /// The range of caret positions on the line containing the given caret
+    /// position. The caret can't be placed after the line break ending a line.
+    fn line_bounds_of_caret(self, caret: usize) -> (usize, usize) {
+        let line = self.line_of_caret(caret);
+        let start = self.line_offset(line).unwrap_or(0);
+        let mut end = start + self.line_length(line).unwrap_or(0);
+        let text = self.text();
+        if end > start && matches!(text.get(end - 1), Some(0x0A | 0x0D)) {
+            end -= 1;
+        }
+        (start, end)
+    }
+
+    /// The index of the line the given caret position is displayed on.
+    fn line_of_caret(self, caret: usize) -> usize {
+        // A caret at the end of the text is on the last line.
+        self.line_index_of_char(caret)
+            .unwrap_or_else(|| self.layout_lines().saturating_sub(1))
+    }
+
+    /// The caret position `lines` lines above or below the given one, keeping
+    /// the same column where possible.
+    fn caret_on_other_line(self, caret: usize, lines: isize) -> usize {
+        let line = self.line_of_caret(caret);
+        let last_line = self.layout_lines().saturating_sub(1) as isize;
+        let target_line = line as isize + lines;
+        if target_line < 0 {
+            return 0;
+        } else if target_line > last_line {
+            return self.text_length();
+        }
+
+        let (line_start, _) = self.line_bounds_of_caret(caret);
+        let column = caret - line_start;
+        let target_line_start = self.line_offset(target_line as usize).unwrap_or(0);
+        let (_, target_line_end) = self.line_bounds_of_caret(target_line_start);
+        (target_line_start + column).min(target_line_end)
+    }
+
+    /// Scrolls the text vertically so that the caret is visible.
+    fn scroll_to_caret(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let caret = match self.selection() {
+            Some(selection) => selection.to(),
+            None => return,
+        };
+        // `scroll` and `bottomScroll` are 1-based.
+        let line = self.line_of_caret(caret) + 1;
+        if line < self.scroll() {
+            self.set_scroll(line as f64, context);
+        } else if line > self.bottom_scroll() {
+            let scroll = self.scroll() + line - self.bottom_scroll();
+            self.set_scroll(scroll as f64, context);
+        }
+    }
+
+    /// The caret position closest to the given point, in the text field's
+    /// local coordinate space. Points above or below the text are placed on
+    /// its first or last line.
+    fn caret_index_at_point(self, position: (Twips, Twips)) -> usize {
+        let (_, origin_y) = self.layout_origin();
+        let y = position.1 - origin_y;
+        let line = {
+            let edit_text = self.0.read();
+            let line_data = &edit_text.line_data;
+            if line_data.is_empty() {
+                return 0;
+            }
+            line_data
+                .iter()
+                .position(|line| y < line.extent)
+                .unwrap_or(line_data.len() - 1)
+        };
+
+        let line_start = self.line_offset(line).unwrap_or(0);
+        let (_, line_end) = self.line_bounds_of_caret(line_start);
+        for index in line_start..line_end {
+            if let Some(bounds) = self.char_bounds(index) {
+                if position.0 < bounds.offset_x() + bounds.width() / 2 {
+                    return index;
                 }
             }
         }
+        line_end
+    }
+
+    /// Places the caret where the user clicked. Double-clicking selects a
+    /// word, and triple-clicking selects a line.
+    fn press_selection(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let position = self.global_to_local(*context.mouse_position);
+        let index = self.caret_index_at_point(position);
 
-        ClipEventResult::NotHandled
+        let now = Utc::now().timestamp_millis();
+        let click_count = match self.0.read().last_click {
+            Some(click)
+                if now - click.time < Self::MULTI_CLICK_INTERVAL_MS && click.index == index =>
+            {
+                click.count % 3 + 1
+            }
+            _ => 1,
+        };
+        self.0.write(context.gc_context).last_click = Some(TextClick {
+            time: now,
+            index,
+            count: click_count,
+        });
+
+        let selection = match click_count {
+            2 => {
+                let (start, end) = word_range(&self.text(), index);
+                TextSelection::for_range(start, end)
+            }
+            3 => {
+                let line = self.line_of_caret(index);
+                let start = self.line_offset(line).unwrap_or(0);
+                TextSelection::for_range(start, start + self.line_length(line).unwrap_or(0))
+            }
+            _ => match self.selection() {
+                Some(selection) if context.input.is_key_down(KeyCode::Shift) => {
+                    TextSelection::for_range(selection.from(), index)
+                }
+                _ => TextSelection::for_position(index),
+            },
+        };
+        self.set_selection(Some(selection), context.gc_context);
+    }
+
+    /// Extends the selection to the mouse while the user drags over the text.
+    ///
+    /// If `auto_scroll` is set, the text is also scrolled by a step when the
+    /// mouse is past the edges of the text field.
+    pub fn drag_selection(self, context: &mut UpdateContext<'_, 'gc, '_>, auto_scroll: bool) {
+        let selection = match self.selection() {
+            Some(selection) => selection,
+            None => return,
+        };
+        let position = self.global_to_local(*context.mouse_position);
+
+        if auto_scroll {
+            let bounds = self.0.read().bounds.clone();
+            if position.1 < bounds.y_min {
+                self.set_scroll(self.scroll() as f64 - 1.0, context);
+            } else if position.1 > bounds.y_max {
+                self.set_scroll(self.scroll() as f64 + 1.0, context);
+            }
+
+            let hscroll = if position.0 < bounds.x_min {
+                self.hscroll() - Self::AUTO_SCROLL_STEP
+            } else if position.0 > bounds.x_max {
+                self.hscroll() + Self::AUTO_SCROLL_STEP
+            } else {
+                self.hscroll()
+            };
+            self.set_hscroll(hscroll.clamp(0.0, self.maxhscroll()), context);
+        }
+
+        let index = self.caret_index_at_point(position);
+        self.set_selection(
+            Some(TextSelection::for_range(selection.from(), index)),
+            context.gc_context,
+        );
+    }
+
+    pub fn always_show_selection(self) -> bool {
+        self.0
+            .read()
+            .flags
+            .contains(EditTextFlag::ALWAYS_SHOW_SELECTION)
+    }
+
+    pub fn set_always_show_selection(
+        self,
+        always_show_selection: bool,
+        gc_context: MutationContext<'gc, '_>,
+    ) {
+        self.0
+            .write(gc_context)
+            .flags
+            .set(EditTextFlag::ALWAYS_SHOW_SELECTION, always_show_selection);
+        self.invalidate_cached_bitmap(gc_context);
     }
 
     fn initialize_as_broadcaster(&self, activation: &mut Avm1Activation<'_, 'gc, '_>) {
@@ -1409,6 +2120,10 @@ impl<'gc> EditText<'gc> {
         let read = self.0.read();
 
         for layout_box in read.layout.iter() {
+            if layout_box.as_image().is_some() {
+                continue;
+            }
+
             if let Some(line) = line {
                 if layout_box.bounds().offset_y() < line.offset
                     || layout_box.bounds().extent_y() > line.extent
@@ -1441,7 +2156,7 @@ impl<'gc> EditText<'gc> {
                         font = Some(box_font);
                         text_format = Some(box_text_format);
                     }
-                    LayoutContent::Drawing { .. } => {}
+                    LayoutContent::Drawing { .. } | LayoutContent::Image(..) => {}
                 }
             }
         }
@@ -1462,6 +2177,232 @@ impl<'gc> EditText<'gc> {
             x: union_bounds.offset_x() + Twips::from_pixels(EditText::INTERNAL_PADDING),
         })
     }
+
+    /// The position of the laid-out text relative to the text field's origin,
+    /// taking padding and scrolling into account.
+    fn layout_origin(&self) -> (Twips, Twips) {
+        let edit_text = self.0.read();
+
+        let scroll_offset = if edit_text.scroll > 1 {
+            edit_text
+                .line_data
+                .get(edit_text.scroll - 1)
+                .map_or(Twips::ZERO, |line| line.offset)
+        } else {
+            Twips::ZERO
+        };
+
+        (
+            edit_text.bounds.x_min + Twips::from_pixels(Self::INTERNAL_PADDING)
+                - Twips::from_pixels(edit_text.hscroll),
+            edit_text.bounds.y_min + Twips::from_pixels(Self::INTERNAL_PADDING) - scroll_offset,
+        )
+    }
+
+    /// The index of the line a layout box belongs to.
+    fn line_of_box(line_data: &[LineData], bounds: BoxBounds<Twips>) -> Option<usize> {
+        line_data
+            .iter()
+            .position(|line| bounds.offset_y() >= line.offset && bounds.extent_y() <= line.extent)
+    }
+
+    /// The bounds of the character at the given index, in the text field's
+    /// local coordinate space.
+    ///
+    /// Returns None for characters that aren't rendered, such as line breaks.
+    pub fn char_bounds(self, index: usize) -> Option<BoxBounds<Twips>> {
+        let (origin_x, origin_y) = self.layout_origin();
+        let edit_text = self.0.read();
+        let text = edit_text.text_spans.displayed_text();
+
+        for layout_box in edit_text.layout.iter() {
+            if let LayoutContent::Text {
+                start,
+                end,
+                font,
+                params,
+                ..
+            } = layout_box.content()
+            {
+                if index < *start || index >= *end {
+                    continue;
+                }
+
+                let mut char_x = None;
+                font.evaluate(
+                    text.slice(*start..*end)?,
+                    Default::default(),
+                    *params,
+                    |pos, _transform, _glyph: &Glyph, advance, x| {
+                        if start + pos == index {
+                            char_x = Some((x, advance));
+                        }
+                    },
+                );
+
+                let (x, advance) = char_x?;
+                let bounds = layout_box.bounds();
+                return Some(BoxBounds::from_position_and_size(
+                    (
+                        origin_x + bounds.offset_x() + x,
+                        origin_y + bounds.offset_y(),
+                    )
+                        .into(),
+                    (advance, bounds.height()).into(),
+                ));
+            }
+        }
+
+        None
+    }
+
+    /// The index of the character under the given point, in the text field's
+    /// local coordinate space.
+    pub fn char_index_at_point(self, position: (Twips, Twips)) -> Option<usize> {
+        let (origin_x, origin_y) = self.layout_origin();
+        let (x, y) = (position.0 - origin_x, position.1 - origin_y);
+        let edit_text = self.0.read();
+        let text = edit_text.text_spans.displayed_text();
+
+        for layout_box in edit_text.layout.iter() {
+            if let LayoutContent::Text {
+                start,
+                end,
+                font,
+                params,
+                ..
+            } = layout_box.content()
+            {
+                let bounds = layout_box.bounds();
+                if y < bounds.offset_y() || y >= bounds.extent_y() || x < bounds.offset_x() {
+                    continue;
+                }
+
+                let local_x = x - bounds.offset_x();
+                let mut result = None;
+                font.evaluate(
+                    text.slice(*start..*end)?,
+                    Default::default(),
+                    *params,
+                    |pos, _transform, _glyph: &Glyph, advance, x| {
+                        if local_x >= x && local_x < x + advance {
+                            result = Some(start + pos);
+                        }
+                    },
+                );
+                if result.is_some() {
+                    return result;
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The index of the line under the given point, in the text field's
+    /// local coordinate space.
+    pub fn line_index_at_point(self, position: (Twips, Twips)) -> Option<usize> {
+        let (_, origin_y) = self.layout_origin();
+        let y = position.1 - origin_y;
+
+        self.0
+            .read()
+            .line_data
+            .iter()
+            .position(|line| y >= line.offset && y < line.extent)
+    }
+
+    /// The index of the line containing the character at the given index.
+    ///
+    /// Line breaks belong to the line they end.
+    pub fn line_index_of_char(self, index: usize) -> Option<usize> {
+        let edit_text = self.0.read();
+        if index >= edit_text.text_spans.text().len() {
+            return None;
+        }
+
+        let layout_box = edit_text
+            .layout
+            .iter()
+            .rev()
+            .find(|layout_box| {
+                matches!(layout_box.content(), LayoutContent::Text { start, .. } if *start <= index)
+            })?;
+        Self::line_of_box(&edit_text.line_data, layout_box.bounds())
+    }
+
+    /// The index of the first character of each line.
+    fn line_starts(self) -> Vec<usize> {
+        let text_length = self.text_length();
+        let edit_text = self.0.read();
+        let text_boxes =
+            edit_text
+                .layout
+                .iter()
+                .filter_map(|layout_box| match layout_box.content() {
+                    LayoutContent::Text { start, .. } => Some((
+                        Self::line_of_box(&edit_text.line_data, layout_box.bounds())?,
+                        *start,
+                    )),
+                    _ => None,
+                });
+        line_starts(edit_text.line_data.len(), text_length, text_boxes)
+    }
+
+    /// The index of the first character in the given line.
+    pub fn line_offset(self, line: usize) -> Option<usize> {
+        line_range(&self.line_starts(), self.text_length(), line).map(|range| range.start)
+    }
+
+    /// The number of characters in the given line, including the line break
+    /// ending it.
+    pub fn line_length(self, line: usize) -> Option<usize> {
+        line_range(&self.line_starts(), self.text_length(), line).map(|range| range.len())
+    }
+
+    /// The text of the given line, including the line break ending it.
+    pub fn line_text(self, line: usize) -> Option<WString> {
+        let offset = self.line_offset(line)?;
+        let length = self.line_length(line)?;
+        let edit_text = self.0.read();
+        Some(
+            edit_text
+                .text_spans
+                .text()
+                .slice(offset..offset + length)?
+                .into(),
+        )
+    }
+
+    /// The index of the first character of the paragraph containing the
+    /// character at the given index.
+    pub fn first_char_in_paragraph(self, index: usize) -> Option<usize> {
+        let edit_text = self.0.read();
+        let text = edit_text.text_spans.text();
+        if index >= text.len() {
+            return None;
+        }
+
+        Some(
+            text.slice(..index)?
+                .rfind(&[b'\n', b'\r'][..])
+                .map_or(0, |newline| newline + 1),
+        )
+    }
+
+    /// The number of characters in the paragraph containing the character at
+    /// the given index, including the line break ending it.
+    pub fn paragraph_length(self, index: usize) -> Option<usize> {
+        let start = self.first_char_in_paragraph(index)?;
+        let edit_text = self.0.read();
+        let text = edit_text.text_spans.text();
+
+        let end = text
+            .slice(start..)?
+            .find(&[b'\n', b'\r'][..])
+            .map_or(text.len(), |newline| start + newline + 1);
+        Some(end - start)
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
@@ -1489,16 +2430,56 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         self.0.read().static_data.swf.clone()
     }
 
+    fn enter_frame(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        for image in self.image_objects() {
+            image.enter_frame(context);
+        }
+    }
+
     /// Construct objects placed on this frame.
     fn construct_frame(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
         if context.is_action_script_3() && matches!(self.object2(), Avm2Value::Null) {
             self.construct_as_avm2_object(context, (*self).into());
             self.on_construction_complete(context);
         }
+
+        for image in self.image_objects() {
+            image.construct_frame(context);
+        }
+    }
+
+    fn run_frame(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        // Images run their own frames; only their size is of interest here.
+        self.relayout_if_images_resized(context);
+
+        // Keep scrolling while the user drags a selection past the edges.
+        let is_dragged = context.input.is_mouse_down()
+            && context.mouse_down_object.map_or(false, |object| {
+                DisplayObject::ptr_eq(object.as_displayobject(), (*self).into())
+            });
+        if is_dragged {
+            self.drag_selection(context, true);
+        }
     }
 
-    fn run_frame(&self, _context: &mut UpdateContext) {
-        // Noop
+    fn run_frame_avm2(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        for image in self.image_objects() {
+            image.run_frame_avm2(context);
+        }
+
+        self.run_frame(context);
+    }
+
+    fn run_frame_scripts(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        for image in self.image_objects() {
+            image.run_frame_scripts(context);
+        }
+    }
+
+    fn on_exit_frame(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        for image in self.image_objects() {
+            image.on_exit_frame(context);
+        }
     }
 
     fn as_edit_text(&self) -> Option<EditText<'gc>> {
@@ -1527,6 +2508,17 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         if !self.movie().is_action_script_3() {
             self.construct_as_avm1_object(context, run_frame);
         }
+
+        // Each instance gets its own copy of the images in its HTML text.
+        let mut write = self.0.write(context.gc_context);
+        if write.images.is_none() {
+            write.images = Some(Vec::new());
+        }
+        let has_images = !write.text_spans.images().is_empty();
+        drop(write);
+        if has_images {
+            self.relayout(context);
+        }
     }
 
     fn object(&self) -> Avm1Value<'gc> {
@@ -1630,11 +2622,6 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext<'_, 'gc, '_>) {
-        if !context.is_offscreen && !self.world_bounds().intersects(&context.stage.view_bounds()) {
-            // Off-screen; culled
-            return;
-        }
-
         let edit_text = self.0.read();
         context.transform_stack.push(&Transform {
             matrix: Matrix::translate(edit_text.bounds.x_min, edit_text.bounds.y_min),
@@ -1679,7 +2666,9 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         });
 
         if edit_text.layout.is_empty() && !edit_text.flags.contains(EditTextFlag::READ_ONLY) {
-            let selection = edit_text.selection;
+            let selection = edit_text
+                .selection
+                .filter(|_| edit_text.flags.contains(EditTextFlag::HAS_FOCUS));
             if let Some(selection) = selection {
                 if selection.is_caret()
                     && selection.start() == 0
@@ -1752,14 +2741,22 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
                 .retain(|&text_field| !DisplayObject::ptr_eq(text_field.into(), (*self).into()));
         }
 
+        for image in self.image_objects() {
+            context.load_manager.cancel_loads_into_clip(image);
+            image.unload(context);
+        }
+
         self.set_removed(context.gc_context, true);
     }
 
     fn on_focus_changed(&self, gc_context: MutationContext<'gc, '_>, focused: bool) {
+        self.invalidate_cached_bitmap(gc_context);
         let mut text = self.0.write(gc_context);
         text.flags.set(EditTextFlag::HAS_FOCUS, focused);
-        if !focused {
-            text.selection = None;
+        // The selection is kept while unfocused, but only shown with
+        // `alwaysShowSelection`.
+        if focused && text.selection.is_none() {
+            text.selection = Some(TextSelection::for_position(text.text_spans.text().len()));
         }
     }
 
@@ -1786,11 +2783,16 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
     }
 
     fn filter_clip_event(self, event: ClipEvent) -> ClipEventResult {
-        if event != ClipEvent::Press {
-            return ClipEventResult::NotHandled;
+        match event {
+            ClipEvent::Press => ClipEventResult::Handled,
+            // Styled links react to the mouse moving over them.
+            ClipEvent::MouseMoveInside | ClipEvent::RollOut { .. }
+                if self.0.read().style_sheet.is_some() =>
+            {
+                ClipEventResult::Handled
+            }
+            _ => ClipEventResult::NotHandled,
         }
-
-        ClipEventResult::Handled
     }
 
     fn event_dispatch(
@@ -1798,17 +2800,21 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         event: ClipEvent<'gc>,
     ) -> ClipEventResult {
+        match event {
+            ClipEvent::MouseMoveInside => {
+                self.update_hovered_link(context, Some(*context.mouse_position));
+                return ClipEventResult::Handled;
+            }
+            ClipEvent::RollOut { .. } => {
+                self.update_hovered_link(context, None);
+                return ClipEventResult::Handled;
+            }
+            _ => {}
+        }
+
         let tracker = context.focus_tracker;
         tracker.set(Some(self.into()), context);
-        if let Some(position) = self
-            .screen_position_to_index(*context.mouse_position)
-            .map(TextSelection::for_position)
-        {
-            self.0.write(context.gc_context).selection = Some(position);
-        } else {
-            self.0.write(context.gc_context).selection =
-                Some(TextSelection::for_position(self.text_length()));
-        }
+        self.press_selection(context);
 
         self.event_dispatch_to_avm2(context, event);
 
@@ -1845,6 +2851,8 @@ bitflags::bitflags! {
         const FIRING_VARIABLE_BINDING = 1 << 0;
         const HAS_BACKGROUND = 1 << 1;
         const HAS_FOCUS = 1 << 2;
+        const CONDENSE_WHITE = 1 << 7;
+        const ALWAYS_SHOW_SELECTION = 1 << 13;
 
         // The following bits need to match `swf::EditTextFlag`.
         const READ_ONLY = 1 << 3;
@@ -1872,6 +2880,216 @@ struct EditTextStatic {
     initial_text: Option<WString>,
 }
 
+/// A click on the text of a text field.
+#[derive(Clone, Copy, Debug)]
+struct TextClick {
+    /// When the click happened, in milliseconds since the Unix epoch.
+    time: i64,
+
+    /// The caret position that was clicked.
+    index: usize,
+
+    /// How many clicks in a row this was part of, up to a triple click.
+    count: u8,
+}
+
+/// Whether a code unit is part of a word, for word selection and navigation.
+/// Find the index of the first character of each line, given the line and
+/// first character of every text box in the layout.
+///
+/// Lines without any text, such as empty paragraphs, are assumed to hold just
+/// a line break, so they start one character before the following line.
+fn line_starts(
+    line_count: usize,
+    text_length: usize,
+    text_boxes: impl IntoIterator<Item = (usize, usize)>,
+) -> Vec<usize> {
+    let mut starts = vec![None; line_count];
+    for (line, start) in text_boxes {
+        if let Some(line_start) = starts.get_mut(line) {
+            *line_start = Some(line_start.map_or(start, |line_start: usize| line_start.min(start)));
+        }
+    }
+
+    // The line after the last one would start past the end of the text.
+    let mut next_start = text_length + 1;
+    let mut starts: Vec<_> = starts
+        .into_iter()
+        .rev()
+        .map(|start| {
+            next_start = start.unwrap_or_else(|| next_start.saturating_sub(1));
+            next_start
+        })
+        .collect();
+    starts.reverse();
+    starts
+}
+
+/// The range of characters in the given line, including the line break
+/// ending it.
+fn line_range(starts: &[usize], text_length: usize, line: usize) -> Option<Range<usize>> {
+    let start = *starts.get(line)?;
+    let end = starts.get(line + 1).copied().unwrap_or(text_length);
+    Some(start..end.max(start))
+}
+
+fn is_word_unit(unit: u16) -> bool {
+    char::from_u32(unit.into()).map_or(true, |c| c.is_alphanumeric() || c == '_')
+}
+
+/// The caret position at the start of the word before `index`.
+fn prev_word_boundary(text: &WStr, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while index > 0 && !is_word_unit(text.at(index - 1)) {
+        index -= 1;
+    }
+    while index > 0 && is_word_unit(text.at(index - 1)) {
+        index -= 1;
+    }
+    index
+}
+
+/// The caret position at the start of the word after `index`.
+fn next_word_boundary(text: &WStr, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while index < text.len() && is_word_unit(text.at(index)) {
+        index += 1;
+    }
+    while index < text.len() && !is_word_unit(text.at(index)) {
+        index += 1;
+    }
+    index
+}
+
+/// The range selected by double-clicking on the character at `index`: the
+/// word or run of whitespace there, or a single other character.
+fn word_range(text: &WStr, index: usize) -> (usize, usize) {
+    if index >= text.len() {
+        return (text.len(), text.len());
+    }
+
+    let is_white = |unit: u16| matches!(unit, 0x09 | 0x20);
+    let unit = text.at(index);
+    let same_class: &dyn Fn(u16) -> bool = if is_word_unit(unit) {
+        &is_word_unit
+    } else if is_white(unit) {
+        &is_white
+    } else {
+        return (index, index + 1);
+    };
+
+    let mut start = index;
+    while start > 0 && same_class(text.at(start - 1)) {
+        start -= 1;
+    }
+    let mut end = index + 1;
+    while end < text.len() && same_class(text.at(end)) {
+        end += 1;
+    }
+    (start, end)
+}
+
+/// A `restrict` pattern, which limits the characters that the user can enter
+/// into a text field.
+///
+/// The pattern lists the allowed characters, with `-` marking ranges such as
+/// `A-Z`. Each `^` switches between listing allowed and disallowed
+/// characters, and `\` escapes the character after it.
+#[derive(Clone, Debug, Collect)]
+#[collect(require_static)]
+struct EditTextRestrict {
+    /// The pattern, as it was set.
+    value: WString,
+
+    /// The listed character ranges, and whether they're allowed. Later
+    /// ranges take precedence over earlier ones.
+    ranges: Vec<(RangeInclusive<char>, bool)>,
+
+    /// Whether characters that aren't listed are allowed, which is the case
+    /// when the pattern starts with `^`.
+    allow_unlisted: bool,
+}
+
+impl EditTextRestrict {
+    fn from_pattern(value: &WStr) -> Self {
+        // Resolve escapes first, remembering which characters were escaped.
+        let mut chars = value
+            .chars()
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER));
+        let mut units = Vec::new();
+        while let Some(c) = chars.next() {
+            if c == '\\' {
+                // A trailing backslash is ignored.
+                if let Some(c) = chars.next() {
+                    units.push((c, true));
+                }
+            } else {
+                units.push((c, false));
+            }
+        }
+
+        let mut ranges = Vec::new();
+        let mut is_allowed = true;
+        let mut allow_unlisted = false;
+        let mut i = 0;
+        while i < units.len() {
+            match units[i] {
+                ('^', false) => {
+                    if i == 0 {
+                        allow_unlisted = true;
+                    }
+                    is_allowed = !is_allowed;
+                    i += 1;
+                }
+                (start, _) => match units.get(i + 1..i + 3) {
+                    // A `-` at either end of the pattern is taken literally.
+                    Some([('-', false), (end, is_escaped)]) if *end != '^' || *is_escaped => {
+                        // Reversed ranges don't match anything.
+                        ranges.push((start..=*end, is_allowed));
+                        i += 3;
+                    }
+                    _ => {
+                        ranges.push((start..=start, is_allowed));
+                        i += 1;
+                    }
+                },
+            }
+        }
+
+        Self {
+            value: value.into(),
+            ranges,
+            allow_unlisted,
+        }
+    }
+
+    fn value(&self) -> &WStr {
+        &self.value
+    }
+
+    fn is_allowed(&self, c: char) -> bool {
+        self.ranges
+            .iter()
+            .rev()
+            .find(|(range, _)| range.contains(&c))
+            .map_or(self.allow_unlisted, |(_, is_allowed)| *is_allowed)
+    }
+
+    /// Returns the character to enter in place of `c`, or `None` if it isn't
+    /// allowed.
+    ///
+    /// Like Flash, a disallowed letter is entered in its other case if that
+    /// is allowed instead.
+    fn to_allowed(&self, c: char) -> Option<char> {
+        if self.is_allowed(c) {
+            return Some(c);
+        }
+
+        let mut other_cases = c.to_uppercase().chain(c.to_lowercase());
+        other_cases.find(|&other| other != c && self.is_allowed(other))
+    }
+}
+
 #[derive(Copy, Clone, Debug, Collect)]
 #[collect(require_static)]
 pub struct TextSelection {
@@ -1879,6 +3097,58 @@ pub struct TextSelection {
     to: usize,
 }
 
+/// Instantiates the AVM2 class with the given name, for use as an image in
+/// HTML text.
+fn instantiate_avm2_symbol<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc, '_>,
+    domain: Avm2Domain<'gc>,
+    name: Avm2QName<'gc>,
+) -> Result<Avm2Object<'gc>, Avm2Error<'gc>> {
+    let class = domain
+        .get_defined_value(activation, name)?
+        .coerce_to_object(activation)?;
+    class.construct(activation, &[])
+}
+
+/// Starts loading an image in HTML text from a URL, returning the AVM2
+/// `Loader` that will display it.
+fn load_avm2_image<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc, '_>,
+    url: AvmString<'gc>,
+) -> Result<Avm2Object<'gc>, Avm2Error<'gc>> {
+    let request_class = activation.resolve_class(&Avm2Multiname::new(
+        Avm2Namespace::package("flash.net"),
+        "URLRequest",
+    ))?;
+    let request = request_class.construct(activation, &[url.into()])?;
+
+    let loader_class = activation.resolve_class(&Avm2Multiname::new(
+        Avm2Namespace::package("flash.display"),
+        "Loader",
+    ))?;
+    let loader = loader_class.construct(activation, &[])?;
+    loader.call_property(
+        &Avm2Multiname::public("load"),
+        &[request.into()],
+        activation,
+    )?;
+
+    Ok(loader)
+}
+
+/// An image embedded in the HTML text of an `EditText`.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct EditTextImage<'gc> {
+    /// The `<img>` tag the image was created from.
+    #[collect(require_static)]
+    source: HtmlImage,
+
+    /// The display object showing the image, or `None` if it couldn't be
+    /// created.
+    display_object: Option<DisplayObject<'gc>>,
+}
+
 /// Information about the start and end y-coordinates of a given line of text
 #[derive(Copy, Clone, Debug, Collect)]
 #[collect(require_static)]
@@ -1952,3 +3222,227 @@ impl TextSelection {
         self.to == self.from
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        line_range, line_starts, next_word_boundary, prev_word_boundary, word_range, EditText,
+        EditTextRestrict, TextSelection,
+    };
+    use crate::backend::ui::UiBackend;
+    use crate::context::UpdateContext;
+    use crate::events::TextControlCode;
+    use crate::player::PlayerBuilder;
+    use crate::string::WStr;
+    use crate::tag_utils::SwfMovie;
+
+    fn allowed(pattern: &str, text: &str) -> String {
+        let restrict = EditTextRestrict::from_pattern(WStr::from_units(pattern.as_bytes()));
+        text.chars()
+            .filter_map(|c| restrict.to_allowed(c))
+            .collect()
+    }
+
+    #[test]
+    fn restrict_empty_allows_nothing() {
+        assert_eq!(allowed("", "abc 123"), "");
+    }
+
+    #[test]
+    fn restrict_ranges() {
+        assert_eq!(allowed("A-Z0-9", "AZ09!"), "AZ09");
+        assert_eq!(allowed("0-9.", "3.14e0"), "3.140");
+        assert_eq!(allowed("z-a", "az"), "");
+    }
+
+    #[test]
+    fn restrict_negation() {
+        assert_eq!(allowed("^0-9", "a1b2"), "ab");
+        assert_eq!(allowed("A-Z^Q", "AQZ!"), "AZ");
+        assert_eq!(allowed("^", "a1!"), "a1!");
+        assert_eq!(allowed("a-z^m-n^n", "lmno"), "lno");
+    }
+
+    #[test]
+    fn restrict_literal_dashes() {
+        assert_eq!(allowed("-a", "-ab"), "-a");
+        assert_eq!(allowed("a-", "-ab"), "-a");
+        assert_eq!(allowed("a-c-e", "bd-e"), "b-e");
+    }
+
+    #[test]
+    fn restrict_escapes() {
+        assert_eq!(allowed("\\^\\-\\\\", "^-\\a"), "^-\\");
+        assert_eq!(allowed("a\\-c", "ab-c"), "a-c");
+        assert_eq!(allowed("a\\", "a\\"), "a");
+    }
+
+    #[test]
+    fn restrict_converts_case() {
+        assert_eq!(allowed("A-Z", "aBc"), "ABC");
+        assert_eq!(allowed("a-z^x", "AXx"), "a");
+        assert_eq!(allowed("a-zA-Z", "aB"), "aB");
+    }
+
+    #[test]
+    fn word_boundaries() {
+        let text = WStr::from_units(b"one  two_2, three");
+        assert_eq!(next_word_boundary(text, 0), 5);
+        assert_eq!(next_word_boundary(text, 5), 12);
+        assert_eq!(next_word_boundary(text, 12), 17);
+        assert_eq!(prev_word_boundary(text, 17), 12);
+        assert_eq!(prev_word_boundary(text, 12), 5);
+        assert_eq!(prev_word_boundary(text, 4), 0);
+        assert_eq!(prev_word_boundary(text, 0), 0);
+    }
+
+    #[test]
+    fn double_click_word_range() {
+        let text = WStr::from_units(b"one  two_2, three");
+        assert_eq!(word_range(text, 6), (5, 10));
+        assert_eq!(word_range(text, 3), (3, 5));
+        assert_eq!(word_range(text, 10), (10, 11));
+        assert_eq!(word_range(text, 17), (17, 17));
+    }
+
+    #[test]
+    fn line_starts_from_text_boxes() {
+        // "one two\nthree", with the first line laid out as two boxes.
+        let starts = line_starts(2, 13, [(0, 4), (0, 0), (1, 8)]);
+        assert_eq!(starts, [0, 8]);
+        assert_eq!(line_range(&starts, 13, 0), Some(0..8));
+        assert_eq!(line_range(&starts, 13, 1), Some(8..13));
+    }
+
+    #[test]
+    fn line_starts_with_empty_lines() {
+        // "a\n\n\nb"
+        let starts = line_starts(4, 5, [(0, 0), (3, 4)]);
+        assert_eq!(starts, [0, 2, 3, 4]);
+        assert_eq!(line_range(&starts, 5, 1), Some(2..3));
+        assert_eq!(line_range(&starts, 5, 2), Some(3..4));
+
+        // An empty field still has a single line.
+        assert_eq!(line_starts(1, 0, []), [0]);
+    }
+
+    #[test]
+    fn line_starts_with_empty_last_lines() {
+        // "ab\n\n"
+        let starts = line_starts(3, 4, [(0, 0)]);
+        assert_eq!(starts, [0, 3, 4]);
+        assert_eq!(line_range(&starts, 4, 0), Some(0..3));
+        assert_eq!(line_range(&starts, 4, 2), Some(4..4));
+    }
+
+    #[test]
+    fn line_range_out_of_range() {
+        let starts = line_starts(2, 13, [(0, 0), (1, 8), (5, 9)]);
+        assert_eq!(starts, [0, 8]);
+        assert_eq!(line_range(&starts, 13, 2), None);
+        assert_eq!(line_range(&starts, 13, usize::MAX), None);
+        assert_eq!(line_range(&[], 0, 0), None);
+    }
+
+    /// Runs a test on an editable text field, with the null UI backend's
+    /// clipboard holding the given content.
+    fn with_text_field<F>(text: &str, selection: TextSelection, clipboard: &str, test: F)
+    where
+        F: for<'gc> FnOnce(&mut UpdateContext<'_, 'gc, '_>, EditText<'gc>),
+    {
+        let player = PlayerBuilder::new().with_movie(SwfMovie::empty(10)).build();
+        let mut player = player.lock().unwrap();
+        player.ui_mut().set_clipboard_content(clipboard.to_string());
+        player.mutate_with_update_context(|context| {
+            let movie = context.swf.clone();
+            let text_field = EditText::new(context, movie, 0.0, 0.0, 200.0, 20.0);
+            text_field.set_editable(true, context);
+            text_field.set_text(WStr::from_units(text.as_bytes()), context);
+            text_field.set_selection(Some(selection), context.gc_context);
+            test(context, text_field);
+        });
+    }
+
+    fn selection_range(text_field: EditText<'_>) -> (usize, usize) {
+        let selection = text_field.selection().unwrap();
+        (selection.start(), selection.end())
+    }
+
+    #[test]
+    fn paste_replaces_selection() {
+        let selection = TextSelection::for_range(6, 11);
+        with_text_field("hello world", selection, "there", |context, text_field| {
+            text_field.text_control_input(TextControlCode::Paste, context);
+            assert_eq!(text_field.text().to_utf8_lossy(), "hello there");
+            assert_eq!(selection_range(text_field), (11, 11));
+        });
+    }
+
+    #[test]
+    fn paste_into_single_line_field_keeps_first_line() {
+        let selection = TextSelection::for_position(1);
+        with_text_field("ab", selection, "one\r\ntwo", |context, text_field| {
+            text_field.text_control_input(TextControlCode::Paste, context);
+            assert_eq!(text_field.text().to_utf8_lossy(), "aoneb");
+            assert_eq!(selection_range(text_field), (4, 4));
+        });
+    }
+
+    #[test]
+    fn paste_respects_restrict_and_max_chars() {
+        let selection = TextSelection::for_position(0);
+        with_text_field("", selection, "a1b2c3", |context, text_field| {
+            text_field.set_restrict(Some(WStr::from_units(b"0-9")), context.gc_context);
+            text_field.set_max_chars(2, context.gc_context);
+            text_field.text_control_input(TextControlCode::Paste, context);
+            assert_eq!(text_field.text().to_utf8_lossy(), "12");
+        });
+    }
+
+    #[test]
+    fn paste_into_read_only_field_does_nothing() {
+        let selection = TextSelection::for_position(0);
+        with_text_field("abc", selection, "xyz", |context, text_field| {
+            text_field.set_editable(false, context);
+            text_field.text_control_input(TextControlCode::Paste, context);
+            assert_eq!(text_field.text().to_utf8_lossy(), "abc");
+        });
+    }
+
+    #[test]
+    fn cut_moves_selection_to_clipboard() {
+        let selection = TextSelection::for_range(6, 0);
+        with_text_field("hello world", selection, "", |context, text_field| {
+            text_field.text_control_input(TextControlCode::Cut, context);
+            assert_eq!(text_field.text().to_utf8_lossy(), "world");
+            assert_eq!(context.ui.clipboard_content(), "hello ");
+            assert_eq!(selection_range(text_field), (0, 0));
+        });
+    }
+
+    #[test]
+    fn cut_from_password_field_does_nothing() {
+        let selection = TextSelection::for_range(0, 3);
+        with_text_field("secret", selection, "old", |context, text_field| {
+            text_field.set_password(true, context);
+            text_field.text_control_input(TextControlCode::Cut, context);
+            assert_eq!(text_field.text().to_utf8_lossy(), "secret");
+            assert_eq!(context.ui.clipboard_content(), "old");
+        });
+    }
+
+    #[test]
+    fn select_all() {
+        let selection = TextSelection::for_position(1);
+        with_text_field("abc", selection, "", |context, text_field| {
+            text_field.text_control_input(TextControlCode::SelectAll, context);
+            assert_eq!(selection_range(text_field), (0, 3));
+        });
+
+        with_text_field("abc", selection, "", |context, text_field| {
+            text_field.set_selectable(false, context);
+            text_field.text_control_input(TextControlCode::SelectAll, context);
+            assert_eq!(selection_range(text_field), (1, 1));
+        });
+    }
+}
@@ -106,11 +106,19 @@ impl<'gc> Graphic<'gc> {
         ))
     }
 
+    /// The drawing of this graphic, for changing it. This invalidates the cached bitmaps of
+    /// the graphic and its ancestors; use `drawing_ref` to only read it.
     pub fn drawing(&self, gc_context: MutationContext<'gc, '_>) -> RefMut<'_, Drawing> {
+        self.invalidate_cached_bitmap(gc_context);
         RefMut::map(self.0.write(gc_context), |w| {
             w.drawing.get_or_insert_with(Drawing::new)
         })
     }
+
+    /// The drawing of this graphic, or `None` if nothing has been drawn into it yet.
+    pub fn drawing_ref(&self) -> Option<Ref<'_, Drawing>> {
+        Ref::filter_map(self.0.read(), |r| r.drawing.as_ref()).ok()
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
@@ -171,6 +179,7 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
             .get_graphic(id)
         {
             self.0.write(context.gc_context).static_data = new_graphic.0.read().static_data;
+            self.invalidate_cached_bitmap(context.gc_context);
         } else {
             log::warn!("PlaceObject: expected Graphic at character ID {}", id);
         }
@@ -181,11 +190,6 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext) {
-        if !context.is_offscreen && !self.world_bounds().intersects(&context.stage.view_bounds()) {
-            // Off-screen; culled
-            return;
-        }
-
         if let Some(drawing) = &self.0.read().drawing {
             drawing.render(context);
         } else if let Some(render_handle) = self.0.read().static_data.render_handle {
@@ -257,6 +261,10 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
     fn as_drawing(&self, gc_context: MutationContext<'gc, '_>) -> Option<RefMut<'_, Drawing>> {
         Some(self.drawing(gc_context))
     }
+
+    fn as_drawing_ref(&self) -> Option<Ref<'_, Drawing>> {
+        self.drawing_ref()
+    }
 }
 
 /// Static data shared between all instances of a Graphic.
@@ -1,3 +1,4 @@
+use crate::avm2::object::TObject;
 use crate::avm2::Object as Avm2Object;
 use crate::context::RenderContext;
 use crate::context::UpdateContext;
@@ -52,6 +53,40 @@ impl<'gc> LoaderDisplay<'gc> {
             },
         ))
     }
+
+    /// Remove this loader's content, as done by `Loader.unload` and
+    /// `Loader.unloadAndStop`.
+    ///
+    /// Any load still in progress is cancelled, and `loader_info` is reset
+    /// and fires `unload` if there was content to remove. When `and_stop` is
+    /// set, everything the unloaded movie left running is torn down as well.
+    pub fn unload_content(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        loader_info: Avm2Object<'gc>,
+        and_stop: bool,
+    ) {
+        context
+            .load_manager
+            .cancel_loads_for_loader_info(loader_info);
+
+        let content = match self.child_by_index(0) {
+            Some(content) => content,
+            None => return,
+        };
+        let movie = content.movie();
+
+        let mut container: DisplayObjectContainer<'gc> = self.into();
+        container.remove_child(context, content);
+
+        if and_stop {
+            crate::loader::stop_movie(context, &movie);
+        }
+
+        if let Some(loader_info) = loader_info.as_loader_info_object() {
+            loader_info.unload(context);
+        }
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for LoaderDisplay<'gc> {
@@ -91,6 +126,10 @@ impl<'gc> TDisplayObject<'gc> for LoaderDisplay<'gc> {
         Some(self.into())
     }
 
+    fn as_loader_display(self) -> Option<LoaderDisplay<'gc>> {
+        Some(self)
+    }
+
     fn as_interactive(self) -> Option<InteractiveObject<'gc>> {
         Some(self.into())
     }
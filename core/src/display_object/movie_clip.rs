@@ -502,6 +502,18 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .define_font_2(context, reader),
+                TagCode::DefineFontAlignZones => self
+                    .0
+                    .write(context.gc_context)
+                    .define_font_align_zones(context, reader),
+                TagCode::DefineFontInfo => self
+                    .0
+                    .write(context.gc_context)
+                    .define_font_info(context, reader, 1),
+                TagCode::DefineFontInfo2 => self
+                    .0
+                    .write(context.gc_context)
+                    .define_font_info(context, reader, 2),
                 TagCode::DefineFont3 => self
                     .0
                     .write(context.gc_context)
@@ -710,9 +722,12 @@ impl<'gc> MovieClip<'gc> {
         let do_abc = reader.read_do_abc()?;
         if !do_abc.data.is_empty() {
             let movie = self.movie();
-            let domain = context.library.library_for_movie_mut(movie).avm2_domain();
+            let domain = context
+                .library
+                .library_for_movie_mut(movie.clone())
+                .avm2_domain();
 
-            if let Err(e) = Avm2::do_abc(context, do_abc, domain) {
+            if let Err(e) = Avm2::do_abc(context, do_abc, domain, movie) {
                 log::warn!("Error loading ABC file: {}", e);
             }
         }
@@ -2140,10 +2155,17 @@ impl<'gc> MovieClip<'gc> {
         self.0.write(context.gc_context).button_mode = button_mode;
     }
 
+    /// The drawing of this clip, for changing it. This invalidates the cached bitmaps of
+    /// the clip and its ancestors; use `drawing_ref` to only read it.
     pub fn drawing(&self, gc_context: MutationContext<'gc, '_>) -> RefMut<'_, Drawing> {
+        self.invalidate_cached_bitmap(gc_context);
         RefMut::map(self.0.write(gc_context), |s| &mut s.drawing)
     }
 
+    pub fn drawing_ref(&self) -> Ref<'_, Drawing> {
+        Ref::map(self.0.read(), |s| &s.drawing)
+    }
+
     pub fn is_button_mode(&self, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
         if self.forced_button_mode()
             || self
@@ -2490,6 +2512,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         Some(self.drawing(gc_context))
     }
 
+    fn as_drawing_ref(&self) -> Option<Ref<'_, Drawing>> {
+        Some(self.drawing_ref())
+    }
+
     fn post_instantiation(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -3369,6 +3395,47 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_font_align_zones(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<'a>,
+    ) -> Result<(), Error> {
+        if let swf::Tag::DefineFontAlignZones { id, zones, .. } =
+            reader.read_define_font_align_zones()?
+        {
+            let library = context.library.library_for_movie_mut(self.movie());
+            if let Some(font) = library.get_font(id) {
+                let font = font.with_align_zones(context.gc_context, &zones);
+                library.replace_font(id, font);
+            } else {
+                log::warn!("DefineFontAlignZones: expected font at character ID {}", id);
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn define_font_info(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<'a>,
+        version: u8,
+    ) -> Result<(), Error> {
+        let font_info = reader.read_define_font_info(version)?;
+        let library = context.library.library_for_movie_mut(self.movie());
+        if let Some(font) = library.get_font(font_info.id) {
+            let font = font.with_font_info(context.gc_context, &font_info, reader.encoding());
+            library.replace_font(font_info.id, font);
+        } else {
+            log::warn!(
+                "DefineFontInfo: expected font at character ID {}",
+                font_info.id
+            );
+        }
+        Ok(())
+    }
+
     #[inline]
     fn define_font_4(
         &mut self,
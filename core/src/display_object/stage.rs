@@ -746,6 +746,10 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
         for stage3d in self.stage3ds().iter() {
             if let Some(context3d) = stage3d.as_stage_3d().unwrap().context3d() {
                 context3d.as_context_3d().unwrap().render(context);
+                // There's no telling what changed in Stage3D content.
+                if let Some(dirty_region) = &mut context.dirty_region {
+                    dirty_region.invalidate_all();
+                }
             }
         }
 
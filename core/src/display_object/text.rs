@@ -3,8 +3,10 @@ use crate::avm2::{
 };
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject};
-use crate::font::TextRenderSettings;
+use crate::font::{Font, TextRenderSettings};
+use crate::library::Library;
 use crate::prelude::*;
+use crate::string::WString;
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
 use core::fmt;
@@ -12,6 +14,7 @@ use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_render::commands::CommandHandler;
 use ruffle_render::transform::Transform;
 use std::cell::{Ref, RefMut};
+use std::ops::Range;
 use std::sync::Arc;
 
 #[derive(Clone, Collect, Copy)]
@@ -33,6 +36,53 @@ pub struct TextData<'gc> {
     static_data: gc_arena::Gc<'gc, TextStatic>,
     render_settings: TextRenderSettings,
     avm2_object: Option<Avm2Object<'gc>>,
+
+    /// Which characters of the text are selected, as set by `TextSnapshot`.
+    /// Characters past the end of this list are not selected.
+    selection: Vec<bool>,
+
+    /// The color used to highlight selected characters.
+    select_color: swf::Color,
+}
+
+/// A single glyph of static text, positioned in the coordinate space of the
+/// text's glyph records.
+#[derive(Clone)]
+pub struct StaticGlyph<'gc> {
+    pub font: Font<'gc>,
+
+    /// The index of the glyph within its font.
+    pub index: usize,
+
+    /// The character this glyph represents.
+    pub code: u16,
+
+    pub color: swf::Color,
+    pub height: Twips,
+
+    /// The transform from glyph space into text space.
+    pub matrix: Matrix,
+
+    pub advance: Twips,
+}
+
+impl<'gc> StaticGlyph<'gc> {
+    /// The box covering this glyph's advance, from the ascent to the descent
+    /// of its font, in text space.
+    pub fn bounds(&self) -> BoundingBox {
+        let ascent = match self.font.get_baseline_for_height(self.height) {
+            Twips::ZERO => self.height,
+            ascent => ascent,
+        };
+        let descent = self.font.get_descent_for_height(self.height);
+        BoundingBox {
+            x_min: self.matrix.tx,
+            y_min: self.matrix.ty - ascent,
+            x_max: self.matrix.tx + self.advance,
+            y_max: self.matrix.ty + descent,
+            valid: true,
+        }
+    }
 }
 
 impl<'gc> Text<'gc> {
@@ -57,6 +107,8 @@ impl<'gc> Text<'gc> {
                 ),
                 render_settings: Default::default(),
                 avm2_object: None,
+                selection: Vec::new(),
+                select_color: swf::Color::from_rgb(0xFFFF00, 0xFF),
             },
         ))
     }
@@ -66,7 +118,107 @@ impl<'gc> Text<'gc> {
         gc_context: MutationContext<'gc, '_>,
         settings: TextRenderSettings,
     ) {
-        self.0.write(gc_context).render_settings = settings
+        self.0.write(gc_context).render_settings = settings;
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
+    /// The matrix from the space of the glyph records into the text's own
+    /// space.
+    pub fn text_transform(&self) -> Matrix {
+        self.0.read().static_data.text_transform
+    }
+
+    /// Lay out the glyphs of this text, in the order they are drawn.
+    pub fn glyphs(&self, library: &Library<'gc>) -> Vec<StaticGlyph<'gc>> {
+        let tf = self.0.read();
+        let library = match library.library_for_movie(self.movie()) {
+            Some(library) => library,
+            None => return Vec::new(),
+        };
+
+        let mut glyphs = Vec::new();
+        let mut color = swf::Color {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 0,
+        };
+        let mut font_id = 0;
+        let mut height = Twips::ZERO;
+        let mut matrix = Matrix::default();
+        for block in &tf.static_data.text_blocks {
+            if let Some(x) = block.x_offset {
+                matrix.tx = x;
+            }
+            if let Some(y) = block.y_offset {
+                matrix.ty = y;
+            }
+            color = block.color.as_ref().unwrap_or(&color).clone();
+            font_id = block.font_id.unwrap_or(font_id);
+            height = block.height.unwrap_or(height);
+            if let Some(font) = library.get_font(font_id) {
+                let scale = (height.get() as f32) / font.scale();
+                matrix.a = scale;
+                matrix.d = scale;
+                for c in &block.glyphs {
+                    if let Some(glyph) = font.get_glyph(c.index as usize) {
+                        let advance = Twips::new(c.advance);
+                        glyphs.push(StaticGlyph {
+                            font,
+                            index: c.index as usize,
+                            code: glyph.code(),
+                            color: color.clone(),
+                            height,
+                            matrix,
+                            advance,
+                        });
+                        matrix.tx += advance;
+                    }
+                }
+            }
+        }
+
+        glyphs
+    }
+
+    /// The text represented by the glyphs of this static text.
+    pub fn text(&self, library: &Library<'gc>) -> WString {
+        let mut text = WString::new();
+        for glyph in self.glyphs(library) {
+            text.push(glyph.code);
+        }
+        text
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.0.read().selection.get(index).copied().unwrap_or(false)
+    }
+
+    /// Select or deselect the characters in `range`.
+    pub fn set_selected(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        range: Range<usize>,
+        selected: bool,
+    ) {
+        self.invalidate_cached_bitmap(gc_context);
+        let mut write = self.0.write(gc_context);
+        if write.selection.len() < range.end {
+            if !selected {
+                return;
+            }
+            write.selection.resize(range.end, false);
+        }
+        write.selection[range].fill(selected);
+    }
+
+    pub fn select_color(&self) -> swf::Color {
+        self.0.read().select_color.clone()
+    }
+
+    pub fn set_select_color(self, gc_context: MutationContext<'gc, '_>, color: swf::Color) {
+        self.0.write(gc_context).select_color = color;
+        self.invalidate_cached_bitmap(gc_context);
     }
 }
 
@@ -102,6 +254,7 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
             .get_text(id)
         {
             self.0.write(context.gc_context).static_data = new_text.0.read().static_data;
+            self.invalidate_cached_bitmap(context.gc_context);
         } else {
             log::warn!("PlaceObject: expected text at character ID {}", id);
         }
@@ -111,53 +264,47 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
         // Noop
     }
 
+    fn as_text(&self) -> Option<Text<'gc>> {
+        Some(*self)
+    }
+
     fn render_self(&self, context: &mut RenderContext) {
+        let glyphs = self.glyphs(context.library);
         let tf = self.0.read();
         context.transform_stack.push(&Transform {
             matrix: tf.static_data.text_transform,
             ..Default::default()
         });
 
-        let mut color = swf::Color {
-            r: 0,
-            g: 0,
-            b: 0,
-            a: 0,
-        };
-        let mut font_id = 0;
-        let mut height = Twips::ZERO;
-        let mut transform: Transform = Default::default();
-        for block in &tf.static_data.text_blocks {
-            if let Some(x) = block.x_offset {
-                transform.matrix.tx = x;
-            }
-            if let Some(y) = block.y_offset {
-                transform.matrix.ty = y;
+        // Selected characters are highlighted behind the glyphs.
+        for (i, glyph) in glyphs.iter().enumerate() {
+            if tf.selection.get(i) == Some(&true) {
+                let bounds = glyph.bounds();
+                let highlight = context.transform_stack.transform().matrix
+                    * Matrix::create_box(
+                        bounds.width().to_pixels() as f32,
+                        bounds.height().to_pixels() as f32,
+                        0.0,
+                        bounds.x_min,
+                        bounds.y_min,
+                    );
+                context
+                    .commands
+                    .draw_rect(tf.select_color.clone(), &highlight);
             }
-            color = block.color.as_ref().unwrap_or(&color).clone();
-            font_id = block.font_id.unwrap_or(font_id);
-            height = block.height.unwrap_or(height);
-            if let Some(font) = context
-                .library
-                .library_for_movie(self.movie())
-                .unwrap()
-                .get_font(font_id)
-            {
-                let scale = (height.get() as f32) / font.scale();
-                transform.matrix.a = scale;
-                transform.matrix.d = scale;
-                transform.color_transform.set_mult_color(&color);
-                for c in &block.glyphs {
-                    if let Some(glyph) = font.get_glyph(c.index as usize) {
-                        context.transform_stack.push(&transform);
-                        let glyph_shape_handle = glyph.shape_handle(context.renderer);
-                        context
-                            .commands
-                            .render_shape(glyph_shape_handle, context.transform_stack.transform());
-                        context.transform_stack.pop();
-                        transform.matrix.tx += Twips::new(c.advance);
-                    }
-                }
+        }
+
+        let mut transform: Transform = Default::default();
+        for glyph in &glyphs {
+            if let Some(shape) = glyph.font.get_glyph(glyph.index) {
+                transform.matrix = glyph.matrix;
+                transform.color_transform.set_mult_color(&glyph.color);
+                context.transform_stack.push(&transform);
+                let glyph_transform = context.transform_stack.transform().clone();
+                glyph
+                    .font
+                    .render_glyph(context, shape, &glyph_transform, &tf.render_settings);
+                context.transform_stack.pop();
             }
         }
         context.transform_stack.pop();
@@ -181,53 +328,26 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
 
             // Transform the point into the text's local space.
             let local_matrix = self.global_to_local_matrix();
-            let tf = self.0.read();
-            let mut text_matrix = tf.static_data.text_transform;
+            let mut text_matrix = self.text_transform();
             text_matrix.invert();
             point = text_matrix * local_matrix * point;
 
-            let mut font_id = 0;
-            let mut height = Twips::ZERO;
-            let mut glyph_matrix = Matrix::default();
-            for block in &tf.static_data.text_blocks {
-                if let Some(x) = block.x_offset {
-                    glyph_matrix.tx = x;
-                }
-                if let Some(y) = block.y_offset {
-                    glyph_matrix.ty = y;
-                }
-                font_id = block.font_id.unwrap_or(font_id);
-                height = block.height.unwrap_or(height);
-
-                if let Some(font) = context
-                    .library
-                    .library_for_movie(self.movie())
-                    .unwrap()
-                    .get_font(font_id)
-                {
-                    let scale = (height.get() as f32) / font.scale();
-                    glyph_matrix.a = scale;
-                    glyph_matrix.d = scale;
-                    for c in &block.glyphs {
-                        if let Some(glyph) = font.get_glyph(c.index as usize) {
-                            // Transform the point into glyph space and test.
-                            let mut matrix = glyph_matrix;
-                            matrix.invert();
-                            let point = matrix * point;
-                            let glyph_shape = glyph.as_shape();
-                            let glyph_bounds: BoundingBox = (&glyph_shape.shape_bounds).into();
-                            if glyph_bounds.contains(point)
-                                && ruffle_render::shape_utils::shape_hit_test(
-                                    &glyph_shape,
-                                    point,
-                                    &local_matrix,
-                                )
-                            {
-                                return true;
-                            }
-
-                            glyph_matrix.tx += Twips::new(c.advance);
-                        }
+            for glyph in self.glyphs(context.library) {
+                if let Some(shape) = glyph.font.get_glyph(glyph.index) {
+                    // Transform the point into glyph space and test.
+                    let mut matrix = glyph.matrix;
+                    matrix.invert();
+                    let point = matrix * point;
+                    let glyph_shape = shape.as_shape();
+                    let glyph_bounds: BoundingBox = (&glyph_shape.shape_bounds).into();
+                    if glyph_bounds.contains(point)
+                        && ruffle_render::shape_utils::shape_hit_test(
+                            &glyph_shape,
+                            point,
+                            &local_matrix,
+                        )
+                    {
+                        return true;
                     }
                 }
             }
@@ -280,6 +280,7 @@ impl<'gc> Video<'gc> {
         match res {
             Ok(bitmap) => {
                 self.0.write(context.gc_context).decoded_frame = Some((frame_id, bitmap));
+                self.invalidate_cached_bitmap(context.gc_context);
             }
             Err(e) => log::error!("Got error when seeking to video frame {}: {}", frame_id, e),
         }
@@ -439,14 +440,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         bounding_box
     }
 
-    fn render(&self, context: &mut RenderContext) {
-        if !context.is_offscreen && !self.world_bounds().intersects(&context.stage.view_bounds()) {
-            // Off-screen; culled
-            return;
-        }
-
-        context.transform_stack.push(self.base().transform());
-
+    fn render_self(&self, context: &mut RenderContext) {
         let read = self.0.read();
 
         if let Some((_frame_id, ref bitmap)) = read.decoded_frame {
@@ -482,8 +476,6 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         } else {
             log::warn!("Video has no decoded frame to render.");
         }
-
-        context.transform_stack.pop();
     }
 
     fn set_object2(&mut self, mc: MutationContext<'gc, '_>, to: Avm2Object<'gc>) {
@@ -209,74 +209,109 @@ impl Drawing {
         id
     }
 
-    pub fn render(&self, context: &mut RenderContext) {
-        if self.dirty.get() {
-            self.dirty.set(false);
-            let mut paths = Vec::with_capacity(self.paths.len());
-
-            for path in &self.paths {
-                match path {
-                    DrawingPath::Fill(fill) => {
-                        paths.push(DrawPath::Fill {
-                            style: &fill.style,
-                            commands: fill.commands.to_owned(),
-                        });
-                    }
-                    DrawingPath::Line(line) => {
-                        paths.push(DrawPath::Stroke {
-                            style: &line.style,
-                            commands: line.commands.to_owned(),
-                            is_closed: line.is_closed,
-                        });
-                    }
-                }
-            }
+    /// The fill style of the fill currently being drawn, if any.
+    pub fn fill_style(&self) -> Option<&FillStyle> {
+        self.current_fill.as_ref().map(|fill| &fill.style)
+    }
 
-            if let Some(fill) = &self.current_fill {
-                paths.push(DrawPath::Fill {
-                    style: &fill.style,
-                    commands: fill.commands.to_owned(),
-                })
-            }
+    /// Replace the fill of the current line style, keeping its width, caps
+    /// and joints.
+    ///
+    /// This does nothing if no line style is set.
+    pub fn set_line_fill_style(&mut self, style: FillStyle) {
+        if let Some(line) = &self.current_line {
+            let line_style = line.style.clone().with_fill_style(style);
+            self.set_line_style(Some(line_style));
+        }
+    }
 
-            for line in &self.pending_lines {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo {
-                        x: self.fill_start.0,
-                        y: self.fill_start.1,
-                    });
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
-            }
+    /// The current position of the drawing pen.
+    pub fn cursor(&self) -> (Twips, Twips) {
+        self.cursor
+    }
 
-            if let Some(line) = &self.current_line {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo {
-                        x: self.fill_start.0,
-                        y: self.fill_start.1,
+    /// Replace the contents of this drawing with a copy of another.
+    pub fn copy_from(&mut self, other: &Drawing) {
+        let render_handle = self.render_handle.take();
+        *self = other.clone();
+        self.render_handle.set(render_handle);
+        self.dirty.set(true);
+    }
+
+    /// All paths of this drawing, including the fill and lines that are
+    /// still being drawn.
+    pub fn draw_paths(&self) -> Vec<DrawPath<'_>> {
+        let mut paths = Vec::with_capacity(self.paths.len());
+
+        for path in &self.paths {
+            match path {
+                DrawingPath::Fill(fill) => {
+                    paths.push(DrawPath::Fill {
+                        style: &fill.style,
+                        commands: fill.commands.to_owned(),
                     });
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
+                }
+                DrawingPath::Line(line) => {
+                    paths.push(DrawPath::Stroke {
+                        style: &line.style,
+                        commands: line.commands.to_owned(),
+                        is_closed: line.is_closed,
+                    });
+                }
             }
+        }
+
+        if let Some(fill) = &self.current_fill {
+            paths.push(DrawPath::Fill {
+                style: &fill.style,
+                commands: fill.commands.to_owned(),
+            })
+        }
 
+        for line in &self.pending_lines {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo {
+                    x: self.fill_start.0,
+                    y: self.fill_start.1,
+                });
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
+
+        if let Some(line) = &self.current_line {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo {
+                    x: self.fill_start.0,
+                    y: self.fill_start.1,
+                });
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
+
+        paths
+    }
+
+    pub fn render(&self, context: &mut RenderContext) {
+        if self.dirty.get() {
+            self.dirty.set(false);
             let shape = DistilledShape {
-                paths,
+                paths: self.draw_paths(),
                 shape_bounds: self.shape_bounds.clone(),
                 edge_bounds: self.edge_bounds.clone(),
                 id: 0,
@@ -32,6 +32,13 @@ pub enum PlayerEvent {
     TextInput {
         codepoint: char,
     },
+    /// An editing command for the focused text field.
+    ///
+    /// Frontends send this for keyboard shortcuts such as Ctrl+V, as they
+    /// know when the platform's clipboard is ready to be read.
+    TextControl {
+        code: TextControlCode,
+    },
 }
 
 /// The distance scrolled by the mouse wheel.
@@ -611,3 +618,27 @@ pub fn key_code_to_button_key_code(key_code: KeyCode) -> Option<ButtonKeyCode> {
     };
     Some(out)
 }
+
+/// Editing commands that text fields handle on their own, either from
+/// keyboard shortcuts or from the context menu.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TextControlCode {
+    Cut,
+    Copy,
+    Paste,
+    Delete,
+    SelectAll,
+}
+
+impl TextControlCode {
+    /// The command for a key pressed together with Ctrl (or Cmd on macOS).
+    pub fn from_shortcut(key_code: KeyCode) -> Option<Self> {
+        match key_code {
+            KeyCode::X => Some(Self::Cut),
+            KeyCode::C => Some(Self::Copy),
+            KeyCode::V => Some(Self::Paste),
+            KeyCode::A => Some(Self::SelectAll),
+            _ => None,
+        }
+    }
+}
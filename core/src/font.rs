@@ -1,8 +1,12 @@
+use crate::context::RenderContext;
 use crate::html::TextSpan;
 use crate::prelude::*;
 use crate::string::WStr;
 use gc_arena::{Collect, Gc, MutationContext};
 use ruffle_render::backend::{RenderBackend, ShapeHandle};
+use ruffle_render::bitmap::BitmapHandle;
+use ruffle_render::glyph::{rasterize_glyph, GlyphRasterSettings};
+use ruffle_render::matrix::Matrix;
 use ruffle_render::transform::Transform;
 use std::cell::{Cell, Ref, RefCell};
 use std::cmp::max;
@@ -14,6 +18,41 @@ pub fn round_down_to_pixel(t: Twips) -> Twips {
     Twips::from_pixels(t.to_pixels().floor())
 }
 
+/// Text larger than this, in pixels per EM square, is always rendered as
+/// shapes rather than rasterized glyphs.
+const MAX_RASTERIZED_EM_SIZE: f32 = 255.0;
+
+/// The number of rasterized bitmaps each glyph keeps around.
+const MAX_CACHED_GLYPH_BITMAPS: usize = 16;
+
+/// Decode a half-precision float, as used by `DefineFontAlignZones`.
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from((bits >> 10) & 0x1F);
+    let mantissa = f32::from(bits & 0x3FF);
+    sign * match exponent {
+        0 => mantissa * 2.0f32.powi(-24),
+        0x1F if mantissa == 0.0 => f32::INFINITY,
+        0x1F => f32::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2.0f32.powi(exponent - 15),
+    }
+}
+
+/// Adjust a one-dimensional transform, in pixels, so that both edges of an
+/// alignment zone fall on pixel boundaries.
+///
+/// Returns `false` if the zone is empty in this dimension.
+fn fit_to_grid(min: f32, max: f32, scale: &mut f32, offset: &mut f32) -> bool {
+    if max <= min {
+        return false;
+    }
+    let start = (min * *scale + *offset).round();
+    let end = (max * *scale + *offset).round().max(start + 1.0);
+    *scale = (end - start) / (max - min);
+    *offset = start - min * *scale;
+    true
+}
+
 /// Parameters necessary to evaluate a font.
 #[derive(Copy, Clone, Debug, Collect)]
 #[collect(require_static)]
@@ -35,8 +74,7 @@ pub struct EvalParameters {
 
 impl EvalParameters {
     /// Construct eval parameters from their individual parts.
-    #[allow(dead_code)]
-    fn from_parts(height: Twips, letter_spacing: Twips, kerning: bool) -> Self {
+    pub fn from_parts(height: Twips, letter_spacing: Twips, kerning: bool) -> Self {
         Self {
             height,
             letter_spacing,
@@ -129,6 +167,8 @@ impl<'gc> Font<'gc> {
                 shape_handle: Cell::new(handle),
                 advance: swf_glyph.advance,
                 shape: RefCell::new(None),
+                align_zone: None,
+                bitmap_cache: Default::default(),
                 swf_glyph,
             };
             let index = glyphs.len();
@@ -412,6 +452,117 @@ impl<'gc> Font<'gc> {
     pub fn descriptor(&self) -> &FontDescriptor {
         &self.0.descriptor
     }
+
+    /// Returns a copy of this font with the code table and identity given by
+    /// a `DefineFontInfo` tag.
+    ///
+    /// `DefineFont` tags don't record which character each glyph represents,
+    /// so this is needed to get the text of static text using them.
+    pub fn with_font_info(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        info: &swf::FontInfo,
+        encoding: &'static swf::Encoding,
+    ) -> Font<'gc> {
+        let mut data = (*self.0).clone();
+        for (glyph, &code) in data.glyphs.iter_mut().zip(&info.code_table) {
+            glyph.swf_glyph.code = code;
+        }
+        data.code_point_to_glyph = data
+            .glyphs
+            .iter()
+            .enumerate()
+            .map(|(index, glyph)| (glyph.swf_glyph.code, index))
+            .collect();
+        data.descriptor = FontDescriptor::from_parts(
+            &info.name.to_string_lossy(encoding),
+            info.flags.contains(swf::FontInfoFlag::IS_BOLD),
+            info.flags.contains(swf::FontInfoFlag::IS_ITALIC),
+        );
+
+        Font(Gc::allocate(gc_context, data))
+    }
+
+    /// Returns a copy of this font using the alignment zones given by a
+    /// `DefineFontAlignZones` tag.
+    pub fn with_align_zones(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        zones: &[swf::FontAlignZone],
+    ) -> Font<'gc> {
+        let mut data = (*self.0).clone();
+        // Zones are given in fractions of the EM square, with Y pointing up.
+        let em = data.scale;
+        for (glyph, zone) in data.glyphs.iter_mut().zip(zones) {
+            let left = f16_to_f32(zone.left as u16) * em;
+            let width = f16_to_f32(zone.width as u16) * em;
+            let bottom = f16_to_f32(zone.bottom as u16) * em;
+            let height = f16_to_f32(zone.height as u16) * em;
+            glyph.align_zone = Some(AlignZone {
+                x_min: left,
+                x_max: left + width,
+                y_min: -(bottom + height),
+                y_max: -bottom,
+            });
+        }
+
+        Font(Gc::allocate(gc_context, data))
+    }
+
+    /// Render a glyph of this font.
+    ///
+    /// Glyphs of text using advanced anti-aliasing are rasterized and cached
+    /// as bitmaps, fitted to the pixel grid using their alignment zones.
+    /// Other glyphs are rendered as shapes.
+    pub fn render_glyph(
+        &self,
+        context: &mut RenderContext<'_, 'gc, '_>,
+        glyph: &Glyph,
+        transform: &Transform,
+        settings: &TextRenderSettings,
+    ) {
+        let em_size = transform.matrix.d * self.0.scale / Twips::TWIPS_PER_PIXEL as f32;
+        if !settings.is_advanced()
+            || em_size > MAX_RASTERIZED_EM_SIZE
+            || !glyph.render_bitmap(context, transform, settings)
+        {
+            let glyph_shape_handle = glyph.shape_handle(context.renderer);
+            context.commands.render_shape(glyph_shape_handle, transform);
+        }
+    }
+}
+
+/// The area of a glyph which is aligned to the pixel grid when using advanced
+/// anti-aliasing, in glyph coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AlignZone {
+    x_min: f32,
+    x_max: f32,
+    y_min: f32,
+    y_max: f32,
+}
+
+/// Identifies a rasterization of a glyph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct GlyphBitmapKey {
+    scale_x: u32,
+    scale_y: u32,
+
+    /// The subpixel offset of the glyph, in quarter pixels.
+    offset_x: u8,
+    offset_y: u8,
+
+    thickness: u32,
+    sharpness: u32,
+}
+
+#[derive(Debug, Clone)]
+struct GlyphBitmap {
+    handle: BitmapHandle,
+
+    /// The position of the bitmap relative to the glyph origin, in pixels.
+    x: i32,
+    y: i32,
 }
 
 #[derive(Debug, Clone)]
@@ -425,6 +576,10 @@ pub struct Glyph {
     // (todo: refactor hit tests to not require this?
     // this literally copies the shape_record, which is wasteful...)
     shape: RefCell<Option<swf::Shape>>,
+    // The area of the glyph to fit to the pixel grid, if given by the font.
+    align_zone: Option<AlignZone>,
+    // Bitmaps of this glyph rasterized for advanced anti-aliasing.
+    bitmap_cache: RefCell<fnv::FnvHashMap<GlyphBitmapKey, Option<GlyphBitmap>>>,
     // The underlying glyph record, containing its shape.
     swf_glyph: swf::Glyph,
 }
@@ -438,6 +593,104 @@ impl Glyph {
         self.shape_handle.get().unwrap()
     }
 
+    /// Render this glyph as a rasterized bitmap.
+    ///
+    /// Returns `false` if the glyph can't be rasterized with this transform.
+    fn render_bitmap(
+        &self,
+        context: &mut RenderContext<'_, '_, '_>,
+        transform: &Transform,
+        settings: &TextRenderSettings,
+    ) -> bool {
+        let matrix = transform.matrix;
+        // Only upright glyphs can be fitted to the pixel grid.
+        if matrix.b != 0.0 || matrix.c != 0.0 || matrix.a <= 0.0 || matrix.d <= 0.0 {
+            return false;
+        }
+
+        let twips_per_pixel = Twips::TWIPS_PER_PIXEL as f32;
+        let mut scale_x = matrix.a / twips_per_pixel;
+        let mut scale_y = matrix.d / twips_per_pixel;
+        let mut x = matrix.tx.to_pixels() as f32;
+        let mut y = matrix.ty.to_pixels() as f32;
+        let grid_fit = settings.grid_fit();
+        if grid_fit != TextGridFit::None {
+            let zone = self.align_zone;
+            if !zone.map_or(false, |zone| {
+                fit_to_grid(zone.y_min, zone.y_max, &mut scale_y, &mut y)
+            }) {
+                y = y.round();
+            }
+            // Subpixel fitting only aligns glyphs vertically.
+            if grid_fit == TextGridFit::Pixel
+                && !zone.map_or(false, |zone| {
+                    fit_to_grid(zone.x_min, zone.x_max, &mut scale_x, &mut x)
+                })
+            {
+                x = x.round();
+            }
+        }
+
+        // Glyphs are positioned to a quarter of a pixel, so that their bitmaps can be reused.
+        let x = (x * 4.0).round() / 4.0;
+        let y = (y * 4.0).round() / 4.0;
+        let (origin_x, origin_y) = (x.floor(), y.floor());
+        let key = GlyphBitmapKey {
+            scale_x: scale_x.to_bits(),
+            scale_y: scale_y.to_bits(),
+            offset_x: ((x - origin_x) * 4.0) as u8,
+            offset_y: ((y - origin_y) * 4.0) as u8,
+            thickness: settings.thickness().to_bits(),
+            sharpness: settings.sharpness().to_bits(),
+        };
+
+        let mut cache = self.bitmap_cache.borrow_mut();
+        if !cache.contains_key(&key) {
+            if cache.len() >= MAX_CACHED_GLYPH_BITMAPS {
+                cache.clear();
+            }
+            let raster_matrix = Matrix {
+                a: scale_x * twips_per_pixel,
+                d: scale_y * twips_per_pixel,
+                tx: Twips::from_pixels((x - origin_x).into()),
+                ty: Twips::from_pixels((y - origin_y).into()),
+                ..Default::default()
+            };
+            let raster_settings = GlyphRasterSettings {
+                thickness: settings.thickness(),
+                sharpness: settings.sharpness(),
+            };
+            let bitmap = rasterize_glyph(&self.swf_glyph, &raster_matrix, raster_settings)
+                .and_then(|raster| {
+                    Some(GlyphBitmap {
+                        handle: context.renderer.register_bitmap(raster.bitmap).ok()?,
+                        x: raster.x,
+                        y: raster.y,
+                    })
+                });
+            cache.insert(key, bitmap);
+        }
+
+        if let Some(bitmap) = &cache[&key] {
+            let bitmap_transform = Transform {
+                matrix: Matrix::translate(
+                    Twips::from_pixels((origin_x as i32 + bitmap.x).into()),
+                    Twips::from_pixels((origin_y as i32 + bitmap.y).into()),
+                ),
+                color_transform: transform.color_transform.clone(),
+            };
+            context
+                .commands
+                .render_bitmap(&bitmap.handle, &bitmap_transform, false);
+        }
+        true
+    }
+
+    /// The UTF-16 code unit of the character this glyph represents.
+    pub fn code(&self) -> u16 {
+        self.swf_glyph.code
+    }
+
     pub fn as_shape(&self) -> Ref<'_, swf::Shape> {
         let mut write = self.shape.borrow_mut();
         if write.is_none() {
@@ -525,8 +778,8 @@ pub enum TextRenderSettings {
     /// This text should render with the advanced rendering engine.
     /// Set via "Anti-alias for readibility" in the Flash IDE.
     /// The parameters are set via the CSMTextSettings SWF tag.
-    /// Ruffle does not support this currently, but this also affects
-    /// hit-testing behavior.
+    /// Glyphs are rasterized and fitted to the pixel grid, and this also
+    /// affects hit-testing behavior.
     Advanced {
         grid_fit: TextGridFit,
         thickness: f32,
@@ -686,7 +939,7 @@ impl Default for TextRenderSettings {
 
 #[cfg(test)]
 mod tests {
-    use crate::font::{EvalParameters, Font};
+    use crate::font::{f16_to_f32, fit_to_grid, EvalParameters, Font};
     use crate::player::Player;
     use crate::string::WStr;
     use gc_arena::{rootless_arena, MutationContext};
@@ -866,4 +1119,50 @@ mod tests {
             assert_eq!(None, breakpoint5);
         });
     }
+
+    #[test]
+    fn font_info_replaces_code_table() {
+        with_device_font(|mc, df| {
+            let original_code = df.get_glyph(0).unwrap().code();
+            let info = swf::FontInfo {
+                id: 1,
+                version: 2,
+                name: swf::SwfStr::from_bytes(b"Test Font"),
+                flags: swf::FontInfoFlag::IS_BOLD,
+                language: swf::Language::Latin,
+                code_table: vec![0xE000, 0xE001],
+            };
+            let font = df.with_font_info(mc, &info, swf::UTF_8);
+
+            assert_eq!(font.get_glyph(0).unwrap().code(), 0xE000);
+            assert_eq!(font.get_glyph(1).unwrap().code(), 0xE001);
+            assert_eq!(font.get_glyph_for_char('\u{E001}').unwrap().code(), 0xE001);
+            assert_eq!(font.descriptor().class(), "Test Font");
+            assert!(font.descriptor().bold());
+            assert!(!font.descriptor().italic());
+
+            // The original font is left untouched.
+            assert_eq!(df.get_glyph(0).unwrap().code(), original_code);
+            assert!(df.get_glyph_for_char('\u{E001}').is_none());
+        });
+    }
+
+    #[test]
+    fn decode_f16() {
+        assert_eq!(f16_to_f32(0x0000), 0.0);
+        assert_eq!(f16_to_f32(0x3C00), 1.0);
+        assert_eq!(f16_to_f32(0x3800), 0.5);
+        assert_eq!(f16_to_f32(0xC000), -2.0);
+        assert_eq!(f16_to_f32(0x0001), 2.0f32.powi(-24));
+    }
+
+    #[test]
+    fn fit_zone_to_grid() {
+        let (mut scale, mut offset) = (0.01, 10.3);
+        assert!(fit_to_grid(-720.0, 0.0, &mut scale, &mut offset));
+        assert!((-720.0 * scale + offset - 3.0).abs() < 1e-4);
+        assert!((offset - 10.0).abs() < 1e-4);
+
+        assert!(!fit_to_grid(0.0, 0.0, &mut scale, &mut offset));
+    }
 }
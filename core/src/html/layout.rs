@@ -4,7 +4,7 @@ use crate::context::UpdateContext;
 use crate::drawing::Drawing;
 use crate::font::{EvalParameters, Font};
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::text_format::{FormatSpans, ImageAlign, TextFormat, TextSpan};
 use crate::string::{utils as string_utils, WStr};
 use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
@@ -87,6 +87,19 @@ pub struct LayoutContext<'a, 'gc> {
 
     /// The total width of the text field being laid out.
     max_bounds: Twips,
+
+    /// The space taken up by images floated beside the text, including the
+    /// space kept free around them.
+    floats: Vec<(ImageAlign, BoxBounds<Twips>)>,
+
+    /// The layout boxes of all placed images.
+    ///
+    /// These are kept apart from the other boxes until layout has finished,
+    /// as they don't belong to any one line.
+    image_boxes: Vec<LayoutBox<'gc>>,
+
+    /// The index of the next image in the text to be placed.
+    next_image: usize,
 }
 
 impl<'a, 'gc> LayoutContext<'a, 'gc> {
@@ -104,6 +117,9 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             current_line: 0,
             current_line_span: Default::default(),
             max_bounds,
+            floats: Vec::new(),
+            image_boxes: Vec::new(),
+            next_image: 0,
         }
     }
 
@@ -257,9 +273,11 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
         let mut line_bounds = line_bounds.unwrap_or_default();
 
+        let (left_float, right_float) = self.float_intrusions();
         let left_adjustment =
-            Self::left_alignment_offset(&self.current_line_span, self.is_first_line);
-        let right_adjustment = Twips::from_pixels(self.current_line_span.right_margin);
+            Self::left_alignment_offset(&self.current_line_span, self.is_first_line) + left_float;
+        let right_adjustment =
+            Twips::from_pixels(self.current_line_span.right_margin) + right_float;
 
         let misalignment =
             self.max_bounds - left_adjustment - right_adjustment - line_bounds.width();
@@ -500,7 +518,8 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
             bullet_cursor.set_x(
                 Twips::from_pixels(18.0)
-                    + Self::left_alignment_offset_without_bullet(span, self.is_first_line),
+                    + Self::left_alignment_offset_without_bullet(span, self.is_first_line)
+                    + self.float_intrusions().0,
             );
 
             let params = EvalParameters::from_span(span);
@@ -559,18 +578,98 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     ///
     /// Offsets returned by this function should not be considered final;
     fn wrap_dimensions(&self, current_span: &TextSpan) -> (Twips, Twips) {
-        let width = self.max_bounds - Twips::from_pixels(self.current_line_span.right_margin);
-        let offset = Self::left_alignment_offset(current_span, self.is_first_line);
+        let (left_float, right_float) = self.float_intrusions();
+        let width =
+            self.max_bounds - Twips::from_pixels(self.current_line_span.right_margin) - right_float;
+        let offset = Self::left_alignment_offset(current_span, self.is_first_line) + left_float;
 
         (width, offset + self.cursor.x())
     }
 
+    /// Calculate how far floated images reach into the current line from the
+    /// left and right edges of the text field.
+    fn float_intrusions(&self) -> (Twips, Twips) {
+        let y = self.cursor.y();
+        let mut left = Twips::ZERO;
+        let mut right = Twips::ZERO;
+
+        for (align, bounds) in &self.floats {
+            if bounds.offset_y() <= y && y < bounds.extent_y() {
+                match align {
+                    ImageAlign::Left => left = max(left, bounds.extent_x()),
+                    ImageAlign::Right => right = max(right, self.max_bounds - bounds.offset_x()),
+                }
+            }
+        }
+
+        (left, right)
+    }
+
+    /// Float any images declared at or before `position` beside the text.
+    ///
+    /// Images are placed at the top of the current line, so this does nothing
+    /// if the line already has content, unless `force` is set. Forced images
+    /// are instead placed below the current line.
+    fn place_images(
+        &mut self,
+        fs: &FormatSpans,
+        image_sizes: &[Size<Twips>],
+        position: usize,
+        force: bool,
+    ) {
+        let y = if self.is_start_of_line() {
+            self.cursor.y()
+        } else if force {
+            self.cursor.y() + self.max_font_size + self.line_leading_adjustment()
+        } else {
+            return;
+        };
+
+        while let Some(image) = fs
+            .images()
+            .get(self.next_image)
+            .filter(|image| image.position <= position)
+        {
+            let size = image_sizes
+                .get(self.next_image)
+                .copied()
+                .unwrap_or_default();
+            let hspace = Twips::from_pixels(image.hspace);
+            let vspace = Twips::from_pixels(image.vspace);
+            let outer_size = Size::from((size.width() + hspace, size.height() + vspace));
+            let (left, right) = self.float_intrusions();
+
+            let (image_x, float_x) = match image.align {
+                ImageAlign::Left => (left, left),
+                ImageAlign::Right => {
+                    let image_x = self.max_bounds - right - size.width();
+                    (image_x, image_x - hspace)
+                }
+            };
+
+            self.floats.push((
+                image.align,
+                BoxBounds::from_position_and_size(Position::from((float_x, y)), outer_size),
+            ));
+
+            let mut image_box = LayoutBox::from_image(self.next_image);
+            image_box.bounds =
+                BoxBounds::from_position_and_size(Position::from((image_x, y)), size);
+            self.image_boxes.push(image_box);
+
+            self.next_image += 1;
+        }
+    }
+
     /// Destroy the layout context, returning the newly constructed layout list.
     fn end_layout(
         mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
         fs: &'a FormatSpans,
+        image_sizes: &[Size<Twips>],
     ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
+        self.place_images(fs, image_sizes, usize::MAX, true);
+
         self.fixup_line(
             context,
             !self.has_line_break,
@@ -579,7 +678,18 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
                 .map(|ls| (fs.displayed_text(), fs.displayed_text().len(), ls)),
         );
 
-        (self.boxes, self.exterior_bounds.unwrap_or_default())
+        let mut exterior_bounds = self.exterior_bounds;
+        for image_box in &self.image_boxes {
+            if let Some(exterior_bounds) = &mut exterior_bounds {
+                *exterior_bounds += image_box.bounds;
+            } else {
+                exterior_bounds = Some(image_box.bounds);
+            }
+        }
+
+        self.boxes.append(&mut self.image_boxes);
+
+        (self.boxes, exterior_bounds.unwrap_or_default())
     }
 
     fn is_start_of_line(&self) -> bool {
@@ -603,7 +713,8 @@ pub struct LayoutBox<'gc> {
 
 /// Represents different content modes of a given `LayoutBox`.
 ///
-/// Currently, a `LayoutBox` can contain `Text`, `Bullet`s, or a `Drawing`.
+/// Currently, a `LayoutBox` can contain `Text`, `Bullet`s, a `Drawing`, or an
+/// `Image`.
 #[derive(Clone, Debug, Collect)]
 #[collect(no_drop)]
 pub enum LayoutContent<'gc> {
@@ -659,6 +770,13 @@ pub enum LayoutContent<'gc> {
     /// layout box's bounds. The size of those bounds do not affect the
     /// rendering of the drawing.
     Drawing(Drawing),
+
+    /// A layout box containing an image embedded with an `<img>` tag.
+    ///
+    /// The image is the one at the given index into the images of the
+    /// `FormatSpans` that generated this layout box. Its bounds are the size
+    /// the image should be displayed at.
+    Image(usize),
 }
 
 impl<'gc> LayoutBox<'gc> {
@@ -702,10 +820,20 @@ impl<'gc> LayoutBox<'gc> {
         }
     }
 
+    /// Construct an image.
+    pub fn from_image(index: usize) -> Self {
+        Self {
+            bounds: Default::default(),
+            content: LayoutContent::Image(index),
+        }
+    }
+
     /// Construct a new layout hierarchy from text spans.
     ///
     /// The returned bounds will include both the text bounds itself, as well
     /// as left and right margins on any of the lines.
+    ///
+    /// `image_sizes` holds the size to display each of the text's images at.
     pub fn lower_from_text_spans(
         fs: &FormatSpans,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -713,6 +841,7 @@ impl<'gc> LayoutBox<'gc> {
         bounds: Twips,
         is_word_wrap: bool,
         is_device_font: bool,
+        image_sizes: &[Size<Twips>],
     ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
         let mut layout_context = LayoutContext::new(movie, bounds, fs.displayed_text());
 
@@ -741,6 +870,7 @@ impl<'gc> LayoutBox<'gc> {
                     }
 
                     let start = span_start + slice_start;
+                    layout_context.place_images(fs, image_sizes, start, false);
 
                     let mut last_breakpoint = 0;
 
@@ -770,6 +900,12 @@ impl<'gc> LayoutBox<'gc> {
                                 break;
                             } else if breakpoint == 0 {
                                 layout_context.newline(context, text, next_breakpoint, span);
+                                layout_context.place_images(
+                                    fs,
+                                    image_sizes,
+                                    start + last_breakpoint,
+                                    false,
+                                );
 
                                 let next_dim = layout_context.wrap_dimensions(span);
 
@@ -796,6 +932,12 @@ impl<'gc> LayoutBox<'gc> {
                             }
 
                             layout_context.newline(context, text, next_breakpoint, span);
+                            layout_context.place_images(
+                                fs,
+                                image_sizes,
+                                start + last_breakpoint,
+                                false,
+                            );
                             let next_dim = layout_context.wrap_dimensions(span);
 
                             width = next_dim.0;
@@ -817,7 +959,7 @@ impl<'gc> LayoutBox<'gc> {
             }
         }
 
-        layout_context.end_layout(context, fs)
+        layout_context.end_layout(context, fs, image_sizes)
     }
 
     pub fn bounds(&self) -> BoxBounds<Twips> {
@@ -861,7 +1003,7 @@ impl<'gc> LayoutBox<'gc> {
                 *params,
                 swf::Color::from_rgb(color.to_rgb(), 0xFF),
             )),
-            LayoutContent::Drawing(..) => None,
+            LayoutContent::Drawing(..) | LayoutContent::Image(..) => None,
         }
     }
 
@@ -871,6 +1013,15 @@ impl<'gc> LayoutBox<'gc> {
             LayoutContent::Text { .. } => None,
             LayoutContent::Bullet { .. } => None,
             LayoutContent::Drawing(drawing) => Some(drawing),
+            LayoutContent::Image(..) => None,
+        }
+    }
+
+    /// Returns the index of the image this box contains, if it has one.
+    pub fn as_image(&self) -> Option<usize> {
+        match &self.content {
+            LayoutContent::Image(index) => Some(*index),
+            _ => None,
         }
     }
 
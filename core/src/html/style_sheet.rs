@@ -0,0 +1,290 @@
+//! CSS style sheets for HTML text
+
+use crate::html::text_format::TextFormat;
+use crate::string::WString;
+use std::collections::BTreeMap;
+
+/// The declarations of a single style, as `(property, value)` pairs.
+///
+/// Property names use Flash's camel case spelling (e.g. `fontSize` rather
+/// than `font-size`), and values are kept as they were written.
+pub type CssStyle = Vec<(String, String)>;
+
+/// How an element styled by a style sheet is laid out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CssDisplay {
+    /// The element starts and ends a paragraph.
+    Block,
+
+    /// The element flows with the surrounding text.
+    Inline,
+
+    /// The element and its contents are not displayed.
+    None,
+}
+
+/// A set of named styles that can be applied to an HTML text field.
+/// Equivalent to AS3 `flash.text.StyleSheet` and AVM1 `TextField.StyleSheet`.
+///
+/// Style names are either tag selectors (`p`), class selectors (`.title`) or
+/// one of the link pseudo-classes `a:link` and `a:hover`. Like Flash Player,
+/// names are case-insensitive.
+#[derive(Clone, Debug, Default)]
+pub struct StyleSheet {
+    styles: BTreeMap<String, CssStyle>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses CSS text into a list of `(selector, declarations)` pairs, in
+    /// source order.
+    ///
+    /// Rules with several comma-separated selectors are split into one pair
+    /// per selector. Returns `None` if the CSS is malformed.
+    pub fn parse_css(css: &str) -> Option<Vec<(String, CssStyle)>> {
+        let css = strip_comments(css)?;
+        let mut rules = Vec::new();
+        let mut rest = css.as_str();
+
+        while let Some(open) = rest.find('{') {
+            let selectors = &rest[..open];
+            let close = open + rest[open..].find('}')?;
+            let body = &rest[open + 1..close];
+            if body.contains('{') {
+                return None;
+            }
+
+            let mut style = CssStyle::new();
+            for declaration in body.split(';') {
+                if declaration.trim().is_empty() {
+                    continue;
+                }
+                let (name, value) = declaration.split_once(':')?;
+                let name = camel_case(name.trim());
+                if name.is_empty() {
+                    return None;
+                }
+                style.push((name, value.trim().to_string()));
+            }
+
+            for selector in selectors.split(',') {
+                let selector = selector.trim();
+                if selector.is_empty() {
+                    return None;
+                }
+                rules.push((selector.to_ascii_lowercase(), style.clone()));
+            }
+
+            rest = &rest[close + 1..];
+        }
+
+        if !rest.trim().is_empty() {
+            return None;
+        }
+
+        Some(rules)
+    }
+
+    /// Adds or replaces the style with the given name.
+    pub fn set_style(&mut self, name: &str, style: CssStyle) {
+        self.styles.insert(name.to_ascii_lowercase(), style);
+    }
+
+    /// Removes the style with the given name.
+    pub fn remove_style(&mut self, name: &str) {
+        self.styles.remove(&name.to_ascii_lowercase());
+    }
+
+    pub fn style(&self, name: &str) -> Option<&CssStyle> {
+        self.styles.get(&name.to_ascii_lowercase())
+    }
+
+    /// The names of all styles, in alphabetical order.
+    pub fn style_names(&self) -> impl Iterator<Item = &str> {
+        self.styles.keys().map(String::as_str)
+    }
+
+    pub fn clear(&mut self) {
+        self.styles.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.styles.is_empty()
+    }
+
+    /// Converts a style into the text format it describes.
+    ///
+    /// Properties that aren't supported by Flash Player, or have invalid
+    /// values, are ignored.
+    pub fn style_to_text_format(style: &[(String, String)]) -> TextFormat {
+        let mut format = TextFormat::default();
+        for (name, value) in style {
+            let value = value.trim();
+            match name.as_str() {
+                "color" => format.color = parse_color(value).or(format.color),
+                "fontFamily" => format.font = Some(font_family(value)),
+                "fontSize" => format.size = parse_length(value).or(format.size),
+                "fontStyle" => match value {
+                    "italic" => format.italic = Some(true),
+                    "normal" => format.italic = Some(false),
+                    _ => {}
+                },
+                "fontWeight" => match value {
+                    "bold" => format.bold = Some(true),
+                    "normal" => format.bold = Some(false),
+                    _ => {}
+                },
+                "kerning" => match value {
+                    "true" => format.kerning = Some(true),
+                    "false" => format.kerning = Some(false),
+                    _ => {}
+                },
+                "leading" => format.leading = parse_length(value).or(format.leading),
+                "letterSpacing" => {
+                    format.letter_spacing = parse_length(value).or(format.letter_spacing)
+                }
+                "marginLeft" => format.left_margin = parse_length(value).or(format.left_margin),
+                "marginRight" => format.right_margin = parse_length(value).or(format.right_margin),
+                "textAlign" => match value {
+                    "left" => format.align = Some(swf::TextAlign::Left),
+                    "center" => format.align = Some(swf::TextAlign::Center),
+                    "right" => format.align = Some(swf::TextAlign::Right),
+                    "justify" => format.align = Some(swf::TextAlign::Justify),
+                    _ => {}
+                },
+                "textDecoration" => match value {
+                    "underline" => format.underline = Some(true),
+                    "none" => format.underline = Some(false),
+                    _ => {}
+                },
+                "textIndent" => format.indent = parse_length(value).or(format.indent),
+                _ => {}
+            }
+        }
+        format
+    }
+
+    /// The text format of the style with the given name, if there is one.
+    pub fn text_format(&self, name: &str) -> Option<TextFormat> {
+        self.style(name)
+            .map(|style| Self::style_to_text_format(style))
+    }
+
+    /// Applies the styles matching an HTML element to `format`.
+    ///
+    /// The tag selector is applied first, followed by `a:link` for links and
+    /// finally the class selector, which takes precedence.
+    pub fn apply_to_element(
+        &self,
+        tag: &str,
+        class: Option<&str>,
+        format: TextFormat,
+    ) -> TextFormat {
+        let mut format = format;
+        if let Some(tag_format) = self.text_format(tag) {
+            format = tag_format.mix_with(format);
+        }
+        if tag.eq_ignore_ascii_case("a") {
+            if let Some(link_format) = self.text_format("a:link") {
+                format = link_format.mix_with(format);
+            }
+        }
+        if let Some(class) = class {
+            if let Some(class_format) = self.text_format(&format!(".{class}")) {
+                format = class_format.mix_with(format);
+            }
+        }
+        format
+    }
+
+    /// How an HTML element is laid out, if its styles say so.
+    pub fn display(&self, tag: &str, class: Option<&str>) -> Option<CssDisplay> {
+        let class_style = class.and_then(|class| self.style(&format!(".{class}")));
+        [class_style, self.style(tag)]
+            .into_iter()
+            .flatten()
+            .find_map(|style| {
+                let (_, value) = style.iter().rev().find(|(name, _)| name == "display")?;
+                match value.trim() {
+                    "block" => Some(CssDisplay::Block),
+                    "inline" => Some(CssDisplay::Inline),
+                    "none" => Some(CssDisplay::None),
+                    _ => None,
+                }
+            })
+    }
+
+    /// The text format applied to links under the mouse cursor.
+    pub fn hover_format(&self) -> Option<TextFormat> {
+        self.text_format("a:hover")
+    }
+}
+
+/// Removes `/* ... */` comments, returning `None` if one is unterminated.
+fn strip_comments(css: &str) -> Option<String> {
+    let mut result = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        result.push_str(&rest[..start]);
+        let end = rest[start + 2..].find("*/")?;
+        rest = &rest[start + 2 + end + 2..];
+    }
+    result.push_str(rest);
+    Some(result)
+}
+
+/// Converts a CSS property name such as `font-size` into Flash's `fontSize`.
+fn camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut uppercase_next = false;
+    for c in name.chars() {
+        if c == '-' {
+            uppercase_next = !result.is_empty();
+        } else if uppercase_next {
+            result.push(c.to_ascii_uppercase());
+            uppercase_next = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Parses a `#RRGGBB` color.
+fn parse_color(value: &str) -> Option<swf::Color> {
+    let hex = value.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    Some(swf::Color::from_rgb(rgb, 0))
+}
+
+/// Parses a length in pixels, such as `12`, `12px` or `12pt`.
+fn parse_length(value: &str) -> Option<f64> {
+    let number = value
+        .strip_suffix("px")
+        .or_else(|| value.strip_suffix("pt"))
+        .unwrap_or(value);
+    number.trim().parse().ok()
+}
+
+/// Maps a CSS font family list to Flash font names, translating the generic
+/// family names into Flash's device fonts.
+fn font_family(value: &str) -> WString {
+    let families: Vec<_> = value
+        .split(',')
+        .map(
+            |family| match family.trim().trim_matches(|c| c == '"' || c == '\'') {
+                "mono" | "monospace" => "_typewriter",
+                "sans-serif" => "_sans",
+                "serif" => "_serif",
+                family => family,
+            },
+        )
+        .collect();
+    WString::from_utf8(&families.join(","))
+}
@@ -2,6 +2,7 @@
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::{HtmlImage, ImageAlign, StyleSheet};
 use crate::string::{WStr, WString};
 use swf::{Rectangle, Twips};
 
@@ -864,3 +865,131 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn stylesheet_parse_css() {
+    let rules = StyleSheet::parse_css(
+        "/* heading */ h1, .Title { font-size: 18px; color: #FF0000 }\np { text-align: center; }",
+    )
+    .unwrap();
+
+    assert_eq!(3, rules.len());
+    assert_eq!("h1", rules[0].0);
+    assert_eq!(".title", rules[1].0);
+    assert_eq!(
+        vec![
+            ("fontSize".to_string(), "18px".to_string()),
+            ("color".to_string(), "#FF0000".to_string()),
+        ],
+        rules[1].1
+    );
+    assert_eq!("p", rules[2].0);
+
+    assert!(StyleSheet::parse_css("p { color: #000000").is_none());
+    assert!(StyleSheet::parse_css("p { color }").is_none());
+}
+
+#[test]
+fn stylesheet_style_to_text_format() {
+    let style = StyleSheet::parse_css(
+        "p { color: #00FF00; font-family: sans-serif; font-weight: bold; text-decoration: underline; margin-left: 4 }",
+    )
+    .unwrap()
+    .remove(0)
+    .1;
+    let tf = StyleSheet::style_to_text_format(&style);
+
+    assert_eq!(Some(swf::Color::from_rgb(0x00FF00, 0)), tf.color);
+    assert_eq!(Some(WString::from_utf8("_sans")), tf.font);
+    assert_eq!(Some(true), tf.bold);
+    assert_eq!(Some(true), tf.underline);
+    assert_eq!(Some(4.0), tf.left_margin);
+    assert_eq!(None, tf.italic);
+}
+
+#[test]
+fn stylesheet_from_html() {
+    let mut style_sheet = StyleSheet::new();
+    style_sheet.set_style(".hidden", vec![("display".into(), "none".into())]);
+    style_sheet.set_style(".big", vec![("fontSize".into(), "30".into())]);
+
+    let fs = FormatSpans::from_html(
+        WStr::from_units(b"<span class=\"big\">a</span><span class=\"hidden\">b</span>c"),
+        TextFormat::default(),
+        Some(&style_sheet),
+        false,
+        false,
+    );
+
+    assert_eq!(WStr::from_units(b"ac"), fs.text());
+    assert_eq!(Some(30.0), fs.get_text_format(0, 1).size);
+}
+
+#[test]
+fn formatspans_from_html_condense_white() {
+    let html = WStr::from_units(b"  a \n\t b<br>  c <b> d</b>");
+
+    let fs = FormatSpans::from_html(html, TextFormat::default(), None, true, true);
+    assert_eq!(WStr::from_units(b"a b\nc d"), fs.text());
+
+    let fs = FormatSpans::from_html(html, TextFormat::default(), None, true, false);
+    assert_eq!(WStr::from_units(b"  a \n\t b\n  c  d"), fs.text());
+}
+
+#[test]
+fn formatspans_from_html_img() {
+    let fs = FormatSpans::from_html(
+        WStr::from_units(
+            b"ab<img src='logo' width='20' align='right' hspace='2' id='pic'>cd<IMG SRC=\"x.png\"/>",
+        ),
+        TextFormat::default(),
+        None,
+        false,
+        false,
+    );
+
+    assert_eq!(WStr::from_units(b"abcd"), fs.text());
+    assert_eq!(
+        &[
+            HtmlImage {
+                src: WString::from_utf8("logo"),
+                id: Some(WString::from_utf8("pic")),
+                width: Some(20.0),
+                height: None,
+                align: ImageAlign::Right,
+                hspace: 2.0,
+                vspace: 8.0,
+                position: 2,
+            },
+            HtmlImage {
+                src: WString::from_utf8("x.png"),
+                id: None,
+                width: None,
+                height: None,
+                align: ImageAlign::Left,
+                hspace: 8.0,
+                vspace: 8.0,
+                position: 4,
+            },
+        ][..],
+        fs.images()
+    );
+}
+
+#[test]
+fn formatspans_replace_text_moves_images() {
+    let mut fs = FormatSpans::from_html(
+        WStr::from_units(b"abc<img src='a'>def<img src='b'>ghi"),
+        TextFormat::default(),
+        None,
+        false,
+        false,
+    );
+
+    fs.replace_text(1, 4, WStr::from_units(b"123456"), None);
+
+    assert_eq!(WStr::from_units(b"a123456efghi"), fs.text());
+    assert_eq!(1, fs.images().len());
+    assert_eq!(WString::from_utf8("b"), fs.images()[0].src);
+    assert_eq!(9, fs.images()[0].position);
+}
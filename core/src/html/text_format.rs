@@ -2,6 +2,7 @@
 
 use crate::context::UpdateContext;
 use crate::html::iterators::TextSpanIter;
+use crate::html::style_sheet::{CssDisplay, StyleSheet};
 use crate::string::{Integer, Units, WStr, WString};
 use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
@@ -10,6 +11,7 @@ use std::borrow::Cow;
 use std::cmp::{min, Ordering};
 use std::collections::VecDeque;
 use std::fmt::Write;
+use std::iter::Peekable;
 use std::sync::Arc;
 
 /// Replace HTML entities with their equivalent characters.
@@ -98,6 +100,32 @@ fn process_html_entity(src: &WStr) -> Option<WString> {
     Some(result_str)
 }
 
+/// Whether a code unit is whitespace that `condenseWhite` collapses.
+fn is_html_white(c: u16) -> bool {
+    matches!(c, 0x09 | 0x0A | 0x0D | 0x20)
+}
+
+/// Collapse each run of whitespace in HTML text into a single space, as
+/// `condenseWhite` does.
+///
+/// Whitespace at the start of a line is removed entirely.
+fn condense_html_white(src: &WStr, is_line_start: bool) -> WString {
+    let mut result = WString::new();
+    let mut last_was_white = is_line_start;
+    for c in src.iter() {
+        if is_html_white(c) {
+            if !last_was_white {
+                result.push_byte(b' ');
+            }
+            last_was_white = true;
+        } else {
+            result.push(c);
+            last_was_white = false;
+        }
+    }
+    result
+}
+
 /// A set of text formatting options to be applied to some part, or the whole
 /// of, a given text field.
 ///
@@ -515,6 +543,58 @@ impl TextSpan {
     }
 }
 
+/// Which side of the text an image embedded in HTML text floats to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImageAlign {
+    #[default]
+    Left,
+    Right,
+}
+
+/// An image embedded in HTML text with an `<img>` tag.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HtmlImage {
+    /// The linkage name of a library symbol, or the URL of an image or SWF.
+    pub src: WString,
+
+    /// The instance name to give the image, used to look it up with
+    /// `getImageReference`.
+    pub id: Option<WString>,
+
+    /// The width to display the image at, in pixels. Defaults to the width of
+    /// the image itself.
+    pub width: Option<f64>,
+
+    /// The height to display the image at, in pixels. Defaults to the height
+    /// of the image itself.
+    pub height: Option<f64>,
+
+    pub align: ImageAlign,
+
+    /// The horizontal space kept free of text beside the image, in pixels.
+    pub hspace: f64,
+
+    /// The vertical space kept free of text below the image, in pixels.
+    pub vspace: f64,
+
+    /// The position in the text where the image was declared.
+    pub position: usize,
+}
+
+impl HtmlImage {
+    /// The space Flash leaves around images when `hspace` or `vspace` aren't
+    /// given.
+    const DEFAULT_SPACE: f64 = 8.0;
+
+    /// Whether two images show the same content, ignoring where they are.
+    pub fn same_content(&self, other: &Self) -> bool {
+        self.src == other.src
+            && self.id == other.id
+            && self.width == other.width
+            && self.height == other.height
+    }
+}
+
 /// Struct which contains text formatted by `TextSpan`s.
 #[derive(Clone, Debug, Collect)]
 #[collect(require_static)]
@@ -523,6 +603,7 @@ pub struct FormatSpans {
     displayed_text: WString,
     spans: Vec<TextSpan>,
     default_format: TextFormat,
+    images: Vec<HtmlImage>,
 }
 
 impl Default for FormatSpans {
@@ -538,6 +619,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: vec![TextSpan::default()],
             default_format: TextFormat::default(),
+            images: Vec::new(),
         }
     }
 
@@ -549,6 +631,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: spans.to_vec(),
             default_format: Default::default(),
+            images: Vec::new(),
         }
     }
 
@@ -559,19 +642,27 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: vec![TextSpan::with_length_and_format(len, format.clone())],
             default_format: format,
+            images: Vec::new(),
         }
     }
 
     /// Lower an HTML tree into text-span representation.
     ///
-    /// This is the "legacy" implementation of this process: it only looks for
-    /// a handful of presentational attributes in the HTML tree to generate
-    /// styling. There's also a `lower_from_css` that respects both
-    /// presentational markup and CSS stylesheets.
-    pub fn from_html(html: &WStr, default_format: TextFormat, is_multiline: bool) -> Self {
+    /// Styling is generated from a handful of presentational attributes in the
+    /// HTML tree and, if a style sheet is given, from the styles matching each
+    /// element's tag and class.
+    pub fn from_html(
+        html: &WStr,
+        default_format: TextFormat,
+        style_sheet: Option<&StyleSheet>,
+        is_multiline: bool,
+        condense_white: bool,
+    ) -> Self {
         let mut format_stack = vec![default_format.clone()];
+        let mut display_stack: Vec<Option<CssDisplay>> = vec![None];
         let mut text = WString::new();
         let mut spans: Vec<TextSpan> = Vec::new();
+        let mut images: Vec<HtmlImage> = Vec::new();
 
         // quick_xml::Reader requires a [u8] slice, but doesn't actually care about Unicode;
         // this means we can pass the raw buffer in the Latin1 case.
@@ -713,6 +804,43 @@ impl FormatSpans {
                                 }
                             }
                         }
+                        b"img" => {
+                            // Images have no contents, and are commonly left
+                            // unclosed, so they don't take part in end tag
+                            // matching.
+                            if let Some(start) = opened_starts.pop() {
+                                opened_buffer.truncate(start);
+                            }
+
+                            if display_stack.contains(&Some(CssDisplay::None)) {
+                                continue;
+                            }
+
+                            if let Some(src) = attribute(b"src") {
+                                let number = |name| {
+                                    attribute(name).and_then(|value: WString| value.parse().ok())
+                                };
+                                let align = match attribute(b"align") {
+                                    Some(align) if align == WStr::from_units(b"right") => {
+                                        ImageAlign::Right
+                                    }
+                                    _ => ImageAlign::Left,
+                                };
+                                images.push(HtmlImage {
+                                    src,
+                                    id: attribute(b"id"),
+                                    width: number(b"width"),
+                                    height: number(b"height"),
+                                    align,
+                                    hspace: number(b"hspace").unwrap_or(HtmlImage::DEFAULT_SPACE),
+                                    vspace: number(b"vspace").unwrap_or(HtmlImage::DEFAULT_SPACE),
+                                    position: text.len(),
+                                });
+                            }
+
+                            // Skip push to `format_stack`.
+                            continue;
+                        }
                         b"b" => {
                             format.bold = Some(true);
                         }
@@ -759,10 +887,36 @@ impl FormatSpans {
                         }
                         _ => {}
                     }
+
+                    let mut display = None;
+                    if let Some(style_sheet) = style_sheet {
+                        let tag = String::from_utf8_lossy(e.name()).to_ascii_lowercase();
+                        let class =
+                            attribute(b"class").map(|class| class.to_utf8_lossy().into_owned());
+                        format = style_sheet.apply_to_element(&tag, class.as_deref(), format);
+                        display = style_sheet.display(&tag, class.as_deref());
+                    }
+
                     format_stack.push(format);
+                    display_stack.push(display);
                 }
                 Ok(Event::Text(e)) if !e.is_empty() => {
-                    let e = decode_to_wstr(e.escaped());
+                    if display_stack.contains(&Some(CssDisplay::None)) {
+                        continue;
+                    }
+
+                    let mut e = decode_to_wstr(e.escaped());
+                    if condense_white {
+                        let is_line_start = text
+                            .len()
+                            .checked_sub(1)
+                            .and_then(|i| text.get(i))
+                            .map_or(true, is_html_white);
+                        e = condense_html_white(&e, is_line_start);
+                        if e.is_empty() {
+                            continue;
+                        }
+                    }
                     let e = process_html_entity(&e).unwrap_or(e);
                     let format = format_stack.last().unwrap().clone();
                     text.push_str(&e);
@@ -782,20 +936,23 @@ impl FormatSpans {
                         None => continue,
                     }
 
-                    match &e.name().to_ascii_lowercase()[..] {
+                    let display = display_stack.last().copied().flatten();
+                    let is_block = match &e.name().to_ascii_lowercase()[..] {
                         b"br" | b"sbr" => {
                             // Skip pop from `format_stack`.
                             continue;
                         }
-                        b"p" | b"li" if is_multiline => {
-                            text.push_byte(b'\n');
-                            if let Some(span) = spans.last_mut() {
-                                span.span_length += 1;
-                            }
+                        b"p" | b"li" => display != Some(CssDisplay::Inline),
+                        _ => display == Some(CssDisplay::Block),
+                    };
+                    if is_block && is_multiline {
+                        text.push_byte(b'\n');
+                        if let Some(span) = spans.last_mut() {
+                            span.span_length += 1;
                         }
-                        _ => {}
                     }
                     format_stack.pop();
+                    display_stack.pop();
                 }
                 Ok(Event::Eof) => break,
                 Err(e) => {
@@ -811,9 +968,15 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans,
             default_format,
+            images,
         }
     }
 
+    /// The images embedded in the text, in the order they were declared.
+    pub fn images(&self) -> &[HtmlImage] {
+        &self.images
+    }
+
     pub fn default_format(&self) -> &TextFormat {
         &self.default_format
     }
@@ -1122,6 +1285,17 @@ impl FormatSpans {
             ));
         }
 
+        // Images inside the replaced text are removed, and images after it
+        // move along with the text.
+        let replaced = from.min(self.text.len())..to.min(self.text.len());
+        self.images
+            .retain(|image| !replaced.contains(&image.position));
+        for image in &mut self.images {
+            if image.position >= replaced.end {
+                image.position = image.position - replaced.end + replaced.start + with.len();
+            }
+        }
+
         let mut new_string = WString::new();
         if let Some(text) = self.text.slice(0..from) {
             new_string.push_str(text);
@@ -1157,23 +1331,28 @@ impl FormatSpans {
     }
 
     pub fn to_html(&self) -> WString {
+        let mut images = self.images.iter().peekable();
         let mut spans = self.iter_spans();
-        let mut state = if let Some((_start, _end, text, span)) = spans.next() {
+        let mut state = if let Some((start, _end, text, span)) = spans.next() {
             let mut state = FormatState {
                 result: WString::new(),
                 font_stack: VecDeque::new(),
                 span,
                 is_open: false,
             };
-            state.push_text(text);
+            state.push_text_and_images(start, text, &mut images);
             state
         } else {
             return WString::new();
         };
 
-        for (_start, _end, text, span) in spans {
+        for (start, _end, text, span) in spans {
             state.set_span(span);
-            state.push_text(text);
+            state.push_text_and_images(start, text, &mut images);
+        }
+
+        for image in images {
+            state.push_image(image);
         }
 
         state.close_tags();
@@ -1426,4 +1605,53 @@ impl<'a> FormatState<'a> {
             self.result.push_str(WStr::from_units(&*escaped));
         }
     }
+
+    /// Push text that starts at `start` in the text field, along with any of
+    /// the upcoming `images` declared within it.
+    fn push_text_and_images(
+        &mut self,
+        start: usize,
+        text: &WStr,
+        images: &mut Peekable<std::slice::Iter<'_, HtmlImage>>,
+    ) {
+        let mut offset = 0;
+        while let Some(image) = images.next_if(|image| image.position < start + text.len()) {
+            let position = image.position.saturating_sub(start).max(offset);
+            self.push_text(&text[offset..position]);
+            self.push_image(image);
+            offset = position;
+        }
+        self.push_text(&text[offset..]);
+    }
+
+    fn push_image(&mut self, image: &HtmlImage) {
+        self.open_tags();
+
+        let escape_attribute = |value: &WStr| {
+            let encoded = value.to_utf8_lossy();
+            String::from_utf8_lossy(&escape(encoded.as_bytes())).into_owned()
+        };
+
+        let _ = write!(self.result, "<IMG SRC=\"{}\"", escape_attribute(&image.src));
+        if let Some(width) = image.width {
+            let _ = write!(self.result, " WIDTH=\"{width}\"");
+        }
+        if let Some(height) = image.height {
+            let _ = write!(self.result, " HEIGHT=\"{height}\"");
+        }
+        let _ = write!(
+            self.result,
+            " ALIGN=\"{}\" HSPACE=\"{}\" VSPACE=\"{}\"",
+            match image.align {
+                ImageAlign::Left => "left",
+                ImageAlign::Right => "right",
+            },
+            image.hspace,
+            image.vspace,
+        );
+        if let Some(id) = &image.id {
+            let _ = write!(self.result, " ID=\"{}\"", escape_attribute(id));
+        }
+        self.result.push_byte(b'>');
+    }
 }
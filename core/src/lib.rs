@@ -21,6 +21,7 @@ pub mod bitmap;
 mod character;
 pub mod context;
 pub mod context_menu;
+mod dirty_region;
 mod drawing;
 mod ecma_conversions;
 pub(crate) mod either;
@@ -35,6 +36,7 @@ pub mod loader;
 mod locale;
 mod player;
 mod prelude;
+pub mod sandbox;
 pub mod string;
 pub mod tag_utils;
 pub mod timer;
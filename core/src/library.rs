@@ -130,6 +130,13 @@ impl<'gc> MovieLibrary<'gc> {
         }
     }
 
+    /// Replaces an already registered font, such as when a `DefineFontInfo`
+    /// tag supplies its code table.
+    pub fn replace_font(&mut self, id: CharacterId, font: Font<'gc>) {
+        self.fonts.insert(font.descriptor().clone(), font);
+        self.characters.insert(id, Character::Font(font));
+    }
+
     /// Registers an export name for a given character ID.
     /// This character will then be instantiable from AVM1.
     pub fn register_export(
@@ -276,6 +283,17 @@ impl<'gc> MovieLibrary<'gc> {
         }
     }
 
+    /// Iterates over the handles of every sound registered in this library.
+    pub fn sounds(&self) -> impl Iterator<Item = SoundHandle> + '_ {
+        self.characters.values().filter_map(|character| {
+            if let Character::Sound(sound) = character {
+                Some(*sound)
+            } else {
+                None
+            }
+        })
+    }
+
     /// Returns the `Text` with the given character ID.
     /// Returns `None` if the ID does not exist or is not a `Text`.
     pub fn get_text(&self, id: CharacterId) -> Option<Text<'gc>> {
@@ -397,6 +415,14 @@ impl<'gc> Library<'gc> {
             .or_insert_with(MovieLibrary::new)
     }
 
+    /// Drop the library of a movie that is being unloaded.
+    ///
+    /// Library characters keep strong references to their movie, so the
+    /// movie can't be freed until its library has been removed.
+    pub fn remove_movie_library(&mut self, movie: &Arc<SwfMovie>) -> Option<MovieLibrary<'gc>> {
+        self.movie_libraries.remove(movie)
+    }
+
     /// Returns the device font for use when a font is unavailable.
     pub fn device_font(&self) -> Option<Font<'gc>> {
         self.device_font
@@ -417,3 +443,29 @@ impl<'gc> Library<'gc> {
         &mut self.avm2_class_registry
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use generational_arena::Index;
+
+    #[test]
+    fn remove_movie_library_drops_only_that_movie() {
+        let root = Arc::new(SwfMovie::empty(10));
+        let loaded = Arc::new(SwfMovie::empty(10));
+        let mut library = Library::empty();
+        library.library_for_movie_mut(root.clone());
+        library
+            .library_for_movie_mut(loaded.clone())
+            .register_character(1, Character::Sound(Index::from_raw_parts(0, 0)));
+
+        let removed = library.remove_movie_library(&loaded).unwrap();
+        assert_eq!(
+            removed.sounds().collect::<Vec<_>>(),
+            [Index::from_raw_parts(0, 0)]
+        );
+        assert!(library.library_for_movie(loaded.clone()).is_none());
+        assert!(library.library_for_movie(root).is_some());
+        assert!(library.remove_movie_library(&loaded).is_none());
+    }
+}
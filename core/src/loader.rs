@@ -11,17 +11,19 @@ use crate::avm2::object::LoaderStream;
 use crate::avm2::object::TObject as _;
 use crate::avm2::{
     Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Multiname as Avm2Multiname,
-    Object as Avm2Object, Value as Avm2Value,
+    Namespace as Avm2Namespace, Object as Avm2Object, Value as Avm2Value,
 };
 use crate::backend::navigator::{OwnedFuture, Request};
+use crate::backend::ui::{DialogFile, DialogResultFuture};
 use crate::context::{ActionQueue, ActionType, UpdateContext};
 use crate::display_object::{
-    Bitmap, DisplayObject, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
+    Bitmap, DisplayObject, MovieClip, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
 };
 use crate::events::ClipEvent;
 use crate::frame_lifecycle::catchup_display_object_to_frame;
 use crate::limits::ExecutionLimit;
 use crate::player::Player;
+use crate::sandbox::PolicyFile;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
@@ -30,12 +32,13 @@ use gc_arena::{Collect, CollectionContext};
 use generational_arena::{Arena, Index};
 use ruffle_render::utils::{determine_jpeg_tag_format, JpegTagFormat};
 use std::fmt;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 use swf::read::{extract_swz, read_compression_type};
 use thiserror::Error;
-use url::form_urlencoded;
+use url::{form_urlencoded, Url};
 
 pub type Handle = Index;
 
@@ -79,6 +82,40 @@ impl FromStr for LoadBehavior {
     }
 }
 
+/// Options from an AVM2 `LoaderContext` that affect how a movie is loaded.
+#[derive(Clone, Debug)]
+pub struct MovieLoadOptions {
+    /// Whether to fetch the policy files that let the loading movie access the content.
+    pub check_policy_file: bool,
+
+    /// Whether SWF content may be loaded at all.
+    pub allow_code_import: bool,
+
+    /// The URL of the movie that started the load, which policy files are
+    /// checked against.
+    pub requester_url: Option<String>,
+}
+
+impl Default for MovieLoadOptions {
+    fn default() -> Self {
+        Self {
+            check_policy_file: false,
+            allow_code_import: true,
+            requester_url: None,
+        }
+    }
+}
+
+/// The reason a cross-domain request was refused by the server's policy files.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum PolicyViolation {
+    /// No policy file grants the requesting movie access to the data.
+    Access,
+
+    /// No policy file allows the requesting movie to send its custom headers.
+    Headers,
+}
+
 /// Enumeration of all content types that `Loader` can handle.
 ///
 /// This is a superset of `JpegTagFormat`.
@@ -164,6 +201,12 @@ pub enum Error {
     #[error("Non-sound loader spawned as sound loader")]
     NotSoundLoader,
 
+    #[error("Non-file reference loader spawned as file reference loader")]
+    NotFileReferenceLoader,
+
+    #[error("Non-file dialog loader spawned as file dialog loader")]
+    NotFileDialogLoader,
+
     #[error("Could not fetch: {0}")]
     FetchError(String),
 
@@ -227,11 +270,18 @@ impl<'gc> LoadManager<'gc> {
             | Loader::LoadVars { self_handle, .. }
             | Loader::LoadURLLoader { self_handle, .. }
             | Loader::SoundAvm1 { self_handle, .. }
-            | Loader::SoundAvm2 { self_handle, .. } => *self_handle = Some(handle),
+            | Loader::SoundAvm2 { self_handle, .. }
+            | Loader::FileReference { self_handle, .. }
+            | Loader::FileDialog { self_handle, .. } => *self_handle = Some(handle),
         }
         handle
     }
 
+    /// Remove a loader whose operation has finished.
+    pub fn remove_loader(&mut self, handle: Handle) {
+        self.0.remove(handle);
+    }
+
     /// Retrieve a loader by handle.
     pub fn get_loader(&self, handle: Handle) -> Option<&Loader<'gc>> {
         self.0.get(handle)
@@ -271,7 +321,8 @@ impl<'gc> LoadManager<'gc> {
         request: Request,
         loader_url: Option<String>,
         event_handler: Option<MovieLoaderEventHandler<'gc>>,
-        context: Option<Avm2Object<'gc>>,
+        avm2_domain: Option<Avm2Domain<'gc>>,
+        options: MovieLoadOptions,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::Movie {
             self_handle: None,
@@ -279,11 +330,11 @@ impl<'gc> LoadManager<'gc> {
             event_handler,
             loader_status: LoaderStatus::Pending,
             movie: None,
-            context,
+            avm2_domain,
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.movie_loader(player, request, loader_url)
+        loader.movie_loader(player, request, loader_url, options)
     }
 
     /// Kick off a movie clip load.
@@ -295,7 +346,7 @@ impl<'gc> LoadManager<'gc> {
         target_clip: DisplayObject<'gc>,
         bytes: Vec<u8>,
         event_handler: Option<MovieLoaderEventHandler<'gc>>,
-        context: Option<Avm2Object<'gc>>,
+        avm2_domain: Option<Avm2Domain<'gc>>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::Movie {
             self_handle: None,
@@ -303,13 +354,40 @@ impl<'gc> LoadManager<'gc> {
             event_handler,
             loader_status: LoaderStatus::Pending,
             movie: None,
-            context,
+            avm2_domain,
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
         loader.movie_loader_bytes(player, bytes)
     }
 
+    /// Cancel all in-progress movie loads that report to the given AVM2
+    /// `LoaderInfo`.
+    ///
+    /// Any futures still running for these loads will find their loader gone
+    /// and fail with `Error::Cancelled`.
+    pub fn cancel_loads_for_loader_info(&mut self, loader_info: Avm2Object<'gc>) {
+        self.0.retain(|_, loader| {
+            !matches!(
+                loader,
+                Loader::Movie {
+                    event_handler: Some(MovieLoaderEventHandler::Avm2LoaderInfo(other)),
+                    ..
+                } if Avm2Object::ptr_eq(*other, loader_info)
+            )
+        });
+    }
+
+    /// Cancel all in-progress movie loads into the given clip.
+    pub fn cancel_loads_into_clip(&mut self, clip: DisplayObject<'gc>) {
+        self.0.retain(|_, loader| {
+            !matches!(
+                loader,
+                Loader::Movie { target_clip, .. } if DisplayObject::ptr_eq(*target_clip, clip)
+            )
+        });
+    }
+
     /// Indicates that a movie clip has initialized (ran its first frame).
     ///
     /// Interested loaders will be invoked from here.
@@ -373,6 +451,7 @@ impl<'gc> LoadManager<'gc> {
         target_object: Avm2Object<'gc>,
         request: Request,
         data_format: DataFormat,
+        requester_url: Option<String>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::LoadURLLoader {
             self_handle: None,
@@ -380,7 +459,7 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.load_url_loader(player, request, data_format)
+        loader.load_url_loader(player, request, data_format, requester_url)
     }
 
     /// Kick off an AVM1 audio load.
@@ -420,6 +499,65 @@ impl<'gc> LoadManager<'gc> {
         loader.sound_loader_avm2(player, request)
     }
 
+    /// Kick off a download or upload for a `FileReference`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn transfer_file_reference(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: FileReferenceTarget<'gc>,
+        request: Request,
+        transfer: FileTransfer,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::FileReference {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.file_reference_loader(player, request, transfer)
+    }
+
+    /// Cancel all in-progress downloads and uploads of a `FileReference`.
+    pub fn cancel_file_reference_transfers(&mut self, file_reference: FileReferenceTarget<'gc>) {
+        self.0.retain(|_, loader| {
+            !matches!(
+                loader,
+                Loader::FileReference { target_object, .. }
+                    if target_object.ptr_eq(file_reference)
+            )
+        });
+    }
+
+    /// Show a file dialog for a `FileReference` or `FileReferenceList`, and
+    /// act on the user's choice once it is closed.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn open_file_dialog(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: FileReferenceTarget<'gc>,
+        dialog: DialogResultFuture<Vec<DialogFile>>,
+        purpose: FileDialogPurpose,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::FileDialog {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.file_dialog_loader(player, dialog, purpose)
+    }
+
+    /// Check if a file dialog is currently open.
+    ///
+    /// Flash Player only allows one file browsing session at a time.
+    pub fn is_file_dialog_open(&self) -> bool {
+        self.0
+            .iter()
+            .any(|(_, loader)| matches!(loader, Loader::FileDialog { .. }))
+    }
+
     /// Process tags on all loaders in the Parsing phase.
     ///
     /// Returns true if *all* loaders finished preloading.
@@ -454,6 +592,185 @@ impl<'gc> Default for LoadManager<'gc> {
     }
 }
 
+/// Unload the movie hosted by an AVM1 clip, as done by `unloadMovie` or by
+/// `loadMovie` with a blank URL.
+///
+/// Pending loads into the clip are cancelled. If the clip was hosting a movie
+/// loaded from elsewhere, that movie's sounds are stopped and its library is
+/// released.
+pub fn unload_avm1_clip<'gc>(context: &mut UpdateContext<'_, 'gc, '_>, mut clip: MovieClip<'gc>) {
+    context.load_manager.cancel_loads_into_clip(clip.into());
+
+    let movie = clip.movie();
+    let is_loaded_movie = clip
+        .parent()
+        .map_or(false, |parent| !Arc::ptr_eq(&parent.movie(), &movie));
+
+    clip.unload(context);
+    clip.replace_with_movie(context, None, None);
+
+    if is_loaded_movie {
+        context
+            .audio_manager
+            .stop_sounds_with_movie(context.audio, &movie);
+        context.library.remove_movie_library(&movie);
+    }
+}
+
+/// Stop everything that an unloaded movie left running in the player.
+///
+/// This is the teardown performed by `Loader.unloadAndStop`: sounds, timers,
+/// frame broadcasts and stage event listeners belonging to `movie` are
+/// discarded, and its library is released so that the movie can be freed.
+pub fn stop_movie<'gc>(context: &mut UpdateContext<'_, 'gc, '_>, movie: &Arc<SwfMovie>) {
+    context
+        .audio_manager
+        .stop_sounds_with_movie(context.audio, movie);
+    if let Some(library) = context.library.library_for_movie(movie.clone()) {
+        for sound in library.sounds() {
+            context
+                .audio_manager
+                .stop_sounds_with_handle(context.audio, sound);
+        }
+    }
+
+    context.timers.remove_timers_for_movie(movie);
+
+    Avm2::unregister_broadcast_listeners_for_movie(context, movie);
+    let stage = context.stage.into();
+    remove_display_list_listeners(context, stage, movie);
+
+    context.library.remove_movie_library(movie);
+}
+
+/// Remove every event listener defined by `movie` from `object` and all of its descendants.
+///
+/// The unloaded movie's own objects are already off the display list by now, so this
+/// catches listeners it registered on the stage and on objects of other movies.
+fn remove_display_list_listeners<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    object: DisplayObject<'gc>,
+    movie: &Arc<SwfMovie>,
+) {
+    if let Avm2Value::Object(target) = object.object2() {
+        if let Err(e) = Avm2::remove_event_listeners_for_movie(context, target, movie) {
+            log::error!("Error removing listeners of unloaded movie: {}", e);
+        }
+    }
+
+    if let Some(container) = object.as_container() {
+        for child in container.iter_render_list() {
+            remove_display_list_listeners(context, child, movie);
+        }
+    }
+}
+
+/// Check whether the movie served from `requester` may read data from `url`
+/// while sending the given custom HTTP headers, fetching and caching any
+/// policy files that the decision depends on.
+///
+/// This always succeeds if the security sandbox is disabled, or if `url` is
+/// in the same domain as the requesting movie.
+async fn check_policy_files(
+    player: &Arc<Mutex<Player>>,
+    requester: Option<&str>,
+    url: &str,
+    headers: &[String],
+) -> Result<(), PolicyViolation> {
+    if !player
+        .lock()
+        .unwrap()
+        .security_sandbox()
+        .is_cross_domain(requester, url)
+    {
+        return Ok(());
+    }
+
+    let (requester, target) = match (requester.map(Url::parse), Url::parse(url)) {
+        (Some(Ok(requester)), Ok(target)) => (requester, target),
+        _ => return Ok(()),
+    };
+
+    // The master policy file decides which other policy files are honored,
+    // so this takes at most two rounds of fetching.
+    loop {
+        let pending = player
+            .lock()
+            .unwrap()
+            .security_sandbox()
+            .pending_policy_files(&target);
+        if pending.is_empty() {
+            break;
+        }
+
+        for policy_url in pending {
+            let fetch = player
+                .lock()
+                .unwrap()
+                .navigator()
+                .fetch(Request::get(policy_url.to_string()));
+            let policy = fetch.await.ok().and_then(|response| {
+                PolicyFile::parse(&response.body)
+                    .map(|policy| policy.with_content_type(response.content_type))
+            });
+            if policy.is_none() {
+                log::warn!("Could not load policy file {}", policy_url);
+            }
+
+            player
+                .lock()
+                .unwrap()
+                .security_sandbox_mut()
+                .set_policy_file(policy_url, policy);
+        }
+    }
+
+    let player = player.lock().unwrap();
+    let sandbox = player.security_sandbox();
+    if !sandbox.policy_allows(&requester, &target) {
+        log::warn!("Cross-domain access to {} denied by policy", url);
+        return Err(PolicyViolation::Access);
+    }
+    if !sandbox.policy_allows_headers(&requester, &target, headers) {
+        log::warn!("Cross-domain headers to {} denied by policy", url);
+        return Err(PolicyViolation::Headers);
+    }
+    Ok(())
+}
+
+/// Dispatch a `securityError` event with the given message to an AVM2 object.
+fn dispatch_security_error<'gc>(
+    uc: &mut UpdateContext<'_, 'gc, '_>,
+    target: Avm2Object<'gc>,
+    message: &str,
+    error_id: i32,
+) -> Result<(), Error> {
+    let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+    let message = AvmString::new_utf8(activation.context.gc_context, message);
+    let security_error_evt_cls = activation.avm2().classes().securityerrorevent;
+    let security_error_evt = security_error_evt_cls
+        .construct(
+            &mut activation,
+            &[
+                "securityError".into(),
+                false.into(),
+                false.into(),
+                message.into(),
+                error_id.into(),
+            ],
+        )
+        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+    if let Err(e) = Avm2::dispatch_event(uc, security_error_evt, target) {
+        log::error!(
+            "Encountered AVM2 error when broadcasting `securityError` event: {}",
+            e
+        );
+    }
+
+    Ok(())
+}
+
 /// The completion status of a `Loader` loading a movie.
 #[derive(Clone, Collect, Copy, Debug, Eq, PartialEq)]
 #[collect(require_static)]
@@ -468,6 +785,296 @@ pub enum LoaderStatus {
     Failed,
 }
 
+/// The direction of a `FileReference` transfer.
+#[derive(Debug)]
+pub enum FileTransfer {
+    /// Download the requested URL into the given file.
+    Download(PathBuf),
+
+    /// Send the request, whose body holds the file, and report the response
+    /// to script code.
+    Upload {
+        /// The size of the uploaded file, as reported in progress events.
+        file_size: usize,
+    },
+}
+
+/// What to do with the files picked in a file dialog.
+pub enum FileDialogPurpose {
+    /// `FileReference.browse`: select a single file.
+    Browse,
+
+    /// `FileReferenceList.browse`: select any number of files.
+    BrowseMultiple,
+
+    /// `FileReference.download`: download the request into the chosen file.
+    Download(Request),
+
+    /// `FileReference.save`: write the data into the chosen file.
+    Save(Vec<u8>),
+}
+
+/// A `FileReference` or `FileReferenceList` from either AVM.
+#[derive(Collect, Clone, Copy)]
+#[collect(no_drop)]
+pub enum FileReferenceTarget<'gc> {
+    Avm1(Object<'gc>),
+    Avm2(Avm2Object<'gc>),
+}
+
+/// An event reported to a `FileReference` or `FileReferenceList`.
+pub enum FileReferenceEvent<'a> {
+    Select,
+    Cancel,
+    Open,
+    Progress {
+        bytes_loaded: usize,
+        bytes_total: usize,
+    },
+    Complete,
+    UploadCompleteData(&'a [u8]),
+    IoError {
+        message: &'static str,
+        code: i32,
+    },
+}
+
+impl FileReferenceEvent<'_> {
+    /// The AVM2 event type and the AVM1 listener method of this event.
+    fn names(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::Select => ("select", "onSelect"),
+            Self::Cancel => ("cancel", "onCancel"),
+            Self::Open => ("open", "onOpen"),
+            Self::Progress { .. } => ("progress", "onProgress"),
+            Self::Complete => ("complete", "onComplete"),
+            Self::UploadCompleteData(_) => ("uploadCompleteData", "onUploadCompleteData"),
+            Self::IoError { .. } => ("ioError", "onIOError"),
+        }
+    }
+}
+
+impl<'gc> FileReferenceTarget<'gc> {
+    pub fn ptr_eq(self, other: Self) -> bool {
+        match (self, other) {
+            (Self::Avm1(a), Self::Avm1(b)) => Object::ptr_eq(a, b),
+            (Self::Avm2(a), Self::Avm2(b)) => Avm2Object::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    /// Report an event to script code.
+    ///
+    /// AVM1 listeners receive the target as their first argument, followed by
+    /// any details of the event.
+    pub fn fire(
+        self,
+        uc: &mut UpdateContext<'_, 'gc, '_>,
+        event: FileReferenceEvent<'_>,
+    ) -> Result<(), Error> {
+        let (event_type, method) = event.names();
+        match self {
+            Self::Avm1(object) => {
+                let mut args: Vec<Value<'gc>> = vec![method.into(), object.into()];
+                match event {
+                    FileReferenceEvent::Progress {
+                        bytes_loaded,
+                        bytes_total,
+                    } => {
+                        args.push(bytes_loaded.into());
+                        args.push(bytes_total.into());
+                    }
+                    FileReferenceEvent::UploadCompleteData(data) => {
+                        args.push(AvmString::new_utf8_bytes(uc.gc_context, data).into());
+                    }
+                    _ => {}
+                }
+
+                Avm1::run_stack_frame_for_method(
+                    uc.stage.root_clip(),
+                    object,
+                    uc,
+                    "broadcastMessage".into(),
+                    &args,
+                );
+            }
+            Self::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                let event = match event {
+                    FileReferenceEvent::Progress {
+                        bytes_loaded,
+                        bytes_total,
+                    } => {
+                        let progress_evt_cls = activation.avm2().classes().progressevent;
+                        progress_evt_cls.construct(
+                            &mut activation,
+                            &[
+                                event_type.into(),
+                                false.into(),
+                                false.into(),
+                                bytes_loaded.into(),
+                                bytes_total.into(),
+                            ],
+                        )
+                    }
+                    FileReferenceEvent::UploadCompleteData(data) => {
+                        let data = AvmString::new_utf8_bytes(activation.context.gc_context, data);
+                        activation
+                            .resolve_class(&Avm2Multiname::new(
+                                Avm2Namespace::package("flash.events"),
+                                "DataEvent",
+                            ))
+                            .and_then(|data_evt_cls| {
+                                data_evt_cls.construct(
+                                    &mut activation,
+                                    &[event_type.into(), false.into(), false.into(), data.into()],
+                                )
+                            })
+                    }
+                    FileReferenceEvent::IoError { message, code } => {
+                        let io_error_evt_cls = activation.avm2().classes().ioerrorevent;
+                        io_error_evt_cls.construct(
+                            &mut activation,
+                            &[
+                                event_type.into(),
+                                false.into(),
+                                false.into(),
+                                message.into(),
+                                code.into(),
+                            ],
+                        )
+                    }
+                    _ => Ok(Avm2EventObject::bare_default_event(
+                        &mut activation.context,
+                        event_type,
+                    )),
+                }
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+                if let Err(e) = Avm2::dispatch_event(&mut activation.context, event, object) {
+                    log::error!(
+                        "Encountered AVM2 error when broadcasting FileReference event: {}",
+                        e
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Store the details of a file picked in a dialog on a `FileReference`.
+    fn set_selected_file(
+        self,
+        uc: &mut UpdateContext<'_, 'gc, '_>,
+        file: DialogFile,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Avm1(object) => {
+                crate::avm1::globals::file_reference::set_selected_file(
+                    uc.gc_context,
+                    object,
+                    file,
+                );
+                Ok(())
+            }
+            Self::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                crate::avm2::globals::flash::net::file_reference::set_selected_file(
+                    &mut activation,
+                    object,
+                    &file,
+                )
+                .map_err(|e| Error::Avm2Error(e.to_string()))
+            }
+        }
+    }
+
+    /// Fill the `fileList` of a `FileReferenceList` with the files picked in a dialog.
+    fn set_file_list(
+        self,
+        uc: &mut UpdateContext<'_, 'gc, '_>,
+        files: Vec<DialogFile>,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Avm1(object) => {
+                let root_clip = uc.stage.root_clip();
+                let mut activation = Activation::from_nothing(
+                    uc.reborrow(),
+                    ActivationIdentifier::root("[FileReferenceList]"),
+                    root_clip,
+                );
+                crate::avm1::globals::file_reference_list::set_file_list(
+                    &mut activation,
+                    object,
+                    files,
+                )?;
+                Ok(())
+            }
+            Self::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                crate::avm2::globals::flash::net::file_reference_list::set_file_list(
+                    &mut activation,
+                    object,
+                    files,
+                )
+                .map_err(|e| Error::Avm2Error(e.to_string()))
+            }
+        }
+    }
+}
+
+/// The boundary separating the parts of an upload's `multipart/form-data` body.
+const UPLOAD_BOUNDARY: &str = "----------Ij5ae0ae0KM7GI3KM7ei4cH2ei4gL6";
+
+/// Build the body of a `FileReference.upload` request, matching the fields
+/// sent by Flash Player.
+///
+/// The request's own variables are sent as form fields ahead of the file.
+/// Returns the body and its MIME type.
+pub fn multipart_upload_body(
+    file_name: &str,
+    field_name: &str,
+    contents: &[u8],
+    variables: &[(String, String)],
+) -> (Vec<u8>, String) {
+    let mut body = Vec::with_capacity(contents.len() + 512);
+    for (name, value) in variables {
+        body.extend_from_slice(
+            format!(
+                "--{UPLOAD_BOUNDARY}\r\n\
+                 Content-Disposition: form-data; name=\"{name}\"\r\n\r\n\
+                 {value}\r\n"
+            )
+            .as_bytes(),
+        );
+    }
+    body.extend_from_slice(
+        format!(
+            "--{UPLOAD_BOUNDARY}\r\n\
+             Content-Disposition: form-data; name=\"Filename\"\r\n\r\n\
+             {file_name}\r\n\
+             --{UPLOAD_BOUNDARY}\r\n\
+             Content-Disposition: form-data; name=\"{field_name}\"; filename=\"{file_name}\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(contents);
+    body.extend_from_slice(
+        format!(
+            "\r\n--{UPLOAD_BOUNDARY}\r\n\
+             Content-Disposition: form-data; name=\"Upload\"\r\n\r\n\
+             Submit Query\r\n\
+             --{UPLOAD_BOUNDARY}--\r\n"
+        )
+        .as_bytes(),
+    );
+    (
+        body,
+        format!("multipart/form-data; boundary={UPLOAD_BOUNDARY}"),
+    )
+}
+
 #[derive(Collect, Clone, Copy)]
 #[collect(no_drop)]
 pub enum MovieLoaderEventHandler<'gc> {
@@ -516,8 +1123,10 @@ pub enum Loader<'gc> {
         /// until loading completes.
         movie: Option<Arc<SwfMovie>>,
 
-        /// The context of the SWF being loaded. (AVM2 only)
-        context: Option<Avm2Object<'gc>>,
+        /// The application domain the SWF being loaded will run in. (AVM2 only)
+        ///
+        /// If not set, the movie gets a new child of the system domain.
+        avm2_domain: Option<Avm2Domain<'gc>>,
     },
 
     /// Loader that is loading form data into an AVM1 object scope.
@@ -570,6 +1179,26 @@ pub enum Loader<'gc> {
         /// The target AVM1 object to load the audio into.
         target_object: Avm2Object<'gc>,
     },
+
+    /// Loader that is downloading or uploading a file for a `FileReference`.
+    FileReference {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The `FileReference` that receives the transfer's events.
+        target_object: FileReferenceTarget<'gc>,
+    },
+
+    /// Loader that is waiting for the user to close a file dialog.
+    FileDialog {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The `FileReference` or `FileReferenceList` that opened the dialog.
+        target_object: FileReferenceTarget<'gc>,
+    },
 }
 
 impl<'gc> Loader<'gc> {
@@ -732,6 +1361,7 @@ impl<'gc> Loader<'gc> {
         player: Weak<Mutex<Player>>,
         request: Request,
         loader_url: Option<String>,
+        options: MovieLoadOptions,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::Movie { self_handle, .. } => self_handle.expect("Loader not self-introduced"),
@@ -773,6 +1403,44 @@ impl<'gc> Loader<'gc> {
                     return Ok(());
                 }
                 Ok(response) => {
+                    if !options.allow_code_import
+                        && ContentType::sniff(&response.body) == ContentType::Swf
+                    {
+                        player.lock().unwrap().update(|uc| {
+                            Loader::movie_loader_security_error(
+                                handle,
+                                uc,
+                                "Error #3226: Cannot import a SWF file when LoaderContext.allowCodeImport is false.",
+                                3226,
+                            )
+                        })?;
+                        return Ok(());
+                    }
+
+                    if options.check_policy_file {
+                        let requester = options.requester_url.as_deref();
+                        if check_policy_files(&player, requester, &response.url, &[])
+                            .await
+                            .is_ok()
+                        {
+                            player
+                                .lock()
+                                .unwrap()
+                                .security_sandbox_mut()
+                                .trust_content(&response.url);
+                        } else {
+                            // The content still loads, but the loading movie can't access it.
+                            let message = format!(
+                                "Error #2048: Security sandbox violation: {} cannot load data from {}.",
+                                requester.unwrap_or_default(),
+                                response.url
+                            );
+                            player.lock().unwrap().update(|uc| {
+                                Loader::movie_loader_policy_error(handle, uc, &message)
+                            })?;
+                        }
+                    }
+
                     Loader::movie_loader_data(
                         handle,
                         player,
@@ -1002,6 +1670,7 @@ impl<'gc> Loader<'gc> {
         player: Weak<Mutex<Player>>,
         request: Request,
         data_format: DataFormat,
+        requester_url: Option<String>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::LoadURLLoader { self_handle, .. } => {
@@ -1015,6 +1684,45 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
+            let url = request.url().to_string();
+            let header_names: Vec<String> = request
+                .headers()
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect();
+            let requester = requester_url.as_deref();
+            if let Err(violation) =
+                check_policy_files(&player, requester, &url, &header_names).await
+            {
+                return player.lock().unwrap().update(|uc| {
+                    let target = match uc.load_manager.get_loader(handle) {
+                        Some(&Loader::LoadURLLoader { target_object, .. }) => target_object,
+                        None => return Err(Error::Cancelled),
+                        _ => unreachable!(),
+                    };
+
+                    let (message, error_id) = match violation {
+                        PolicyViolation::Access => (
+                            format!(
+                                "Error #2048: Security sandbox violation: {} cannot load data from {}.",
+                                requester.unwrap_or_default(),
+                                url
+                            ),
+                            2048,
+                        ),
+                        PolicyViolation::Headers => (
+                            format!(
+                                "Error #2170: Security sandbox violation: {} cannot send HTTP headers to {}.",
+                                requester.unwrap_or_default(),
+                                url
+                            ),
+                            2170,
+                        ),
+                    };
+                    dispatch_security_error(uc, target, &message, error_id)
+                });
+            }
+
             let fetch = player.lock().unwrap().navigator().fetch(request);
             let response = fetch.await;
 
@@ -1283,6 +1991,191 @@ impl<'gc> Loader<'gc> {
         })
     }
 
+    /// Creates a future for a `FileReference` download or upload.
+    fn file_reference_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+        transfer: FileTransfer,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::FileReference { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileReferenceLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        fn target<'gc>(
+            uc: &UpdateContext<'_, 'gc, '_>,
+            handle: Handle,
+        ) -> Result<FileReferenceTarget<'gc>, Error> {
+            match uc.load_manager.get_loader(handle) {
+                Some(&Loader::FileReference { target_object, .. }) => Ok(target_object),
+                None => Err(Error::Cancelled),
+                _ => Err(Error::NotFileReferenceLoader),
+            }
+        }
+
+        Box::pin(async move {
+            player
+                .lock()
+                .unwrap()
+                .update(|uc| target(uc, handle)?.fire(uc, FileReferenceEvent::Open))?;
+
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let response = fetch.await;
+
+            player.lock().unwrap().update(|uc| {
+                let target = target(uc, handle)?;
+                uc.load_manager.remove_loader(handle);
+
+                // Files written to disk are reported as an I/O error, while
+                // network failures are reported as stream errors.
+                let result = response
+                    .map_err(|_| ("Error #2032: Stream Error", 2032))
+                    .and_then(|response| match &transfer {
+                        FileTransfer::Download(path) => uc
+                            .ui
+                            .write_file(path, &response.body)
+                            .map(|_| response)
+                            .map_err(|_| ("Error #2038: File I/O Error.", 2038)),
+                        FileTransfer::Upload { .. } => Ok(response),
+                    });
+
+                match result {
+                    Ok(response) => {
+                        let size = match &transfer {
+                            FileTransfer::Download(_) => response.body.len(),
+                            FileTransfer::Upload { file_size } => *file_size,
+                        };
+
+                        // Fetches aren't streamed, so the only progress we can
+                        // report is the completed transfer.
+                        target.fire(
+                            uc,
+                            FileReferenceEvent::Progress {
+                                bytes_loaded: size,
+                                bytes_total: size,
+                            },
+                        )?;
+                        target.fire(uc, FileReferenceEvent::Complete)?;
+
+                        // This is only reported if the server sent something back.
+                        if matches!(transfer, FileTransfer::Upload { .. })
+                            && !response.body.is_empty()
+                        {
+                            target
+                                .fire(uc, FileReferenceEvent::UploadCompleteData(&response.body))?;
+                        }
+                    }
+                    Err((message, code)) => {
+                        target.fire(uc, FileReferenceEvent::IoError { message, code })?;
+                    }
+                }
+
+                Ok(())
+            })
+        })
+    }
+
+    /// Creates a future that waits for a file dialog to close.
+    fn file_dialog_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        dialog: DialogResultFuture<Vec<DialogFile>>,
+        purpose: FileDialogPurpose,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::FileDialog { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileDialogLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let files = dialog.await;
+
+            player.lock().unwrap().update(|uc| {
+                let target = match uc.load_manager.get_loader(handle) {
+                    Some(&Loader::FileDialog { target_object, .. }) => target_object,
+                    None => return Err(Error::Cancelled),
+                    _ => return Err(Error::NotFileDialogLoader),
+                };
+                uc.load_manager.remove_loader(handle);
+
+                let mut files = match files {
+                    Some(files) if !files.is_empty() => files,
+                    _ => return target.fire(uc, FileReferenceEvent::Cancel),
+                };
+
+                match purpose {
+                    FileDialogPurpose::Browse => {
+                        target.set_selected_file(uc, files.swap_remove(0))?;
+                        target.fire(uc, FileReferenceEvent::Select)
+                    }
+                    FileDialogPurpose::BrowseMultiple => {
+                        target.set_file_list(uc, files)?;
+                        target.fire(uc, FileReferenceEvent::Select)
+                    }
+                    FileDialogPurpose::Download(request) => {
+                        let path = files[0].path.clone();
+                        target.set_selected_file(uc, files.swap_remove(0))?;
+                        target.fire(uc, FileReferenceEvent::Select)?;
+
+                        let future = uc.load_manager.transfer_file_reference(
+                            uc.player.clone(),
+                            target,
+                            request,
+                            FileTransfer::Download(path),
+                        );
+                        uc.navigator.spawn_future(future);
+                        Ok(())
+                    }
+                    FileDialogPurpose::Save(data) => {
+                        let path = files.swap_remove(0).path;
+                        let result = uc.ui.write_file(&path, &data);
+
+                        // Describe the file as written, so that its size and dates are up to date.
+                        target.set_selected_file(uc, DialogFile::from_path(path))?;
+                        target.fire(uc, FileReferenceEvent::Select)?;
+
+                        match result {
+                            Ok(()) => {
+                                target.fire(uc, FileReferenceEvent::Open)?;
+                                target.fire(
+                                    uc,
+                                    FileReferenceEvent::Progress {
+                                        bytes_loaded: data.len(),
+                                        bytes_total: data.len(),
+                                    },
+                                )?;
+                                target.fire(uc, FileReferenceEvent::Complete)
+                            }
+                            Err(e) => {
+                                log::warn!("FileReference.save: unable to write file: {}", e);
+                                target.fire(
+                                    uc,
+                                    FileReferenceEvent::IoError {
+                                        message: "Error #2038: File I/O Error.",
+                                        code: 2038,
+                                    },
+                                )
+                            }
+                        }
+                    }
+                }
+            })
+        })
+    }
+
     /// Report a movie loader start event to script code.
     fn movie_loader_start(handle: Index, uc: &mut UpdateContext<'_, 'gc, '_>) -> Result<(), Error> {
         let me = uc.load_manager.get_loader_mut(handle);
@@ -1346,13 +2239,13 @@ impl<'gc> Loader<'gc> {
             }
         }
         player.lock().unwrap().update(|uc| {
-            let (clip, event_handler, context) = match uc.load_manager.get_loader(handle) {
+            let (clip, event_handler, avm2_domain) = match uc.load_manager.get_loader(handle) {
                 Some(Loader::Movie {
                     target_clip,
                     event_handler,
-                    context,
+                    avm2_domain,
                     ..
-                }) => (*target_clip, *event_handler, *context),
+                }) => (*target_clip, *event_handler, *avm2_domain),
                 None => return Err(Error::Cancelled),
                 _ => unreachable!(),
             };
@@ -1397,20 +2290,10 @@ impl<'gc> Loader<'gc> {
                     }
 
                     let mut activation = Avm2Activation::from_nothing(uc.reborrow());
-                    let domain = context
-                        .and_then(|o| {
-                            o.get_property(
-                                &Avm2Multiname::public("applicationDomain"),
-                                &mut activation,
-                            )
-                            .ok()
-                        })
-                        .and_then(|v| v.coerce_to_object(&mut activation).ok())
-                        .and_then(|o| o.as_application_domain())
-                        .unwrap_or_else(|| {
-                            let parent_domain = activation.avm2().global_domain();
-                            Avm2Domain::movie_domain(&mut activation, parent_domain)
-                        });
+                    let domain = avm2_domain.unwrap_or_else(|| {
+                        let parent_domain = activation.avm2().global_domain();
+                        Avm2Domain::movie_domain(&mut activation, parent_domain)
+                    });
                     activation
                         .context
                         .library
@@ -1452,6 +2335,11 @@ impl<'gc> Loader<'gc> {
 
                     let bitmap = ruffle_render::utils::decode_define_bits_jpeg(data, None)?;
                     let bitmap_obj = Bitmap::new(uc, 0, bitmap)?;
+                    if url.is_some() {
+                        let mut image_movie = SwfMovie::empty(uc.swf.version());
+                        image_movie.set_url(url);
+                        bitmap_obj.set_movie(uc.gc_context, Arc::new(image_movie));
+                    }
 
                     if let Some(mc) = clip.as_movie_clip() {
                         mc.replace_at_depth(uc, bitmap_obj.into(), 1);
@@ -1657,6 +2545,60 @@ impl<'gc> Loader<'gc> {
         Ok(())
     }
 
+    /// Fail a movie load because the security sandbox forbids it.
+    ///
+    /// AVM2 loaders get a `securityError` event with the given message, while AVM1 loaders
+    /// see the same error as any other failed load.
+    fn movie_loader_security_error(
+        handle: Index,
+        uc: &mut UpdateContext<'_, 'gc, '_>,
+        message: &str,
+        error_id: i32,
+    ) -> Result<(), Error> {
+        let event_handler = match uc.load_manager.get_loader(handle) {
+            Some(Loader::Movie { event_handler, .. }) => *event_handler,
+            None => return Err(Error::Cancelled),
+            _ => unreachable!(),
+        };
+
+        let loader_info = match event_handler {
+            Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)) => loader_info,
+            _ => return Loader::movie_loader_error(handle, uc),
+        };
+
+        dispatch_security_error(uc, loader_info, message, error_id)?;
+
+        if let Loader::Movie { loader_status, .. } = uc.load_manager.get_loader_mut(handle).unwrap()
+        {
+            *loader_status = LoaderStatus::Failed;
+        };
+
+        Ok(())
+    }
+
+    /// Report that the policy files checked for `LoaderContext.checkPolicyFile`
+    /// did not grant the loading movie access to the content.
+    ///
+    /// Unlike other security errors this doesn't fail the load; the content is
+    /// still shown, but stays inaccessible to the loading movie.
+    fn movie_loader_policy_error(
+        handle: Index,
+        uc: &mut UpdateContext<'_, 'gc, '_>,
+        message: &str,
+    ) -> Result<(), Error> {
+        match uc.load_manager.get_loader(handle) {
+            Some(Loader::Movie {
+                event_handler: Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)),
+                ..
+            }) => {
+                let loader_info = *loader_info;
+                dispatch_security_error(uc, loader_info, message, 2048)
+            }
+            Some(_) => Ok(()),
+            None => Err(Error::Cancelled),
+        }
+    }
+
     /// Event handler morally equivalent to `onLoad` on a movie clip.
     ///
     /// Returns `true` if the loader has completed and should be removed.
@@ -1695,3 +2637,33 @@ impl<'gc> Loader<'gc> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multipart_upload_body_sends_variables_before_file() {
+        let variables = [("user".to_string(), "ruffle".to_string())];
+        let (body, mime_type) = multipart_upload_body("a.txt", "Filedata", b"hello", &variables);
+        assert_eq!(
+            mime_type,
+            format!("multipart/form-data; boundary={UPLOAD_BOUNDARY}")
+        );
+
+        let body = String::from_utf8(body).unwrap();
+        let parts: Vec<_> = body.split(&format!("--{UPLOAD_BOUNDARY}")).collect();
+        assert_eq!(
+            parts,
+            [
+                "",
+                "\r\nContent-Disposition: form-data; name=\"user\"\r\n\r\nruffle\r\n",
+                "\r\nContent-Disposition: form-data; name=\"Filename\"\r\n\r\na.txt\r\n",
+                "\r\nContent-Disposition: form-data; name=\"Filedata\"; filename=\"a.txt\"\r\n\
+                 Content-Type: application/octet-stream\r\n\r\nhello\r\n",
+                "\r\nContent-Disposition: form-data; name=\"Upload\"\r\n\r\nSubmit Query\r\n",
+                "--\r\n",
+            ]
+        );
+    }
+}
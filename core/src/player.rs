@@ -21,6 +21,7 @@ use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{
     BuiltInItemFlags, ContextMenuCallback, ContextMenuItem, ContextMenuState,
 };
+use crate::dirty_region::DirtyRegion;
 use crate::display_object::{
     EditText, InteractiveObject, MovieClip, Stage, StageAlign, StageDisplayState, StageQuality,
     StageScaleMode, TInteractiveObject, WindowMode,
@@ -36,6 +37,7 @@ use crate::limits::ExecutionLimit;
 use crate::loader::{LoadBehavior, LoadManager};
 use crate::locale::get_current_date_time;
 use crate::prelude::*;
+use crate::sandbox::SecuritySandbox;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
 use crate::timer::Timers;
@@ -228,6 +230,10 @@ pub struct Player {
 
     transform_stack: TransformStack,
 
+    /// Finds the parts of the viewport that have to be redrawn on each frame.
+    /// `None` if the whole viewport is always redrawn.
+    dirty_region: Option<DirtyRegion>,
+
     rng: SmallRng,
 
     gc_arena: Rc<RefCell<GcArena>>,
@@ -287,6 +293,9 @@ pub struct Player {
     /// The root SWF URL provided to ActionScript. If None,
     /// the actual loaded url will be used
     spoofed_url: Option<String>,
+
+    /// The cross-domain security sandbox.
+    security_sandbox: SecuritySandbox,
 }
 
 impl Player {
@@ -564,7 +573,7 @@ impl Player {
             // TODO: This should use a pointed display object with `.menu`
             let root_dobj = context.stage.root_clip();
 
-            let menu = if let Value::Object(obj) = root_dobj.object() {
+            let mut menu = if let Value::Object(obj) = root_dobj.object() {
                 let mut activation = Activation::from_stub(
                     context.reborrow(),
                     ActivationIdentifier::root("[ContextMenu]"),
@@ -603,6 +612,16 @@ impl Player {
                 menu
             };
 
+            // Text fields add their editing commands to the menu.
+            let text = context
+                .mouse_over_object
+                .map(|o| o.as_displayobject())
+                .or_else(|| context.focus_tracker.get())
+                .and_then(|o| o.as_edit_text());
+            if let Some(text) = text {
+                menu.build_text_items(text);
+            }
+
             let ret = menu.info().clone();
             *context.current_context_menu = Some(menu);
             ret
@@ -631,6 +650,9 @@ impl Player {
                     ContextMenuCallback::Avm2 { .. } => {
                         // TODO: Send menuItemSelect event
                     }
+                    ContextMenuCallback::TextControl { code, text } => {
+                        text.text_control_input(*code, context)
+                    }
                     _ => {}
                 }
                 Self::run_actions(context);
@@ -747,6 +769,9 @@ impl Player {
     }
 
     pub fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        if let Some(dirty_region) = &mut self.dirty_region {
+            dirty_region.invalidate_all();
+        }
         self.mutate_with_update_context(|context| {
             context.renderer.set_viewport_dimensions(dimensions);
             context.stage.build_matrices(context);
@@ -896,6 +921,13 @@ impl Player {
         }
 
         self.mutate_with_update_context(|context| {
+            // Text fields handle editing commands, such as pasting.
+            if let PlayerEvent::TextControl { code } = event {
+                if let Some(text) = context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
+                    text.text_control_input(code, context);
+                }
+            }
+
             // Propagate button events.
             let button_event = match event {
                 // ASCII characters convert directly to keyPress button events.
@@ -1332,6 +1364,17 @@ impl Player {
                 }
             }
 
+            // A text field the user is dragging over extends its selection,
+            // even once the mouse has left it.
+            if is_mouse_moved && context.input.is_mouse_down() {
+                if let Some(text) = context
+                    .mouse_down_object
+                    .and_then(|o| o.as_displayobject().as_edit_text())
+                {
+                    text.drag_selection(context, false);
+                }
+            }
+
             // Fire any pending mouse events.
             let needs_render = if events.is_empty() {
                 false
@@ -1464,13 +1507,19 @@ impl Player {
     }
 
     pub fn render(&mut self) {
-        let (renderer, ui, transform_stack) =
-            (&mut self.renderer, &mut self.ui, &mut self.transform_stack);
+        let (renderer, ui, transform_stack, dirty_region) = (
+            &mut self.renderer,
+            &mut self.ui,
+            &mut self.transform_stack,
+            &mut self.dirty_region,
+        );
         let mut background_color = Color::WHITE;
+        let mut view_matrix = Matrix::IDENTITY;
 
         let commands = self.gc_arena.borrow().mutate(|gc_context, gc_root| {
             let root_data = gc_root.data.read();
             let stage = root_data.stage;
+            view_matrix = *stage.base().matrix();
 
             let mut render_context = RenderContext {
                 renderer: renderer.deref_mut(),
@@ -1480,6 +1529,9 @@ impl Player {
                 library: &root_data.library,
                 transform_stack,
                 is_offscreen: false,
+                cull_bounds: stage.view_bounds().transform(&view_matrix),
+                dirty_region: dirty_region.as_mut(),
+                known_viewport_bounds: Default::default(),
                 stage,
                 clip_depth_stack: vec![],
                 allow_mask: true,
@@ -1497,7 +1549,23 @@ impl Player {
             render_context.commands
         });
 
-        renderer.submit_frame(background_color, commands);
+        let dirty_rects = dirty_region.as_mut().and_then(|dirty_region| {
+            let viewport = renderer.viewport_dimensions();
+            let viewport = BoundingBox {
+                x_min: Twips::ZERO,
+                y_min: Twips::ZERO,
+                x_max: Twips::from_pixels(viewport.width.into()),
+                y_max: Twips::from_pixels(viewport.height.into()),
+                valid: true,
+            };
+            dirty_region.finish_frame(background_color, viewport, view_matrix)
+        });
+        match dirty_rects {
+            Some(dirty_rects) => {
+                renderer.submit_frame_partial(background_color, commands, &dirty_rects)
+            }
+            None => renderer.submit_frame(background_color, commands),
+        }
 
         self.needs_render = false;
     }
@@ -1520,6 +1588,14 @@ impl Player {
         &self.navigator
     }
 
+    pub fn security_sandbox(&self) -> &SecuritySandbox {
+        &self.security_sandbox
+    }
+
+    pub fn security_sandbox_mut(&mut self) -> &mut SecuritySandbox {
+        &mut self.security_sandbox
+    }
+
     // The frame rate of the current movie in FPS.
     pub fn frame_rate(&self) -> f64 {
         self.frame_rate
@@ -1708,6 +1784,7 @@ impl Player {
                 player: self.self_reference.clone(),
                 load_manager,
                 system: &mut self.system,
+                security_sandbox: &mut self.security_sandbox,
                 instance_counter: &mut self.instance_counter,
                 storage: self.storage.deref_mut(),
                 log: self.log.deref_mut(),
@@ -1913,6 +1990,8 @@ pub struct PlayerBuilder {
     warn_on_unsupported_content: bool,
     load_behavior: LoadBehavior,
     spoofed_url: Option<String>,
+    enforce_policy_files: bool,
+    dirty_region_tracking: bool,
 }
 
 impl PlayerBuilder {
@@ -1948,6 +2027,8 @@ impl PlayerBuilder {
             warn_on_unsupported_content: true,
             load_behavior: LoadBehavior::Streaming,
             spoofed_url: None,
+            enforce_policy_files: false,
+            dirty_region_tracking: false,
         }
     }
 
@@ -2067,6 +2148,25 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets whether cross-domain requests are restricted by policy files.
+    ///
+    /// When enabled, movies may only read data, draw content and script
+    /// movies from other domains if the target server's `crossdomain.xml`
+    /// (or the target movie, via `Security.allowDomain`) permits it.
+    pub fn with_policy_file_enforcement(mut self, enforce_policy_files: bool) -> Self {
+        self.enforce_policy_files = enforce_policy_files;
+        self
+    }
+
+    /// Sets whether only the parts of the viewport that changed are redrawn on each frame.
+    ///
+    /// This only helps renderers that keep the last frame around, such as the software
+    /// renderer; others redraw everything regardless.
+    pub fn with_dirty_region_tracking(mut self, enabled: bool) -> Self {
+        self.dirty_region_tracking = enabled;
+        self
+    }
+
     /// Builds the player, wiring up the backends and configuring the specified settings.
     pub fn build(self) -> Arc<Mutex<Player>> {
         use crate::backend::*;
@@ -2136,6 +2236,7 @@ impl PlayerBuilder {
                 rng: SmallRng::seed_from_u64(get_current_date_time().timestamp_millis() as u64),
                 system: SystemProperties::default(),
                 transform_stack: TransformStack::new(),
+                dirty_region: self.dirty_region_tracking.then(DirtyRegion::new),
                 instance_counter: 0,
                 player_version: NEWEST_PLAYER_VERSION,
                 is_playing: self.autoplay,
@@ -2144,6 +2245,7 @@ impl PlayerBuilder {
                 self_reference: self_ref.clone(),
                 load_behavior: self.load_behavior,
                 spoofed_url: self.spoofed_url.clone(),
+                security_sandbox: SecuritySandbox::new(self.enforce_policy_files),
 
                 // GC data
                 gc_arena: Rc::new(RefCell::new(GcArena::new(
@@ -0,0 +1,642 @@
+//! Cross-domain security sandbox and policy file handling.
+//!
+//! The sandbox is opt-in: when it is disabled (the default), every request is
+//! treated as if the target domain had served a permissive policy file.
+
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use quick_xml::{events::Event, Reader};
+use std::collections::{HashMap, HashSet};
+use url::Url;
+
+/// The path of the master policy file on any given server.
+const MASTER_POLICY_FILE_PATH: &str = "/crossdomain.xml";
+
+/// The MIME type that marks a response as a policy file under the
+/// `by-content-type` meta-policy.
+const POLICY_FILE_CONTENT_TYPE: &str = "text/x-cross-domain-policy";
+
+/// The meta-policy of a server, as declared by the `site-control` tag of its
+/// master policy file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetaPolicy {
+    /// No policy files are permitted on this server, including the master.
+    None,
+
+    /// Only the master policy file is permitted.
+    MasterOnly,
+
+    /// Only policy files served with `Content-Type: text/x-cross-domain-policy`
+    /// are permitted.
+    ByContentType,
+
+    /// Only FTP policy files named `crossdomain.xml` are permitted.
+    ByFtpFilename,
+
+    /// All policy files on this server are permitted.
+    All,
+}
+
+impl MetaPolicy {
+    fn from_attribute(value: &[u8]) -> Option<Self> {
+        match value {
+            b"none" | b"none-this-response" => Some(Self::None),
+            b"master-only" => Some(Self::MasterOnly),
+            b"by-content-type" => Some(Self::ByContentType),
+            b"by-ftp-filename" => Some(Self::ByFtpFilename),
+            b"all" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
+
+/// A domain pattern, as used in policy files and by `Security.allowDomain`.
+///
+/// Patterns are either `*`, an exact host name or IP address, or a wildcard
+/// subdomain pattern such as `*.example.com`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DomainPattern(String);
+
+impl DomainPattern {
+    pub fn new(pattern: &str) -> Self {
+        Self(pattern.trim().to_ascii_lowercase())
+    }
+
+    /// Check if this pattern matches the given host name.
+    pub fn matches(&self, host: &str) -> bool {
+        let host = host.to_ascii_lowercase();
+
+        if self.0 == "*" {
+            true
+        } else if let Some(suffix) = self.0.strip_prefix("*.") {
+            host == suffix
+                || host
+                    .strip_suffix(suffix)
+                    .map_or(false, |sub| sub.ends_with('.'))
+        } else {
+            host == self.0
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct AllowAccessFrom {
+    domain: DomainPattern,
+    secure: bool,
+}
+
+#[derive(Clone, Debug)]
+struct AllowHttpRequestHeadersFrom {
+    domain: DomainPattern,
+    headers: Vec<String>,
+    secure: bool,
+}
+
+/// A parsed `crossdomain.xml` policy file.
+#[derive(Clone, Debug, Default)]
+pub struct PolicyFile {
+    meta_policy: Option<MetaPolicy>,
+    allow_access_from: Vec<AllowAccessFrom>,
+    allow_http_request_headers_from: Vec<AllowHttpRequestHeadersFrom>,
+    content_type: Option<String>,
+}
+
+impl PolicyFile {
+    /// Parse a policy file.
+    ///
+    /// Returns `None` if the data is not a well-formed policy file, in which
+    /// case Flash Player ignores it entirely.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let mut reader = Reader::from_reader(data);
+        let mut buf = Vec::new();
+        let mut policy = Self::default();
+        let mut depth = 0;
+        let mut found_root = false;
+
+        loop {
+            let (bs, is_empty) = match reader.read_event(&mut buf).ok()? {
+                Event::Start(bs) => (bs, false),
+                Event::Empty(bs) => (bs, true),
+                Event::End(_) => {
+                    depth -= 1;
+                    buf.clear();
+                    continue;
+                }
+                Event::Eof => break,
+                _ => {
+                    buf.clear();
+                    continue;
+                }
+            };
+
+            if depth == 0 {
+                if bs.name() != b"cross-domain-policy" {
+                    return None;
+                }
+                found_root = true;
+            } else if depth == 1 {
+                let mut domain = None;
+                let mut headers = None;
+                let mut secure = true;
+                let mut permitted_policies = None;
+
+                for attribute in bs.attributes() {
+                    let attribute = attribute.ok()?;
+                    let value = attribute.unescaped_value().ok()?;
+                    match attribute.key {
+                        b"domain" => domain = Some(String::from_utf8_lossy(&value).into_owned()),
+                        b"headers" => headers = Some(String::from_utf8_lossy(&value).into_owned()),
+                        b"secure" => secure = &*value != b"false",
+                        b"permitted-cross-domain-policies" => {
+                            permitted_policies = MetaPolicy::from_attribute(&value)
+                        }
+                        _ => {}
+                    }
+                }
+
+                match bs.name() {
+                    b"allow-access-from" => {
+                        if let Some(domain) = domain {
+                            policy.allow_access_from.push(AllowAccessFrom {
+                                domain: DomainPattern::new(&domain),
+                                secure,
+                            });
+                        }
+                    }
+                    b"allow-http-request-headers-from" => {
+                        if let (Some(domain), Some(headers)) = (domain, headers) {
+                            policy.allow_http_request_headers_from.push(
+                                AllowHttpRequestHeadersFrom {
+                                    domain: DomainPattern::new(&domain),
+                                    headers: headers
+                                        .split(',')
+                                        .map(|h| h.trim().to_ascii_lowercase())
+                                        .filter(|h| !h.is_empty())
+                                        .collect(),
+                                    secure,
+                                },
+                            );
+                        }
+                    }
+                    b"site-control" => policy.meta_policy = permitted_policies,
+                    _ => {}
+                }
+            }
+
+            if !is_empty {
+                depth += 1;
+            }
+            buf.clear();
+        }
+
+        found_root.then_some(policy)
+    }
+
+    /// Record the `Content-Type` this file was served with.
+    pub fn with_content_type(mut self, content_type: Option<String>) -> Self {
+        self.content_type = content_type;
+        self
+    }
+
+    /// Check if this file was served with the dedicated policy file MIME
+    /// type, ignoring any parameters such as the charset.
+    fn has_policy_content_type(&self) -> bool {
+        self.content_type.as_deref().map_or(false, |content_type| {
+            content_type
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .eq_ignore_ascii_case(POLICY_FILE_CONTENT_TYPE)
+        })
+    }
+
+    /// The meta-policy declared by this file, if any.
+    pub fn meta_policy(&self) -> Option<MetaPolicy> {
+        self.meta_policy
+    }
+
+    /// Check if this policy grants data access to a movie served from
+    /// `requester`, when the policy itself was served from `policy_url`.
+    pub fn allows_access(&self, requester: &Url, policy_url: &Url) -> bool {
+        let host = requester.host_str().unwrap_or_default();
+        self.allow_access_from.iter().any(|rule| {
+            rule.domain.matches(host) && is_secure_enough(rule.secure, requester, policy_url)
+        })
+    }
+
+    /// Check if this policy allows a movie served from `requester` to send
+    /// all of the given custom HTTP headers.
+    pub fn allows_headers<'a>(
+        &self,
+        requester: &Url,
+        policy_url: &Url,
+        headers: impl IntoIterator<Item = &'a str>,
+    ) -> bool {
+        let host = requester.host_str().unwrap_or_default();
+        let rules: Vec<_> = self
+            .allow_http_request_headers_from
+            .iter()
+            .filter(|rule| {
+                rule.domain.matches(host) && is_secure_enough(rule.secure, requester, policy_url)
+            })
+            .collect();
+
+        headers.into_iter().all(|header| {
+            let header = header.to_ascii_lowercase();
+            rules.iter().any(|rule| {
+                rule.headers
+                    .iter()
+                    .any(|allowed| match allowed.strip_suffix('*') {
+                        Some(prefix) => header.starts_with(prefix),
+                        None => header == *allowed,
+                    })
+            })
+        })
+    }
+}
+
+/// Policies served over HTTPS only apply to HTTPS requesters, unless they
+/// opt out with `secure="false"`.
+fn is_secure_enough(secure: bool, requester: &Url, policy_url: &Url) -> bool {
+    !secure || policy_url.scheme() != "https" || requester.scheme() == "https"
+}
+
+/// Check if a URL refers to content on a network server, which is the only
+/// kind of content policy files apply to.
+fn is_network_url(url: &Url) -> bool {
+    matches!(url.scheme(), "http" | "https")
+}
+
+/// A domain that a movie has granted script access to.
+#[derive(Clone, Debug)]
+struct AllowedDomain {
+    domain: DomainPattern,
+    insecure: bool,
+}
+
+/// The state of the cross-domain security sandbox.
+#[derive(Default)]
+pub struct SecuritySandbox {
+    /// Whether cross-domain restrictions are enforced at all.
+    enabled: bool,
+
+    /// All policy files that have been fetched so far.
+    ///
+    /// Files that could not be fetched or parsed are cached as `None`.
+    policy_files: HashMap<Url, Option<PolicyFile>>,
+
+    /// Additional policy file locations registered with
+    /// `Security.loadPolicyFile`.
+    extra_policy_files: Vec<Url>,
+
+    /// Domains that movies have granted script access to with
+    /// `Security.allowDomain`, keyed by the granting movie's URL.
+    allowed_domains: HashMap<String, Vec<AllowedDomain>>,
+
+    /// URLs of loaded content whose policy file granted access to the root
+    /// movie when it was loaded.
+    trusted_content: HashSet<String>,
+}
+
+impl SecuritySandbox {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Check if a movie served from `requester` accessing `target` crosses
+    /// a sandbox boundary.
+    ///
+    /// This is always `false` if the sandbox is disabled. Movies without a URL
+    /// and targets that aren't network resources are never restricted.
+    pub fn is_cross_domain(&self, requester: Option<&str>, target: &str) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        let (requester, target) = match (requester.map(Url::parse), Url::parse(target)) {
+            (Some(Ok(requester)), Ok(target)) => (requester, target),
+            _ => return false,
+        };
+
+        is_network_url(&target) && requester.origin() != target.origin()
+    }
+
+    /// Register an additional policy file location.
+    pub fn load_policy_file(&mut self, url: Url) {
+        if !self.extra_policy_files.contains(&url) {
+            self.extra_policy_files.push(url);
+        }
+    }
+
+    /// Store the result of fetching a policy file.
+    pub fn set_policy_file(&mut self, url: Url, policy: Option<PolicyFile>) {
+        self.policy_files.insert(url, policy);
+    }
+
+    fn master_policy_file_url(target: &Url) -> Option<Url> {
+        target.join(MASTER_POLICY_FILE_PATH).ok()
+    }
+
+    /// List the policy files that apply to `target` and are permitted by the
+    /// server's meta-policy.
+    ///
+    /// Until the master policy file has been fetched, it's the only file
+    /// returned, as its meta-policy decides which other files are honored.
+    fn applicable_policy_files(&self, target: &Url) -> Vec<Url> {
+        let master = match Self::master_policy_file_url(target) {
+            Some(master) => master,
+            None => return vec![],
+        };
+
+        let meta_policy = match self.policy_files.get(&master) {
+            Some(Some(policy)) => policy.meta_policy().unwrap_or(MetaPolicy::MasterOnly),
+            Some(None) => MetaPolicy::MasterOnly,
+            None => return vec![master],
+        };
+
+        match meta_policy {
+            MetaPolicy::None => vec![],
+            MetaPolicy::MasterOnly => vec![master],
+            _ => {
+                let mut urls = vec![master];
+                urls.extend(
+                    self.extra_policy_files
+                        .iter()
+                        .filter(|url| {
+                            url.origin() == target.origin()
+                                && target.path().starts_with(policy_file_directory(url))
+                        })
+                        .filter(|url| {
+                            // Files that haven't been fetched yet must be, to learn their type.
+                            meta_policy != MetaPolicy::ByContentType
+                                || match self.policy_files.get(url) {
+                                    Some(Some(policy)) => policy.has_policy_content_type(),
+                                    Some(None) => false,
+                                    None => true,
+                                }
+                        })
+                        .cloned(),
+                );
+                urls
+            }
+        }
+    }
+
+    /// List the policy files that need to be fetched before access to
+    /// `target` can be decided.
+    pub fn pending_policy_files(&self, target: &Url) -> Vec<Url> {
+        self.applicable_policy_files(target)
+            .into_iter()
+            .filter(|url| !self.policy_files.contains_key(url))
+            .collect()
+    }
+
+    /// Check if the cached policy files for `target` grant access to a movie
+    /// served from `requester`.
+    pub fn policy_allows(&self, requester: &Url, target: &Url) -> bool {
+        self.applicable_policy_files(target).iter().any(|url| {
+            matches!(self.policy_files.get(url), Some(Some(policy)) if policy.allows_access(requester, url))
+        })
+    }
+
+    /// Check if the cached policy files for `target` allow a movie served
+    /// from `requester` to send all of the given custom HTTP headers.
+    pub fn policy_allows_headers(&self, requester: &Url, target: &Url, headers: &[String]) -> bool {
+        headers.is_empty()
+            || self.applicable_policy_files(target).iter().any(|url| {
+                matches!(
+                    self.policy_files.get(url),
+                    Some(Some(policy)) if policy.allows_headers(requester, url, headers.iter().map(String::as_str))
+                )
+            })
+    }
+
+    /// Grant script access to a movie from the movie served at `movie_url`.
+    pub fn allow_domain(&mut self, movie_url: &str, domain: &str, insecure: bool) {
+        self.allowed_domains
+            .entry(movie_url.to_string())
+            .or_default()
+            .push(AllowedDomain {
+                domain: DomainPattern::new(domain),
+                insecure,
+            });
+    }
+
+    /// Record that the policy file for the content at `url` was checked and
+    /// granted access.
+    pub fn trust_content(&mut self, url: &str) {
+        self.trusted_content.insert(url.to_string());
+    }
+
+    /// Check if a movie served from `requester` may access the content (a
+    /// loaded movie or image) served from `content`.
+    pub fn can_access_content(&self, requester: Option<&str>, content: Option<&str>) -> bool {
+        let content = match content {
+            Some(content) if self.is_cross_domain(requester, content) => content,
+            _ => return true,
+        };
+
+        if self.trusted_content.contains(content) {
+            return true;
+        }
+
+        let (requester, content_url) = match (requester.map(Url::parse), Url::parse(content)) {
+            (Some(Ok(requester)), Ok(content_url)) => (requester, content_url),
+            _ => return true,
+        };
+        let host = requester.host_str().unwrap_or_default();
+
+        self.allowed_domains.get(content).map_or(false, |domains| {
+            domains.iter().any(|allowed| {
+                allowed.domain.matches(host)
+                    && (allowed.insecure
+                        || content_url.scheme() != "https"
+                        || requester.scheme() == "https")
+            })
+        })
+    }
+
+    /// Find content within `object` (including `object` itself) that a movie
+    /// served from `requester` may not access, and return its URL.
+    ///
+    /// Content hosted by nested `Loader`s is only examined if
+    /// `include_loaded_content` is set.
+    pub fn find_inaccessible_content(
+        &self,
+        requester: Option<&str>,
+        object: DisplayObject<'_>,
+        include_loaded_content: bool,
+    ) -> Option<String> {
+        if !self.enabled {
+            return None;
+        }
+
+        let movie = object.movie();
+        if let Some(url) = movie.url() {
+            if !self.can_access_content(requester, Some(url)) {
+                return Some(url.to_string());
+            }
+        }
+
+        if !include_loaded_content && object.as_loader_display().is_some() {
+            return None;
+        }
+
+        if let Some(container) = object.as_container() {
+            for child in container.iter_render_list() {
+                if let Some(url) =
+                    self.find_inaccessible_content(requester, child, include_loaded_content)
+                {
+                    return Some(url);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// The directory a policy file applies to, including the trailing slash.
+fn policy_file_directory(url: &Url) -> &str {
+    let path = url.path();
+    &path[..path.rfind('/').map_or(0, |i| i + 1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &[u8] = br#"<?xml version="1.0"?>
+<!DOCTYPE cross-domain-policy SYSTEM "http://www.adobe.com/xml/dtds/cross-domain-policy.dtd">
+<cross-domain-policy>
+    <site-control permitted-cross-domain-policies="all"/>
+    <allow-access-from domain="*.example.com"/>
+    <allow-access-from domain="insecure.test" secure="false"/>
+    <allow-http-request-headers-from domain="*.example.com" headers="SOAPAction, X-Custom-*"/>
+</cross-domain-policy>"#;
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn domain_pattern() {
+        let pattern = DomainPattern::new("*.Example.com");
+        assert!(pattern.matches("example.com"));
+        assert!(pattern.matches("www.example.com"));
+        assert!(!pattern.matches("badexample.com"));
+        assert!(DomainPattern::new("*").matches("anything.test"));
+        assert!(!DomainPattern::new("example.com").matches("www.example.com"));
+    }
+
+    #[test]
+    fn parse_policy_file() {
+        let policy = PolicyFile::parse(POLICY).unwrap();
+        let policy_url = url("https://cdn.test/crossdomain.xml");
+
+        assert_eq!(policy.meta_policy(), Some(MetaPolicy::All));
+        assert!(policy.allows_access(&url("https://www.example.com/a.swf"), &policy_url));
+        assert!(!policy.allows_access(&url("http://www.example.com/a.swf"), &policy_url));
+        assert!(policy.allows_access(&url("http://insecure.test/a.swf"), &policy_url));
+        assert!(!policy.allows_access(&url("https://other.test/a.swf"), &policy_url));
+
+        let requester = url("https://example.com/a.swf");
+        assert!(policy.allows_headers(&requester, &policy_url, ["soapaction", "X-Custom-Foo"]));
+        assert!(!policy.allows_headers(&requester, &policy_url, ["X-Other"]));
+    }
+
+    #[test]
+    fn reject_invalid_policy_file() {
+        assert!(PolicyFile::parse(b"<html><body>Not found</body></html>").is_none());
+        assert!(PolicyFile::parse(b"not xml at all <").is_none());
+    }
+
+    #[test]
+    fn meta_policy_restricts_policy_files() {
+        let mut sandbox = SecuritySandbox::new(true);
+        let target = url("http://cdn.test/data/file.txt");
+        let master = url("http://cdn.test/crossdomain.xml");
+        let extra = url("http://cdn.test/data/policy.xml");
+
+        sandbox.load_policy_file(extra.clone());
+        assert_eq!(sandbox.pending_policy_files(&target), vec![master.clone()]);
+
+        let master_only = PolicyFile::parse(
+            br#"<cross-domain-policy><site-control permitted-cross-domain-policies="master-only"/></cross-domain-policy>"#,
+        );
+        sandbox.set_policy_file(master.clone(), master_only);
+        assert!(sandbox.pending_policy_files(&target).is_empty());
+
+        sandbox.set_policy_file(master, PolicyFile::parse(POLICY));
+        assert_eq!(sandbox.pending_policy_files(&target), vec![extra]);
+        assert!(sandbox.policy_allows(&url("http://insecure.test/a.swf"), &target));
+    }
+
+    #[test]
+    fn by_content_type_meta_policy() {
+        let mut sandbox = SecuritySandbox::new(true);
+        let target = url("http://cdn.test/data/file.txt");
+        let master = url("http://cdn.test/crossdomain.xml");
+        let plain = url("http://cdn.test/data/plain.xml");
+        let typed = url("http://cdn.test/data/typed.xml");
+        let requester = url("http://www.example.com/a.swf");
+        let extra_policy = br#"<cross-domain-policy><allow-access-from domain="www.example.com"/></cross-domain-policy>"#;
+
+        sandbox.load_policy_file(plain.clone());
+        sandbox.load_policy_file(typed.clone());
+        sandbox.set_policy_file(
+            master.clone(),
+            PolicyFile::parse(
+                br#"<cross-domain-policy><site-control permitted-cross-domain-policies="by-content-type"/></cross-domain-policy>"#,
+            ),
+        );
+        assert_eq!(
+            sandbox.pending_policy_files(&target),
+            vec![plain.clone(), typed.clone()]
+        );
+
+        sandbox.set_policy_file(
+            plain,
+            PolicyFile::parse(extra_policy).map(|p| p.with_content_type(Some("text/xml".into()))),
+        );
+        sandbox.set_policy_file(typed.clone(), None);
+        assert!(sandbox.pending_policy_files(&target).is_empty());
+        assert!(!sandbox.policy_allows(&requester, &target));
+
+        sandbox.set_policy_file(
+            typed,
+            PolicyFile::parse(extra_policy).map(|p| {
+                p.with_content_type(Some("text/x-cross-domain-policy; charset=utf-8".into()))
+            }),
+        );
+        assert!(sandbox.policy_allows(&requester, &target));
+        assert_eq!(sandbox.applicable_policy_files(&target).len(), 2);
+    }
+
+    #[test]
+    fn policy_allows_headers() {
+        let mut sandbox = SecuritySandbox::new(true);
+        let target = url("http://cdn.test/api");
+        let requester = url("http://www.example.com/a.swf");
+        sandbox.set_policy_file(
+            url("http://cdn.test/crossdomain.xml"),
+            PolicyFile::parse(POLICY),
+        );
+
+        assert!(sandbox.policy_allows_headers(&requester, &target, &[]));
+        assert!(sandbox.policy_allows_headers(&requester, &target, &["SOAPAction".into()]));
+        assert!(!sandbox.policy_allows_headers(&requester, &target, &["X-Other".into()]));
+        assert!(!sandbox.policy_allows_headers(
+            &url("http://other.test/a.swf"),
+            &target,
+            &["SOAPAction".into()]
+        ));
+    }
+}
@@ -6,14 +6,18 @@
 
 use crate::avm1::ExecutionReason;
 use crate::avm1::{
-    Activation, ActivationIdentifier, Object as Avm1Object, TObject as _, Value as Avm1Value,
+    Activation, ActivationIdentifier, Executable as Avm1Executable, Object as Avm1Object,
+    TObject as _, Value as Avm1Value,
 };
 use crate::avm2::object::TObject;
 use crate::avm2::{Activation as Avm2Activation, Object as Avm2Object, Value as Avm2Value};
 use crate::context::UpdateContext;
+use crate::display_object::TDisplayObject;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
 use std::collections::{binary_heap::PeekMut, BinaryHeap};
+use std::sync::Arc;
 
 /// Manages the collection of timers.
 pub struct Timers<'gc> {
@@ -217,6 +221,19 @@ impl<'gc> Timers<'gc> {
         }
     }
 
+    /// Removes all timers whose callbacks were defined by the given movie.
+    ///
+    /// Used when a loaded movie is torn down by `Loader.unloadAndStop`.
+    pub fn remove_timers_for_movie(&mut self, movie: &Arc<SwfMovie>) {
+        for timer in self.timers.iter() {
+            if let Some(owner) = timer.callback.movie() {
+                if Arc::ptr_eq(&owner, movie) {
+                    timer.is_alive.set(false);
+                }
+            }
+        }
+    }
+
     fn peek(&self) -> Option<&Timer<'gc>> {
         self.timers.peek()
     }
@@ -312,3 +329,19 @@ pub enum TimerCallback<'gc> {
         params: Vec<Avm2Value<'gc>>,
     },
 }
+
+impl<'gc> TimerCallback<'gc> {
+    /// The movie that defined this callback, if it can be determined.
+    fn movie(&self) -> Option<Arc<SwfMovie>> {
+        match self {
+            TimerCallback::Avm1Function { func, .. } => match func.as_executable()? {
+                Avm1Executable::Action(af) => Some(af.data().movie),
+                Avm1Executable::Native(_) => None,
+            },
+            TimerCallback::Avm1Method { this, .. } => {
+                this.as_display_object().map(|clip| clip.movie())
+            }
+            TimerCallback::Avm2Callback { closure, .. } => closure.as_executable()?.movie(),
+        }
+    }
+}
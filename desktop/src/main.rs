@@ -19,6 +19,8 @@ use anyhow::{anyhow, Context, Error};
 use clap::Parser;
 use isahc::{config::RedirectPolicy, prelude::*, HttpClient};
 use rfd::FileDialog;
+use ruffle_core::backend::storage::StorageBackend;
+use ruffle_core::events::TextControlCode;
 use ruffle_core::{
     config::Letterbox, events::KeyCode, tag_utils::SwfMovie, LoadBehavior, Player, PlayerBuilder,
     PlayerEvent, StageDisplayState, StaticCallstack, ViewportDimensions,
@@ -107,6 +109,23 @@ struct Opt {
     /// Spoofs the root SWF URL provided to ActionScript.
     #[clap(long, value_parser)]
     spoof_url: Option<Url>,
+
+    /// Restrict cross-domain requests according to `crossdomain.xml` policy files.
+    #[clap(long, action)]
+    enforce_policy_files: bool,
+
+    /// List all stored shared objects and exit.
+    #[clap(long, action)]
+    list_shared_objects: bool,
+
+    /// Copy all stored shared objects into a directory as `.sol` files and exit.
+    #[clap(long)]
+    export_shared_objects: Option<PathBuf>,
+
+    /// Import all `.sol` files from a directory into shared object storage and exit.
+    /// Existing shared objects with the same names are overwritten.
+    #[clap(long)]
+    import_shared_objects: Option<PathBuf>,
 }
 
 #[cfg(feature = "render_trace")]
@@ -276,7 +295,8 @@ impl App {
             .with_warn_on_unsupported_content(!opt.dont_warn_on_unsupported_content)
             .with_fullscreen(opt.fullscreen)
             .with_load_behavior(opt.load_behavior)
-            .with_spoofed_url(opt.spoof_url.clone().map(|url| url.to_string()));
+            .with_spoofed_url(opt.spoof_url.clone().map(|url| url.to_string()))
+            .with_policy_file_enforcement(opt.enforce_policy_files);
 
         let player = builder.build();
 
@@ -479,6 +499,15 @@ impl App {
                                     }
                                 };
                                 player_lock.handle_event(event);
+
+                                // Text fields handle editing shortcuts, such as Ctrl+V to paste.
+                                let is_command_down =
+                                    input.modifiers.ctrl() || input.modifiers.logo();
+                                if input.state == ElementState::Pressed && is_command_down {
+                                    if let Some(code) = TextControlCode::from_shortcut(key_code) {
+                                        player_lock.handle_event(PlayerEvent::TextControl { code });
+                                    }
+                                }
                                 if player_lock.needs_render() {
                                     self.window.request_redraw();
                                 }
@@ -859,10 +888,42 @@ fn shutdown() {
     }
 }
 
+fn run_shared_object_command(opt: &Opt) -> Result<(), Error> {
+    let mut storage = storage::DiskStorageBackend::new();
+
+    if let Some(dir) = &opt.import_shared_objects {
+        let count = storage
+            .import_shared_objects(dir)
+            .context("Couldn't import shared objects")?;
+        println!("Imported {count} shared objects from {}", dir.display());
+    }
+
+    if let Some(dir) = &opt.export_shared_objects {
+        let count = storage
+            .export_shared_objects(dir)
+            .context("Couldn't export shared objects")?;
+        println!("Exported {count} shared objects to {}", dir.display());
+    }
+
+    if opt.list_shared_objects {
+        for name in storage.keys() {
+            let size = storage.get_size(&name).unwrap_or_default();
+            println!("{name} ({size} bytes)");
+        }
+    }
+
+    Ok(())
+}
+
 fn main() -> Result<(), Error> {
     init();
     let opt = Opt::parse();
-    let result = if opt.timedemo {
+    let result = if opt.list_shared_objects
+        || opt.export_shared_objects.is_some()
+        || opt.import_shared_objects.is_some()
+    {
+        run_shared_object_command(&opt)
+    } else if opt.timedemo {
         run_timedemo(opt)
     } else {
         App::new(opt).map(|app| app.run())
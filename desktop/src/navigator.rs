@@ -158,16 +158,23 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                     Err(e)
                 }).map_err(|e| Error::FetchError(e.to_string()))?;
 
-                Ok(Response { url, body })
+                Ok(Response {
+                    url,
+                    body,
+                    content_type: None,
+                })
             }),
             _ => Box::pin(async move {
                 let client =
                     client.ok_or_else(|| Error::FetchError("Network unavailable".to_string()))?;
 
-                let isahc_request = match request.method() {
+                let mut isahc_request = match request.method() {
                     NavigationMethod::Get => IsahcRequest::get(processed_url.to_string()),
                     NavigationMethod::Post => IsahcRequest::post(processed_url.to_string()),
                 };
+                for (name, value) in request.headers() {
+                    isahc_request = isahc_request.header(name.as_str(), value.as_str());
+                }
 
                 let (body_data, _) = request.body().clone().unwrap_or_default();
                 let body = isahc_request
@@ -192,13 +199,23 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                     processed_url.into()
                 };
 
+                let content_type = response
+                    .headers()
+                    .get("content-type")
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+
                 let mut body = vec![];
                 response
                     .copy_to(&mut body)
                     .await
                     .map_err(|e| Error::FetchError(e.to_string()))?;
 
-                Ok(Response { url, body })
+                Ok(Response {
+                    url,
+                    body,
+                    content_type,
+                })
             }),
         }
     }
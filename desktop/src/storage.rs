@@ -1,9 +1,13 @@
+use anyhow::Error;
 use ruffle_core::backend::storage::StorageBackend;
 use std::fs;
 use std::fs::File;
 use std::io::Write;
 use std::path::{Component, Path, PathBuf};
 
+/// The first two bytes of every `.sol` file.
+const LSO_MAGIC: [u8; 2] = [0x00, 0xBF];
+
 pub struct DiskStorageBackend {
     base_path: PathBuf,
     shared_objects_path: PathBuf,
@@ -37,6 +41,43 @@ impl DiskStorageBackend {
         self.shared_objects_path.join(format!("{name}.sol"))
     }
 
+    /// Copy every stored shared object into `dir` as a `.sol` file, keeping
+    /// the storage's directory layout.
+    ///
+    /// Returns the number of shared objects exported.
+    pub fn export_shared_objects(&self, dir: &Path) -> Result<usize, Error> {
+        let mut count = 0;
+        for (name, path) in find_sol_files(&self.shared_objects_path)? {
+            let target = dir.join(format!("{name}.sol"));
+            if let Some(parent_dir) = target.parent() {
+                fs::create_dir_all(parent_dir)?;
+            }
+            fs::copy(&path, &target)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Store every `.sol` file found in `dir`, overwriting any existing
+    /// shared object with the same name.
+    ///
+    /// Files that are not valid shared objects are skipped. Returns the
+    /// number of shared objects imported.
+    pub fn import_shared_objects(&mut self, dir: &Path) -> Result<usize, Error> {
+        let mut count = 0;
+        for (name, path) in find_sol_files(dir)? {
+            let data = fs::read(&path)?;
+            if !data.starts_with(&LSO_MAGIC) {
+                log::warn!("Skipping {:?}: not a shared object file", path);
+                continue;
+            }
+            if self.put(&name, &data) {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
     fn get_back_compat_shared_object_path(&self, name: &str) -> PathBuf {
         // Backwards compatibility with pre-05/09/2021:
         // Search for data in old location, without .sol extension and # prefix.
@@ -107,4 +148,49 @@ impl StorageBackend for DiskStorageBackend {
         }
         let _ = fs::remove_file(path);
     }
+
+    fn keys(&self) -> Vec<String> {
+        match find_sol_files(&self.shared_objects_path) {
+            Ok(files) => files.into_iter().map(|(name, _)| name).collect(),
+            Err(e) => {
+                log::warn!("Unable to list shared objects {:?}", e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// Recursively find all `.sol` files in `dir`.
+///
+/// Each file is returned along with its shared object name: its path relative
+/// to `dir`, using `/` separators and without the extension.
+fn find_sol_files(dir: &Path) -> Result<Vec<(String, PathBuf)>, Error> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        let entries = match fs::read_dir(&current) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().map_or(false, |ext| ext == "sol") {
+                let relative = path.strip_prefix(dir)?.with_extension("");
+                let name = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.push((name, path));
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
 }
@@ -1,6 +1,8 @@
 use arboard::Clipboard;
-use rfd::{MessageButtons, MessageDialog, MessageLevel};
-use ruffle_core::backend::ui::{FullscreenError, MouseCursor, UiBackend};
+use rfd::{AsyncFileDialog, MessageButtons, MessageDialog, MessageLevel};
+use ruffle_core::backend::ui::{
+    DialogFile, DialogResultFuture, FileFilter, FullscreenError, MouseCursor, UiBackend,
+};
 use std::rc::Rc;
 use winit::window::{Fullscreen, Window};
 
@@ -55,6 +57,10 @@ impl UiBackend for DesktopUiBackend {
         self.clipboard.set_text(content).unwrap();
     }
 
+    fn clipboard_content(&mut self) -> String {
+        self.clipboard.get_text().unwrap_or_default()
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
         self.window.set_fullscreen(if is_full {
             Some(Fullscreen::Borderless(None))
@@ -90,4 +96,38 @@ impl UiBackend for DesktopUiBackend {
             .set_buttons(MessageButtons::Ok);
         dialog.show();
     }
+
+    fn display_file_open_dialog(
+        &mut self,
+        filters: Vec<FileFilter>,
+        multiple: bool,
+    ) -> DialogResultFuture<Vec<DialogFile>> {
+        let mut dialog = AsyncFileDialog::new();
+        for filter in &filters {
+            let extensions: Vec<&str> = filter.extensions.iter().map(String::as_str).collect();
+            dialog = dialog.add_filter(&filter.description, &extensions);
+        }
+
+        Box::pin(async move {
+            let files = if multiple {
+                dialog.pick_files().await?
+            } else {
+                vec![dialog.pick_file().await?]
+            };
+            Some(
+                files
+                    .into_iter()
+                    .map(|file| DialogFile::from_path(file.path().to_path_buf()))
+                    .collect(),
+            )
+        })
+    }
+
+    fn display_file_save_dialog(&mut self, default_name: String) -> DialogResultFuture<DialogFile> {
+        let dialog = AsyncFileDialog::new().set_file_name(&default_name);
+        Box::pin(async move {
+            let file = dialog.save_file().await?;
+            Some(DialogFile::from_path(file.path().to_path_buf()))
+        })
+    }
 }
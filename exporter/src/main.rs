@@ -6,6 +6,7 @@ use rayon::prelude::*;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::PlayerBuilder;
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::backend::WgpuRenderBackend;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::descriptors::Descriptors;
@@ -80,11 +81,18 @@ struct Opt {
     /// Skip unsupported movie types (currently AVM 2)
     #[clap(long, action)]
     skip_unsupported: bool,
+
+    /// Render on the CPU instead of using a graphics device.
+    /// This is slower, but works on machines without a GPU.
+    #[clap(long, action)]
+    software: bool,
 }
 
 /// Captures a screenshot. The resulting image uses straight alpha
+///
+/// If no `descriptors` are given, the software renderer is used.
 fn take_screenshot(
-    descriptors: Arc<Descriptors>,
+    descriptors: Option<Arc<Descriptors>>,
     swf_path: &Path,
     frames: u32,
     skipframes: u32,
@@ -110,12 +118,20 @@ fn take_screenshot(
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height))
-        .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
-        .with_renderer(
-            WgpuRenderBackend::new(descriptors, target, 4).map_err(|e| anyhow!(e.to_string()))?,
-        )
+    let builder = match descriptors {
+        Some(descriptors) => {
+            let target = TextureTarget::new(&descriptors.device, (width, height))
+                .map_err(|e| anyhow!(e.to_string()))?;
+            PlayerBuilder::new().with_renderer(
+                WgpuRenderBackend::new(descriptors, target, 4)
+                    .map_err(|e| anyhow!(e.to_string()))?,
+            )
+        }
+        None => PlayerBuilder::new().with_renderer(
+            SoftwareRenderBackend::new(width, height).map_err(|e| anyhow!(e.to_string()))?,
+        ),
+    };
+    let player = builder
         .with_movie(movie)
         .with_viewport_dimensions(width, height, size.scale)
         .build();
@@ -139,12 +155,19 @@ fn take_screenshot(
             match catch_unwind(|| {
                 player.lock().unwrap().render();
                 let mut player = player.lock().unwrap();
-                let renderer = player
-                    .renderer_mut()
-                    .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                    .unwrap();
+                let renderer = player.renderer_mut();
                 // Use straight alpha
-                renderer.capture_frame(false)
+                if renderer.is::<SoftwareRenderBackend>() {
+                    renderer
+                        .downcast_ref::<SoftwareRenderBackend>()
+                        .unwrap()
+                        .capture_frame(false)
+                } else {
+                    renderer
+                        .downcast_ref::<WgpuRenderBackend<TextureTarget>>()
+                        .unwrap()
+                        .capture_frame(false)
+                }
             }) {
                 Ok(Some(image)) => result.push(image),
                 Ok(None) => return Err(anyhow!("Unable to capture frame {} of {:?}", i, swf_path)),
@@ -196,7 +219,7 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_single_swf(descriptors: Option<Arc<Descriptors>>, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         result.set_file_name(opt.swf.file_stem().unwrap());
@@ -273,7 +296,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
 }
 
 #[allow(clippy::branches_sharing_code)]
-fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_multiple_swfs(descriptors: Option<Arc<Descriptors>>, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -381,18 +404,21 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
 
 fn main() -> Result<()> {
     let opt: Opt = Opt::parse();
-    let instance = wgpu::Instance::new(opt.graphics.into());
-    let descriptors =
-        futures::executor::block_on(WgpuRenderBackend::<TextureTarget>::build_descriptors(
-            opt.graphics.into(),
-            instance,
-            None,
-            opt.power.into(),
-            trace_path(&opt),
-        ))
-        .map_err(|e| anyhow!(e.to_string()))?;
-
-    let descriptors = Arc::new(descriptors);
+    let descriptors = if opt.software {
+        None
+    } else {
+        let instance = wgpu::Instance::new(opt.graphics.into());
+        let descriptors =
+            futures::executor::block_on(WgpuRenderBackend::<TextureTarget>::build_descriptors(
+                opt.graphics.into(),
+                instance,
+                None,
+                opt.power.into(),
+                trace_path(&opt),
+            ))
+            .map_err(|e| anyhow!(e.to_string()))?;
+        Some(Arc::new(descriptors))
+    };
 
     if opt.swf.is_file() {
         capture_single_swf(descriptors, &opt)?;
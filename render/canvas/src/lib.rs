@@ -8,6 +8,7 @@ use ruffle_render::color_transform::ColorTransform;
 use ruffle_render::commands::{CommandHandler, CommandList};
 use ruffle_render::error::Error;
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::shape_utils::{DistilledShape, DrawCommand, LineScaleMode, LineScales};
 use ruffle_render::transform::Transform;
 use ruffle_web_common::{JsError, JsResult};
@@ -522,6 +523,25 @@ impl CommandHandler for WebCanvasRenderBackend {
         self.clear_color_filter();
     }
 
+    fn render_bitmap_3d(
+        &mut self,
+        bitmap: &BitmapHandle,
+        matrix: &Matrix3D,
+        color_transform: &ColorTransform,
+        smoothing: bool,
+    ) {
+        // Canvas can't draw perspective, so draw the bitmap with the closest 2D transform.
+        let data = as_bitmap_data(bitmap);
+        let (width, height) = (data.bitmap.width(), data.bitmap.height());
+        if let Some(matrix) = matrix.affine_approximation(width.into(), height.into()) {
+            let transform = Transform {
+                matrix,
+                color_transform: *color_transform,
+            };
+            self.render_bitmap(bitmap, &transform, smoothing);
+        }
+    }
+
     fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
         match &self.mask_state {
             MaskState::DrawContent => {
@@ -771,11 +791,32 @@ impl CommandHandler for WebCanvasRenderBackend {
         }
     }
 
-    fn blend(&mut self, commands: &CommandList, blend: BlendMode) {
+    fn blend(
+        &mut self,
+        commands: &CommandList,
+        blend: BlendMode,
+        color_transform: &ColorTransform,
+    ) {
         self.push_blend_mode(blend);
-        commands.execute(self);
+        if *color_transform == ColorTransform::IDENTITY {
+            commands.execute(self);
+        } else {
+            // Layers aren't supported, so apply the color transform to each draw instead.
+            commands.with_color_transform(color_transform).execute(self);
+        }
         self.pop_blend_mode();
     }
+
+    fn alpha_mask(&mut self, maskee: &CommandList, mask: &CommandList) {
+        // Alpha masks aren't supported, so fall back to a regular stencil mask.
+        self.push_mask();
+        mask.execute(self);
+        self.activate_mask();
+        maskee.execute(self);
+        self.deactivate_mask();
+        mask.execute(self);
+        self.pop_mask();
+    }
 }
 
 /// Convert a series of `DrawCommands` to a `Path2d` shape.
@@ -0,0 +1,256 @@
+use crate::bitmap::{as_bitmap, Pixels, SoftwareBitmap};
+use crate::mesh::Mesh;
+use crate::surface::Surface;
+use gc_arena::MutationContext;
+use ruffle_render::backend::null::NullBitmapSource;
+use ruffle_render::backend::{
+    Context3D, Context3DCommand, RenderBackend, ShapeHandle, ViewportDimensions,
+};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapHandle, BitmapSource};
+use ruffle_render::bounding_box::BoundingBox;
+use ruffle_render::commands::CommandList;
+use ruffle_render::error::Error;
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render::tessellator::ShapeTessellator;
+use std::borrow::Cow;
+use std::sync::Arc;
+use swf::Color;
+
+/// The largest width or height of a surface or bitmap, in pixels.
+const MAX_DIMENSION: u32 = 8192;
+
+pub struct SoftwareRenderBackend {
+    meshes: Vec<Mesh>,
+    shape_tessellator: ShapeTessellator,
+    surface: Surface,
+    viewport_scale_factor: f64,
+}
+
+impl SoftwareRenderBackend {
+    pub fn new(width: u32, height: u32) -> Result<Self, Error> {
+        if width > MAX_DIMENSION || height > MAX_DIMENSION {
+            return Err(Error::TooLarge);
+        }
+        Ok(Self {
+            meshes: Vec::new(),
+            shape_tessellator: ShapeTessellator::new(),
+            surface: Surface::new(width.max(1), height.max(1)),
+            viewport_scale_factor: 1.0,
+        })
+    }
+
+    /// Returns the last frame that was rendered.
+    ///
+    /// If `premultiplied_alpha` is false, the alpha is divided out of the colors.
+    pub fn capture_frame(&self, premultiplied_alpha: bool) -> Option<image::RgbaImage> {
+        let pixels = self.surface.resolve();
+        let mut rgba = pixels.to_rgba();
+        if !premultiplied_alpha {
+            ruffle_render::utils::unmultiply_alpha_rgba(&mut rgba);
+        }
+        image::RgbaImage::from_raw(pixels.width, pixels.height, rgba)
+    }
+
+    fn register_shape_internal(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Mesh {
+        let lyon_mesh = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+        Mesh::new(self, lyon_mesh, bitmap_source)
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        ViewportDimensions {
+            width: self.surface.width(),
+            height: self.surface.height(),
+            scale_factor: self.viewport_scale_factor,
+        }
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        let width = dimensions.width.clamp(1, MAX_DIMENSION);
+        let height = dimensions.height.clamp(1, MAX_DIMENSION);
+        self.surface = Surface::new(width, height);
+        self.viewport_scale_factor = dimensions.scale_factor;
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let handle = ShapeHandle(self.meshes.len());
+        let mesh = self.register_shape_internal(shape, bitmap_source);
+        self.meshes.push(mesh);
+        handle
+    }
+
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+        handle: ShapeHandle,
+    ) {
+        let mesh = self.register_shape_internal(shape, bitmap_source);
+        self.meshes[handle.0] = mesh;
+    }
+
+    fn register_glyph_shape(&mut self, glyph: &swf::Glyph) -> ShapeHandle {
+        let shape = ruffle_render::shape_utils::swf_glyph_to_shape(glyph);
+        let handle = ShapeHandle(self.meshes.len());
+        let mesh = self.register_shape_internal((&shape).into(), &NullBitmapSource);
+        self.meshes.push(mesh);
+        handle
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        commands: CommandList,
+    ) -> Result<Bitmap, Error> {
+        let bitmap = as_bitmap(&handle);
+        let mut surface = Surface::from_pixels(&bitmap.pixels());
+        if width > surface.width() || height > surface.height() {
+            return Err(Error::TooLarge);
+        }
+
+        // Like the wgpu backend, only the top left `width` by `height` pixels are drawn.
+        surface.set_clip(Some((0, 0, width, height)));
+        surface.draw_commands(&commands, &self.meshes);
+
+        let pixels = surface.resolve();
+        let rgba = pixels
+            .data
+            .chunks_exact(pixels.width as usize)
+            .take(height as usize)
+            .flat_map(|row| row[..width as usize].iter().flatten().copied())
+            .collect();
+        bitmap.set_pixels(pixels);
+        Ok(Bitmap::new(width, height, BitmapFormat::Rgba, rgba))
+    }
+
+    fn submit_frame(&mut self, clear: Color, commands: CommandList) {
+        self.surface.clear(clear);
+        self.surface.draw_commands(&commands, &self.meshes);
+    }
+
+    fn submit_frame_partial(
+        &mut self,
+        clear: Color,
+        commands: CommandList,
+        dirty_rects: &[BoundingBox],
+    ) {
+        // The surface still holds the last frame, so only the dirty pixels are redrawn.
+        for rect in dirty_rects.iter().filter(|rect| rect.valid) {
+            let x_min = rect.x_min.to_pixels().floor().max(0.0) as u32;
+            let y_min = rect.y_min.to_pixels().floor().max(0.0) as u32;
+            let x_max = rect.x_max.to_pixels().ceil().max(0.0) as u32;
+            let y_max = rect.y_max.to_pixels().ceil().max(0.0) as u32;
+            self.surface.set_clip(Some((x_min, y_min, x_max, y_max)));
+            self.surface.clear(clear.clone());
+            self.surface.draw_commands(&commands, &self.meshes);
+        }
+        self.surface.set_clip(None);
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        if bitmap.width() > MAX_DIMENSION || bitmap.height() > MAX_DIMENSION {
+            return Err(Error::TooLarge);
+        }
+        Ok(BitmapHandle(Arc::new(SoftwareBitmap::new(bitmap))))
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<(), Error> {
+        as_bitmap(handle).set_pixels(Pixels::new(width, height, &rgba));
+        Ok(())
+    }
+
+    fn create_context3d(&mut self) -> Result<Box<dyn Context3D>, Error> {
+        Err(Error::Unimplemented)
+    }
+
+    fn context3d_present<'gc>(
+        &mut self,
+        _context: &mut dyn Context3D,
+        _commands: Vec<Context3DCommand<'gc>>,
+        _mc: MutationContext<'gc, '_>,
+    ) -> Result<(), Error> {
+        Err(Error::Unimplemented)
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        Cow::Owned(format!(
+            "Renderer: Software\nSurface size: {}x{}",
+            self.surface.width(),
+            self.surface.height()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_render::commands::CommandHandler;
+    use ruffle_render::matrix::Matrix;
+    use swf::Twips;
+
+    const RED: Color = Color {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+
+    fn fill_commands() -> CommandList {
+        let mut commands = CommandList::new();
+        commands.draw_rect(
+            RED,
+            &Matrix::create_box(4.0, 2.0, 0.0, Twips::ZERO, Twips::ZERO),
+        );
+        commands
+    }
+
+    #[test]
+    fn render_offscreen_draws_requested_area() {
+        let mut backend = SoftwareRenderBackend::new(1, 1).unwrap();
+        let handle = backend
+            .register_bitmap(Bitmap::new(4, 2, BitmapFormat::Rgba, vec![0; 4 * 2 * 4]))
+            .unwrap();
+
+        let result = backend
+            .render_offscreen(handle.clone(), 2, 1, fill_commands())
+            .unwrap();
+        assert_eq!((result.width(), result.height()), (2, 1));
+        assert_eq!(result.data(), [255, 0, 0, 255, 255, 0, 0, 255]);
+
+        let pixels = as_bitmap(&handle).pixels();
+        assert_eq!(pixels.data[1], [255, 0, 0, 255]);
+        assert_eq!(pixels.data[2], [0, 0, 0, 0]);
+        assert_eq!(pixels.data[4], [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn render_offscreen_rejects_area_larger_than_bitmap() {
+        let mut backend = SoftwareRenderBackend::new(1, 1).unwrap();
+        let handle = backend
+            .register_bitmap(Bitmap::new(4, 2, BitmapFormat::Rgba, vec![0; 4 * 2 * 4]))
+            .unwrap();
+
+        assert!(backend
+            .render_offscreen(handle, 5, 2, fill_commands())
+            .is_err());
+    }
+}
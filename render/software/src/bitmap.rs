@@ -0,0 +1,105 @@
+use ruffle_render::bitmap::{Bitmap, BitmapHandle, BitmapHandleImpl};
+use std::sync::{RwLock, RwLockReadGuard};
+
+/// A bitmap stored in main memory, with premultiplied alpha.
+#[derive(Debug)]
+pub struct SoftwareBitmap(RwLock<Pixels>);
+
+#[derive(Clone, Debug)]
+pub struct Pixels {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<[u8; 4]>,
+}
+
+impl BitmapHandleImpl for SoftwareBitmap {}
+
+impl SoftwareBitmap {
+    pub fn new(bitmap: Bitmap) -> Self {
+        let bitmap = bitmap.to_rgba();
+        Self(RwLock::new(Pixels {
+            width: bitmap.width(),
+            height: bitmap.height(),
+            data: bitmap
+                .data()
+                .chunks_exact(4)
+                .map(|c| [c[0], c[1], c[2], c[3]])
+                .collect(),
+        }))
+    }
+
+    pub fn pixels(&self) -> RwLockReadGuard<'_, Pixels> {
+        self.0.read().unwrap()
+    }
+
+    pub fn set_pixels(&self, pixels: Pixels) {
+        *self.0.write().unwrap() = pixels;
+    }
+}
+
+pub fn as_bitmap(handle: &BitmapHandle) -> &SoftwareBitmap {
+    <dyn BitmapHandleImpl>::downcast_ref(&*handle.0).expect("Bitmap handle must be software")
+}
+
+impl Pixels {
+    pub fn new(width: u32, height: u32, data: &[u8]) -> Self {
+        Self {
+            width,
+            height,
+            data: data
+                .chunks_exact(4)
+                .map(|c| [c[0], c[1], c[2], c[3]])
+                .collect(),
+        }
+    }
+
+    pub fn to_rgba(&self) -> Vec<u8> {
+        self.data.iter().flatten().copied().collect()
+    }
+
+    /// Sample the bitmap at the normalized coordinates `u` and `v`, returning
+    /// a premultiplied color.
+    ///
+    /// Smoothed samples are bilinearly filtered between the four nearest
+    /// texels. Coordinates outside of the bitmap either wrap around or are
+    /// clamped to its edges.
+    pub fn sample(&self, u: f32, v: f32, smoothing: bool, repeating: bool) -> [f32; 4] {
+        if self.width == 0 || self.height == 0 {
+            return [0.0; 4];
+        }
+        let x = u * self.width as f32;
+        let y = v * self.height as f32;
+        if !smoothing {
+            return self.texel(x.floor() as i64, y.floor() as i64, repeating);
+        }
+
+        let (x, y) = (x - 0.5, y - 0.5);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let mut color = [0.0; 4];
+        for (dx, dy, weight) in [
+            (0, 0, (1.0 - fx) * (1.0 - fy)),
+            (1, 0, fx * (1.0 - fy)),
+            (0, 1, (1.0 - fx) * fy),
+            (1, 1, fx * fy),
+        ] {
+            let texel = self.texel(x0 + dx, y0 + dy, repeating);
+            for (c, t) in color.iter_mut().zip(texel) {
+                *c += t * weight;
+            }
+        }
+        color
+    }
+
+    fn texel(&self, x: i64, y: i64, repeating: bool) -> [f32; 4] {
+        let (width, height) = (i64::from(self.width), i64::from(self.height));
+        let (x, y) = if repeating {
+            (x.rem_euclid(width), y.rem_euclid(height))
+        } else {
+            (x.clamp(0, width - 1), y.clamp(0, height - 1))
+        };
+        let texel = self.data[(y * width + x) as usize];
+        texel.map(|c| f32::from(c) / 255.0)
+    }
+}
@@ -0,0 +1,12 @@
+//! A render backend which draws on the CPU.
+//!
+//! This backend does not need a GPU or a window, which makes it suitable for
+//! exporting frames and running image tests on machines without graphics
+//! drivers. It aims to produce the same output as the wgpu backend.
+
+pub mod backend;
+mod bitmap;
+mod mesh;
+mod surface;
+
+pub use backend::SoftwareRenderBackend;
@@ -0,0 +1,146 @@
+use crate::backend::SoftwareRenderBackend;
+use ruffle_render::bitmap::{BitmapHandle, BitmapSource};
+use ruffle_render::tessellator::{
+    DrawType as TessDrawType, Gradient, GradientType, Vertex as TessVertex,
+};
+use swf::{GradientInterpolation, GradientSpread};
+
+#[derive(Debug)]
+pub struct Mesh {
+    pub draws: Vec<Draw>,
+}
+
+#[derive(Debug)]
+pub struct Draw {
+    pub paint: Paint,
+    pub vertices: Vec<TessVertex>,
+    pub indices: Vec<u32>,
+    pub mask_index_count: u32,
+}
+
+/// How the triangles of a draw are colored.
+#[derive(Debug)]
+pub enum Paint {
+    /// Each triangle is filled with the color of its vertices.
+    Color,
+
+    Gradient(Gradient),
+
+    Bitmap {
+        bitmap: BitmapHandle,
+        matrix: [[f32; 3]; 3],
+        smoothing: bool,
+        repeating: bool,
+    },
+}
+
+impl Mesh {
+    pub fn new(
+        backend: &mut SoftwareRenderBackend,
+        lyon_mesh: Vec<ruffle_render::tessellator::Draw>,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Self {
+        let mut draws = Vec::with_capacity(lyon_mesh.len());
+        for draw in lyon_mesh {
+            let paint = match draw.draw_type {
+                TessDrawType::Color => Paint::Color,
+                TessDrawType::Gradient(gradient) => Paint::Gradient(gradient),
+                TessDrawType::Bitmap(bitmap) => {
+                    match bitmap_source.bitmap_handle(bitmap.bitmap_id, backend) {
+                        Some(handle) => Paint::Bitmap {
+                            bitmap: handle,
+                            matrix: bitmap.matrix,
+                            smoothing: bitmap.is_smoothed,
+                            repeating: bitmap.is_repeating,
+                        },
+                        None => {
+                            log::warn!(
+                                "Couldn't fill shape with unknown bitmap {}",
+                                bitmap.bitmap_id
+                            );
+                            continue;
+                        }
+                    }
+                }
+            };
+            draws.push(Draw {
+                paint,
+                vertices: draw.vertices,
+                indices: draw.indices,
+                mask_index_count: draw.mask_index_count,
+            });
+        }
+        Self { draws }
+    }
+}
+
+/// Apply a gradient or bitmap matrix to a point in shape space.
+pub fn texture_coords(matrix: &[[f32; 3]; 3], x: f32, y: f32) -> (f32, f32) {
+    (
+        matrix[0][0] * x + matrix[1][0] * y + matrix[2][0],
+        matrix[0][1] * x + matrix[1][1] * y + matrix[2][1],
+    )
+}
+
+/// Evaluate a gradient at a point in shape space, returning a straight color.
+pub fn gradient_color(gradient: &Gradient, x: f32, y: f32) -> [f32; 4] {
+    let (u, v) = texture_coords(&gradient.matrix, x, y);
+    let t = match gradient.gradient_type {
+        GradientType::Linear => u,
+        GradientType::Radial => (u * 2.0 - 1.0).hypot(v * 2.0 - 1.0),
+        GradientType::Focal => {
+            let focal_point = f32::from(gradient.focal_point).clamp(-0.98, 0.98);
+            let (u, v) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+            let (dx, dy) = (focal_point - u, -v);
+            let length = dx.hypot(dy);
+            if length == 0.0 {
+                0.0
+            } else {
+                let (dx, dy) = (dx / length, dy / length);
+                length / ((1.0 - focal_point * focal_point * dy * dy).sqrt() + focal_point * dx)
+            }
+        }
+    };
+    let t = match gradient.repeat_mode {
+        GradientSpread::Pad => t.clamp(0.0, 1.0),
+        GradientSpread::Reflect => {
+            let t = t.rem_euclid(2.0);
+            if t > 1.0 {
+                2.0 - t
+            } else {
+                t
+            }
+        }
+        GradientSpread::Repeat => t.rem_euclid(1.0),
+    };
+
+    let ratios = &gradient.ratios[..gradient.num_colors];
+    let colors = &gradient.colors[..gradient.num_colors];
+    let t = match (ratios.first(), ratios.last()) {
+        (Some(first), Some(last)) => t.clamp(*first, *last),
+        _ => return [0.0; 4],
+    };
+    let next = ratios.iter().position(|r| *r >= t).unwrap_or(0).max(1);
+    let mut color = if next >= ratios.len() {
+        colors[0]
+    } else {
+        let (r0, r1) = (ratios[next - 1], ratios[next]);
+        let a = if r1 > r0 { (t - r0) / (r1 - r0) } else { 0.0 };
+        let (c0, c1) = (colors[next - 1], colors[next]);
+        [0, 1, 2, 3].map(|i| c0[i] + (c1[i] - c0[i]) * a)
+    };
+    if gradient.interpolation == GradientInterpolation::LinearRgb {
+        linear_to_srgb(&mut color);
+    }
+    color
+}
+
+fn linear_to_srgb(color: &mut [f32; 4]) {
+    for n in &mut color[..3] {
+        *n = if *n <= 0.0031308 {
+            *n * 12.92
+        } else {
+            1.055 * n.powf(1.0 / 2.4) - 0.055
+        };
+    }
+}
@@ -0,0 +1,863 @@
+use crate::bitmap::{as_bitmap, Pixels};
+use crate::mesh::{gradient_color, texture_coords, Mesh, Paint};
+use ruffle_render::backend::ShapeHandle;
+use ruffle_render::bitmap::BitmapHandle;
+use ruffle_render::color_transform::ColorTransform;
+use ruffle_render::commands::{CommandHandler, CommandList};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::transform::Transform;
+use std::ops::Range;
+use swf::{BlendMode, Color};
+
+/// The number of samples taken per pixel, matching the MSAA of the wgpu backend.
+const SAMPLE_COUNT: usize = 4;
+
+/// The position of each sample within a pixel, using the standard 4x MSAA pattern.
+const SAMPLE_OFFSETS: [(f64, f64); SAMPLE_COUNT] = [
+    (0.375, 0.125),
+    (0.875, 0.375),
+    (0.125, 0.625),
+    (0.625, 0.875),
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum MaskState {
+    NoMask,
+    DrawMaskStencil,
+    DrawMaskedContent,
+    ClearMaskStencil,
+}
+
+/// A multisampled render target with a stencil buffer for masks.
+///
+/// Colors are stored with premultiplied alpha.
+pub struct Surface {
+    width: u32,
+    height: u32,
+    samples: Vec<[u8; 4]>,
+    stencil: Vec<u8>,
+    num_masks: u8,
+    mask_state: MaskState,
+
+    /// The pixels that can be drawn to, as `(x_min, y_min, x_max, y_max)` with exclusive maximums.
+    clip: (u32, u32, u32, u32),
+}
+
+impl Surface {
+    pub fn new(width: u32, height: u32) -> Self {
+        let len = width as usize * height as usize * SAMPLE_COUNT;
+        Self {
+            width,
+            height,
+            samples: vec![[0; 4]; len],
+            stencil: vec![0; len],
+            num_masks: 0,
+            mask_state: MaskState::NoMask,
+            clip: (0, 0, width, height),
+        }
+    }
+
+    /// Create a surface whose samples start out with the colors of `pixels`.
+    pub fn from_pixels(pixels: &Pixels) -> Self {
+        let mut surface = Self::new(pixels.width, pixels.height);
+        for (samples, pixel) in surface
+            .samples
+            .chunks_exact_mut(SAMPLE_COUNT)
+            .zip(&pixels.data)
+        {
+            samples.fill(*pixel);
+        }
+        surface
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Restrict all drawing to a rectangle of pixels, or to the whole surface if `None`.
+    /// The maximums are exclusive.
+    pub fn set_clip(&mut self, clip: Option<(u32, u32, u32, u32)>) {
+        self.clip = match clip {
+            Some((x_min, y_min, x_max, y_max)) => {
+                let x_max = x_max.min(self.width);
+                let y_max = y_max.min(self.height);
+                (x_min.min(x_max), y_min.min(y_max), x_max, y_max)
+            }
+            None => (0, 0, self.width, self.height),
+        };
+    }
+
+    /// The ranges of sample indices within the clip, one per row.
+    fn clip_rows(&self) -> impl Iterator<Item = Range<usize>> {
+        let (x_min, y_min, x_max, y_max) = self.clip;
+        let width = self.width as usize;
+        (y_min as usize..y_max as usize).map(move |y| {
+            (y * width + x_min as usize) * SAMPLE_COUNT..(y * width + x_max as usize) * SAMPLE_COUNT
+        })
+    }
+
+    /// Fill the clipped part of the surface with a straight color, and reset its masks.
+    pub fn clear(&mut self, color: Color) {
+        let color = to_u8(premultiply([
+            f32::from(color.r) / 255.0,
+            f32::from(color.g) / 255.0,
+            f32::from(color.b) / 255.0,
+            f32::from(color.a) / 255.0,
+        ]));
+        for row in self.clip_rows() {
+            self.samples[row.clone()].fill(color);
+            self.stencil[row].fill(0);
+        }
+        self.num_masks = 0;
+        self.mask_state = MaskState::NoMask;
+    }
+
+    /// Average the samples of each pixel.
+    pub fn resolve(&self) -> Pixels {
+        let data = self
+            .samples
+            .chunks_exact(SAMPLE_COUNT)
+            .map(|samples| {
+                let mut sum = [0u32; 4];
+                for sample in samples {
+                    for (s, c) in sum.iter_mut().zip(sample) {
+                        *s += u32::from(*c);
+                    }
+                }
+                sum.map(|s| ((s + SAMPLE_COUNT as u32 / 2) / SAMPLE_COUNT as u32) as u8)
+            })
+            .collect();
+        Pixels {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    pub fn draw_commands(&mut self, commands: &CommandList, meshes: &[Mesh]) {
+        self.draw_commands_in_layer(commands, meshes, None);
+    }
+
+    /// Draw commands onto this surface, which is nested in `nearest_layer`.
+    ///
+    /// The alpha and erase blend modes apply to the nearest layer, or this
+    /// surface if it's a layer itself.
+    fn draw_commands_in_layer(
+        &mut self,
+        commands: &CommandList,
+        meshes: &[Mesh],
+        nearest_layer: Option<&Surface>,
+    ) {
+        commands.execute(&mut CommandRenderer {
+            surface: self,
+            meshes,
+            nearest_layer,
+        });
+    }
+
+    /// Rasterize a triangle given in pixels.
+    ///
+    /// `shade` is called at most once per pixel with the center of that
+    /// pixel, and returns a premultiplied color for all of its covered samples.
+    fn fill_triangle(&mut self, points: [(f64, f64); 3], shade: &dyn Fn(f64, f64) -> [f32; 4]) {
+        let [p0, mut p1, mut p2] = points;
+        let area = (p1.0 - p0.0) * (p2.1 - p0.1) - (p1.1 - p0.1) * (p2.0 - p0.0);
+        if area == 0.0 || !area.is_finite() {
+            return;
+        }
+        if area < 0.0 {
+            std::mem::swap(&mut p1, &mut p2);
+        }
+        let edges = [(p0, p1), (p1, p2), (p2, p0)];
+
+        let (clip_x_min, clip_y_min, clip_x_max, clip_y_max) = self.clip;
+        let x_min = (p0.0.min(p1.0).min(p2.0).floor().max(0.0) as u32).max(clip_x_min);
+        let y_min = (p0.1.min(p1.1).min(p2.1).floor().max(0.0) as u32).max(clip_y_min);
+        let x_max = (p0.0.max(p1.0).max(p2.0).ceil().max(0.0) as u32).min(clip_x_max);
+        let y_max = (p0.1.max(p1.1).max(p2.1).ceil().max(0.0) as u32).min(clip_y_max);
+
+        for y in y_min..y_max {
+            for x in x_min..x_max {
+                let mut covered = [false; SAMPLE_COUNT];
+                for (covered, (sx, sy)) in covered.iter_mut().zip(SAMPLE_OFFSETS) {
+                    let (px, py) = (f64::from(x) + sx, f64::from(y) + sy);
+                    *covered = edges.iter().all(|&(a, b)| {
+                        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+                        let w = dx * (py - a.1) - dy * (px - a.0);
+                        // Samples exactly on an edge belong to only one of the
+                        // two triangles sharing it.
+                        w > 0.0 || (w == 0.0 && (dy > 0.0 || (dy == 0.0 && dx < 0.0)))
+                    });
+                }
+                if covered.contains(&true) {
+                    let index = (y as usize * self.width as usize + x as usize) * SAMPLE_COUNT;
+                    self.write_samples(index, covered, || {
+                        shade(f64::from(x) + 0.5, f64::from(y) + 0.5)
+                    });
+                }
+            }
+        }
+    }
+
+    /// Draw a color onto the covered samples starting at `index`, subject to the
+    /// current mask state.
+    fn write_samples(
+        &mut self,
+        index: usize,
+        covered: [bool; SAMPLE_COUNT],
+        color: impl FnOnce() -> [f32; 4],
+    ) {
+        let mut color = Some(color);
+        let mut src = [0.0; 4];
+        for (i, covered) in covered.into_iter().enumerate() {
+            if !covered {
+                continue;
+            }
+            let stencil = &mut self.stencil[index + i];
+            match self.mask_state {
+                MaskState::DrawMaskStencil => {
+                    if *stencil == self.num_masks - 1 {
+                        *stencil += 1;
+                    }
+                    continue;
+                }
+                MaskState::ClearMaskStencil => {
+                    if *stencil == self.num_masks {
+                        *stencil -= 1;
+                    }
+                    continue;
+                }
+                MaskState::DrawMaskedContent if *stencil != self.num_masks => continue,
+                _ => {}
+            }
+
+            if let Some(color) = color.take() {
+                src = color();
+            }
+            let sample = &mut self.samples[index + i];
+            *sample = to_u8(blend(src, to_f32(*sample), BlendMode::Normal));
+        }
+    }
+
+    /// Create a transparent surface of the same size and clip as this one.
+    fn new_layer(&self) -> Surface {
+        let mut layer = Surface::new(self.width, self.height);
+        layer.clip = self.clip;
+        layer
+    }
+
+    /// Apply a color transform to every visible sample within the clip.
+    fn apply_color_transform(&mut self, color_transform: &ColorTransform) {
+        for row in self.clip_rows() {
+            for sample in &mut self.samples[row] {
+                if sample[3] > 0 {
+                    let color = unmultiply(to_f32(*sample));
+                    *sample = to_u8(apply_color_transform(color, color_transform));
+                }
+            }
+        }
+    }
+
+    /// Multiply every sample within the clip by the alpha of the matching sample of `mask`.
+    fn apply_alpha_mask(&mut self, mask: &Surface) {
+        for row in self.clip_rows() {
+            for (sample, mask) in self.samples[row.clone()].iter_mut().zip(&mask.samples[row]) {
+                let alpha = f32::from(mask[3]) / 255.0;
+                *sample = to_u8(to_f32(*sample).map(|c| c * alpha));
+            }
+        }
+    }
+
+    /// Composite the clipped part of another surface of the same size onto this one.
+    ///
+    /// If a `backdrop` is given, the visible parts of the layer are blended with
+    /// it instead of this surface.
+    fn composite(&mut self, layer: &Surface, blend_mode: BlendMode, backdrop: Option<&Surface>) {
+        for index in self.clip_rows().flatten() {
+            let src = &layer.samples[index];
+            let stencil = &mut self.stencil[index];
+            // Only the visible parts of the layer take part in a mask.
+            match self.mask_state {
+                MaskState::DrawMaskStencil => {
+                    if src[3] > 0 && *stencil == self.num_masks - 1 {
+                        *stencil += 1;
+                    }
+                    continue;
+                }
+                MaskState::ClearMaskStencil => {
+                    if src[3] > 0 && *stencil == self.num_masks {
+                        *stencil -= 1;
+                    }
+                    continue;
+                }
+                MaskState::DrawMaskedContent if *stencil != self.num_masks => continue,
+                _ => {}
+            }
+            let sample = &mut self.samples[index];
+            let dst = match backdrop {
+                Some(backdrop) if src[3] > 0 => backdrop.samples[index],
+                _ => *sample,
+            };
+            *sample = to_u8(blend(to_f32(*src), to_f32(dst), blend_mode));
+        }
+    }
+}
+
+struct CommandRenderer<'a> {
+    surface: &'a mut Surface,
+    meshes: &'a [Mesh],
+
+    /// The layer that `surface` is nested in, if it isn't a layer itself.
+    nearest_layer: Option<&'a Surface>,
+}
+
+impl<'a> CommandRenderer<'a> {
+    /// Fill a unit square transformed by `matrix`. The shader is given
+    /// coordinates within the square.
+    fn fill_quad(&mut self, matrix: &Matrix, shade: &dyn Fn(f32, f32) -> [f32; 4]) {
+        let matrix = Affine::from(matrix);
+        let inverse = match matrix.inverse() {
+            Some(inverse) => inverse,
+            None => return,
+        };
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].map(|p| matrix.apply(p));
+        let shade = |x, y| {
+            let (u, v) = inverse.apply((x, y));
+            shade(u as f32, v as f32)
+        };
+        self.surface
+            .fill_triangle([corners[0], corners[1], corners[2]], &shade);
+        self.surface
+            .fill_triangle([corners[0], corners[2], corners[3]], &shade);
+    }
+}
+
+impl<'a> CommandHandler for CommandRenderer<'a> {
+    fn render_bitmap(&mut self, bitmap: &BitmapHandle, transform: &Transform, smoothing: bool) {
+        let pixels = as_bitmap(bitmap).pixels();
+        let matrix = transform.matrix
+            * Matrix {
+                a: pixels.width as f32,
+                d: pixels.height as f32,
+                ..Default::default()
+            };
+        let color_transform = &transform.color_transform;
+        self.fill_quad(&matrix, &|u, v| {
+            let color = pixels.sample(u, v, smoothing, false);
+            apply_color_transform(unmultiply(color), color_transform)
+        });
+    }
+
+    fn render_bitmap_3d(
+        &mut self,
+        bitmap: &BitmapHandle,
+        matrix: &Matrix3D,
+        color_transform: &ColorTransform,
+        smoothing: bool,
+    ) {
+        let pixels = as_bitmap(bitmap).pixels();
+        let matrix = *matrix * Matrix3D::scale(pixels.width.into(), pixels.height.into(), 1.0);
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .map(|(u, v)| matrix.project_point(u, v, 0.0));
+        // Bitmaps that cross the plane of the viewer aren't clipped, so they aren't drawn.
+        let corners = match corners {
+            [Some(p0), Some(p1), Some(p2), Some(p3)] => [p0, p1, p2, p3],
+            _ => return,
+        };
+        let inverse = match Projective::from(&matrix).inverse() {
+            Some(inverse) => inverse,
+            None => return,
+        };
+        let shade = |x, y| {
+            let (u, v) = inverse.apply((x, y));
+            let color = pixels.sample(u as f32, v as f32, smoothing, false);
+            apply_color_transform(unmultiply(color), color_transform)
+        };
+        self.surface
+            .fill_triangle([corners[0], corners[1], corners[2]], &shade);
+        self.surface
+            .fill_triangle([corners[0], corners[2], corners[3]], &shade);
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
+        let matrix = Affine::from(&transform.matrix);
+        let inverse = match matrix.inverse() {
+            Some(inverse) => inverse,
+            None => return,
+        };
+        let color_transform = &transform.color_transform;
+        let is_mask = matches!(
+            self.surface.mask_state,
+            MaskState::DrawMaskStencil | MaskState::ClearMaskStencil
+        );
+
+        let meshes = self.meshes;
+        let mesh = &meshes[shape.0];
+        for draw in &mesh.draws {
+            let num_indices = if is_mask {
+                // Omit strokes when drawing a mask stencil.
+                draw.mask_index_count as usize
+            } else {
+                draw.indices.len()
+            };
+            let pixels = match &draw.paint {
+                Paint::Bitmap { bitmap, .. } => Some(as_bitmap(bitmap).pixels()),
+                _ => None,
+            };
+
+            for triangle in draw.indices[..num_indices].chunks_exact(3) {
+                let vertices = [0, 1, 2].map(|i| &draw.vertices[triangle[i] as usize]);
+                let points = vertices.map(|v| matrix.apply((f64::from(v.x), f64::from(v.y))));
+                let vertex_color = &vertices[0].color;
+                let shade = |x, y| {
+                    let (x, y) = inverse.apply((x, y));
+                    let (x, y) = (x as f32, y as f32);
+                    let color = match &draw.paint {
+                        Paint::Color => [
+                            f32::from(vertex_color.r) / 255.0,
+                            f32::from(vertex_color.g) / 255.0,
+                            f32::from(vertex_color.b) / 255.0,
+                            f32::from(vertex_color.a) / 255.0,
+                        ],
+                        Paint::Gradient(gradient) => gradient_color(gradient, x, y),
+                        Paint::Bitmap {
+                            matrix,
+                            smoothing,
+                            repeating,
+                            ..
+                        } => {
+                            let (u, v) = texture_coords(matrix, x, y);
+                            let pixels = pixels.as_ref().unwrap();
+                            unmultiply(pixels.sample(u, v, *smoothing, *repeating))
+                        }
+                    };
+                    apply_color_transform(color, color_transform)
+                };
+                self.surface.fill_triangle(points, &shade);
+            }
+        }
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: &Matrix) {
+        let color = premultiply([
+            f32::from(color.r) / 255.0,
+            f32::from(color.g) / 255.0,
+            f32::from(color.b) / 255.0,
+            f32::from(color.a) / 255.0,
+        ]);
+        self.fill_quad(matrix, &|_, _| color);
+    }
+
+    fn push_mask(&mut self) {
+        debug_assert!(
+            self.surface.mask_state == MaskState::NoMask
+                || self.surface.mask_state == MaskState::DrawMaskedContent
+        );
+        self.surface.num_masks += 1;
+        self.surface.mask_state = MaskState::DrawMaskStencil;
+    }
+
+    fn activate_mask(&mut self) {
+        debug_assert!(
+            self.surface.num_masks > 0 && self.surface.mask_state == MaskState::DrawMaskStencil
+        );
+        self.surface.mask_state = MaskState::DrawMaskedContent;
+    }
+
+    fn deactivate_mask(&mut self) {
+        debug_assert!(
+            self.surface.num_masks > 0 && self.surface.mask_state == MaskState::DrawMaskedContent
+        );
+        self.surface.mask_state = MaskState::ClearMaskStencil;
+    }
+
+    fn pop_mask(&mut self) {
+        debug_assert!(
+            self.surface.num_masks > 0 && self.surface.mask_state == MaskState::ClearMaskStencil
+        );
+        self.surface.num_masks -= 1;
+        self.surface.mask_state = if self.surface.num_masks == 0 {
+            MaskState::NoMask
+        } else {
+            MaskState::DrawMaskedContent
+        };
+    }
+
+    fn blend(
+        &mut self,
+        commands: &CommandList,
+        blend_mode: BlendMode,
+        color_transform: &ColorTransform,
+    ) {
+        let mut layer = self.surface.new_layer();
+        let nearest_layer = if blend_mode == BlendMode::Layer {
+            None
+        } else {
+            Some(self.nearest_layer.unwrap_or(&*self.surface))
+        };
+        layer.draw_commands_in_layer(commands, self.meshes, nearest_layer);
+        if *color_transform != ColorTransform::IDENTITY {
+            layer.apply_color_transform(color_transform);
+        }
+        let backdrop = match blend_mode {
+            BlendMode::Alpha | BlendMode::Erase => self.nearest_layer,
+            _ => None,
+        };
+        self.surface.composite(&layer, blend_mode, backdrop);
+    }
+
+    fn alpha_mask(&mut self, maskee: &CommandList, mask: &CommandList) {
+        let mut layer = self.surface.new_layer();
+        layer.draw_commands(maskee, self.meshes);
+        let mut mask_layer = self.surface.new_layer();
+        mask_layer.draw_commands(mask, self.meshes);
+        layer.apply_alpha_mask(&mask_layer);
+        self.surface.composite(&layer, BlendMode::Normal, None);
+    }
+}
+
+/// A 2D affine transform in pixels.
+#[derive(Clone, Copy, Debug)]
+struct Affine {
+    a: f64,
+    b: f64,
+    c: f64,
+    d: f64,
+    tx: f64,
+    ty: f64,
+}
+
+impl From<&Matrix> for Affine {
+    fn from(matrix: &Matrix) -> Self {
+        Self {
+            a: f64::from(matrix.a),
+            b: f64::from(matrix.b),
+            c: f64::from(matrix.c),
+            d: f64::from(matrix.d),
+            tx: matrix.tx.to_pixels(),
+            ty: matrix.ty.to_pixels(),
+        }
+    }
+}
+
+impl Affine {
+    fn apply(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            self.a * x + self.c * y + self.tx,
+            self.b * x + self.d * y + self.ty,
+        )
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        Some(Self {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            tx: (self.c * self.ty - self.d * self.tx) / det,
+            ty: (self.b * self.tx - self.a * self.ty) / det,
+        })
+    }
+}
+
+/// A 2D projective transform in pixels, stored as a 3x3 matrix of rows.
+#[derive(Clone, Copy, Debug)]
+struct Projective([[f64; 3]; 3]);
+
+impl From<&Matrix3D> for Projective {
+    /// Takes the part of a 3D matrix that applies to points with a depth of 0,
+    /// ignoring the depth of the result.
+    fn from(matrix: &Matrix3D) -> Self {
+        Self([0, 1, 3].map(|row| [0, 1, 3].map(|column| matrix.get(row, column))))
+    }
+}
+
+impl Projective {
+    fn apply(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let [r0, r1, r2] = self.0.map(|r| r[0] * x + r[1] * y + r[2]);
+        (r0 / r2, r1 / r2)
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        let [[a, b, c], [d, e, f], [g, h, i]] = self.0;
+        let cofactors = [
+            [e * i - f * h, c * h - b * i, b * f - c * e],
+            [f * g - d * i, a * i - c * g, c * d - a * f],
+            [d * h - e * g, b * g - a * h, a * e - b * d],
+        ];
+        let det = a * cofactors[0][0] + b * cofactors[1][0] + c * cofactors[2][0];
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        Some(Self(cofactors.map(|row| row.map(|n| n / det))))
+    }
+}
+
+/// Apply a color transform to a straight color, returning a premultiplied color.
+fn apply_color_transform(color: [f32; 4], color_transform: &ColorTransform) -> [f32; 4] {
+    let mult = color_transform.mult_rgba_normalized();
+    let add = color_transform.add_rgba_normalized();
+    premultiply([0, 1, 2, 3].map(|i| (color[i] * mult[i] + add[i]).clamp(0.0, 1.0)))
+}
+
+fn premultiply(color: [f32; 4]) -> [f32; 4] {
+    let a = color[3];
+    [color[0] * a, color[1] * a, color[2] * a, a]
+}
+
+fn unmultiply(color: [f32; 4]) -> [f32; 4] {
+    let a = color[3];
+    if a > 0.0 {
+        [color[0] / a, color[1] / a, color[2] / a, a]
+    } else {
+        [0.0; 4]
+    }
+}
+
+fn to_f32(color: [u8; 4]) -> [f32; 4] {
+    color.map(|c| f32::from(c) / 255.0)
+}
+
+fn to_u8(color: [f32; 4]) -> [u8; 4] {
+    color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+/// Blend a premultiplied source color onto a premultiplied destination color.
+fn blend(src: [f32; 4], dst: [f32; 4], blend_mode: BlendMode) -> [f32; 4] {
+    let [sr, sg, sb, sa] = src;
+    let [dr, dg, db, da] = dst;
+    let over = sa + da * (1.0 - sa);
+    let complex = |f: fn(f32, f32) -> f32| {
+        if sa <= 0.0 {
+            return dst;
+        }
+        let channel = |s: f32, d: f32| {
+            let d_straight = if da > 0.0 { d / da } else { 0.0 };
+            s * (1.0 - da) + d * (1.0 - sa) + sa * da * f(s / sa, d_straight)
+        };
+        [channel(sr, dr), channel(sg, dg), channel(sb, db), over]
+    };
+    match blend_mode {
+        BlendMode::Normal | BlendMode::Layer => [
+            sr + dr * (1.0 - sa),
+            sg + dg * (1.0 - sa),
+            sb + db * (1.0 - sa),
+            over,
+        ],
+        BlendMode::Add => [sr + dr, sg + dg, sb + db, over],
+        BlendMode::Subtract => [dr - sr, dg - sg, db - sb, over],
+        BlendMode::Multiply => complex(|s, d| s * d),
+        BlendMode::Screen => complex(|s, d| s + d - s * d),
+        BlendMode::Lighten => complex(f32::max),
+        BlendMode::Darken => complex(f32::min),
+        BlendMode::Difference => complex(|s, d| (d - s).abs()),
+        BlendMode::Invert => complex(|_, d| 1.0 - d),
+        BlendMode::HardLight => complex(hard_light),
+        BlendMode::Overlay => complex(|s, d| hard_light(d, s)),
+        BlendMode::Alpha if sa > 0.0 => [dr * sa, dg * sa, db * sa, da * sa],
+        BlendMode::Erase if sa > 0.0 => [
+            dr * (1.0 - sa),
+            dg * (1.0 - sa),
+            db * (1.0 - sa),
+            da * (1.0 - sa),
+        ],
+        BlendMode::Alpha | BlendMode::Erase => dst,
+    }
+}
+
+fn hard_light(s: f32, d: f32) -> f32 {
+    if s <= 0.5 {
+        2.0 * s * d
+    } else {
+        1.0 - 2.0 * (1.0 - d) * (1.0 - s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::Twips;
+
+    const RED: Color = Color {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+
+    fn pixel(surface: &Surface, x: u32, y: u32) -> [u8; 4] {
+        surface.resolve().data[(y * surface.width() + x) as usize]
+    }
+
+    #[test]
+    fn draw_rect() {
+        let mut surface = Surface::new(4, 4);
+        let mut commands = CommandList::new();
+        commands.draw_rect(
+            RED,
+            &Matrix::create_box(2.0, 2.0, 0.0, Twips::from_pixels(1.0), Twips::ZERO),
+        );
+        surface.draw_commands(&commands, &[]);
+        assert_eq!(pixel(&surface, 0, 0), [0, 0, 0, 0]);
+        assert_eq!(pixel(&surface, 1, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(&surface, 2, 1), [255, 0, 0, 255]);
+        assert_eq!(pixel(&surface, 3, 2), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn clipped_redraw() {
+        let mut surface = Surface::new(4, 1);
+        let mut commands = CommandList::new();
+        commands.draw_rect(
+            RED,
+            &Matrix::create_box(4.0, 1.0, 0.0, Twips::ZERO, Twips::ZERO),
+        );
+        surface.draw_commands(&commands, &[]);
+
+        // Only the clipped pixels are cleared and drawn again.
+        surface.set_clip(Some((1, 0, 3, 1)));
+        surface.clear(Color::WHITE);
+        let mut commands = CommandList::new();
+        commands.blend(
+            &CommandList::new(),
+            BlendMode::Normal,
+            &ColorTransform::IDENTITY,
+        );
+        commands.draw_rect(
+            Color::BLACK,
+            &Matrix::create_box(3.0, 1.0, 0.0, Twips::ZERO, Twips::ZERO),
+        );
+        surface.draw_commands(&commands, &[]);
+        surface.set_clip(None);
+        assert_eq!(pixel(&surface, 0, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(&surface, 1, 0), [0, 0, 0, 255]);
+        assert_eq!(pixel(&surface, 2, 0), [0, 0, 0, 255]);
+        assert_eq!(pixel(&surface, 3, 0), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn masked_rect() {
+        let mut surface = Surface::new(4, 1);
+        let mask = Matrix::create_box(2.0, 1.0, 0.0, Twips::ZERO, Twips::ZERO);
+        let content = Matrix::create_box(4.0, 1.0, 0.0, Twips::ZERO, Twips::ZERO);
+        let mut commands = CommandList::new();
+        commands.push_mask();
+        commands.draw_rect(Color::WHITE, &mask);
+        commands.activate_mask();
+        commands.draw_rect(RED, &content);
+        commands.deactivate_mask();
+        commands.draw_rect(Color::WHITE, &mask);
+        commands.pop_mask();
+        surface.draw_commands(&commands, &[]);
+        assert_eq!(pixel(&surface, 1, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(&surface, 2, 0), [0, 0, 0, 0]);
+        assert!(surface.stencil.iter().all(|s| *s == 0));
+    }
+
+    #[test]
+    fn alpha_mask() {
+        let mut surface = Surface::new(4, 1);
+        let mut mask = CommandList::new();
+        mask.draw_rect(
+            Color::from_rgba(0x80FFFFFF),
+            &Matrix::create_box(2.0, 1.0, 0.0, Twips::ZERO, Twips::ZERO),
+        );
+        let mut maskee = CommandList::new();
+        maskee.draw_rect(
+            RED,
+            &Matrix::create_box(4.0, 1.0, 0.0, Twips::ZERO, Twips::ZERO),
+        );
+        let mut commands = CommandList::new();
+        commands.alpha_mask(&maskee, &mask);
+        surface.draw_commands(&commands, &[]);
+        assert_eq!(pixel(&surface, 1, 0), [128, 0, 0, 128]);
+        assert_eq!(pixel(&surface, 2, 0), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn alpha_blend_applies_to_nearest_layer() {
+        let unit_box = Matrix::create_box(1.0, 1.0, 0.0, Twips::ZERO, Twips::ZERO);
+        let mut alpha = CommandList::new();
+        alpha.draw_rect(Color::from_rgba(0x80FFFFFF), &unit_box);
+        let mut subtract = CommandList::new();
+        subtract.blend(&alpha, BlendMode::Alpha, &ColorTransform::IDENTITY);
+        let mut layer = CommandList::new();
+        layer.draw_rect(Color::from_rgba(0xFF00FF00), &unit_box);
+        layer.blend(&subtract, BlendMode::Subtract, &ColorTransform::IDENTITY);
+        let mut commands = CommandList::new();
+        commands.blend(&layer, BlendMode::Layer, &ColorTransform::IDENTITY);
+
+        let mut surface = Surface::new(1, 1);
+        surface.draw_commands(&commands, &[]);
+        // The alpha blend is applied to the green of the enclosing layer,
+        // not to the empty subtract group, and the result is subtracted.
+        assert_eq!(pixel(&surface, 0, 0), [0, 127, 0, 255]);
+    }
+
+    #[test]
+    fn layer_color_transform() {
+        let mut surface = Surface::new(3, 1);
+        let mut layer = CommandList::new();
+        for x in [0.0, 1.0] {
+            layer.draw_rect(
+                RED,
+                &Matrix::create_box(2.0, 1.0, 0.0, Twips::from_pixels(x), Twips::ZERO),
+            );
+        }
+        let mut color_transform = ColorTransform::IDENTITY;
+        color_transform.set_mult_color(&Color::from_rgba(0x80FFFFFF));
+        let mut commands = CommandList::new();
+        commands.blend(&layer, BlendMode::Layer, &color_transform);
+        surface.draw_commands(&commands, &[]);
+        // Overlapping draws inside of the layer don't accumulate alpha.
+        assert_eq!(pixel(&surface, 0, 0), [128, 0, 0, 128]);
+        assert_eq!(pixel(&surface, 1, 0), [128, 0, 0, 128]);
+        assert_eq!(pixel(&surface, 2, 0), [128, 0, 0, 128]);
+    }
+
+    #[test]
+    fn bitmap_3d() {
+        let bitmap = BitmapHandle(std::sync::Arc::new(crate::bitmap::SoftwareBitmap::new(
+            ruffle_render::bitmap::Bitmap::new(
+                2,
+                2,
+                ruffle_render::bitmap::BitmapFormat::Rgba,
+                [255, 0, 0, 255].repeat(4),
+            ),
+        )));
+        // A 2x2 bitmap scaled to 8x8, then rotated away from the viewer around its
+        // left edge. The right edge recedes and shrinks towards the center.
+        let matrix = Matrix3D::perspective(8.0, 0.0, 4.0)
+            * Matrix3D::rotate_y(-std::f64::consts::FRAC_PI_4)
+            * Matrix3D::scale(4.0, 4.0, 1.0);
+        let mut surface = Surface::new(8, 8);
+        let mut commands = CommandList::new();
+        commands.render_bitmap_3d(&bitmap, &matrix, &ColorTransform::IDENTITY, false);
+        surface.draw_commands(&commands, &[]);
+        assert_eq!(pixel(&surface, 0, 3), [255, 0, 0, 255]);
+        assert_eq!(pixel(&surface, 2, 4), [255, 0, 0, 255]);
+        assert_eq!(pixel(&surface, 2, 0), [0, 0, 0, 0]);
+        assert_eq!(pixel(&surface, 5, 4), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn blend_modes() {
+        let src = [0.5, 0.25, 0.0, 0.5];
+        let dst = [0.0, 0.0, 1.0, 1.0];
+        assert_eq!(blend(src, dst, BlendMode::Normal), [0.5, 0.25, 0.5, 1.0]);
+        assert_eq!(blend(src, dst, BlendMode::Add), [0.5, 0.25, 1.0, 1.0]);
+        assert_eq!(blend(src, dst, BlendMode::Multiply), [0.0, 0.0, 0.5, 1.0]);
+        assert_eq!(blend(src, dst, BlendMode::Alpha), [0.0, 0.0, 0.5, 0.5]);
+        assert_eq!(blend(src, dst, BlendMode::Erase), [0.0, 0.0, 0.5, 0.5]);
+        assert_eq!(blend([0.0; 4], dst, BlendMode::Difference), dst);
+    }
+}
@@ -1,6 +1,7 @@
 pub mod null;
 
 use crate::bitmap::{Bitmap, BitmapHandle, BitmapSource};
+use crate::bounding_box::BoundingBox;
 use crate::commands::CommandList;
 use crate::error::Error;
 use crate::shape_utils::DistilledShape;
@@ -47,6 +48,19 @@ pub trait RenderBackend: Downcast {
 
     fn submit_frame(&mut self, clear: swf::Color, commands: CommandList);
 
+    /// Draws a frame of which only the pixels within `dirty_rects` changed since the last
+    /// one. The rectangles are in twips of the viewport and don't overlap.
+    ///
+    /// Backends that can't keep the last frame around draw the whole frame instead.
+    fn submit_frame_partial(
+        &mut self,
+        clear: swf::Color,
+        commands: CommandList,
+        _dirty_rects: &[BoundingBox],
+    ) {
+        self.submit_frame(clear, commands)
+    }
+
     fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error>;
     fn update_texture(
         &mut self,
@@ -1,11 +1,24 @@
 use crate::backend::ShapeHandle;
 use crate::bitmap::BitmapHandle;
+use crate::color_transform::ColorTransform;
 use crate::matrix::Matrix;
+use crate::matrix3d::Matrix3D;
 use crate::transform::Transform;
 use swf::{BlendMode, Color};
 
 pub trait CommandHandler {
     fn render_bitmap(&mut self, bitmap: &BitmapHandle, transform: &Transform, smoothing: bool);
+
+    /// Draws a bitmap with a perspective projection. `matrix` maps pixels of the
+    /// bitmap to pixels on screen, and its depth output is ignored.
+    fn render_bitmap_3d(
+        &mut self,
+        bitmap: &BitmapHandle,
+        matrix: &Matrix3D,
+        color_transform: &ColorTransform,
+        smoothing: bool,
+    );
+
     fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform);
     fn draw_rect(&mut self, color: Color, matrix: &Matrix);
     fn push_mask(&mut self);
@@ -13,7 +26,17 @@ pub trait CommandHandler {
     fn deactivate_mask(&mut self);
     fn pop_mask(&mut self);
 
-    fn blend(&mut self, commands: &CommandList, blend_mode: BlendMode);
+    /// Draws `commands` into an isolated transparent layer, which is then composited
+    /// with `blend_mode`. The color transform is applied once to the whole layer.
+    fn blend(
+        &mut self,
+        commands: &CommandList,
+        blend_mode: BlendMode,
+        color_transform: &ColorTransform,
+    );
+
+    /// Draws `maskee` with its alpha multiplied by the alpha of `mask`.
+    fn alpha_mask(&mut self, maskee: &CommandList, mask: &CommandList);
 }
 
 #[derive(Debug, Default, Clone)]
@@ -34,6 +57,12 @@ impl CommandList {
                     transform,
                     smoothing,
                 } => handler.render_bitmap(bitmap, &transform, *smoothing),
+                Command::RenderBitmap3D {
+                    bitmap,
+                    matrix,
+                    color_transform,
+                    smoothing,
+                } => handler.render_bitmap_3d(bitmap, matrix, color_transform, *smoothing),
                 Command::RenderShape { shape, transform } => {
                     handler.render_shape(*shape, &transform)
                 }
@@ -42,10 +71,72 @@ impl CommandList {
                 Command::ActivateMask => handler.activate_mask(),
                 Command::DeactivateMask => handler.deactivate_mask(),
                 Command::PopMask => handler.pop_mask(),
-                Command::Blend(commands, blend_mode) => handler.blend(commands, *blend_mode),
+                Command::Blend(commands, blend_mode, color_transform) => {
+                    handler.blend(commands, *blend_mode, color_transform)
+                }
+                Command::AlphaMask { maskee, mask } => handler.alpha_mask(maskee, mask),
             }
         }
     }
+
+    /// Returns a copy of these commands with `color_transform` applied on top of
+    /// the color of everything they draw.
+    ///
+    /// This is used by backends that can't draw into layers to emulate the color
+    /// transform of a layer.
+    pub fn with_color_transform(&self, color_transform: &ColorTransform) -> Self {
+        let commands = self
+            .commands
+            .iter()
+            .map(|command| match command {
+                Command::RenderBitmap {
+                    bitmap,
+                    transform,
+                    smoothing,
+                } => Command::RenderBitmap {
+                    bitmap: bitmap.clone(),
+                    transform: Transform {
+                        matrix: transform.matrix,
+                        color_transform: *color_transform * transform.color_transform,
+                    },
+                    smoothing: *smoothing,
+                },
+                Command::RenderBitmap3D {
+                    bitmap,
+                    matrix,
+                    color_transform: bitmap_color_transform,
+                    smoothing,
+                } => Command::RenderBitmap3D {
+                    bitmap: bitmap.clone(),
+                    matrix: *matrix,
+                    color_transform: *color_transform * *bitmap_color_transform,
+                    smoothing: *smoothing,
+                },
+                Command::RenderShape { shape, transform } => Command::RenderShape {
+                    shape: *shape,
+                    transform: Transform {
+                        matrix: transform.matrix,
+                        color_transform: *color_transform * transform.color_transform,
+                    },
+                },
+                Command::DrawRect { color, matrix } => Command::DrawRect {
+                    color: *color_transform * color.clone(),
+                    matrix: *matrix,
+                },
+                Command::Blend(commands, blend_mode, layer_color_transform) => Command::Blend(
+                    commands.clone(),
+                    *blend_mode,
+                    *color_transform * *layer_color_transform,
+                ),
+                Command::AlphaMask { maskee, mask } => Command::AlphaMask {
+                    maskee: maskee.with_color_transform(color_transform),
+                    mask: mask.clone(),
+                },
+                command => command.clone(),
+            })
+            .collect();
+        Self { commands }
+    }
 }
 
 impl CommandHandler for CommandList {
@@ -57,6 +148,21 @@ impl CommandHandler for CommandList {
         });
     }
 
+    fn render_bitmap_3d(
+        &mut self,
+        bitmap: &BitmapHandle,
+        matrix: &Matrix3D,
+        color_transform: &ColorTransform,
+        smoothing: bool,
+    ) {
+        self.commands.push(Command::RenderBitmap3D {
+            bitmap: bitmap.clone(),
+            matrix: *matrix,
+            color_transform: *color_transform,
+            smoothing,
+        });
+    }
+
     fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
         self.commands.push(Command::RenderShape {
             shape,
@@ -87,9 +193,24 @@ impl CommandHandler for CommandList {
         self.commands.push(Command::PopMask);
     }
 
-    fn blend(&mut self, commands: &CommandList, blend_mode: BlendMode) {
-        self.commands
-            .push(Command::Blend(commands.to_owned(), blend_mode));
+    fn blend(
+        &mut self,
+        commands: &CommandList,
+        blend_mode: BlendMode,
+        color_transform: &ColorTransform,
+    ) {
+        self.commands.push(Command::Blend(
+            commands.to_owned(),
+            blend_mode,
+            *color_transform,
+        ));
+    }
+
+    fn alpha_mask(&mut self, maskee: &CommandList, mask: &CommandList) {
+        self.commands.push(Command::AlphaMask {
+            maskee: maskee.to_owned(),
+            mask: mask.to_owned(),
+        });
     }
 }
 
@@ -100,6 +221,12 @@ pub enum Command {
         transform: Transform,
         smoothing: bool,
     },
+    RenderBitmap3D {
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    },
     RenderShape {
         shape: ShapeHandle,
         transform: Transform,
@@ -112,5 +239,9 @@ pub enum Command {
     ActivateMask,
     DeactivateMask,
     PopMask,
-    Blend(CommandList, BlendMode),
+    Blend(CommandList, BlendMode, ColorTransform),
+    AlphaMask {
+        maskee: CommandList,
+        mask: CommandList,
+    },
 }
@@ -0,0 +1,275 @@
+//! Rasterization of glyphs for advanced text anti-aliasing.
+//!
+//! Text using the "advanced" anti-aliasing engine is rendered as coverage
+//! bitmaps rather than as tessellated shapes, so that its thickness and
+//! sharpness can be adjusted and its edges can be fitted to the pixel grid.
+
+use crate::bitmap::{Bitmap, BitmapFormat};
+use crate::matrix::Matrix;
+use crate::shape_utils::{swf_glyph_to_shape, DistilledShape, DrawCommand, DrawPath};
+use swf::Twips;
+
+/// The number of sample rows taken per pixel when computing coverage.
+/// Horizontal coverage is computed exactly for each row.
+const SAMPLES_PER_PIXEL: usize = 4;
+
+/// The largest bitmap that will be rasterized for a single glyph, in pixels.
+const MAX_GLYPH_PIXELS: usize = 512 * 512;
+
+/// Adjustments applied to the coverage of a rasterized glyph.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GlyphRasterSettings {
+    /// The thickness of glyph strokes, between -200 and 200.
+    /// Positive values make glyphs bolder, negative values make them thinner.
+    pub thickness: f32,
+
+    /// The sharpness of glyph edges, between -400 and 400.
+    /// Positive values make edges crisper, negative values make them softer.
+    pub sharpness: f32,
+}
+
+impl GlyphRasterSettings {
+    /// Apply the thickness and sharpness adjustments to a coverage value.
+    fn adjust_coverage(&self, coverage: f32) -> f32 {
+        let mut coverage = coverage.clamp(0.0, 1.0);
+        if self.thickness != 0.0 {
+            coverage = coverage.powf(2.0f32.powf(-self.thickness / 100.0));
+        }
+        if self.sharpness != 0.0 && coverage > 0.0 && coverage < 1.0 {
+            // A contrast curve which keeps fully covered and uncovered pixels as they are.
+            let k = 2.0f32.powf(self.sharpness / 200.0);
+            let inside = coverage.powf(k);
+            let outside = (1.0 - coverage).powf(k);
+            coverage = inside / (inside + outside);
+        }
+        coverage
+    }
+}
+
+/// A glyph rendered into a bitmap.
+#[derive(Clone, Debug)]
+pub struct RasterizedGlyph {
+    /// A white bitmap with premultiplied alpha, where the alpha of each pixel
+    /// is how much of it is covered by the glyph.
+    pub bitmap: Bitmap,
+
+    /// The position of the top-left corner of the bitmap, in pixels relative
+    /// to the origin of the matrix the glyph was rasterized with.
+    pub x: i32,
+    pub y: i32,
+}
+
+/// A line segment of a flattened glyph outline, in pixels.
+#[derive(Clone, Copy, Debug)]
+struct Edge {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+}
+
+/// Rasterize a glyph transformed by `matrix`.
+///
+/// Returns `None` if the glyph has no outline, or is too large to rasterize.
+pub fn rasterize_glyph(
+    glyph: &swf::Glyph,
+    matrix: &Matrix,
+    settings: GlyphRasterSettings,
+) -> Option<RasterizedGlyph> {
+    let shape = swf_glyph_to_shape(glyph);
+    let shape = DistilledShape::from(&shape);
+    let mut edges = vec![];
+    for path in &shape.paths {
+        if let DrawPath::Fill { commands, .. } = path {
+            flatten(commands, matrix, &mut edges);
+        }
+    }
+    if edges.is_empty() {
+        return None;
+    }
+
+    let (mut x_min, mut y_min) = (f32::MAX, f32::MAX);
+    let (mut x_max, mut y_max) = (f32::MIN, f32::MIN);
+    for edge in &edges {
+        x_min = x_min.min(edge.x0).min(edge.x1);
+        x_max = x_max.max(edge.x0).max(edge.x1);
+        y_min = y_min.min(edge.y0).min(edge.y1);
+        y_max = y_max.max(edge.y0).max(edge.y1);
+    }
+    let left = x_min.floor() as i32;
+    let top = y_min.floor() as i32;
+    let width = (x_max.ceil() as i32 - left).max(1) as usize;
+    let height = (y_max.ceil() as i32 - top).max(1) as usize;
+    if width * height > MAX_GLYPH_PIXELS {
+        return None;
+    }
+
+    let mut coverage = vec![0.0f32; width * height];
+    let mut crossings = vec![];
+    let weight = 1.0 / SAMPLES_PER_PIXEL as f32;
+    for (row, line) in coverage.chunks_exact_mut(width).enumerate() {
+        for sample in 0..SAMPLES_PER_PIXEL {
+            let y = (top + row as i32) as f32 + (sample as f32 + 0.5) * weight;
+            crossings.clear();
+            for edge in &edges {
+                if (edge.y0 <= y) != (edge.y1 <= y) {
+                    let t = (y - edge.y0) / (edge.y1 - edge.y0);
+                    crossings.push(edge.x0 + t * (edge.x1 - edge.x0) - left as f32);
+                }
+            }
+            crossings.sort_unstable_by(f32::total_cmp);
+
+            // Glyphs are filled using the even-odd rule.
+            for span in crossings.chunks_exact(2) {
+                add_span(line, span[0], span[1], weight);
+            }
+        }
+    }
+
+    let mut data = Vec::with_capacity(width * height * 4);
+    for value in coverage {
+        let alpha = (settings.adjust_coverage(value) * 255.0).round() as u8;
+        data.extend_from_slice(&[alpha, alpha, alpha, alpha]);
+    }
+
+    Some(RasterizedGlyph {
+        bitmap: Bitmap::new(width as u32, height as u32, BitmapFormat::Rgba, data),
+        x: left,
+        y: top,
+    })
+}
+
+/// Convert the draw commands of a fill into line segments in pixel space.
+fn flatten(commands: &[DrawCommand], matrix: &Matrix, edges: &mut Vec<Edge>) {
+    let pixels = |twips: f32| twips / Twips::TWIPS_PER_PIXEL as f32;
+    let transform = |x: Twips, y: Twips| {
+        let (x, y) = (x.get() as f32, y.get() as f32);
+        (
+            pixels(matrix.a * x + matrix.c * y + matrix.tx.get() as f32),
+            pixels(matrix.b * x + matrix.d * y + matrix.ty.get() as f32),
+        )
+    };
+    let mut push_edge = |from: (f32, f32), to: (f32, f32)| {
+        if from.1 != to.1 {
+            edges.push(Edge {
+                x0: from.0,
+                y0: from.1,
+                x1: to.0,
+                y1: to.1,
+            });
+        }
+    };
+
+    let mut start = (0.0, 0.0);
+    let mut cursor = (0.0, 0.0);
+    for command in commands {
+        match *command {
+            DrawCommand::MoveTo { x, y } => {
+                push_edge(cursor, start);
+                start = transform(x, y);
+                cursor = start;
+            }
+            DrawCommand::LineTo { x, y } => {
+                let point = transform(x, y);
+                push_edge(cursor, point);
+                cursor = point;
+            }
+            DrawCommand::CurveTo { x1, y1, x2, y2 } => {
+                let control = transform(x1, y1);
+                let end = transform(x2, y2);
+                let length = (control.0 - cursor.0).hypot(control.1 - cursor.1)
+                    + (end.0 - control.0).hypot(end.1 - control.1);
+                let segments = (length.sqrt().ceil() as usize).clamp(1, 16);
+                let mut previous = cursor;
+                for i in 1..=segments {
+                    let t = i as f32 / segments as f32;
+                    let u = 1.0 - t;
+                    let point = (
+                        u * u * cursor.0 + 2.0 * u * t * control.0 + t * t * end.0,
+                        u * u * cursor.1 + 2.0 * u * t * control.1 + t * t * end.1,
+                    );
+                    push_edge(previous, point);
+                    previous = point;
+                }
+                cursor = end;
+            }
+        }
+    }
+    push_edge(cursor, start);
+}
+
+/// Add the coverage of the horizontal span from `x0` to `x1` to a row of pixels.
+fn add_span(line: &mut [f32], x0: f32, x1: f32, weight: f32) {
+    let x0 = x0.max(0.0);
+    let x1 = x1.min(line.len() as f32);
+    if x1 <= x0 {
+        return;
+    }
+    let first = x0.floor() as usize;
+    let last = (x1.ceil() as usize).min(line.len());
+    for (i, value) in line[first..last].iter_mut().enumerate() {
+        let pixel = (first + i) as f32;
+        let covered = x1.min(pixel + 1.0) - x0.max(pixel);
+        *value += covered.max(0.0) * weight;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{Glyph, ShapeRecord, StyleChangeData};
+
+    fn square_glyph(size: i32) -> Glyph {
+        let edge = |dx: i32, dy: i32| ShapeRecord::StraightEdge {
+            delta_x: Twips::new(dx),
+            delta_y: Twips::new(dy),
+        };
+        Glyph {
+            shape_records: vec![
+                ShapeRecord::StyleChange(Box::new(StyleChangeData {
+                    move_to: Some((Twips::ZERO, Twips::ZERO)),
+                    fill_style_0: None,
+                    fill_style_1: Some(1),
+                    line_style: None,
+                    new_styles: None,
+                })),
+                edge(size, 0),
+                edge(0, size),
+                edge(-size, 0),
+                edge(0, -size),
+            ],
+            code: 0,
+            advance: size as i16,
+            bounds: None,
+        }
+    }
+
+    #[test]
+    fn rasterize_square() {
+        // A 4x4 pixel square, offset by half a pixel.
+        let glyph = square_glyph(80);
+        let matrix = Matrix::translate(Twips::new(10), Twips::ZERO);
+        let raster = rasterize_glyph(&glyph, &matrix, Default::default()).unwrap();
+        assert_eq!((raster.x, raster.y), (0, 0));
+        assert_eq!(raster.bitmap.width(), 5);
+        assert_eq!(raster.bitmap.height(), 4);
+        let alpha: Vec<u8> = raster.bitmap.data().chunks(4).map(|c| c[3]).collect();
+        assert_eq!(&alpha[0..5], &[128, 255, 255, 255, 128]);
+    }
+
+    #[test]
+    fn thickness_and_sharpness() {
+        let bold = GlyphRasterSettings {
+            thickness: 100.0,
+            sharpness: 0.0,
+        };
+        assert!(bold.adjust_coverage(0.5) > 0.5);
+        let sharp = GlyphRasterSettings {
+            thickness: 0.0,
+            sharpness: 200.0,
+        };
+        assert!(sharp.adjust_coverage(0.25) < 0.25);
+        assert_eq!(sharp.adjust_coverage(0.0), 0.0);
+        assert_eq!(sharp.adjust_coverage(1.0), 1.0);
+    }
+}
@@ -3,7 +3,9 @@ pub mod bitmap;
 pub mod bounding_box;
 pub mod color_transform;
 pub mod error;
+pub mod glyph;
 pub mod matrix;
+pub mod matrix3d;
 pub mod shape_utils;
 pub mod transform;
 pub mod utils;
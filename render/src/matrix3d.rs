@@ -0,0 +1,420 @@
+use crate::matrix::Matrix;
+use swf::Twips;
+
+/// A 4x4 transformation matrix used for the 3D transforms of display objects.
+///
+/// This matches the layout of `flash.geom.Matrix3D`: the elements are stored in
+/// column-major order, and translations are in pixels rather than twips.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Matrix3D {
+    pub raw_data: [f64; 16],
+}
+
+/// The components of a `Matrix3D` that has no perspective, as returned by
+/// `Matrix3D::decompose`.
+///
+/// Rotations are Euler angles in radians, applied in X, Y, Z order.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Components3D {
+    pub translation: [f64; 3],
+    pub rotation: [f64; 3],
+    pub scale: [f64; 3],
+}
+
+impl Matrix3D {
+    pub const IDENTITY: Self = Self {
+        raw_data: [
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0, //
+        ],
+    };
+
+    /// Returns the element at `row` and `column`.
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        self.raw_data[column * 4 + row]
+    }
+
+    fn set(&mut self, row: usize, column: usize, value: f64) {
+        self.raw_data[column * 4 + row] = value;
+    }
+
+    /// Creates a 3D matrix with the same effect as a 2D matrix on the XY plane.
+    pub fn from_matrix(matrix: &Matrix) -> Self {
+        let mut result = Self::IDENTITY;
+        result.set(0, 0, matrix.a.into());
+        result.set(1, 0, matrix.b.into());
+        result.set(0, 1, matrix.c.into());
+        result.set(1, 1, matrix.d.into());
+        result.set(0, 3, matrix.tx.to_pixels());
+        result.set(1, 3, matrix.ty.to_pixels());
+        result
+    }
+
+    /// Flattens this matrix onto the XY plane, ignoring depth and perspective.
+    pub fn to_matrix(&self) -> Matrix {
+        Matrix {
+            a: self.get(0, 0) as f32,
+            b: self.get(1, 0) as f32,
+            c: self.get(0, 1) as f32,
+            d: self.get(1, 1) as f32,
+            tx: Twips::from_pixels(self.get(0, 3)),
+            ty: Twips::from_pixels(self.get(1, 3)),
+        }
+    }
+
+    /// Returns the 2D matrix that matches the projection of this matrix at the
+    /// top-left, top-right and bottom-left corners of a `width` by `height` rectangle.
+    ///
+    /// This approximates a perspective transform where only 2D transforms can be drawn.
+    /// Returns `None` if any of those corners end up behind the viewer.
+    pub fn affine_approximation(&self, width: f64, height: f64) -> Option<Matrix> {
+        if width <= 0.0 || height <= 0.0 {
+            return None;
+        }
+        let (x0, y0) = self.project_point(0.0, 0.0, 0.0)?;
+        let (x1, y1) = self.project_point(width, 0.0, 0.0)?;
+        let (x2, y2) = self.project_point(0.0, height, 0.0)?;
+        Some(Matrix {
+            a: ((x1 - x0) / width) as f32,
+            b: ((y1 - y0) / width) as f32,
+            c: ((x2 - x0) / height) as f32,
+            d: ((y2 - y0) / height) as f32,
+            tx: Twips::from_pixels(x0),
+            ty: Twips::from_pixels(y0),
+        })
+    }
+
+    pub fn translate(x: f64, y: f64, z: f64) -> Self {
+        let mut result = Self::IDENTITY;
+        result.set(0, 3, x);
+        result.set(1, 3, y);
+        result.set(2, 3, z);
+        result
+    }
+
+    pub fn scale(x: f64, y: f64, z: f64) -> Self {
+        let mut result = Self::IDENTITY;
+        result.set(0, 0, x);
+        result.set(1, 1, y);
+        result.set(2, 2, z);
+        result
+    }
+
+    /// A rotation around the X axis, in radians.
+    pub fn rotate_x(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut result = Self::IDENTITY;
+        result.set(1, 1, cos);
+        result.set(1, 2, -sin);
+        result.set(2, 1, sin);
+        result.set(2, 2, cos);
+        result
+    }
+
+    /// A rotation around the Y axis, in radians.
+    pub fn rotate_y(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut result = Self::IDENTITY;
+        result.set(0, 0, cos);
+        result.set(0, 2, sin);
+        result.set(2, 0, -sin);
+        result.set(2, 2, cos);
+        result
+    }
+
+    /// A rotation around the Z axis, in radians. This is the same as a 2D rotation.
+    pub fn rotate_z(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut result = Self::IDENTITY;
+        result.set(0, 0, cos);
+        result.set(0, 1, -sin);
+        result.set(1, 0, sin);
+        result.set(1, 1, cos);
+        result
+    }
+
+    /// A perspective projection as seen from a viewer at `-focal_length` on the
+    /// Z axis, looking towards the point `(center_x, center_y)`.
+    ///
+    /// Points on the XY plane are unaffected, points with a positive Z move
+    /// towards the center, and the depth of every point is flattened to 0.
+    pub fn perspective(focal_length: f64, center_x: f64, center_y: f64) -> Self {
+        let mut result = Self::IDENTITY;
+        result.set(0, 2, center_x / focal_length);
+        result.set(1, 2, center_y / focal_length);
+        result.set(2, 2, 0.0);
+        result.set(3, 2, 1.0 / focal_length);
+        result
+    }
+
+    /// Builds a matrix from its components, in the same order as `decompose`.
+    pub fn recompose(components: &Components3D) -> Self {
+        let [tx, ty, tz] = components.translation;
+        let [rx, ry, rz] = components.rotation;
+        let [sx, sy, sz] = components.scale;
+        Self::translate(tx, ty, tz)
+            * Self::rotate_z(rz)
+            * Self::rotate_y(ry)
+            * Self::rotate_x(rx)
+            * Self::scale(sx, sy, sz)
+    }
+
+    /// Splits this matrix into a translation, a rotation and a scale.
+    ///
+    /// Any perspective and skew in the matrix is lost. Returns `None` if the
+    /// matrix has a scale of 0 on any axis.
+    pub fn decompose(&self) -> Option<Components3D> {
+        let translation = [self.get(0, 3), self.get(1, 3), self.get(2, 3)];
+        let mut columns = [0, 1, 2].map(|c| [self.get(0, c), self.get(1, c), self.get(2, c)]);
+        let mut scale = columns.map(|[x, y, z]| (x * x + y * y + z * z).sqrt());
+        if scale.contains(&0.0) {
+            return None;
+        }
+        // A mirrored matrix is treated as having a negative Z scale.
+        if self.upper_determinant() < 0.0 {
+            scale[2] = -scale[2];
+        }
+        for (column, scale) in columns.iter_mut().zip(scale) {
+            for n in column.iter_mut() {
+                *n /= scale;
+            }
+        }
+
+        // The rotation matrix is Rz * Ry * Rx, whose bottom-left element is -sin(ry).
+        let r = |row: usize, column: usize| columns[column][row];
+        let ry = (-r(2, 0)).clamp(-1.0, 1.0).asin();
+        let (rx, rz) = if ry.cos().abs() > 1e-9 {
+            (r(2, 1).atan2(r(2, 2)), r(1, 0).atan2(r(0, 0)))
+        } else {
+            // Gimbal lock: X and Z rotate around the same axis, so put it all in X.
+            (ry.sin() * r(0, 1).atan2(r(1, 1)), 0.0)
+        };
+
+        Some(Components3D {
+            translation,
+            rotation: [rx, ry, rz],
+            scale,
+        })
+    }
+
+    fn upper_determinant(&self) -> f64 {
+        let m = |row: usize, column: usize| self.get(row, column);
+        m(0, 0) * (m(1, 1) * m(2, 2) - m(2, 1) * m(1, 2))
+            - m(0, 1) * (m(1, 0) * m(2, 2) - m(2, 0) * m(1, 2))
+            + m(0, 2) * (m(1, 0) * m(2, 1) - m(2, 0) * m(1, 1))
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let m = &self.raw_data;
+        let (s0, s1, s2, s3, s4, s5, c5, c4, c3, c2, c1, c0) = minors(m);
+        s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0
+    }
+
+    /// Returns the inverse of this matrix, or `None` if it isn't invertible.
+    pub fn inverse(&self) -> Option<Self> {
+        let m = &self.raw_data;
+        let (s0, s1, s2, s3, s4, s5, c5, c4, c3, c2, c1, c0) = minors(m);
+        let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+        if det.abs() < f64::EPSILON {
+            return None;
+        }
+        let (a00, a01, a02, a03) = (m[0], m[4], m[8], m[12]);
+        let (a10, a11, a12, a13) = (m[1], m[5], m[9], m[13]);
+        let (a20, a21, a22, a23) = (m[2], m[6], m[10], m[14]);
+        let (a30, a31, a32, a33) = (m[3], m[7], m[11], m[15]);
+        let inverse = [
+            [
+                a11 * c5 - a12 * c4 + a13 * c3,
+                -a01 * c5 + a02 * c4 - a03 * c3,
+                a31 * s5 - a32 * s4 + a33 * s3,
+                -a21 * s5 + a22 * s4 - a23 * s3,
+            ],
+            [
+                -a10 * c5 + a12 * c2 - a13 * c1,
+                a00 * c5 - a02 * c2 + a03 * c1,
+                -a30 * s5 + a32 * s2 - a33 * s1,
+                a20 * s5 - a22 * s2 + a23 * s1,
+            ],
+            [
+                a10 * c4 - a11 * c2 + a13 * c0,
+                -a00 * c4 + a01 * c2 - a03 * c0,
+                a30 * s4 - a31 * s2 + a33 * s0,
+                -a20 * s4 + a21 * s2 - a23 * s0,
+            ],
+            [
+                -a10 * c3 + a11 * c1 - a12 * c0,
+                a00 * c3 - a01 * c1 + a02 * c0,
+                -a30 * s3 + a31 * s1 - a32 * s0,
+                a20 * s3 - a21 * s1 + a22 * s0,
+            ],
+        ];
+        let mut result = Self::IDENTITY;
+        for (row, values) in inverse.iter().enumerate() {
+            for (column, value) in values.iter().enumerate() {
+                result.set(row, column, value / det);
+            }
+        }
+        Some(result)
+    }
+
+    /// Transforms a point, returning its homogeneous coordinates `[x, y, z, w]`.
+    pub fn transform_point(&self, x: f64, y: f64, z: f64) -> [f64; 4] {
+        [0, 1, 2, 3].map(|row| {
+            self.get(row, 0) * x + self.get(row, 1) * y + self.get(row, 2) * z + self.get(row, 3)
+        })
+    }
+
+    /// Transforms a point and divides it by its W coordinate.
+    ///
+    /// Returns `None` for points that end up behind the viewer.
+    pub fn project_point(&self, x: f64, y: f64, z: f64) -> Option<(f64, f64)> {
+        let [x, y, _, w] = self.transform_point(x, y, z);
+        if w > 0.0 {
+            Some((x / w, y / w))
+        } else {
+            None
+        }
+    }
+}
+
+/// The 2x2 minors of the top and bottom halves of a matrix, used for its
+/// determinant and inverse.
+#[allow(clippy::type_complexity)]
+fn minors(m: &[f64; 16]) -> (f64, f64, f64, f64, f64, f64, f64, f64, f64, f64, f64, f64) {
+    let (a00, a01, a02, a03) = (m[0], m[4], m[8], m[12]);
+    let (a10, a11, a12, a13) = (m[1], m[5], m[9], m[13]);
+    let (a20, a21, a22, a23) = (m[2], m[6], m[10], m[14]);
+    let (a30, a31, a32, a33) = (m[3], m[7], m[11], m[15]);
+    (
+        a00 * a11 - a10 * a01,
+        a00 * a12 - a10 * a02,
+        a00 * a13 - a10 * a03,
+        a01 * a12 - a11 * a02,
+        a01 * a13 - a11 * a03,
+        a02 * a13 - a12 * a03,
+        a22 * a33 - a32 * a23,
+        a21 * a33 - a31 * a23,
+        a21 * a32 - a31 * a22,
+        a20 * a33 - a30 * a23,
+        a20 * a32 - a30 * a22,
+        a20 * a31 - a30 * a21,
+    )
+}
+
+impl std::ops::Mul for Matrix3D {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let mut result = Self::IDENTITY;
+        for row in 0..4 {
+            for column in 0..4 {
+                let value = (0..4).map(|i| self.get(row, i) * rhs.get(i, column)).sum();
+                result.set(row, column, value);
+            }
+        }
+        result
+    }
+}
+
+impl Default for Matrix3D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn assert_matrix_eq(left: &Matrix3D, right: &Matrix3D) {
+        for (l, r) in left.raw_data.iter().zip(&right.raw_data) {
+            assert_abs_diff_eq!(l, r, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn rotations() {
+        // A quarter turn around Z moves the X axis onto the Y axis.
+        let [x, y, z, _] = Matrix3D::rotate_z(FRAC_PI_2).transform_point(1.0, 0.0, 0.0);
+        assert_abs_diff_eq!([x, y, z][..], [0.0, 1.0, 0.0][..], epsilon = 1e-9);
+
+        // A quarter turn around Y moves the X axis towards the viewer.
+        let [x, y, z, _] = Matrix3D::rotate_y(FRAC_PI_2).transform_point(1.0, 0.0, 0.0);
+        assert_abs_diff_eq!([x, y, z][..], [0.0, 0.0, -1.0][..], epsilon = 1e-9);
+
+        let [x, y, z, _] = Matrix3D::rotate_x(FRAC_PI_2).transform_point(0.0, 1.0, 0.0);
+        assert_abs_diff_eq!([x, y, z][..], [0.0, 0.0, 1.0][..], epsilon = 1e-9);
+    }
+
+    #[test]
+    fn decompose_recompose() {
+        let components = Components3D {
+            translation: [10.0, -20.0, 30.0],
+            rotation: [0.3, -0.5, 1.2],
+            scale: [2.0, 0.5, 1.5],
+        };
+        let matrix = Matrix3D::recompose(&components);
+        let decomposed = matrix.decompose().unwrap();
+        assert_abs_diff_eq!(
+            decomposed.translation[..],
+            components.translation[..],
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            decomposed.rotation[..],
+            components.rotation[..],
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(decomposed.scale[..], components.scale[..], epsilon = 1e-9);
+        assert_matrix_eq(&Matrix3D::recompose(&decomposed), &matrix);
+    }
+
+    #[test]
+    fn inverse() {
+        let mut perspective = Matrix3D::IDENTITY;
+        perspective.raw_data[11] = 0.002;
+        let matrix = Matrix3D::translate(5.0, 6.0, 7.0)
+            * Matrix3D::rotate_y(0.7)
+            * Matrix3D::scale(2.0, 3.0, 4.0)
+            * perspective;
+        let inverse = matrix.inverse().unwrap();
+        assert_matrix_eq(&(matrix * inverse), &Matrix3D::IDENTITY);
+        assert_abs_diff_eq!(
+            matrix.determinant() * inverse.determinant(),
+            1.0,
+            epsilon = 1e-9
+        );
+        assert!(Matrix3D::scale(1.0, 0.0, 1.0).inverse().is_none());
+        // Perspective projections flatten depth, so they can't be inverted.
+        assert!(Matrix3D::perspective(500.0, 100.0, 50.0)
+            .inverse()
+            .is_none());
+    }
+
+    #[test]
+    fn perspective() {
+        let projection = Matrix3D::perspective(100.0, 50.0, 50.0);
+        // Points at a depth of 0 are unchanged.
+        assert_eq!(
+            projection.project_point(10.0, 20.0, 0.0),
+            Some((10.0, 20.0))
+        );
+        // Points at a depth of the focal length are halfway to the center.
+        assert_eq!(
+            projection.project_point(10.0, 90.0, 100.0),
+            Some((30.0, 70.0))
+        );
+        assert_eq!(projection.project_point(10.0, 20.0, -100.0), None);
+    }
+
+    #[test]
+    fn from_matrix() {
+        let matrix = Matrix::create_box(2.0, 3.0, 0.5, Twips::new(40), Twips::new(-60));
+        let matrix3d = Matrix3D::from_matrix(&matrix);
+        assert_eq!(matrix3d.to_matrix(), matrix);
+        assert_eq!(matrix3d.project_point(0.0, 0.0, 0.0), Some((2.0, -3.0)));
+    }
+}
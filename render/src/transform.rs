@@ -26,6 +26,18 @@ impl TransformStack {
         });
     }
 
+    /// Pushes a transform with the current matrix and an identity color transform.
+    ///
+    /// This is used for content drawn into a layer, whose color transform is
+    /// applied to the layer as a whole.
+    pub fn push_layer(&mut self) {
+        let matrix = self.transform().matrix;
+        self.0.push(Transform {
+            matrix,
+            color_transform: Default::default(),
+        });
+    }
+
     pub fn pop(&mut self) {
         assert!(self.0.len() > 1, "Transform stack underflow");
         self.0.pop();
@@ -9,8 +9,10 @@ use ruffle_render::backend::{
     Context3D, Context3DCommand, RenderBackend, ShapeHandle, ViewportDimensions,
 };
 use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapHandle, BitmapHandleImpl, BitmapSource};
+use ruffle_render::color_transform::ColorTransform;
 use ruffle_render::commands::{CommandHandler, CommandList};
 use ruffle_render::error::Error as BitmapError;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::shape_utils::DistilledShape;
 use ruffle_render::tessellator::{
     Gradient as TessGradient, GradientType, ShapeTessellator, Vertex as TessVertex,
@@ -927,6 +929,84 @@ impl WebGlRenderBackend {
             self.apply_blend_mode(current);
         }
     }
+
+    /// Draws a bitmap onto the unit quad transformed by `world_matrix`.
+    fn draw_bitmap_quad(
+        &mut self,
+        bitmap: &BitmapHandle,
+        world_matrix: [[f32; 4]; 4],
+        color_transform: &ColorTransform,
+        smoothing: bool,
+    ) {
+        self.set_stencil_state();
+        let entry = as_registry_data(bitmap);
+        // Adjust the quad draw to use the target bitmap.
+        let mesh = &self.meshes[self.bitmap_quad_shape.0];
+        let draw = &mesh.draws[0];
+        let bitmap_matrix = if let DrawType::Bitmap(BitmapDraw { matrix, .. }) = &draw.draw_type {
+            matrix
+        } else {
+            unreachable!()
+        };
+
+        let mult_color = color_transform.mult_rgba_normalized();
+        let add_color = color_transform.add_rgba_normalized();
+
+        self.bind_vertex_array(Some(&draw.vao));
+
+        let program = &self.bitmap_program;
+
+        // Set common render state, while minimizing unnecessary state changes.
+        // TODO: Using designated layout specifiers in WebGL2/OpenGL ES 3, we could guarantee that uniforms
+        // are in the same location between shaders, and avoid changing them unless necessary.
+        if program as *const ShaderProgram != self.active_program {
+            self.gl.use_program(Some(&program.program));
+            self.active_program = program as *const ShaderProgram;
+
+            program.uniform_matrix4fv(&self.gl, ShaderUniform::ViewMatrix, &self.view_matrix);
+
+            self.mult_color = None;
+            self.add_color = None;
+        }
+
+        program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
+        if Some(mult_color) != self.mult_color {
+            program.uniform4fv(&self.gl, ShaderUniform::MultColor, &mult_color);
+            self.mult_color = Some(mult_color);
+        }
+        if Some(add_color) != self.add_color {
+            program.uniform4fv(&self.gl, ShaderUniform::AddColor, &add_color);
+            self.add_color = Some(add_color);
+        }
+
+        program.uniform_matrix3fv(&self.gl, ShaderUniform::TextureMatrix, bitmap_matrix);
+
+        // Bind texture.
+        self.gl.active_texture(Gl::TEXTURE0);
+        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&entry.texture));
+        program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
+
+        // Set texture parameters.
+        let filter = if smoothing {
+            Gl::LINEAR as i32
+        } else {
+            Gl::NEAREST as i32
+        };
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, filter);
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, filter);
+
+        let wrap = Gl::CLAMP_TO_EDGE as i32;
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, wrap);
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, wrap);
+
+        // Draw the triangles.
+        self.gl
+            .draw_elements_with_i32(Gl::TRIANGLES, draw.num_indices, Gl::UNSIGNED_INT, 0);
+    }
 }
 
 impl RenderBackend for WebGlRenderBackend {
@@ -1124,17 +1204,7 @@ impl RenderBackend for WebGlRenderBackend {
 
 impl CommandHandler for WebGlRenderBackend {
     fn render_bitmap(&mut self, bitmap: &BitmapHandle, transform: &Transform, smoothing: bool) {
-        self.set_stencil_state();
         let entry = as_registry_data(bitmap);
-        // Adjust the quad draw to use the target bitmap.
-        let mesh = &self.meshes[self.bitmap_quad_shape.0];
-        let draw = &mesh.draws[0];
-        let bitmap_matrix = if let DrawType::Bitmap(BitmapDraw { matrix, .. }) = &draw.draw_type {
-            matrix
-        } else {
-            unreachable!()
-        };
-
         // Scale the quad to the bitmap's dimensions.
         let matrix = transform.matrix
             * ruffle_render::matrix::Matrix::scale(
@@ -1153,64 +1223,28 @@ impl CommandHandler for WebGlRenderBackend {
                 1.0,
             ],
         ];
+        self.draw_bitmap_quad(bitmap, world_matrix, &transform.color_transform, smoothing);
+    }
 
-        let mult_color = transform.color_transform.mult_rgba_normalized();
-        let add_color = transform.color_transform.add_rgba_normalized();
-
-        self.bind_vertex_array(Some(&draw.vao));
-
-        let program = &self.bitmap_program;
-
-        // Set common render state, while minimizing unnecessary state changes.
-        // TODO: Using designated layout specifiers in WebGL2/OpenGL ES 3, we could guarantee that uniforms
-        // are in the same location between shaders, and avoid changing them unless necessary.
-        if program as *const ShaderProgram != self.active_program {
-            self.gl.use_program(Some(&program.program));
-            self.active_program = program as *const ShaderProgram;
-
-            program.uniform_matrix4fv(&self.gl, ShaderUniform::ViewMatrix, &self.view_matrix);
-
-            self.mult_color = None;
-            self.add_color = None;
-        }
-
-        program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
-        if Some(mult_color) != self.mult_color {
-            program.uniform4fv(&self.gl, ShaderUniform::MultColor, &mult_color);
-            self.mult_color = Some(mult_color);
-        }
-        if Some(add_color) != self.add_color {
-            program.uniform4fv(&self.gl, ShaderUniform::AddColor, &add_color);
-            self.add_color = Some(add_color);
-        }
-
-        program.uniform_matrix3fv(&self.gl, ShaderUniform::TextureMatrix, bitmap_matrix);
-
-        // Bind texture.
-        self.gl.active_texture(Gl::TEXTURE0);
-        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&entry.texture));
-        program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
-
-        // Set texture parameters.
-        let filter = if smoothing {
-            Gl::LINEAR as i32
-        } else {
-            Gl::NEAREST as i32
-        };
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, filter);
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, filter);
-
-        let wrap = Gl::CLAMP_TO_EDGE as i32;
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, wrap);
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, wrap);
+    fn render_bitmap_3d(
+        &mut self,
+        bitmap: &BitmapHandle,
+        matrix: &Matrix3D,
+        color_transform: &ColorTransform,
+        smoothing: bool,
+    ) {
+        let entry = as_registry_data(bitmap);
+        // Scale the quad to the bitmap's dimensions.
+        let matrix = *matrix
+            * Matrix3D::scale(
+                entry.bitmap.width().into(),
+                entry.bitmap.height().into(),
+                1.0,
+            );
 
-        // Draw the triangles.
-        self.gl
-            .draw_elements_with_i32(Gl::TRIANGLES, draw.num_indices, Gl::UNSIGNED_INT, 0);
+        let world_matrix =
+            [0, 1, 2, 3].map(|column| [0, 1, 2, 3].map(|row| matrix.get(row, column) as f32));
+        self.draw_bitmap_quad(bitmap, world_matrix, color_transform, smoothing);
     }
 
     fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
@@ -1449,11 +1483,32 @@ impl CommandHandler for WebGlRenderBackend {
         self.mask_state_dirty = true;
     }
 
-    fn blend(&mut self, commands: &CommandList, blend: BlendMode) {
+    fn blend(
+        &mut self,
+        commands: &CommandList,
+        blend: BlendMode,
+        color_transform: &ColorTransform,
+    ) {
         self.push_blend_mode(blend);
-        commands.execute(self);
+        if *color_transform == ColorTransform::IDENTITY {
+            commands.execute(self);
+        } else {
+            // Layers aren't supported, so apply the color transform to each draw instead.
+            commands.with_color_transform(color_transform).execute(self);
+        }
         self.pop_blend_mode();
     }
+
+    fn alpha_mask(&mut self, maskee: &CommandList, mask: &CommandList) {
+        // Alpha masks aren't supported, so fall back to a regular stencil mask.
+        self.push_mask();
+        mask.execute(self);
+        self.activate_mask();
+        maskee.execute(self);
+        self.deactivate_mask();
+        mask.execute(self);
+        self.pop_mask();
+    }
 }
 
 #[derive(Clone, Debug)]
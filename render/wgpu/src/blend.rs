@@ -29,6 +29,7 @@ impl BlendType {
     pub fn from(mode: BlendMode) -> BlendType {
         match mode {
             BlendMode::Normal => BlendType::Trivial(TrivialBlend::Normal),
+            // Layers are drawn into their own isolated target, and then composited normally
             BlendMode::Layer => BlendType::Trivial(TrivialBlend::Normal),
             BlendMode::Multiply => BlendType::Complex(ComplexBlend::Multiply),
             BlendMode::Screen => BlendType::Complex(ComplexBlend::Screen),
@@ -55,6 +56,7 @@ pub enum TrivialBlend {
     Normal,
     Add,
     Subtract,
+    AlphaMask, // Multiplies the destination by the source alpha
 }
 
 impl TrivialBlend {
@@ -78,6 +80,17 @@ impl TrivialBlend {
                 },
                 alpha: wgpu::BlendComponent::OVER,
             },
+            TrivialBlend::AlphaMask => {
+                let component = wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::Zero,
+                    dst_factor: wgpu::BlendFactor::SrcAlpha,
+                    operation: wgpu::BlendOperation::Add,
+                };
+                wgpu::BlendState {
+                    color: component,
+                    alpha: component,
+                }
+            }
         }
     }
 }
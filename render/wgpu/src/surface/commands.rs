@@ -10,8 +10,9 @@ use crate::{
 use ruffle_render::backend::ShapeHandle;
 use ruffle_render::bitmap::BitmapHandle;
 use ruffle_render::color_transform::ColorTransform;
-use ruffle_render::commands::Command;
+use ruffle_render::commands::{Command, CommandList};
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::tessellator::GradientType;
 use ruffle_render::transform::Transform;
 use swf::{BlendMode, Color, Fixed8, GradientSpread};
@@ -80,6 +81,12 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
                 smoothing,
                 blend_mode,
             } => self.render_bitmap(bitmap, &transform, *smoothing, *blend_mode),
+            DrawCommand::RenderBitmap3D {
+                bitmap,
+                matrix,
+                color_transform,
+                smoothing,
+            } => self.render_bitmap_3d(bitmap, matrix, color_transform, *smoothing),
             DrawCommand::RenderTexture {
                 _texture,
                 binds,
@@ -159,7 +166,14 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
                 1.0,
             ],
         ];
+        self.apply_world_matrix(world_matrix, color_adjustments);
+    }
 
+    pub fn apply_world_matrix(
+        &mut self,
+        world_matrix: [[f32; 4]; 4],
+        color_adjustments: &ColorTransform,
+    ) {
         self.uniform_buffers.write_uniforms(
             &self.descriptors.device,
             &self.descriptors.bind_layouts.transforms,
@@ -228,6 +242,46 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
         }
     }
 
+    pub fn render_bitmap_3d(
+        &mut self,
+        bitmap: &'frame BitmapHandle,
+        matrix: &Matrix3D,
+        color_transform: &ColorTransform,
+        smoothing: bool,
+    ) {
+        if cfg!(feature = "render_debug_labels") {
+            self.render_pass
+                .push_debug_group(&format!("render_bitmap_3d {:?}", bitmap.0));
+        }
+        let texture = as_texture(bitmap);
+
+        // The shaders already use a 4x4 world matrix, so the projection can be used as is.
+        let matrix = *matrix * Matrix3D::scale(texture.width.into(), texture.height.into(), 1.0);
+        let world_matrix =
+            [0, 1, 2, 3].map(|column| [0, 1, 2, 3].map(|row| matrix.get(row, column) as f32));
+        self.apply_world_matrix(world_matrix, color_transform);
+        let descriptors = self.descriptors;
+        let bind = texture.bind_group(
+            smoothing,
+            &descriptors.device,
+            &descriptors.bind_layouts.bitmap,
+            &descriptors.quad,
+            bitmap.clone(),
+            &descriptors.bitmap_samplers,
+        );
+
+        self.prep_bitmap(&bind.bind_group, TrivialBlend::Normal);
+
+        self.draw(
+            self.descriptors.quad.vertices.slice(..),
+            self.descriptors.quad.indices.slice(..),
+            6,
+        );
+        if cfg!(feature = "render_debug_labels") {
+            self.render_pass.pop_debug_group();
+        }
+    }
+
     pub fn render_texture(
         &mut self,
         transform: &Transform,
@@ -379,6 +433,12 @@ pub enum DrawCommand {
         smoothing: bool,
         blend_mode: TrivialBlend,
     },
+    RenderBitmap3D {
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    },
     RenderTexture {
         _texture: PoolEntry<wgpu::Texture>,
         binds: wgpu::BindGroup,
@@ -423,7 +483,18 @@ pub fn chunk_blends<'a>(
 
     for command in commands {
         match command {
-            Command::Blend(commands, blend_mode) => {
+            Command::Blend(mut commands, blend_mode, color_transform) => {
+                let blend_type = BlendType::from(blend_mode);
+                if matches!(blend_type, BlendType::Complex(_))
+                    && color_transform != ColorTransform::IDENTITY
+                {
+                    // Complex blends can't apply a color transform,
+                    // so it's applied to a layer that's drawn inside of the blend.
+                    commands = CommandList {
+                        commands: vec![Command::Blend(commands, BlendMode::Layer, color_transform)],
+                    };
+                }
+
                 let mut surface = Surface::new(
                     &descriptors,
                     sample_count,
@@ -431,7 +502,7 @@ pub fn chunk_blends<'a>(
                     height,
                     wgpu::TextureFormat::Rgba8Unorm,
                 );
-                let clear_color = BlendType::from(blend_mode).default_color();
+                let clear_color = blend_type.default_color();
                 let target = surface.draw_commands(
                     clear_color,
                     &descriptors,
@@ -450,11 +521,11 @@ pub fn chunk_blends<'a>(
                 );
                 target.ensure_cleared(draw_encoder, clear_color);
 
-                match BlendType::from(blend_mode) {
+                match blend_type {
                     BlendType::Trivial(blend_mode) => {
                         let transform = Transform {
                             matrix: Matrix::scale(target.width() as f32, target.height() as f32),
-                            color_transform: Default::default(),
+                            color_transform,
                         };
                         let texture = target.take_color_texture();
                         let binds = BitmapBinds::new(
@@ -485,6 +556,70 @@ pub fn chunk_blends<'a>(
                     }
                 }
             }
+            Command::AlphaMask { maskee, mask } => {
+                let mut surface = Surface::new(
+                    &descriptors,
+                    sample_count,
+                    width,
+                    height,
+                    wgpu::TextureFormat::Rgba8Unorm,
+                );
+                let mask_target = surface.draw_commands(
+                    wgpu::Color::TRANSPARENT,
+                    &descriptors,
+                    &meshes,
+                    mask,
+                    uniform_buffers,
+                    color_buffers,
+                    uniform_encoder,
+                    draw_encoder,
+                    None,
+                    texture_pool,
+                );
+                mask_target.ensure_cleared(draw_encoder, wgpu::Color::TRANSPARENT);
+                let maskee_target = surface.draw_commands(
+                    wgpu::Color::TRANSPARENT,
+                    &descriptors,
+                    &meshes,
+                    maskee,
+                    uniform_buffers,
+                    color_buffers,
+                    uniform_encoder,
+                    draw_encoder,
+                    None,
+                    texture_pool,
+                );
+                apply_alpha_mask(
+                    &surface,
+                    descriptors,
+                    draw_encoder,
+                    &maskee_target,
+                    mask_target.take_color_texture(),
+                );
+
+                let transform = Transform {
+                    matrix: Matrix::scale(
+                        maskee_target.width() as f32,
+                        maskee_target.height() as f32,
+                    ),
+                    color_transform: Default::default(),
+                };
+                let texture = maskee_target.take_color_texture();
+                let binds = BitmapBinds::new(
+                    &descriptors.device,
+                    &descriptors.bind_layouts.bitmap,
+                    descriptors.bitmap_samplers.get_sampler(false, false),
+                    &descriptors.quad.texture_transforms,
+                    texture.create_view(&Default::default()),
+                    None,
+                );
+                current.push(DrawCommand::RenderTexture {
+                    _texture: texture,
+                    binds: binds.bind_group,
+                    transform,
+                    blend_mode: TrivialBlend::Normal,
+                })
+            }
             Command::RenderBitmap {
                 bitmap,
                 transform,
@@ -495,6 +630,17 @@ pub fn chunk_blends<'a>(
                 smoothing,
                 blend_mode: TrivialBlend::Normal,
             }),
+            Command::RenderBitmap3D {
+                bitmap,
+                matrix,
+                color_transform,
+                smoothing,
+            } => current.push(DrawCommand::RenderBitmap3D {
+                bitmap,
+                matrix,
+                color_transform,
+                smoothing,
+            }),
             Command::RenderShape { shape, transform } => {
                 current.push(DrawCommand::RenderShape { shape, transform })
             }
@@ -528,3 +674,42 @@ pub fn chunk_blends<'a>(
 
     result
 }
+
+/// Multiplies the contents of `target` by the alpha channel of `mask`,
+/// which must be the same size as the target.
+fn apply_alpha_mask(
+    surface: &Surface,
+    descriptors: &Descriptors,
+    draw_encoder: &mut wgpu::CommandEncoder,
+    target: &CommandTarget,
+    mask: PoolEntry<wgpu::Texture>,
+) {
+    let binds = BitmapBinds::new(
+        &descriptors.device,
+        &descriptors.bind_layouts.bitmap,
+        descriptors.bitmap_samplers.get_sampler(false, false),
+        &descriptors.quad.texture_transforms,
+        mask.create_view(&Default::default()),
+        create_debug_label!("Alpha mask binds"),
+    );
+
+    let mut render_pass = draw_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: create_debug_label!("Alpha mask").as_deref(),
+        color_attachments: &[target.color_attachments(wgpu::Color::TRANSPARENT)],
+        depth_stencil_attachment: None,
+    });
+    render_pass
+        .set_pipeline(surface.pipelines.bitmap[TrivialBlend::AlphaMask].depthless_pipeline());
+    render_pass.set_bind_group(0, target.globals().bind_group(), &[]);
+    render_pass.set_bind_group(1, target.whole_frame_bind_group(descriptors), &[0]);
+    render_pass.set_bind_group(2, &descriptors.default_color_bind_group, &[0]);
+    render_pass.set_bind_group(3, &binds.bind_group, &[]);
+
+    render_pass.set_vertex_buffer(0, descriptors.quad.vertices.slice(..));
+    render_pass.set_index_buffer(
+        descriptors.quad.indices.slice(..),
+        wgpu::IndexFormat::Uint32,
+    );
+
+    render_pass.draw_indexed(0..6, 0, 0..1);
+}
@@ -18,6 +18,8 @@ use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{Player, PlayerBuilder, PlayerEvent, ViewportDimensions};
 use ruffle_input_format::{AutomatedEvent, InputInjector, MouseButton as InputMouseButton};
 
+#[cfg(all(feature = "software_imgtests", not(feature = "imgtests")))]
+use ruffle_render_software::SoftwareRenderBackend;
 #[cfg(feature = "imgtests")]
 use ruffle_render_wgpu::backend::WgpuRenderBackend;
 #[cfg(feature = "imgtests")]
@@ -29,7 +31,7 @@ use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-const RUN_IMG_TESTS: bool = cfg!(feature = "imgtests");
+const RUN_IMG_TESTS: bool = cfg!(any(feature = "imgtests", feature = "software_imgtests"));
 
 fn set_logger() {
     let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
@@ -191,16 +193,16 @@ swf_tests! {
     (as3_bitmapdata_copypixels, "avm2/bitmapdata_copypixels", 2, img = true),
     #[ignore] (as3_bitmap_properties, "avm2/bitmap_properties", 1),
     (as3_bitmap_subclass, "avm2/bitmap_subclass", 1),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (as3_bitmap_subclass_properties, "avm2/bitmap_subclass_properties", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (as3_bitmap_subclass_properties, "avm2/bitmap_subclass_properties", 1, img = true),
     (as3_bitmap_timeline, "avm2/bitmap_timeline", 1),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (as3_bitmapdata_clone, "avm2/bitmapdata_clone", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (as3_bitmapdata_clone, "avm2/bitmapdata_clone", 1, img = true),
     (as3_bitmapdata_constr, "avm2/bitmapdata_constr", 1),
     (as3_bitmapdata_dispose, "avm2/bitmapdata_dispose", 1),
     // We need a render backend in order to call `BitmapData.draw`
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (as3_bitmapdata_draw, "avm2/bitmapdata_draw", 1, img = true),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (as3_bitmapdata_opaque, "avm2/bitmapdata_opaque", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (as3_bitmapdata_draw, "avm2/bitmapdata_draw", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (as3_bitmapdata_opaque, "avm2/bitmapdata_opaque", 1, img = true),
     (as3_bitmapdata_zero_size, "avm2/bitmapdata_zero_size", 1),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (as3_bitmapdata_embedded, "avm2/bitmapdata_embedded", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (as3_bitmapdata_embedded, "avm2/bitmapdata_embedded", 1, img = true),
     (as3_bitmapdata_fillrect, "avm2/bitmapdata_fillrect", 1),
     (as3_bitnot, "avm2/bitnot", 1),
     (as3_bitor, "avm2/bitor", 1),
@@ -847,18 +849,18 @@ swf_tests! {
     (unloadmovienum, "avm1/unloadmovienum", 11),
     (use_hand_cursor, "avm1/use_hand_cursor", 1),
     (variable_args, "avm1/variable_args", 1),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (visual_blendmodes_add, "visual/blend_modes/add", 1, img = true),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (visual_blendmodes_darken, "visual/blend_modes/darken", 1, img = true),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (visual_blendmodes_difference, "visual/blend_modes/difference", 1, img = true),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (visual_blendmodes_hardlight, "visual/blend_modes/hardlight", 1, img = true),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (visual_blendmodes_invert, "visual/blend_modes/invert", 1, img = true),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (visual_blendmodes_layer_alpha, "visual/blend_modes/layer_alpha", 1, img = true),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (visual_blendmodes_layer_erase, "visual/blend_modes/layer_erase", 1, img = true),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (visual_blendmodes_lighten, "visual/blend_modes/lighten", 1, img = true),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (visual_blendmodes_multiply, "visual/blend_modes/multiply", 1, img = true),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (visual_blendmodes_overlay, "visual/blend_modes/overlay", 1, img = true),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (visual_blendmodes_screen, "visual/blend_modes/screen", 1, img = true),
-    #[cfg_attr(not(feature = "imgtests"), ignore)] (visual_blendmodes_subtract, "visual/blend_modes/subtract", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (visual_blendmodes_add, "visual/blend_modes/add", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (visual_blendmodes_darken, "visual/blend_modes/darken", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (visual_blendmodes_difference, "visual/blend_modes/difference", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (visual_blendmodes_hardlight, "visual/blend_modes/hardlight", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (visual_blendmodes_invert, "visual/blend_modes/invert", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (visual_blendmodes_layer_alpha, "visual/blend_modes/layer_alpha", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (visual_blendmodes_layer_erase, "visual/blend_modes/layer_erase", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (visual_blendmodes_lighten, "visual/blend_modes/lighten", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (visual_blendmodes_multiply, "visual/blend_modes/multiply", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (visual_blendmodes_overlay, "visual/blend_modes/overlay", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (visual_blendmodes_screen, "visual/blend_modes/screen", 1, img = true),
+    #[cfg_attr(not(any(feature = "imgtests", feature = "software_imgtests")), ignore)] (visual_blendmodes_subtract, "visual/blend_modes/subtract", 1, img = true),
     (waitforframe, "avm1/waitforframe", 1),
     (watch_textfield, "avm1/watch_textfield", 1),
     (watch_virtual_property_proto, "avm1/watch_virtual_property_proto", 1),
@@ -1441,6 +1443,20 @@ fn run_swf(
             .with_viewport_dimensions(width, height, 1.0);
     };
 
+    // The software renderer is only used when the wgpu renderer isn't available.
+    // It keeps the last frame around, so only what changed is redrawn on each frame;
+    // the resulting image must still match one drawn from scratch.
+    #[cfg(all(feature = "software_imgtests", not(feature = "imgtests")))]
+    if check_img {
+        let width = movie.width().to_pixels() as u32;
+        let height = movie.height().to_pixels() as u32;
+
+        builder = builder
+            .with_renderer(SoftwareRenderBackend::new(width, height)?)
+            .with_viewport_dimensions(width, height, 1.0)
+            .with_dirty_region_tracking(true);
+    };
+
     let player = builder
         .with_log(TestLogBackend::new(trace_output.clone()))
         .with_navigator(NullNavigatorBackend::with_base_path(base_path, &executor))
@@ -1513,30 +1529,53 @@ fn run_swf(
 
     // Render the image to disk
     // FIXME: Determine how we want to compare against on on-disk image
-    #[cfg(feature = "imgtests")]
+    #[cfg(any(feature = "imgtests", feature = "software_imgtests"))]
     if check_img {
         let mut player_lock = player.lock().unwrap();
         player_lock.render();
-        let renderer = player_lock
-            .renderer_mut()
-            .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-            .unwrap();
+        let renderer = player_lock.renderer_mut();
 
         // Use straight alpha, since we want to save this as a PNG
-        let actual_image = renderer
-            .capture_frame(false)
-            .expect("Failed to capture image");
-
-        let info = renderer.descriptors().adapter.get_info();
-        let suffix = format!("{}-{:?}", std::env::consts::OS, info.backend);
+        #[cfg(feature = "imgtests")]
+        let (actual_image, suffix, expected_image_path, tolerance) = {
+            let renderer = renderer
+                .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+                .unwrap();
+            let actual_image = renderer
+                .capture_frame(false)
+                .expect("Failed to capture image");
+            let info = renderer.descriptors().adapter.get_info();
+            let suffix = format!("{}-{:?}", std::env::consts::OS, info.backend);
+            let expected_image_path = base_path.join(format!("expected-{}.png", &suffix));
+            (actual_image, suffix, expected_image_path, (0, 0.0))
+        };
+        #[cfg(not(feature = "imgtests"))]
+        let (actual_image, suffix, expected_image_path, tolerance) = {
+            let renderer = renderer.downcast_mut::<SoftwareRenderBackend>().unwrap();
+            let actual_image = renderer
+                .capture_frame(false)
+                .expect("Failed to capture image");
+            // The software renderer gives the same output on every platform.
+            // Tests without a baseline of their own are compared against the
+            // Vulkan baseline instead, allowing for differences in rasterization.
+            let expected_image_path = base_path.join("expected-software.png");
+            if expected_image_path.exists() {
+                (actual_image, "software", expected_image_path, (0, 0.0))
+            } else {
+                let expected_image_path = base_path.join("expected-linux-Vulkan.png");
+                (actual_image, "software", expected_image_path, (3, 0.01))
+            }
+        };
 
-        let expected_image_path = base_path.join(format!("expected-{}.png", &suffix));
         let expected_image = image::open(&expected_image_path);
 
         let matches = match expected_image {
-            Ok(img) => {
-                img.as_rgba8().expect("Expected 8-bit RGBA image").as_raw() == actual_image.as_raw()
-            }
+            Ok(img) => images_match(
+                img.as_rgba8().expect("Expected 8-bit RGBA image"),
+                &actual_image,
+                tolerance.0,
+                tolerance.1,
+            ),
             Err(e) => {
                 eprintln!(
                     "Failed to open expected image {:?}: {e:?}",
@@ -1561,6 +1600,37 @@ fn run_swf(
     Ok(trace)
 }
 
+/// Compare a rendered image to its baseline.
+///
+/// Each color channel may differ by up to `max_difference`, except in a
+/// fraction of up to `max_outliers` of the pixels, such as along anti-aliased
+/// edges.
+#[cfg(any(feature = "imgtests", feature = "software_imgtests"))]
+fn images_match(
+    expected: &image::RgbaImage,
+    actual: &image::RgbaImage,
+    max_difference: u8,
+    max_outliers: f64,
+) -> bool {
+    if expected.dimensions() != actual.dimensions() {
+        return false;
+    }
+
+    let outliers = expected
+        .pixels()
+        .zip(actual.pixels())
+        .filter(|(expected, actual)| {
+            expected
+                .0
+                .iter()
+                .zip(actual.0)
+                .any(|(&e, a)| e.abs_diff(a) > max_difference)
+        })
+        .count();
+    let pixel_count = (expected.width() * expected.height()) as f64;
+    outliers as f64 <= pixel_count * max_outliers
+}
+
 struct TestLogBackend {
     trace_output: Rc<RefCell<Vec<String>>>,
 }
@@ -9,7 +9,7 @@ use generational_arena::{Arena, Index};
 use js_sys::{Array, Function, Object, Promise, Uint8Array};
 use ruffle_core::config::Letterbox;
 use ruffle_core::context::UpdateContext;
-use ruffle_core::events::{KeyCode, MouseButton, MouseWheelDelta};
+use ruffle_core::events::{KeyCode, MouseButton, MouseWheelDelta, TextControlCode};
 use ruffle_core::external::{
     ExternalInterfaceMethod, ExternalInterfaceProvider, Value as ExternalValue, Value,
 };
@@ -25,8 +25,8 @@ use std::time::Duration;
 use std::{cell::RefCell, error::Error, num::NonZeroI32};
 use wasm_bindgen::{prelude::*, JsCast, JsValue};
 use web_sys::{
-    AddEventListenerOptions, Element, Event, EventTarget, HtmlCanvasElement, HtmlElement,
-    KeyboardEvent, PointerEvent, WheelEvent, Window,
+    AddEventListenerOptions, ClipboardEvent, Element, Event, EventTarget, HtmlCanvasElement,
+    HtmlElement, KeyboardEvent, PointerEvent, WheelEvent, Window,
 };
 
 static RUFFLE_GLOBAL_PANIC: Once = Once::new();
@@ -63,6 +63,7 @@ struct RuffleInstance {
     mouse_wheel_callback: Option<Closure<dyn FnMut(WheelEvent)>>,
     key_down_callback: Option<Closure<dyn FnMut(KeyboardEvent)>>,
     key_up_callback: Option<Closure<dyn FnMut(KeyboardEvent)>>,
+    paste_callback: Option<Closure<dyn FnMut(ClipboardEvent)>>,
     unload_callback: Option<Closure<dyn FnMut(Event)>>,
     has_focus: bool,
     trace_observer: Arc<RefCell<JsValue>>,
@@ -378,6 +379,16 @@ impl Ruffle {
                     .warn_on_error();
                 instance.key_up_callback = None;
             }
+            if let Some(paste_callback) = &instance.paste_callback {
+                instance
+                    .window
+                    .remove_event_listener_with_callback(
+                        "paste",
+                        paste_callback.as_ref().unchecked_ref(),
+                    )
+                    .warn_on_error();
+                instance.paste_callback = None;
+            }
             if let Some(unload_callback) = &instance.unload_callback {
                 instance
                     .window
@@ -536,6 +547,7 @@ impl Ruffle {
             mouse_wheel_callback: None,
             key_down_callback: None,
             key_up_callback: None,
+            paste_callback: None,
             unload_callback: None,
             timestamp: None,
             has_focus: false,
@@ -728,17 +740,32 @@ impl Ruffle {
             let key_down_callback = Closure::wrap(Box::new(move |js_event: KeyboardEvent| {
                 let _ = ruffle.with_instance(|instance| {
                     if instance.has_focus {
+                        let key_code = web_to_ruffle_key_code(&js_event.code());
+                        let key_char = web_key_to_codepoint(&js_event.key());
+                        let is_command_down = js_event.ctrl_key() || js_event.meta_key();
+                        let control_code =
+                            TextControlCode::from_shortcut(key_code).filter(|_| is_command_down);
+
                         let _ = instance.with_core_mut(|core| {
-                            let key_code = web_to_ruffle_key_code(&js_event.code());
-                            let key_char = web_key_to_codepoint(&js_event.key());
                             core.handle_event(PlayerEvent::KeyDown { key_code, key_char });
 
-                            if let Some(codepoint) = key_char {
-                                core.handle_event(PlayerEvent::TextInput { codepoint });
+                            match control_code {
+                                // The clipboard can only be read from the paste event that
+                                // follows, so that's where pasting is handled.
+                                Some(TextControlCode::Paste) => {}
+                                Some(code) => core.handle_event(PlayerEvent::TextControl { code }),
+                                None => {
+                                    if let Some(codepoint) = key_char {
+                                        core.handle_event(PlayerEvent::TextInput { codepoint });
+                                    }
+                                }
                             }
                         });
 
-                        js_event.prevent_default();
+                        // Let the browser fire the paste event.
+                        if control_code != Some(TextControlCode::Paste) {
+                            js_event.prevent_default();
+                        }
                     }
                 });
             }) as Box<dyn FnMut(KeyboardEvent)>);
@@ -770,6 +797,30 @@ impl Ruffle {
                 .warn_on_error();
             instance.key_up_callback = Some(key_up_callback);
 
+            // Create paste event handler.
+            let paste_callback = Closure::wrap(Box::new(move |js_event: ClipboardEvent| {
+                let _ = ruffle.with_instance(|instance| {
+                    if instance.has_focus {
+                        let content = js_event
+                            .clipboard_data()
+                            .and_then(|data| data.get_data("text/plain").ok())
+                            .unwrap_or_default();
+                        let _ = instance.with_core_mut(|core| {
+                            core.ui_mut().set_clipboard_content(content);
+                            core.handle_event(PlayerEvent::TextControl {
+                                code: TextControlCode::Paste,
+                            });
+                        });
+                        js_event.prevent_default();
+                    }
+                });
+            }) as Box<dyn FnMut(ClipboardEvent)>);
+
+            window
+                .add_event_listener_with_callback("paste", paste_callback.as_ref().unchecked_ref())
+                .warn_on_error();
+            instance.paste_callback = Some(paste_callback);
+
             let unload_callback = Closure::wrap(Box::new(move |_| {
                 let _ = ruffle.with_core_mut(|core| {
                     core.flush_shared_objects();
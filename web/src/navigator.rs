@@ -10,7 +10,8 @@ use url::Url;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::{spawn_local, JsFuture};
 use web_sys::{
-    window, Blob, BlobPropertyBag, Request as WebRequest, RequestInit, Response as WebResponse,
+    window, Blob, BlobPropertyBag, Headers, Request as WebRequest, RequestInit,
+    Response as WebResponse,
 };
 
 pub struct WebNavigatorBackend {
@@ -185,6 +186,18 @@ impl NavigatorBackend for WebNavigatorBackend {
                 init.body(Some(&datablob));
             }
 
+            if !request.headers().is_empty() {
+                let headers = Headers::new().map_err(|_| {
+                    Error::FetchError("Unable to create request headers".to_string())
+                })?;
+                for (name, value) in request.headers() {
+                    headers
+                        .append(name, value)
+                        .map_err(|_| Error::FetchError(format!("Invalid request header {name}")))?;
+                }
+                init.headers(&headers);
+            }
+
             let request = WebRequest::new_with_str_and_init(&url, &init)
                 .map_err(|_| Error::FetchError(format!("Unable to create request for {url}")))?;
 
@@ -202,6 +215,7 @@ impl NavigatorBackend for WebNavigatorBackend {
             }
 
             let url = response.url();
+            let content_type = response.headers().get("content-type").ok().flatten();
 
             let body: ArrayBuffer = JsFuture::from(response.array_buffer().unwrap())
                 .await
@@ -212,7 +226,11 @@ impl NavigatorBackend for WebNavigatorBackend {
                 .unwrap();
             let body = Uint8Array::new(&body).to_vec();
 
-            Ok(Response { url, body })
+            Ok(Response {
+                url,
+                body,
+                content_type,
+            })
         })
     }
 
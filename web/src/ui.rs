@@ -1,5 +1,7 @@
 use super::JavascriptPlayer;
-use ruffle_core::backend::ui::{FullscreenError, MouseCursor, UiBackend};
+use ruffle_core::backend::ui::{
+    DialogFile, DialogResultFuture, FileFilter, FullscreenError, MouseCursor, UiBackend,
+};
 use ruffle_web_common::JsResult;
 use std::borrow::Cow;
 use web_sys::HtmlCanvasElement;
@@ -10,6 +12,9 @@ pub struct WebUiBackend {
     canvas: HtmlCanvasElement,
     cursor_visible: bool,
     cursor: MouseCursor,
+
+    /// The text last copied by the movie, or pasted by the user.
+    clipboard_content: String,
 }
 
 impl WebUiBackend {
@@ -19,6 +24,7 @@ impl WebUiBackend {
             canvas: canvas.clone(),
             cursor_visible: true,
             cursor: MouseCursor::Arrow,
+            clipboard_content: String::new(),
         }
     }
 
@@ -55,10 +61,16 @@ impl UiBackend for WebUiBackend {
         self.update_mouse_cursor();
     }
 
-    fn set_clipboard_content(&mut self, _content: String) {
-        //TODO: in AVM2 FP9+ this only works when called from a button handler due to sandbox
-        // restrictions
-        log::warn!("set clipboard not implemented");
+    fn set_clipboard_content(&mut self, content: String) {
+        // TODO: Copy to the system clipboard too. In AVM2 FP9+ this only works when
+        // called from a button handler due to sandbox restrictions.
+        self.clipboard_content = content;
+    }
+
+    fn clipboard_content(&mut self) -> String {
+        // The browser only gives access to the clipboard from within a paste
+        // event, which stores its content here before pasting.
+        self.clipboard_content.clone()
     }
 
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
@@ -82,4 +94,23 @@ impl UiBackend for WebUiBackend {
     fn message(&self, message: &str) {
         self.js_player.display_message(message);
     }
+
+    fn display_file_open_dialog(
+        &mut self,
+        _filters: Vec<FileFilter>,
+        _multiple: bool,
+    ) -> DialogResultFuture<Vec<DialogFile>> {
+        // Browsers only open file pickers in response to user input, which
+        // has been handled by the time the movie asks for one.
+        log::warn!("File dialogs are not yet supported on web");
+        Box::pin(std::future::ready(None))
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        _default_name: String,
+    ) -> DialogResultFuture<DialogFile> {
+        log::warn!("File dialogs are not yet supported on web");
+        Box::pin(std::future::ready(None))
+    }
 }